@@ -15,10 +15,13 @@ const MESSAGES: [wmidi::MidiMessage<'static>; 19] = [
     ),
     wmidi::MidiMessage::ProgramChange(wmidi::Channel::Ch5, wmidi::U7::MIN),
     wmidi::MidiMessage::ChannelPressure(wmidi::Channel::Ch6, wmidi::U7::MAX),
-    wmidi::MidiMessage::PitchBendChange(wmidi::Channel::Ch7, wmidi::U14::MAX),
+    wmidi::MidiMessage::PitchBendChange(wmidi::Channel::Ch7, wmidi::PitchBend::MAX),
     wmidi::MidiMessage::Start,
     wmidi::MidiMessage::SysEx(&[wmidi::U7::MIN, wmidi::U7::MAX]),
-    wmidi::MidiMessage::MidiTimeCode(wmidi::U7::MAX),
+    wmidi::MidiMessage::MidiTimeCode(wmidi::QuarterFrame::new(
+        wmidi::FrameType::HoursHigh,
+        wmidi::U4::MAX,
+    )),
     wmidi::MidiMessage::SongPositionPointer(wmidi::U14::MIN),
     wmidi::MidiMessage::SongSelect(wmidi::U7::MIN),
     wmidi::MidiMessage::TuneRequest,
@@ -120,9 +123,7 @@ fn bench_notes(c: &mut Criterion) {
 
 #[cfg(feature = "std")]
 fn bench_frequency(c: &mut Criterion) {
-    let all_notes: Vec<wmidi::Note> = (0..128)
-        .map(|n| wmidi::Note::try_from(n).unwrap())
-        .collect();
+    let all_notes: Vec<wmidi::Note> = wmidi::Note::all().collect();
     let all_notes = black_box(all_notes);
     c.bench_function("Note::to_freq_f32", |b| {
         b.iter(|| {
@@ -0,0 +1,84 @@
+use crate::MidiMessage;
+
+/// Tells a sender when to transmit `ActiveSensing` so that receivers know the connection is
+/// still alive.
+///
+/// Call `sent` with the time of every transmission and `poll` regularly. `poll` returns an
+/// `ActiveSensing` message when nothing has been sent for `interval`. The unit of time is up to
+/// the caller. Receivers assume the connection is lost after 300 ms of silence, so an interval of
+/// around 270 ms leaves room for jitter. Nothing is requested before the first transmission, as
+/// receivers only expect `ActiveSensing` once they have seen it.
+///
+/// # Example
+/// ```
+/// use wmidi::{ActiveSensingSender, MidiMessage};
+/// let mut sender = ActiveSensingSender::new(270);
+/// sender.sent(1000);
+/// assert_eq!(sender.poll(1100), None);
+/// assert_eq!(sender.poll(1270), Some(MidiMessage::ActiveSensing));
+/// assert_eq!(sender.next_due(), Some(1540));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ActiveSensingSender {
+    interval: u64,
+    last_sent: Option<u64>,
+}
+
+impl ActiveSensingSender {
+    /// Create a sender that requests `ActiveSensing` after `interval` without a transmission.
+    pub fn new(interval: u64) -> ActiveSensingSender {
+        ActiveSensingSender {
+            interval,
+            last_sent: None,
+        }
+    }
+
+    /// The time without a transmission after which `ActiveSensing` is requested.
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Record that a message was sent at `now`.
+    pub fn sent(&mut self, now: u64) {
+        self.last_sent = Some(now);
+    }
+
+    /// The time at which `ActiveSensing` should be sent, or `None` if nothing has been sent yet.
+    pub fn next_due(&self) -> Option<u64> {
+        self.last_sent.map(|t| t.saturating_add(self.interval))
+    }
+
+    /// Returns `ActiveSensing` if it should be sent at `now`, and records it as sent.
+    pub fn poll(&mut self, now: u64) -> Option<MidiMessage<'static>> {
+        if now < self.next_due()? {
+            return None;
+        }
+        self.sent(now);
+        Some(MidiMessage::ActiveSensing)
+    }
+
+    /// Stop requesting `ActiveSensing` until the next transmission, for example when the output
+    /// is closed.
+    pub fn reset(&mut self) {
+        self.last_sent = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn waits_for_first_transmission() {
+        let mut sender = ActiveSensingSender::new(10);
+        assert_eq!(sender.poll(1000), None);
+        assert_eq!(sender.next_due(), None);
+        sender.sent(1000);
+        sender.sent(1005);
+        assert_eq!(sender.poll(1014), None);
+        assert_eq!(sender.poll(1015), Some(MidiMessage::ActiveSensing));
+        assert_eq!(sender.poll(1016), None);
+        sender.reset();
+        assert_eq!(sender.poll(2000), None);
+    }
+}
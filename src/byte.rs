@@ -79,6 +79,56 @@ impl TryFrom<u8> for U7 {
     }
 }
 
+/// A nibble that holds 4 bits of information.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct U4(pub(crate) u8);
+
+impl U4 {
+    /// The minimum value for a u4 nibble.
+    pub const MIN: U4 = U4(0x0);
+    /// The maximum value for a u4 nibble.
+    pub const MAX: U4 = U4(0xF);
+
+    /// Create a new `U4` or return an error if it is out of range.
+    #[inline(always)]
+    pub fn new(data: u8) -> Result<U4, Error> {
+        if data > u8::from(U4::MAX) {
+            Err(Error::DataByteOutOfRange)
+        } else {
+            Ok(U4(data))
+        }
+    }
+
+    /// Create a `U4` from a `u8`. Only the 4 least significant bits of `data` are kept.
+    #[inline(always)]
+    pub const fn from_u8_lossy(data: u8) -> U4 {
+        U4(data & 0x0F)
+    }
+}
+
+impl From<U4> for u8 {
+    #[inline(always)]
+    fn from(data: U4) -> u8 {
+        data.0
+    }
+}
+
+impl From<U4> for U7 {
+    #[inline(always)]
+    fn from(data: U4) -> U7 {
+        U7(data.0)
+    }
+}
+
+impl TryFrom<u8> for U4 {
+    type Error = Error;
+
+    #[inline(always)]
+    fn try_from(data: u8) -> Result<U4, Error> {
+        U4::new(data)
+    }
+}
+
 /// A combination of 2 data bytes that holds 14 bits of information.
 #[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct U14(u16);
@@ -98,6 +148,12 @@ impl U14 {
         U14(data)
     }
 
+    /// Create a `U14` from a `u16`. Only the 14 least significant bits of `data` are used.
+    #[inline(always)]
+    pub const fn from_u16_lossy(data: u16) -> U14 {
+        U14(data & 0x3FFF)
+    }
+
     /// Convert a slice of `u16` into a slice of `U14`. If any of the data is out of range, then an
     /// error is returned.
     #[inline(always)]
@@ -249,4 +305,12 @@ mod tests {
         assert_eq!(U7::from_u8_lossy(128), U7::try_from(0).unwrap());
         assert_eq!(U7::from_u8_lossy(200), U7::try_from(72).unwrap());
     }
+
+    #[test]
+    fn u4_range() {
+        for n in 0..=u8::MAX {
+            assert_eq!(U4::try_from(n).is_ok(), n < 16);
+            assert_eq!(u8::from(U4::from_u8_lossy(n)), n % 16);
+        }
+    }
 }
@@ -10,6 +10,8 @@
 //! * [CA-031]: RPN05 Modulation Depth Range
 
 use crate::byte::U7;
+use crate::ParseControlFunctionError;
+use core::str::FromStr;
 
 /// A Control Change signal. The names of each variant of the constants
 /// are from the 1997 MIDI 1.0 specification. The names and description
@@ -369,6 +371,342 @@ impl ControlFunction {
     ///
     /// [GM2] Same as AllNotesOff (123), then set the **channel** to mode 3.
     pub const POLY_OPERATION: ControlFunction = ControlFunction(U7(127));
+
+    /// The name of each controller, indexed by controller number.
+    const NAMES: [&'static str; 128] = [
+        "BANK_SELECT",
+        "MODULATION_WHEEL",
+        "BREATH_CONTROLLER",
+        "UNDEFINED_3",
+        "FOOT_CONTROLLER",
+        "PORTAMENTO_TIME",
+        "DATA_ENTRY_MSB",
+        "CHANNEL_VOLUME",
+        "BALANCE",
+        "UNDEFINED_9",
+        "PAN",
+        "EXPRESSION_CONTROLLER",
+        "EFFECT_CONTROL_1",
+        "EFFECT_CONTROL_2",
+        "UNDEFINED_14",
+        "UNDEFINED_15",
+        "GENERAL_PURPOSE_CONTROLLER_1",
+        "GENERAL_PURPOSE_CONTROLLER_2",
+        "GENERAL_PURPOSE_CONTROLLER_3",
+        "GENERAL_PURPOSE_CONTROLLER_4",
+        "UNDEFINED_20",
+        "UNDEFINED_21",
+        "UNDEFINED_22",
+        "UNDEFINED_23",
+        "UNDEFINED_24",
+        "UNDEFINED_25",
+        "UNDEFINED_26",
+        "UNDEFINED_27",
+        "UNDEFINED_28",
+        "UNDEFINED_29",
+        "UNDEFINED_30",
+        "UNDEFINED_31",
+        "BANK_SELECT_LSB",
+        "MODULATION_WHEEL_LSB",
+        "BREATH_CONTROLLER_LSB",
+        "UNDEFINED_3_LSB",
+        "FOOT_CONTROLLER_LSB",
+        "PORTAMENTO_TIME_LSB",
+        "DATA_ENTRY_LSB",
+        "CHANNEL_VOLUME_LSB",
+        "BALANCE_LSB",
+        "UNDEFINED_9_LSB",
+        "PAN_LSB",
+        "EXPRESSION_CONTROLLER_LSB",
+        "EFFECT_CONTROL_1_LSB",
+        "EFFECT_CONTROL_2_LSB",
+        "UNDEFINED_14_LSB",
+        "UNDEFINED_15_LSB",
+        "GENERAL_PURPOSE_CONTROLLER_1_LSB",
+        "GENERAL_PURPOSE_CONTROLLER_2_LSB",
+        "GENERAL_PURPOSE_CONTROLLER_3_LSB",
+        "GENERAL_PURPOSE_CONTROLLER_4_LSB",
+        "UNDEFINED_20_LSB",
+        "UNDEFINED_21_LSB",
+        "UNDEFINED_22_LSB",
+        "UNDEFINED_23_LSB",
+        "UNDEFINED_24_LSB",
+        "UNDEFINED_25_LSB",
+        "UNDEFINED_26_LSB",
+        "UNDEFINED_27_LSB",
+        "UNDEFINED_28_LSB",
+        "UNDEFINED_29_LSB",
+        "UNDEFINED_30_LSB",
+        "UNDEFINED_31_LSB",
+        "DAMPER_PEDAL",
+        "PORTAMENTO_ON_OFF",
+        "SOSTENUTO",
+        "SOFT_PEDAL",
+        "LEGATO_FOOTSWITCH",
+        "HOLD_2",
+        "SOUND_CONTROLLER_1",
+        "SOUND_CONTROLLER_2",
+        "SOUND_CONTROLLER_3",
+        "SOUND_CONTROLLER_4",
+        "SOUND_CONTROLLER_5",
+        "SOUND_CONTROLLER_6",
+        "SOUND_CONTROLLER_7",
+        "SOUND_CONTROLLER_8",
+        "SOUND_CONTROLLER_9",
+        "SOUND_CONTROLLER_10",
+        "GENERAL_PURPOSE_CONTROLLER_5",
+        "GENERAL_PURPOSE_CONTROLLER_6",
+        "GENERAL_PURPOSE_CONTROLLER_7",
+        "GENERAL_PURPOSE_CONTROLLER_8",
+        "PORTAMENTO_CONTROL",
+        "UNDEFINED_85",
+        "UNDEFINED_86",
+        "UNDEFINED_87",
+        "UNDEFINED_88",
+        "UNDEFINED_89",
+        "UNDEFINED_90",
+        "EFFECTS_1_DEPTH",
+        "EFFECTS_2_DEPTH",
+        "EFFECTS_3_DEPTH",
+        "EFFECTS_4_DEPTH",
+        "EFFECTS_5_DEPTH",
+        "DATA_INCREMENT",
+        "DATA_DECREMENT",
+        "NON_REGISTERED_PARAMETER_NUMBER_LSB",
+        "NON_REGISTERED_PARAMETER_NUMBER_MSB",
+        "REGISTERED_PARAMETER_NUMBER_LSB",
+        "REGISTERED_PARAMETER_NUMBER_MSB",
+        "UNDEFINED_102",
+        "UNDEFINED_103",
+        "UNDEFINED_104",
+        "UNDEFINED_105",
+        "UNDEFINED_106",
+        "UNDEFINED_107",
+        "UNDEFINED_108",
+        "UNDEFINED_109",
+        "UNDEFINED_110",
+        "UNDEFINED_111",
+        "UNDEFINED_112",
+        "UNDEFINED_113",
+        "UNDEFINED_114",
+        "UNDEFINED_115",
+        "UNDEFINED_116",
+        "UNDEFINED_117",
+        "UNDEFINED_118",
+        "UNDEFINED_119",
+        "ALL_SOUND_OFF",
+        "RESET_ALL_CONTROLLERS",
+        "LOCAL_CONTROL",
+        "ALL_NOTES_OFF",
+        "OMNI_MODE_ON",
+        "OMNI_MODE_OFF",
+        "MONO_OPERATION",
+        "POLY_OPERATION",
+    ];
+
+    /// Common names that are not the name of the constant.
+    const ALIASES: [(&'static str, ControlFunction); 14] = [
+        ("BANK", ControlFunction::BANK_SELECT),
+        ("MOD", ControlFunction::MODULATION_WHEEL),
+        ("MOD_WHEEL", ControlFunction::MODULATION_WHEEL),
+        ("MODULATION", ControlFunction::MODULATION_WHEEL),
+        ("BREATH", ControlFunction::BREATH_CONTROLLER),
+        ("VOLUME", ControlFunction::CHANNEL_VOLUME),
+        ("EXPRESSION", ControlFunction::EXPRESSION_CONTROLLER),
+        ("SUSTAIN", ControlFunction::DAMPER_PEDAL),
+        ("SUSTAIN_PEDAL", ControlFunction::DAMPER_PEDAL),
+        ("HOLD", ControlFunction::DAMPER_PEDAL),
+        ("PORTAMENTO", ControlFunction::PORTAMENTO_ON_OFF),
+        ("REVERB", ControlFunction::EFFECTS_1_DEPTH),
+        ("CHORUS", ControlFunction::EFFECTS_3_DEPTH),
+        ("SOFT", ControlFunction::SOFT_PEDAL),
+    ];
+
+    /// `true` for the channel mode messages, controllers 120-127.
+    pub fn is_channel_mode(self) -> bool {
+        u8::from(self) >= 120
+    }
+
+    /// `true` for the MSB of the 14 bit continuous controllers, controllers 0-31.
+    pub fn is_msb(self) -> bool {
+        u8::from(self) < 32
+    }
+
+    /// `true` for the LSB of the 14 bit continuous controllers, controllers 32-63.
+    pub fn is_lsb(self) -> bool {
+        (32..64).contains(&u8::from(self))
+    }
+
+    /// `true` for the on/off switches, controllers 64-69. Values of 64 and above are on and
+    /// values below 64 are off.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ControlFunction;
+    /// assert!(ControlFunction::SOSTENUTO.is_switch());
+    /// assert!(!ControlFunction::SOUND_CONTROLLER_1.is_switch());
+    /// ```
+    pub fn is_switch(self) -> bool {
+        (64..70).contains(&u8::from(self))
+    }
+
+    /// `true` for controllers that have no standard assignment in [MIDI 1.0], which are the
+    /// `UNDEFINED_*` constants.
+    pub fn is_undefined(self) -> bool {
+        self.name().starts_with("UNDEFINED")
+    }
+
+    /// The LSB controller that pairs with this MSB controller, or `None` if `self` is not
+    /// between 0 and 31 inclusive.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ControlFunction;
+    /// assert_eq!(
+    ///     ControlFunction::CHANNEL_VOLUME.lsb_counterpart(),
+    ///     Some(ControlFunction::CHANNEL_VOLUME_LSB)
+    /// );
+    /// assert_eq!(ControlFunction::DAMPER_PEDAL.lsb_counterpart(), None);
+    /// ```
+    pub fn lsb_counterpart(self) -> Option<ControlFunction> {
+        if self.is_msb() {
+            Some(ControlFunction(U7(u8::from(self) + 32)))
+        } else {
+            None
+        }
+    }
+
+    /// The MSB controller that pairs with this LSB controller, or `None` if `self` is not
+    /// between 32 and 63 inclusive.
+    pub fn msb_counterpart(self) -> Option<ControlFunction> {
+        if self.is_lsb() {
+            Some(ControlFunction(U7(u8::from(self) - 32)))
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over all 128 controllers in ascending order.
+    #[inline(always)]
+    pub fn all() -> ControlFunctions {
+        ControlFunctions { next: 0, end: 128 }
+    }
+
+    /// Iterate over the controllers that are not `is_undefined` in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ControlFunction;
+    /// let names = ControlFunction::defined().take(3).map(ControlFunction::name);
+    /// assert!(names.eq(["BANK_SELECT", "MODULATION_WHEEL", "BREATH_CONTROLLER"].iter().copied()));
+    /// assert_eq!(ControlFunction::defined().next(), Some(ControlFunction::BANK_SELECT));
+    /// ```
+    #[inline(always)]
+    pub fn defined() -> core::iter::Filter<ControlFunctions, fn(&ControlFunction) -> bool> {
+        ControlFunction::all().filter(|cc| !cc.is_undefined())
+    }
+
+    /// The name of the controller, as written in the constant for its number.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ControlFunction;
+    /// assert_eq!(ControlFunction::DAMPER_PEDAL.name(), "DAMPER_PEDAL");
+    /// ```
+    pub fn name(self) -> &'static str {
+        ControlFunction::NAMES[usize::from(u8::from(self))]
+    }
+
+    /// Look up a controller by the name of its constant or a common alias such as `"sustain"`
+    /// or `"mod wheel"`. Names are case insensitive, and spaces and dashes are treated as
+    /// underscores.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ControlFunction;
+    /// assert_eq!(ControlFunction::from_name("Sustain"), Some(ControlFunction::DAMPER_PEDAL));
+    /// assert_eq!(ControlFunction::from_name("mod wheel"), Some(ControlFunction::MODULATION_WHEEL));
+    /// assert_eq!(ControlFunction::from_name("channel-volume"), Some(ControlFunction::CHANNEL_VOLUME));
+    /// assert_eq!(ControlFunction::from_name("wah"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<ControlFunction> {
+        let name = name.trim();
+        if let Some(number) = ControlFunction::NAMES
+            .iter()
+            .position(|n| name_matches(name, n))
+        {
+            return Some(ControlFunction(U7(number as u8)));
+        }
+        ControlFunction::ALIASES
+            .iter()
+            .find(|(alias, _)| name_matches(name, alias))
+            .map(|(_, cc)| *cc)
+    }
+}
+
+/// Compare `input` to `name`, ignoring ASCII case and treating spaces and dashes as underscores.
+fn name_matches(input: &str, name: &str) -> bool {
+    let normalize = |b: u8| match b {
+        b' ' | b'-' => b'_',
+        b => b.to_ascii_uppercase(),
+    };
+    input.len() == name.len()
+        && input
+            .bytes()
+            .zip(name.bytes())
+            .all(|(a, b)| normalize(a) == normalize(b))
+}
+
+/// An iterator over consecutive controllers. Created with `ControlFunction::all`.
+#[derive(Clone, Debug)]
+pub struct ControlFunctions {
+    next: u8,
+    // Exclusive, may be 128.
+    end: u8,
+}
+
+impl Iterator for ControlFunctions {
+    type Item = ControlFunction;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<ControlFunction> {
+        if self.next < self.end {
+            let cc = ControlFunction(U7(self.next));
+            self.next += 1;
+            Some(cc)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.end - self.next);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for ControlFunctions {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<ControlFunction> {
+        if self.next < self.end {
+            self.end -= 1;
+            Some(ControlFunction(U7(self.end)))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for ControlFunctions {}
+
+impl FromStr for ControlFunction {
+    type Err = ParseControlFunctionError;
+
+    /// Parse a controller with `ControlFunction::from_name`.
+    fn from_str(s: &str) -> Result<ControlFunction, ParseControlFunctionError> {
+        ControlFunction::from_name(s).ok_or(ParseControlFunctionError)
+    }
 }
 
 impl From<U7> for ControlFunction {
@@ -402,4 +740,66 @@ mod test {
             assert_eq!(value, cc.into());
         }
     }
+
+    #[test]
+    fn from_name() {
+        for value in 0..128 {
+            let cc = ControlFunction(U7(value));
+            assert_eq!(ControlFunction::from_name(cc.name()), Some(cc));
+        }
+        assert_eq!(
+            "undefined 3 lsb".parse(),
+            Ok(ControlFunction::UNDEFINED_3_LSB)
+        );
+        assert_eq!(" reverb ".parse(), Ok(ControlFunction::EFFECTS_1_DEPTH));
+        assert_eq!(
+            "damper pedal!".parse::<ControlFunction>(),
+            Err(ParseControlFunctionError)
+        );
+        assert_eq!(ControlFunction::from_name(""), None);
+    }
+
+    #[test]
+    fn classification() {
+        let count = |f: fn(ControlFunction) -> bool| {
+            (0..128).filter(|n| f(ControlFunction(U7(*n)))).count()
+        };
+        assert_eq!(count(ControlFunction::is_msb), 32);
+        assert_eq!(count(ControlFunction::is_lsb), 32);
+        assert_eq!(count(ControlFunction::is_switch), 6);
+        assert_eq!(count(ControlFunction::is_channel_mode), 8);
+        assert_eq!(count(ControlFunction::is_undefined), 56);
+        assert!(ControlFunction::UNDEFINED_3_LSB.is_lsb());
+        assert!(ControlFunction::UNDEFINED_3_LSB.is_undefined());
+        assert!(ControlFunction::DAMPER_PEDAL.is_switch());
+        assert!(!ControlFunction::BANK_SELECT.is_undefined());
+        assert!(ControlFunction::ALL_SOUND_OFF.is_channel_mode());
+    }
+
+    #[test]
+    fn iterators() {
+        assert_eq!(ControlFunction::all().len(), 128);
+        assert_eq!(
+            ControlFunction::all().next_back(),
+            Some(ControlFunction::MAX)
+        );
+        assert_eq!(ControlFunction::defined().count(), 128 - 56);
+        assert!(ControlFunction::defined().all(|cc| !cc.is_undefined()));
+    }
+
+    #[test]
+    fn counterparts() {
+        for value in 0..128 {
+            let cc = ControlFunction(U7(value));
+            assert_eq!(cc.lsb_counterpart().is_some(), cc.is_msb());
+            assert_eq!(cc.msb_counterpart().is_some(), cc.is_lsb());
+            if let Some(lsb) = cc.lsb_counterpart() {
+                assert_eq!(lsb.msb_counterpart(), Some(cc));
+            }
+        }
+        assert_eq!(
+            ControlFunction::DATA_ENTRY_LSB.msb_counterpart(),
+            Some(ControlFunction::DATA_ENTRY_MSB)
+        );
+    }
 }
@@ -0,0 +1,424 @@
+use crate::{ControlFunction, U7};
+
+/// An exhaustive alternative to `ControlFunction`, with one variant for each controller that
+/// has a standard assignment. Matching on `ControlFunctionKind` lets the compiler check that every
+/// controller is handled.
+///
+/// # Example
+/// ```
+/// use wmidi::{ControlFunction, ControlFunctionKind, U7};
+/// let kind = ControlFunction::DAMPER_PEDAL.kind();
+/// assert_eq!(kind, ControlFunctionKind::DamperPedal);
+/// assert_eq!(ControlFunction::from(kind), ControlFunction::DAMPER_PEDAL);
+/// assert_eq!(
+///     ControlFunction::UNDEFINED_3.kind(),
+///     ControlFunctionKind::Undefined(U7::from_u8_lossy(3))
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ControlFunctionKind {
+    /// `ControlFunction::BANK_SELECT`, controller 0.
+    BankSelect,
+    /// `ControlFunction::MODULATION_WHEEL`, controller 1.
+    ModulationWheel,
+    /// `ControlFunction::BREATH_CONTROLLER`, controller 2.
+    BreathController,
+    /// `ControlFunction::FOOT_CONTROLLER`, controller 4.
+    FootController,
+    /// `ControlFunction::PORTAMENTO_TIME`, controller 5.
+    PortamentoTime,
+    /// `ControlFunction::DATA_ENTRY_MSB`, controller 6.
+    DataEntryMsb,
+    /// `ControlFunction::CHANNEL_VOLUME`, controller 7.
+    ChannelVolume,
+    /// `ControlFunction::BALANCE`, controller 8.
+    Balance,
+    /// `ControlFunction::PAN`, controller 10.
+    Pan,
+    /// `ControlFunction::EXPRESSION_CONTROLLER`, controller 11.
+    ExpressionController,
+    /// `ControlFunction::EFFECT_CONTROL_1`, controller 12.
+    EffectControl1,
+    /// `ControlFunction::EFFECT_CONTROL_2`, controller 13.
+    EffectControl2,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_1`, controller 16.
+    GeneralPurposeController1,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_2`, controller 17.
+    GeneralPurposeController2,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_3`, controller 18.
+    GeneralPurposeController3,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_4`, controller 19.
+    GeneralPurposeController4,
+    /// `ControlFunction::BANK_SELECT_LSB`, controller 32.
+    BankSelectLsb,
+    /// `ControlFunction::MODULATION_WHEEL_LSB`, controller 33.
+    ModulationWheelLsb,
+    /// `ControlFunction::BREATH_CONTROLLER_LSB`, controller 34.
+    BreathControllerLsb,
+    /// `ControlFunction::FOOT_CONTROLLER_LSB`, controller 36.
+    FootControllerLsb,
+    /// `ControlFunction::PORTAMENTO_TIME_LSB`, controller 37.
+    PortamentoTimeLsb,
+    /// `ControlFunction::DATA_ENTRY_LSB`, controller 38.
+    DataEntryLsb,
+    /// `ControlFunction::CHANNEL_VOLUME_LSB`, controller 39.
+    ChannelVolumeLsb,
+    /// `ControlFunction::BALANCE_LSB`, controller 40.
+    BalanceLsb,
+    /// `ControlFunction::PAN_LSB`, controller 42.
+    PanLsb,
+    /// `ControlFunction::EXPRESSION_CONTROLLER_LSB`, controller 43.
+    ExpressionControllerLsb,
+    /// `ControlFunction::EFFECT_CONTROL_1_LSB`, controller 44.
+    EffectControl1Lsb,
+    /// `ControlFunction::EFFECT_CONTROL_2_LSB`, controller 45.
+    EffectControl2Lsb,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_1_LSB`, controller 48.
+    GeneralPurposeController1Lsb,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_2_LSB`, controller 49.
+    GeneralPurposeController2Lsb,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_3_LSB`, controller 50.
+    GeneralPurposeController3Lsb,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_4_LSB`, controller 51.
+    GeneralPurposeController4Lsb,
+    /// `ControlFunction::DAMPER_PEDAL`, controller 64.
+    DamperPedal,
+    /// `ControlFunction::PORTAMENTO_ON_OFF`, controller 65.
+    PortamentoOnOff,
+    /// `ControlFunction::SOSTENUTO`, controller 66.
+    Sostenuto,
+    /// `ControlFunction::SOFT_PEDAL`, controller 67.
+    SoftPedal,
+    /// `ControlFunction::LEGATO_FOOTSWITCH`, controller 68.
+    LegatoFootswitch,
+    /// `ControlFunction::HOLD_2`, controller 69.
+    Hold2,
+    /// `ControlFunction::SOUND_CONTROLLER_1`, controller 70.
+    SoundController1,
+    /// `ControlFunction::SOUND_CONTROLLER_2`, controller 71.
+    SoundController2,
+    /// `ControlFunction::SOUND_CONTROLLER_3`, controller 72.
+    SoundController3,
+    /// `ControlFunction::SOUND_CONTROLLER_4`, controller 73.
+    SoundController4,
+    /// `ControlFunction::SOUND_CONTROLLER_5`, controller 74.
+    SoundController5,
+    /// `ControlFunction::SOUND_CONTROLLER_6`, controller 75.
+    SoundController6,
+    /// `ControlFunction::SOUND_CONTROLLER_7`, controller 76.
+    SoundController7,
+    /// `ControlFunction::SOUND_CONTROLLER_8`, controller 77.
+    SoundController8,
+    /// `ControlFunction::SOUND_CONTROLLER_9`, controller 78.
+    SoundController9,
+    /// `ControlFunction::SOUND_CONTROLLER_10`, controller 79.
+    SoundController10,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_5`, controller 80.
+    GeneralPurposeController5,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_6`, controller 81.
+    GeneralPurposeController6,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_7`, controller 82.
+    GeneralPurposeController7,
+    /// `ControlFunction::GENERAL_PURPOSE_CONTROLLER_8`, controller 83.
+    GeneralPurposeController8,
+    /// `ControlFunction::PORTAMENTO_CONTROL`, controller 84.
+    PortamentoControl,
+    /// `ControlFunction::EFFECTS_1_DEPTH`, controller 91.
+    Effects1Depth,
+    /// `ControlFunction::EFFECTS_2_DEPTH`, controller 92.
+    Effects2Depth,
+    /// `ControlFunction::EFFECTS_3_DEPTH`, controller 93.
+    Effects3Depth,
+    /// `ControlFunction::EFFECTS_4_DEPTH`, controller 94.
+    Effects4Depth,
+    /// `ControlFunction::EFFECTS_5_DEPTH`, controller 95.
+    Effects5Depth,
+    /// `ControlFunction::DATA_INCREMENT`, controller 96.
+    DataIncrement,
+    /// `ControlFunction::DATA_DECREMENT`, controller 97.
+    DataDecrement,
+    /// `ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB`, controller 98.
+    NonRegisteredParameterNumberLsb,
+    /// `ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB`, controller 99.
+    NonRegisteredParameterNumberMsb,
+    /// `ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB`, controller 100.
+    RegisteredParameterNumberLsb,
+    /// `ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB`, controller 101.
+    RegisteredParameterNumberMsb,
+    /// `ControlFunction::ALL_SOUND_OFF`, controller 120.
+    AllSoundOff,
+    /// `ControlFunction::RESET_ALL_CONTROLLERS`, controller 121.
+    ResetAllControllers,
+    /// `ControlFunction::LOCAL_CONTROL`, controller 122.
+    LocalControl,
+    /// `ControlFunction::ALL_NOTES_OFF`, controller 123.
+    AllNotesOff,
+    /// `ControlFunction::OMNI_MODE_ON`, controller 124.
+    OmniModeOn,
+    /// `ControlFunction::OMNI_MODE_OFF`, controller 125.
+    OmniModeOff,
+    /// `ControlFunction::MONO_OPERATION`, controller 126.
+    MonoOperation,
+    /// `ControlFunction::POLY_OPERATION`, controller 127.
+    PolyOperation,
+    /// A controller for which `ControlFunction::is_undefined` is `true`.
+    Undefined(U7),
+}
+
+impl ControlFunction {
+    /// The exhaustive kind of the controller.
+    pub fn kind(self) -> ControlFunctionKind {
+        ControlFunctionKind::from(self)
+    }
+}
+
+impl From<ControlFunction> for ControlFunctionKind {
+    fn from(function: ControlFunction) -> ControlFunctionKind {
+        match function {
+            ControlFunction::BANK_SELECT => ControlFunctionKind::BankSelect,
+            ControlFunction::MODULATION_WHEEL => ControlFunctionKind::ModulationWheel,
+            ControlFunction::BREATH_CONTROLLER => ControlFunctionKind::BreathController,
+            ControlFunction::FOOT_CONTROLLER => ControlFunctionKind::FootController,
+            ControlFunction::PORTAMENTO_TIME => ControlFunctionKind::PortamentoTime,
+            ControlFunction::DATA_ENTRY_MSB => ControlFunctionKind::DataEntryMsb,
+            ControlFunction::CHANNEL_VOLUME => ControlFunctionKind::ChannelVolume,
+            ControlFunction::BALANCE => ControlFunctionKind::Balance,
+            ControlFunction::PAN => ControlFunctionKind::Pan,
+            ControlFunction::EXPRESSION_CONTROLLER => ControlFunctionKind::ExpressionController,
+            ControlFunction::EFFECT_CONTROL_1 => ControlFunctionKind::EffectControl1,
+            ControlFunction::EFFECT_CONTROL_2 => ControlFunctionKind::EffectControl2,
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_1 => {
+                ControlFunctionKind::GeneralPurposeController1
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_2 => {
+                ControlFunctionKind::GeneralPurposeController2
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_3 => {
+                ControlFunctionKind::GeneralPurposeController3
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_4 => {
+                ControlFunctionKind::GeneralPurposeController4
+            }
+            ControlFunction::BANK_SELECT_LSB => ControlFunctionKind::BankSelectLsb,
+            ControlFunction::MODULATION_WHEEL_LSB => ControlFunctionKind::ModulationWheelLsb,
+            ControlFunction::BREATH_CONTROLLER_LSB => ControlFunctionKind::BreathControllerLsb,
+            ControlFunction::FOOT_CONTROLLER_LSB => ControlFunctionKind::FootControllerLsb,
+            ControlFunction::PORTAMENTO_TIME_LSB => ControlFunctionKind::PortamentoTimeLsb,
+            ControlFunction::DATA_ENTRY_LSB => ControlFunctionKind::DataEntryLsb,
+            ControlFunction::CHANNEL_VOLUME_LSB => ControlFunctionKind::ChannelVolumeLsb,
+            ControlFunction::BALANCE_LSB => ControlFunctionKind::BalanceLsb,
+            ControlFunction::PAN_LSB => ControlFunctionKind::PanLsb,
+            ControlFunction::EXPRESSION_CONTROLLER_LSB => {
+                ControlFunctionKind::ExpressionControllerLsb
+            }
+            ControlFunction::EFFECT_CONTROL_1_LSB => ControlFunctionKind::EffectControl1Lsb,
+            ControlFunction::EFFECT_CONTROL_2_LSB => ControlFunctionKind::EffectControl2Lsb,
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_1_LSB => {
+                ControlFunctionKind::GeneralPurposeController1Lsb
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_2_LSB => {
+                ControlFunctionKind::GeneralPurposeController2Lsb
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_3_LSB => {
+                ControlFunctionKind::GeneralPurposeController3Lsb
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_4_LSB => {
+                ControlFunctionKind::GeneralPurposeController4Lsb
+            }
+            ControlFunction::DAMPER_PEDAL => ControlFunctionKind::DamperPedal,
+            ControlFunction::PORTAMENTO_ON_OFF => ControlFunctionKind::PortamentoOnOff,
+            ControlFunction::SOSTENUTO => ControlFunctionKind::Sostenuto,
+            ControlFunction::SOFT_PEDAL => ControlFunctionKind::SoftPedal,
+            ControlFunction::LEGATO_FOOTSWITCH => ControlFunctionKind::LegatoFootswitch,
+            ControlFunction::HOLD_2 => ControlFunctionKind::Hold2,
+            ControlFunction::SOUND_CONTROLLER_1 => ControlFunctionKind::SoundController1,
+            ControlFunction::SOUND_CONTROLLER_2 => ControlFunctionKind::SoundController2,
+            ControlFunction::SOUND_CONTROLLER_3 => ControlFunctionKind::SoundController3,
+            ControlFunction::SOUND_CONTROLLER_4 => ControlFunctionKind::SoundController4,
+            ControlFunction::SOUND_CONTROLLER_5 => ControlFunctionKind::SoundController5,
+            ControlFunction::SOUND_CONTROLLER_6 => ControlFunctionKind::SoundController6,
+            ControlFunction::SOUND_CONTROLLER_7 => ControlFunctionKind::SoundController7,
+            ControlFunction::SOUND_CONTROLLER_8 => ControlFunctionKind::SoundController8,
+            ControlFunction::SOUND_CONTROLLER_9 => ControlFunctionKind::SoundController9,
+            ControlFunction::SOUND_CONTROLLER_10 => ControlFunctionKind::SoundController10,
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_5 => {
+                ControlFunctionKind::GeneralPurposeController5
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_6 => {
+                ControlFunctionKind::GeneralPurposeController6
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_7 => {
+                ControlFunctionKind::GeneralPurposeController7
+            }
+            ControlFunction::GENERAL_PURPOSE_CONTROLLER_8 => {
+                ControlFunctionKind::GeneralPurposeController8
+            }
+            ControlFunction::PORTAMENTO_CONTROL => ControlFunctionKind::PortamentoControl,
+            ControlFunction::EFFECTS_1_DEPTH => ControlFunctionKind::Effects1Depth,
+            ControlFunction::EFFECTS_2_DEPTH => ControlFunctionKind::Effects2Depth,
+            ControlFunction::EFFECTS_3_DEPTH => ControlFunctionKind::Effects3Depth,
+            ControlFunction::EFFECTS_4_DEPTH => ControlFunctionKind::Effects4Depth,
+            ControlFunction::EFFECTS_5_DEPTH => ControlFunctionKind::Effects5Depth,
+            ControlFunction::DATA_INCREMENT => ControlFunctionKind::DataIncrement,
+            ControlFunction::DATA_DECREMENT => ControlFunctionKind::DataDecrement,
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB => {
+                ControlFunctionKind::NonRegisteredParameterNumberLsb
+            }
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB => {
+                ControlFunctionKind::NonRegisteredParameterNumberMsb
+            }
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB => {
+                ControlFunctionKind::RegisteredParameterNumberLsb
+            }
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB => {
+                ControlFunctionKind::RegisteredParameterNumberMsb
+            }
+            ControlFunction::ALL_SOUND_OFF => ControlFunctionKind::AllSoundOff,
+            ControlFunction::RESET_ALL_CONTROLLERS => ControlFunctionKind::ResetAllControllers,
+            ControlFunction::LOCAL_CONTROL => ControlFunctionKind::LocalControl,
+            ControlFunction::ALL_NOTES_OFF => ControlFunctionKind::AllNotesOff,
+            ControlFunction::OMNI_MODE_ON => ControlFunctionKind::OmniModeOn,
+            ControlFunction::OMNI_MODE_OFF => ControlFunctionKind::OmniModeOff,
+            ControlFunction::MONO_OPERATION => ControlFunctionKind::MonoOperation,
+            ControlFunction::POLY_OPERATION => ControlFunctionKind::PolyOperation,
+            undefined => ControlFunctionKind::Undefined(U7::from(undefined)),
+        }
+    }
+}
+
+/// `ControlFunctionKind::Undefined` converts to the controller it holds, even if that controller
+/// has a standard assignment.
+impl From<ControlFunctionKind> for ControlFunction {
+    fn from(kind: ControlFunctionKind) -> ControlFunction {
+        match kind {
+            ControlFunctionKind::BankSelect => ControlFunction::BANK_SELECT,
+            ControlFunctionKind::ModulationWheel => ControlFunction::MODULATION_WHEEL,
+            ControlFunctionKind::BreathController => ControlFunction::BREATH_CONTROLLER,
+            ControlFunctionKind::FootController => ControlFunction::FOOT_CONTROLLER,
+            ControlFunctionKind::PortamentoTime => ControlFunction::PORTAMENTO_TIME,
+            ControlFunctionKind::DataEntryMsb => ControlFunction::DATA_ENTRY_MSB,
+            ControlFunctionKind::ChannelVolume => ControlFunction::CHANNEL_VOLUME,
+            ControlFunctionKind::Balance => ControlFunction::BALANCE,
+            ControlFunctionKind::Pan => ControlFunction::PAN,
+            ControlFunctionKind::ExpressionController => ControlFunction::EXPRESSION_CONTROLLER,
+            ControlFunctionKind::EffectControl1 => ControlFunction::EFFECT_CONTROL_1,
+            ControlFunctionKind::EffectControl2 => ControlFunction::EFFECT_CONTROL_2,
+            ControlFunctionKind::GeneralPurposeController1 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_1
+            }
+            ControlFunctionKind::GeneralPurposeController2 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_2
+            }
+            ControlFunctionKind::GeneralPurposeController3 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_3
+            }
+            ControlFunctionKind::GeneralPurposeController4 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_4
+            }
+            ControlFunctionKind::BankSelectLsb => ControlFunction::BANK_SELECT_LSB,
+            ControlFunctionKind::ModulationWheelLsb => ControlFunction::MODULATION_WHEEL_LSB,
+            ControlFunctionKind::BreathControllerLsb => ControlFunction::BREATH_CONTROLLER_LSB,
+            ControlFunctionKind::FootControllerLsb => ControlFunction::FOOT_CONTROLLER_LSB,
+            ControlFunctionKind::PortamentoTimeLsb => ControlFunction::PORTAMENTO_TIME_LSB,
+            ControlFunctionKind::DataEntryLsb => ControlFunction::DATA_ENTRY_LSB,
+            ControlFunctionKind::ChannelVolumeLsb => ControlFunction::CHANNEL_VOLUME_LSB,
+            ControlFunctionKind::BalanceLsb => ControlFunction::BALANCE_LSB,
+            ControlFunctionKind::PanLsb => ControlFunction::PAN_LSB,
+            ControlFunctionKind::ExpressionControllerLsb => {
+                ControlFunction::EXPRESSION_CONTROLLER_LSB
+            }
+            ControlFunctionKind::EffectControl1Lsb => ControlFunction::EFFECT_CONTROL_1_LSB,
+            ControlFunctionKind::EffectControl2Lsb => ControlFunction::EFFECT_CONTROL_2_LSB,
+            ControlFunctionKind::GeneralPurposeController1Lsb => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_1_LSB
+            }
+            ControlFunctionKind::GeneralPurposeController2Lsb => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_2_LSB
+            }
+            ControlFunctionKind::GeneralPurposeController3Lsb => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_3_LSB
+            }
+            ControlFunctionKind::GeneralPurposeController4Lsb => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_4_LSB
+            }
+            ControlFunctionKind::DamperPedal => ControlFunction::DAMPER_PEDAL,
+            ControlFunctionKind::PortamentoOnOff => ControlFunction::PORTAMENTO_ON_OFF,
+            ControlFunctionKind::Sostenuto => ControlFunction::SOSTENUTO,
+            ControlFunctionKind::SoftPedal => ControlFunction::SOFT_PEDAL,
+            ControlFunctionKind::LegatoFootswitch => ControlFunction::LEGATO_FOOTSWITCH,
+            ControlFunctionKind::Hold2 => ControlFunction::HOLD_2,
+            ControlFunctionKind::SoundController1 => ControlFunction::SOUND_CONTROLLER_1,
+            ControlFunctionKind::SoundController2 => ControlFunction::SOUND_CONTROLLER_2,
+            ControlFunctionKind::SoundController3 => ControlFunction::SOUND_CONTROLLER_3,
+            ControlFunctionKind::SoundController4 => ControlFunction::SOUND_CONTROLLER_4,
+            ControlFunctionKind::SoundController5 => ControlFunction::SOUND_CONTROLLER_5,
+            ControlFunctionKind::SoundController6 => ControlFunction::SOUND_CONTROLLER_6,
+            ControlFunctionKind::SoundController7 => ControlFunction::SOUND_CONTROLLER_7,
+            ControlFunctionKind::SoundController8 => ControlFunction::SOUND_CONTROLLER_8,
+            ControlFunctionKind::SoundController9 => ControlFunction::SOUND_CONTROLLER_9,
+            ControlFunctionKind::SoundController10 => ControlFunction::SOUND_CONTROLLER_10,
+            ControlFunctionKind::GeneralPurposeController5 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_5
+            }
+            ControlFunctionKind::GeneralPurposeController6 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_6
+            }
+            ControlFunctionKind::GeneralPurposeController7 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_7
+            }
+            ControlFunctionKind::GeneralPurposeController8 => {
+                ControlFunction::GENERAL_PURPOSE_CONTROLLER_8
+            }
+            ControlFunctionKind::PortamentoControl => ControlFunction::PORTAMENTO_CONTROL,
+            ControlFunctionKind::Effects1Depth => ControlFunction::EFFECTS_1_DEPTH,
+            ControlFunctionKind::Effects2Depth => ControlFunction::EFFECTS_2_DEPTH,
+            ControlFunctionKind::Effects3Depth => ControlFunction::EFFECTS_3_DEPTH,
+            ControlFunctionKind::Effects4Depth => ControlFunction::EFFECTS_4_DEPTH,
+            ControlFunctionKind::Effects5Depth => ControlFunction::EFFECTS_5_DEPTH,
+            ControlFunctionKind::DataIncrement => ControlFunction::DATA_INCREMENT,
+            ControlFunctionKind::DataDecrement => ControlFunction::DATA_DECREMENT,
+            ControlFunctionKind::NonRegisteredParameterNumberLsb => {
+                ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB
+            }
+            ControlFunctionKind::NonRegisteredParameterNumberMsb => {
+                ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB
+            }
+            ControlFunctionKind::RegisteredParameterNumberLsb => {
+                ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB
+            }
+            ControlFunctionKind::RegisteredParameterNumberMsb => {
+                ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB
+            }
+            ControlFunctionKind::AllSoundOff => ControlFunction::ALL_SOUND_OFF,
+            ControlFunctionKind::ResetAllControllers => ControlFunction::RESET_ALL_CONTROLLERS,
+            ControlFunctionKind::LocalControl => ControlFunction::LOCAL_CONTROL,
+            ControlFunctionKind::AllNotesOff => ControlFunction::ALL_NOTES_OFF,
+            ControlFunctionKind::OmniModeOn => ControlFunction::OMNI_MODE_ON,
+            ControlFunctionKind::OmniModeOff => ControlFunction::OMNI_MODE_OFF,
+            ControlFunctionKind::MonoOperation => ControlFunction::MONO_OPERATION,
+            ControlFunctionKind::PolyOperation => ControlFunction::POLY_OPERATION,
+            ControlFunctionKind::Undefined(cc) => ControlFunction::from(cc),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for cc in ControlFunction::all() {
+            let kind = cc.kind();
+            assert_eq!(
+                matches!(kind, ControlFunctionKind::Undefined(_)),
+                cc.is_undefined()
+            );
+            assert_eq!(ControlFunction::from(kind), cc);
+        }
+        assert_eq!(
+            ControlFunction::BANK_SELECT_LSB.kind(),
+            ControlFunctionKind::BankSelectLsb
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::ControlFunction;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, Not};
+
+/// A set of controllers.
+///
+/// # Example
+/// ```
+/// use wmidi::{ControlFunction, ControlFunctionSet};
+/// let pedals: ControlFunctionSet = ControlFunction::all().filter(|f| f.is_switch()).collect();
+/// assert!(pedals.contains(ControlFunction::DAMPER_PEDAL));
+/// assert_eq!(pedals.len(), 6);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ControlFunctionSet(u128);
+
+impl ControlFunctionSet {
+    /// The set with no controllers.
+    pub const EMPTY: ControlFunctionSet = ControlFunctionSet(0);
+    /// The set with all 128 controllers.
+    pub const ALL: ControlFunctionSet = ControlFunctionSet(u128::MAX);
+
+    /// Returns `true` if `function` is in the set.
+    #[inline(always)]
+    pub fn contains(self, function: ControlFunction) -> bool {
+        self.0 & (1 << u8::from(function)) != 0
+    }
+
+    /// Add `function` to the set.
+    #[inline(always)]
+    pub fn insert(&mut self, function: ControlFunction) {
+        self.0 |= 1 << u8::from(function);
+    }
+
+    /// Remove `function` from the set.
+    #[inline(always)]
+    pub fn remove(&mut self, function: ControlFunction) {
+        self.0 &= !(1 << u8::from(function));
+    }
+
+    /// The number of controllers in the set.
+    #[inline(always)]
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns `true` if the set has no controllers.
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the controllers in the set in ascending order.
+    pub fn iter(self) -> impl Iterator<Item = ControlFunction> {
+        ControlFunction::all().filter(move |f| self.contains(*f))
+    }
+}
+
+impl From<ControlFunction> for ControlFunctionSet {
+    #[inline(always)]
+    fn from(function: ControlFunction) -> ControlFunctionSet {
+        ControlFunctionSet(1 << u8::from(function))
+    }
+}
+
+impl FromIterator<ControlFunction> for ControlFunctionSet {
+    fn from_iter<I: IntoIterator<Item = ControlFunction>>(iter: I) -> ControlFunctionSet {
+        let mut set = ControlFunctionSet::EMPTY;
+        for function in iter {
+            set.insert(function);
+        }
+        set
+    }
+}
+
+impl BitOr for ControlFunctionSet {
+    type Output = ControlFunctionSet;
+
+    #[inline(always)]
+    fn bitor(self, rhs: ControlFunctionSet) -> ControlFunctionSet {
+        ControlFunctionSet(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for ControlFunctionSet {
+    type Output = ControlFunctionSet;
+
+    #[inline(always)]
+    fn bitand(self, rhs: ControlFunctionSet) -> ControlFunctionSet {
+        ControlFunctionSet(self.0 & rhs.0)
+    }
+}
+
+impl Not for ControlFunctionSet {
+    type Output = ControlFunctionSet;
+
+    #[inline(always)]
+    fn not(self) -> ControlFunctionSet {
+        ControlFunctionSet(!self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U7;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = ControlFunctionSet::EMPTY;
+        set.insert(ControlFunction(U7(127)));
+        set.insert(ControlFunction::BANK_SELECT);
+        assert!(set
+            .iter()
+            .eq([ControlFunction::BANK_SELECT, ControlFunction(U7(127))]
+                .iter()
+                .copied()));
+        set.remove(ControlFunction::BANK_SELECT);
+        assert_eq!(set, ControlFunctionSet::from(ControlFunction(U7(127))));
+        assert_eq!((!set).len(), 127);
+        assert_eq!(set | !set, ControlFunctionSet::ALL);
+    }
+}
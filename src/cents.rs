@@ -0,0 +1,131 @@
+use crate::PitchBend;
+use core::ops::{Add, Neg, Sub};
+
+/// A pitch interval measured in cents. There are 100 cents in a half step and 1200 in an octave.
+///
+/// # Example
+/// ```
+/// use wmidi::{Cents, PitchBend};
+/// let whole_step = Cents::from_semitones(2.0);
+/// // A full upwards bend with a +/-2 semitone range.
+/// assert_eq!(whole_step.to_pitch_bend(whole_step), PitchBend::MAX);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Cents(pub f32);
+
+impl Cents {
+    /// No detune.
+    pub const ZERO: Cents = Cents(0.0);
+
+    /// Create an interval from a number of half steps.
+    #[inline(always)]
+    pub fn from_semitones(semitones: f32) -> Cents {
+        Cents(semitones * 100.0)
+    }
+
+    /// The interval in half steps.
+    #[inline(always)]
+    pub fn semitones(self) -> f32 {
+        self.0 / 100.0
+    }
+
+    /// Create an interval from a frequency ratio. For example, `2.0` is one octave.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn from_ratio(ratio: f32) -> Cents {
+        Cents(1200.0 * ratio.log2())
+    }
+
+    /// The frequency ratio of the interval. Multiply a frequency by the ratio to detune it.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn to_ratio(self) -> f32 {
+        2_f32.powf(self.0 / 1200.0)
+    }
+
+    /// The detune produced by `bend` when the pitch bender spans `range` in each direction.
+    ///
+    /// The center (8192) maps to zero and the minimum (0) maps to `-range`. The maximum (16383)
+    /// maps to just below `range`.
+    pub fn from_pitch_bend(bend: PitchBend, range: Cents) -> Cents {
+        Cents(bend.to_f32() * range.0)
+    }
+
+    /// The pitch bend value that produces `self` when the pitch bender spans `range` in each
+    /// direction. Values are rounded to the nearest step and saturate at the ends of the pitch
+    /// bend range.
+    pub fn to_pitch_bend(self, range: Cents) -> PitchBend {
+        let offset = self.0 * 8192.0 / range.0;
+        if offset.is_nan() {
+            return PitchBend::CENTER;
+        }
+        let clamped = offset.clamp(-8192.0, 8191.0);
+        // Round half up. `as` truncates toward zero, so shift to positive values first.
+        PitchBend::from_signed_clamped(((clamped + 8192.5) as i16) - 8192)
+    }
+}
+
+impl Add for Cents {
+    type Output = Cents;
+
+    fn add(self, rhs: Cents) -> Cents {
+        Cents(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Cents {
+    type Output = Cents;
+
+    fn sub(self, rhs: Cents) -> Cents {
+        Cents(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Cents {
+    type Output = Cents;
+
+    fn neg(self) -> Cents {
+        Cents(-self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn pitch_bend_round_trip() {
+        let range = Cents::from_semitones(2.0);
+        assert_eq!(Cents::from_pitch_bend(PitchBend::MIN, range), -range);
+        assert_eq!(
+            Cents::from_pitch_bend(PitchBend::CENTER, range),
+            Cents::ZERO
+        );
+        for raw in 0..=16383 {
+            let bend = PitchBend::try_from(raw).unwrap();
+            let cents = Cents::from_pitch_bend(bend, range);
+            assert_eq!(cents.to_pitch_bend(range), bend);
+        }
+    }
+
+    #[test]
+    fn to_pitch_bend_saturates() {
+        let range = Cents(100.0);
+        assert_eq!(Cents(250.0).to_pitch_bend(range), PitchBend::MAX);
+        assert_eq!(Cents(-250.0).to_pitch_bend(range), PitchBend::MIN);
+        assert_eq!(
+            Cents(10.0).to_pitch_bend(Cents::ZERO),
+            PitchBend::MAX,
+            "a zero range saturates"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ratio() {
+        assert!((Cents::from_ratio(2.0).0 - 1200.0).abs() < 1E-3);
+        assert!((Cents(-1200.0).to_ratio() - 0.5).abs() < 1E-6);
+        assert!((Cents::from_semitones(7.0).to_ratio() - 1.498_307).abs() < 1E-5);
+    }
+}
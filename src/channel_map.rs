@@ -0,0 +1,123 @@
+use crate::{Channel, Channels};
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// Holds a value for each of the 16 MIDI channels.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ChannelMap, MidiMessage};
+/// let mut held_notes: ChannelMap<u32> = ChannelMap::default();
+/// if let MidiMessage::NoteOn(channel, _, _) = MidiMessage::try_from(&[0x92, 60, 100][..]).unwrap() {
+///     held_notes[channel] += 1;
+/// }
+/// assert_eq!(held_notes[Channel::Ch3], 1);
+/// # use std::convert::TryFrom;
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ChannelMap<T>([T; 16]);
+
+impl<T> ChannelMap<T> {
+    /// Create a map from values indexed by `Channel::index`.
+    #[inline(always)]
+    pub fn new(values: [T; 16]) -> ChannelMap<T> {
+        ChannelMap(values)
+    }
+
+    /// Create a map by calling `f` with each channel in ascending order.
+    pub fn from_fn<F: FnMut(Channel) -> T>(mut f: F) -> ChannelMap<T> {
+        ChannelMap(core::array::from_fn(|i| {
+            f(Channel::from_index(i as u8).unwrap())
+        }))
+    }
+
+    /// Iterate over each channel and its value in ascending channel order.
+    #[inline(always)]
+    pub fn iter(&self) -> core::iter::Zip<Channels, slice::Iter<'_, T>> {
+        Channel::all().zip(self.0.iter())
+    }
+
+    /// Iterate over each channel and a mutable reference to its value in ascending channel order.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> core::iter::Zip<Channels, slice::IterMut<'_, T>> {
+        Channel::all().zip(self.0.iter_mut())
+    }
+
+    /// The values, indexed by `Channel::index`.
+    #[inline(always)]
+    pub fn values(&self) -> &[T; 16] {
+        &self.0
+    }
+
+    /// Take the values, indexed by `Channel::index`.
+    #[inline(always)]
+    pub fn into_inner(self) -> [T; 16] {
+        self.0
+    }
+}
+
+impl<T> Index<Channel> for ChannelMap<T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, channel: Channel) -> &T {
+        &self.0[usize::from(channel.index())]
+    }
+}
+
+impl<T> IndexMut<Channel> for ChannelMap<T> {
+    #[inline(always)]
+    fn index_mut(&mut self, channel: Channel) -> &mut T {
+        &mut self.0[usize::from(channel.index())]
+    }
+}
+
+impl<T> From<[T; 16]> for ChannelMap<T> {
+    fn from(values: [T; 16]) -> ChannelMap<T> {
+        ChannelMap::new(values)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ChannelMap<T> {
+    type Item = (Channel, &'a T);
+    type IntoIter = core::iter::Zip<Channels, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ChannelMap<T> {
+    type Item = (Channel, &'a mut T);
+    type IntoIter = core::iter::Zip<Channels, slice::IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_by_channel() {
+        let mut map = ChannelMap::from_fn(|c| c.number());
+        assert_eq!(map[Channel::Ch10], 10);
+        map[Channel::Ch10] = 0;
+        assert_eq!(map.values()[9], 0);
+    }
+
+    #[test]
+    fn iterate_with_channel() {
+        let mut map: ChannelMap<u8> = ChannelMap::default();
+        for (channel, value) in &mut map {
+            *value = channel.index();
+        }
+        for (channel, value) in &map {
+            assert_eq!(channel.index(), *value);
+        }
+        assert_eq!(map.iter().count(), 16);
+        assert_eq!(map, ChannelMap::from_fn(Channel::index));
+    }
+}
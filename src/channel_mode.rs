@@ -0,0 +1,202 @@
+use crate::{Channel, ControlFunction, MidiMessage, U7};
+
+/// [MIDI 1.0] Tracks the mode of a receiver as it is changed by the Omni Mode Off/On, Mono
+/// Operation, and Poly Operation channel mode messages.
+///
+/// | Mode | Omni | Voices |
+/// |------|------|--------|
+/// | 1    | On   | Poly   |
+/// | 2    | On   | Mono   |
+/// | 3    | Off  | Poly   |
+/// | 4    | Off  | Mono   |
+///
+/// Mode changes are only recognized on the basic channel. A receiver starts in mode 1.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ChannelModeState, ControlFunction, MidiMessage, Note, U7};
+/// let mut state = ChannelModeState::new(Channel::Ch1);
+/// state.handle(&MidiMessage::ControlChange(Channel::Ch1, ControlFunction::OMNI_MODE_OFF, U7::MIN));
+/// assert_eq!(state.mode(), 3);
+/// assert!(state.should_honor(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX)));
+/// assert!(!state.should_honor(&MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7::MAX)));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ChannelModeState {
+    basic_channel: Channel,
+    omni: bool,
+    mono: Option<U7>,
+}
+
+impl ChannelModeState {
+    /// Create a receiver in mode 1 (Omni On, Poly) with `basic_channel`.
+    pub fn new(basic_channel: Channel) -> ChannelModeState {
+        ChannelModeState {
+            basic_channel,
+            omni: true,
+            mono: None,
+        }
+    }
+
+    /// The channel that mode messages are recognized on.
+    pub fn basic_channel(&self) -> Channel {
+        self.basic_channel
+    }
+
+    /// `true` if voice messages are received on all channels.
+    pub fn omni(&self) -> bool {
+        self.omni
+    }
+
+    /// The channel count from the last Mono Operation message, or `None` when in Poly
+    /// operation. A count of 0 means one channel per voice of the receiver.
+    pub fn mono(&self) -> Option<U7> {
+        self.mono
+    }
+
+    /// The MIDI 1.0 mode number, between 1 and 4 inclusive.
+    pub fn mode(&self) -> u8 {
+        match (self.omni, self.mono.is_some()) {
+            (true, false) => 1,
+            (true, true) => 2,
+            (false, false) => 3,
+            (false, true) => 4,
+        }
+    }
+
+    /// Update the mode from `message`. Returns `true` if `message` is a mode change message on
+    /// the basic channel. Receivers should also treat these messages as All Notes Off.
+    pub fn handle(&mut self, message: &MidiMessage) -> bool {
+        let (channel, function, value) = match message {
+            MidiMessage::ControlChange(c, f, v) => (*c, *f, *v),
+            _ => return false,
+        };
+        if channel != self.basic_channel {
+            return false;
+        }
+        match function {
+            ControlFunction::OMNI_MODE_OFF => self.omni = false,
+            ControlFunction::OMNI_MODE_ON => self.omni = true,
+            ControlFunction::MONO_OPERATION => self.mono = Some(value),
+            ControlFunction::POLY_OPERATION => self.mono = None,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Returns `true` if a receiver in the current mode should respond to `message`.
+    ///
+    /// System messages are always honored. Channel mode messages (controllers 120-127) are
+    /// only honored on the basic channel. Other channel messages are honored on any channel in
+    /// Omni On, on the basic channel in mode 3, and on the `M` channels starting at the basic
+    /// channel in mode 4, where `M` is the Mono Operation channel count.
+    pub fn should_honor(&self, message: &MidiMessage) -> bool {
+        let channel = match message.channel() {
+            Some(c) => c,
+            None => return true,
+        };
+        if let MidiMessage::ControlChange(_, f, _) = message {
+            if u8::from(*f) >= u8::from(ControlFunction::ALL_SOUND_OFF) {
+                return channel == self.basic_channel;
+            }
+        }
+        if self.omni {
+            return true;
+        }
+        let offset = channel.index().wrapping_sub(self.basic_channel.index());
+        match self.mono {
+            None => offset == 0,
+            Some(count) if u8::from(count) == 0 => offset < 16,
+            Some(count) => offset < u8::from(count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Note;
+
+    fn mode_message(
+        channel: Channel,
+        function: ControlFunction,
+        value: u8,
+    ) -> MidiMessage<'static> {
+        MidiMessage::ControlChange(channel, function, U7(value))
+    }
+
+    #[test]
+    fn mode_changes_only_on_basic_channel() {
+        let mut state = ChannelModeState::new(Channel::Ch3);
+        assert_eq!(state.mode(), 1);
+        assert!(!state.handle(&mode_message(
+            Channel::Ch4,
+            ControlFunction::OMNI_MODE_OFF,
+            0
+        )));
+        assert_eq!(state.mode(), 1);
+        assert!(state.handle(&mode_message(
+            Channel::Ch3,
+            ControlFunction::MONO_OPERATION,
+            4
+        )));
+        assert_eq!(state.mode(), 2);
+        assert!(state.handle(&mode_message(
+            Channel::Ch3,
+            ControlFunction::OMNI_MODE_OFF,
+            0
+        )));
+        assert_eq!(state.mode(), 4);
+        assert_eq!(state.mono(), Some(U7(4)));
+        assert!(state.handle(&mode_message(
+            Channel::Ch3,
+            ControlFunction::POLY_OPERATION,
+            0
+        )));
+        assert_eq!(state.mode(), 3);
+        assert!(!state.handle(&mode_message(
+            Channel::Ch3,
+            ControlFunction::ALL_NOTES_OFF,
+            0
+        )));
+    }
+
+    #[test]
+    fn should_honor() {
+        let note_on = |c| MidiMessage::NoteOn(c, Note::C4, U7(100));
+        let mut state = ChannelModeState::new(Channel::Ch3);
+        assert!(state.should_honor(&note_on(Channel::Ch16)));
+        assert!(state.should_honor(&MidiMessage::TimingClock));
+        assert!(!state.should_honor(&mode_message(
+            Channel::Ch1,
+            ControlFunction::ALL_NOTES_OFF,
+            0
+        )));
+
+        state.handle(&mode_message(
+            Channel::Ch3,
+            ControlFunction::OMNI_MODE_OFF,
+            0,
+        ));
+        assert!(state.should_honor(&note_on(Channel::Ch3)));
+        assert!(!state.should_honor(&note_on(Channel::Ch4)));
+
+        state.handle(&mode_message(
+            Channel::Ch3,
+            ControlFunction::MONO_OPERATION,
+            2,
+        ));
+        assert!(!state.should_honor(&note_on(Channel::Ch2)));
+        assert!(state.should_honor(&note_on(Channel::Ch3)));
+        assert!(state.should_honor(&note_on(Channel::Ch4)));
+        assert!(!state.should_honor(&note_on(Channel::Ch5)));
+
+        state.handle(&mode_message(
+            Channel::Ch3,
+            ControlFunction::MONO_OPERATION,
+            0,
+        ));
+        assert!(!state.should_honor(&note_on(Channel::Ch2)));
+        assert!(state.should_honor(&note_on(Channel::Ch16)));
+    }
+}
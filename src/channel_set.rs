@@ -0,0 +1,180 @@
+use crate::Channel;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, Not};
+
+/// A set of MIDI channels.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ChannelSet};
+/// let drums = ChannelSet::from(Channel::Ch10);
+/// let melodic = !drums;
+/// assert_eq!(melodic.len(), 15);
+/// assert!(!melodic.contains(Channel::Ch10));
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct ChannelSet(u16);
+
+impl ChannelSet {
+    /// The set with no channels.
+    pub const EMPTY: ChannelSet = ChannelSet(0);
+    /// The set with all 16 channels.
+    pub const ALL: ChannelSet = ChannelSet(0xFFFF);
+
+    /// Create a set from a bit mask where bit `i` holds the channel with index `i`.
+    #[inline(always)]
+    pub const fn from_bits(bits: u16) -> ChannelSet {
+        ChannelSet(bits)
+    }
+
+    /// The bit mask where bit `i` holds the channel with index `i`.
+    #[inline(always)]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if `channel` is in the set.
+    #[inline(always)]
+    pub fn contains(self, channel: Channel) -> bool {
+        self.0 & (1 << channel.index()) != 0
+    }
+
+    /// Add `channel` to the set.
+    #[inline(always)]
+    pub fn insert(&mut self, channel: Channel) {
+        self.0 |= 1 << channel.index();
+    }
+
+    /// Remove `channel` from the set.
+    #[inline(always)]
+    pub fn remove(&mut self, channel: Channel) {
+        self.0 &= !(1 << channel.index());
+    }
+
+    /// The number of channels in the set.
+    #[inline(always)]
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns `true` if the set has no channels.
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the channels in the set in ascending order.
+    #[inline(always)]
+    pub fn iter(self) -> ChannelSetIter {
+        ChannelSetIter { bits: self.0 }
+    }
+}
+
+impl From<Channel> for ChannelSet {
+    #[inline(always)]
+    fn from(channel: Channel) -> ChannelSet {
+        ChannelSet(1 << channel.index())
+    }
+}
+
+impl FromIterator<Channel> for ChannelSet {
+    fn from_iter<I: IntoIterator<Item = Channel>>(iter: I) -> ChannelSet {
+        let mut set = ChannelSet::EMPTY;
+        for channel in iter {
+            set.insert(channel);
+        }
+        set
+    }
+}
+
+impl IntoIterator for ChannelSet {
+    type Item = Channel;
+    type IntoIter = ChannelSetIter;
+
+    #[inline(always)]
+    fn into_iter(self) -> ChannelSetIter {
+        self.iter()
+    }
+}
+
+impl BitOr for ChannelSet {
+    type Output = ChannelSet;
+
+    #[inline(always)]
+    fn bitor(self, rhs: ChannelSet) -> ChannelSet {
+        ChannelSet(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for ChannelSet {
+    type Output = ChannelSet;
+
+    #[inline(always)]
+    fn bitand(self, rhs: ChannelSet) -> ChannelSet {
+        ChannelSet(self.0 & rhs.0)
+    }
+}
+
+impl Not for ChannelSet {
+    type Output = ChannelSet;
+
+    #[inline(always)]
+    fn not(self) -> ChannelSet {
+        ChannelSet(!self.0)
+    }
+}
+
+/// An iterator over the channels in a `ChannelSet`. Created with `ChannelSet::iter`.
+#[derive(Clone, Debug)]
+pub struct ChannelSetIter {
+    bits: u16,
+}
+
+impl Iterator for ChannelSetIter {
+    type Item = Channel;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Channel> {
+        if self.bits == 0 {
+            return None;
+        }
+        let index = self.bits.trailing_zeros() as u8;
+        self.bits &= self.bits - 1;
+        Channel::from_index(index).ok()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ChannelSetIter {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = ChannelSet::EMPTY;
+        assert!(set.is_empty());
+        set.insert(Channel::Ch16);
+        set.insert(Channel::Ch1);
+        set.insert(Channel::Ch1);
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.bits(), 0x8001);
+        assert!(set.iter().eq([Channel::Ch1, Channel::Ch16].iter().copied()));
+        set.remove(Channel::Ch1);
+        assert_eq!(set, ChannelSet::from(Channel::Ch16));
+    }
+
+    #[test]
+    fn operators() {
+        let low: ChannelSet = Channel::all().take(8).collect();
+        assert_eq!(low | !low, ChannelSet::ALL);
+        assert_eq!(low & !low, ChannelSet::EMPTY);
+        assert_eq!(ChannelSet::ALL.iter().len(), 16);
+    }
+}
@@ -0,0 +1,373 @@
+//! Documents referred to in this module:
+//! * [RP-015]: Recommended Practice (RP-015): Response to Reset All Controllers
+
+use crate::{
+    Channel, ControlFunction, ControlValue, MidiMessage, Note, PitchBend, ProgramNumber, Velocity,
+    U7,
+};
+
+/// The last seen controller values, program, pitch bend, and pressure of a single channel.
+///
+/// `ChannelState::diff` produces the messages that bring a device from one state to another,
+/// which can be used to save and restore the state of a device or to switch between devices
+/// seamlessly.
+///
+/// Values that have not been received are unknown and are never sent by `diff`. Data Entry,
+/// Data Increment/Decrement, parameter number selection, and channel mode messages are not
+/// tracked since they do not describe a lasting value.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ChannelMap, ChannelState, ControlFunction, MidiMessage, U7};
+/// let mut states: ChannelMap<ChannelState> = ChannelMap::default();
+/// let message = MidiMessage::ControlChange(Channel::Ch2, ControlFunction::CHANNEL_VOLUME, U7::MAX);
+/// if let Some(channel) = message.channel() {
+///     states[channel].handle(&message);
+/// }
+/// assert_eq!(states[Channel::Ch2].controller(ControlFunction::CHANNEL_VOLUME), Some(U7::MAX));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelState {
+    controllers: [Option<ControlValue>; 128],
+    program: Option<ProgramNumber>,
+    pitch_bend: Option<PitchBend>,
+    channel_pressure: Option<Velocity>,
+    note_pressure: [Option<Velocity>; 128],
+}
+
+impl ChannelState {
+    /// Create a state where every value is unknown.
+    pub fn new() -> ChannelState {
+        ChannelState {
+            controllers: [None; 128],
+            program: None,
+            pitch_bend: None,
+            channel_pressure: None,
+            note_pressure: [None; 128],
+        }
+    }
+
+    /// Update the state from `message`. The channel of `message` is not checked.
+    pub fn handle(&mut self, message: &MidiMessage) {
+        match message {
+            MidiMessage::ControlChange(_, ControlFunction::RESET_ALL_CONTROLLERS, _) => {
+                self.reset_all_controllers()
+            }
+            MidiMessage::ControlChange(_, f, v) if is_tracked(*f) => {
+                if f.is_msb() {
+                    // Setting the MSB resets the LSB.
+                    if let Some(lsb) = f.lsb_counterpart() {
+                        self.controllers[usize::from(u8::from(lsb))] = None;
+                    }
+                }
+                self.controllers[usize::from(u8::from(*f))] = Some(*v);
+            }
+            MidiMessage::ProgramChange(_, p) => self.program = Some(*p),
+            MidiMessage::PitchBendChange(_, b) => self.pitch_bend = Some(*b),
+            MidiMessage::ChannelPressure(_, p) => self.channel_pressure = Some(*p),
+            MidiMessage::PolyphonicKeyPressure(_, n, p) => {
+                self.note_pressure[usize::from(u8::from(*n))] = Some(*p)
+            }
+            _ => {}
+        }
+    }
+
+    /// The last value of `function`, or `None` if it is unknown or not tracked.
+    pub fn controller(&self, function: ControlFunction) -> Option<ControlValue> {
+        self.controllers[usize::from(u8::from(function))]
+    }
+
+    /// The last program, or `None` if it is unknown.
+    pub fn program(&self) -> Option<ProgramNumber> {
+        self.program
+    }
+
+    /// The last pitch bend, or `None` if it is unknown.
+    pub fn pitch_bend(&self) -> Option<PitchBend> {
+        self.pitch_bend
+    }
+
+    /// The last channel pressure, or `None` if it is unknown.
+    pub fn channel_pressure(&self) -> Option<Velocity> {
+        self.channel_pressure
+    }
+
+    /// The last polyphonic key pressure of `note`, or `None` if it is unknown.
+    pub fn note_pressure(&self, note: Note) -> Option<Velocity> {
+        self.note_pressure[usize::from(u8::from(note))]
+    }
+
+    /// Emit the messages for `channel` that change a device in state `current` to `self`.
+    /// Only values that are known in `self` and differ from `current` are sent.
+    ///
+    /// Bank Select is sent before the program, and the program is sent again whenever the bank
+    /// changes. MSB controllers are sent before their LSB. Key pressure that is unknown in
+    /// `current` is assumed to be at its reset value of 0.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Channel, ChannelState, MidiMessage, U7};
+    /// let mut saved = ChannelState::new();
+    /// saved.handle(&MidiMessage::ProgramChange(Channel::Ch1, U7::MIN));
+    /// let mut messages = Vec::new();
+    /// saved.diff(&ChannelState::new(), Channel::Ch3, |m| messages.push(m));
+    /// assert_eq!(messages, [MidiMessage::ProgramChange(Channel::Ch3, U7::MIN)]);
+    /// ```
+    pub fn diff<F: FnMut(MidiMessage<'static>)>(
+        &self,
+        current: &ChannelState,
+        channel: Channel,
+        mut emit: F,
+    ) {
+        let bank_functions = [
+            ControlFunction::BANK_SELECT,
+            ControlFunction::BANK_SELECT_LSB,
+        ];
+        let mut bank_changed = false;
+        for function in bank_functions.iter() {
+            if let Some(value) = self.changed_controller(current, *function) {
+                emit(MidiMessage::ControlChange(channel, *function, value));
+                bank_changed = true;
+            }
+        }
+        if let Some(program) = self.program {
+            if bank_changed || current.program != Some(program) {
+                emit(MidiMessage::ProgramChange(channel, program));
+            }
+        }
+        for function in ControlFunction::all() {
+            if bank_functions.contains(&function) {
+                continue;
+            }
+            if let Some(value) = self.changed_controller(current, function) {
+                emit(MidiMessage::ControlChange(channel, function, value));
+            }
+        }
+        if let Some(bend) = self.pitch_bend {
+            if current.pitch_bend != Some(bend) {
+                emit(MidiMessage::PitchBendChange(channel, bend));
+            }
+        }
+        if let Some(pressure) = self.channel_pressure {
+            if current.channel_pressure != Some(pressure) {
+                emit(MidiMessage::ChannelPressure(channel, pressure));
+            }
+        }
+        for note in Note::all() {
+            let index = usize::from(u8::from(note));
+            if let Some(pressure) = self.note_pressure[index] {
+                if current.note_pressure[index].unwrap_or(U7(0)) != pressure {
+                    emit(MidiMessage::PolyphonicKeyPressure(channel, note, pressure));
+                }
+            }
+        }
+    }
+
+    /// The value of `function` in `self` if it is known and either differs from `current`, or
+    /// is an LSB whose MSB is about to be sent.
+    fn changed_controller(
+        &self,
+        current: &ChannelState,
+        function: ControlFunction,
+    ) -> Option<ControlValue> {
+        let value = self.controller(function)?;
+        let msb_changed = function
+            .msb_counterpart()
+            .is_some_and(|msb| self.changed_controller(current, msb).is_some());
+        if msb_changed || current.controller(function) != Some(value) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// [RP-015] Apply Reset All Controllers.
+    fn reset_all_controllers(&mut self) {
+        let resets = [
+            (ControlFunction::MODULATION_WHEEL, U7(0)),
+            (ControlFunction::EXPRESSION_CONTROLLER, U7(127)),
+            (ControlFunction::DAMPER_PEDAL, U7(0)),
+            (ControlFunction::PORTAMENTO_ON_OFF, U7(0)),
+            (ControlFunction::SOSTENUTO, U7(0)),
+            (ControlFunction::SOFT_PEDAL, U7(0)),
+        ];
+        for (function, value) in resets.iter() {
+            self.controllers[usize::from(u8::from(*function))] = Some(*value);
+            if let Some(lsb) = function.lsb_counterpart() {
+                self.controllers[usize::from(u8::from(lsb))] = None;
+            }
+        }
+        self.pitch_bend = Some(PitchBend::CENTER);
+        self.channel_pressure = Some(U7(0));
+        self.note_pressure = [Some(U7(0)); 128];
+    }
+}
+
+impl Default for ChannelState {
+    fn default() -> ChannelState {
+        ChannelState::new()
+    }
+}
+
+/// Returns `true` if `function` holds a lasting value.
+fn is_tracked(function: ControlFunction) -> bool {
+    !function.is_channel_mode()
+        && !matches!(
+            function,
+            ControlFunction::DATA_ENTRY_MSB
+                | ControlFunction::DATA_ENTRY_LSB
+                | ControlFunction::DATA_INCREMENT
+                | ControlFunction::DATA_DECREMENT
+                | ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB
+                | ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB
+                | ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB
+                | ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn diff_count(target: &ChannelState, current: &ChannelState) -> usize {
+        let mut count = 0;
+        target.diff(current, Channel::Ch1, |_| count += 1);
+        count
+    }
+
+    fn replay(target: &ChannelState, current: &ChannelState) -> ChannelState {
+        let mut result = current.clone();
+        target.diff(current, Channel::Ch1, |m| result.handle(&m));
+        result
+    }
+
+    #[test]
+    fn tracks_values() {
+        let mut state = ChannelState::new();
+        state.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::PAN,
+            U7(10),
+        ));
+        state.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::DATA_ENTRY_MSB,
+            U7(10),
+        ));
+        state.handle(&MidiMessage::PitchBendChange(Channel::Ch1, PitchBend::MAX));
+        state.handle(&MidiMessage::PolyphonicKeyPressure(
+            Channel::Ch1,
+            Note::C4,
+            U7(3),
+        ));
+        assert_eq!(state.controller(ControlFunction::PAN), Some(U7(10)));
+        assert_eq!(state.controller(ControlFunction::DATA_ENTRY_MSB), None);
+        assert_eq!(state.pitch_bend(), Some(PitchBend::MAX));
+        assert_eq!(state.note_pressure(Note::C4), Some(U7(3)));
+        assert_eq!(state.note_pressure(Note::D4), None);
+        assert_eq!(state.program(), None);
+        assert_eq!(state.channel_pressure(), None);
+
+        state.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::RESET_ALL_CONTROLLERS,
+            U7(0),
+        ));
+        assert_eq!(state.pitch_bend(), Some(PitchBend::CENTER));
+        assert_eq!(state.note_pressure(Note::C4), Some(U7(0)));
+        assert_eq!(state.controller(ControlFunction::PAN), Some(U7(10)));
+    }
+
+    #[test]
+    fn msb_resets_lsb() {
+        let mut state = ChannelState::new();
+        state.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::CHANNEL_VOLUME_LSB,
+            U7(1),
+        ));
+        state.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::CHANNEL_VOLUME,
+            U7(100),
+        ));
+        assert_eq!(state.controller(ControlFunction::CHANNEL_VOLUME_LSB), None);
+    }
+
+    #[test]
+    fn diff_restores_state() {
+        let mut target = ChannelState::new();
+        let messages = [
+            MidiMessage::ControlChange(Channel::Ch1, ControlFunction::BANK_SELECT, U7(1)),
+            MidiMessage::ControlChange(Channel::Ch1, ControlFunction::CHANNEL_VOLUME, U7(90)),
+            MidiMessage::ControlChange(Channel::Ch1, ControlFunction::CHANNEL_VOLUME_LSB, U7(5)),
+            MidiMessage::ProgramChange(Channel::Ch1, U7(7)),
+            MidiMessage::ChannelPressure(Channel::Ch1, U7(2)),
+            MidiMessage::PolyphonicKeyPressure(Channel::Ch1, Note::A0, U7(9)),
+        ];
+        for m in messages.iter() {
+            target.handle(m);
+        }
+        let restored = replay(&target, &ChannelState::new());
+        assert_eq!(restored, target);
+        assert_eq!(diff_count(&target, &restored), 0);
+        assert_eq!(diff_count(&target, &ChannelState::new()), messages.len());
+    }
+
+    #[test]
+    fn diff_skips_reset_key_pressure() {
+        let mut target = ChannelState::new();
+        target.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::RESET_ALL_CONTROLLERS,
+            U7(0),
+        ));
+        target.handle(&MidiMessage::PolyphonicKeyPressure(
+            Channel::Ch1,
+            Note::C4,
+            U7(3),
+        ));
+        // Six controllers, pitch bend, channel pressure and the one note with pressure.
+        assert_eq!(diff_count(&target, &ChannelState::new()), 9);
+    }
+
+    #[test]
+    fn diff_resends_lsb_and_program() {
+        let mut target = ChannelState::new();
+        let mut current = ChannelState::new();
+        for state in [&mut target, &mut current].iter_mut() {
+            state.handle(&MidiMessage::ControlChange(
+                Channel::Ch1,
+                ControlFunction::BANK_SELECT,
+                U7(1),
+            ));
+            state.handle(&MidiMessage::ControlChange(
+                Channel::Ch1,
+                ControlFunction::CHANNEL_VOLUME_LSB,
+                U7(5),
+            ));
+            state.handle(&MidiMessage::ProgramChange(Channel::Ch1, U7(7)));
+        }
+        target.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::CHANNEL_VOLUME,
+            U7(90),
+        ));
+        target.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::CHANNEL_VOLUME_LSB,
+            U7(5),
+        ));
+        // The volume LSB must follow the MSB even though it has not changed.
+        assert_eq!(diff_count(&target, &current), 2);
+        assert_eq!(replay(&target, &current), target);
+
+        target.handle(&MidiMessage::ControlChange(
+            Channel::Ch1,
+            ControlFunction::BANK_SELECT,
+            U7(2),
+        ));
+        // The program is resent to apply the new bank.
+        assert_eq!(diff_count(&target, &current), 4);
+    }
+}
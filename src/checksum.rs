@@ -0,0 +1,74 @@
+//! Checksums used by SysEx messages to detect corrupted data.
+//!
+//! Each function takes the bytes covered by the checksum, which depend on the message. For
+//! example, Roland checksums cover the address and data, while Sample Dump Standard checksums
+//! cover every byte after the start byte.
+//!
+//! # Example
+//! ```
+//! use wmidi::{checksum, U7};
+//! // The address and data of a Roland GS Reset.
+//! let data = U7::try_from_bytes(&[0x40, 0x00, 0x7F, 0x00]).unwrap();
+//! let sum = checksum::sum_to_zero(data);
+//! assert_eq!(sum, U7::from_u8_lossy(0x41));
+//! assert!(checksum::is_sum_to_zero(data, sum));
+//! ```
+
+use crate::U7;
+
+/// The checksum that makes the sum of `data` and the checksum a multiple of 128. This is the
+/// two's complement of the 7 bit sum, used by Roland exclusive messages and Yamaha bulk dumps.
+pub fn sum_to_zero(data: &[U7]) -> U7 {
+    U7(sum_to_zero_bytes(U7::data_to_bytes(data)))
+}
+
+/// Returns `true` if `checksum` is the `sum_to_zero` checksum of `data`.
+pub fn is_sum_to_zero(data: &[U7], checksum: U7) -> bool {
+    sum_to_zero(data) == checksum
+}
+
+/// The sum of `data`, masked to 7 bits. Some Yamaha and Kawai devices send this sum itself
+/// rather than its complement.
+pub fn sum(data: &[U7]) -> U7 {
+    U7(sum_bytes(U7::data_to_bytes(data)))
+}
+
+/// The exclusive or of `data`. This is used by the Sample Dump, File Dump, and MIDI Tuning
+/// standards, and by some Yamaha devices.
+pub fn xor(data: &[U7]) -> U7 {
+    U7(xor_bytes(U7::data_to_bytes(data)))
+}
+
+pub(crate) fn sum_to_zero_bytes(bytes: &[u8]) -> u8 {
+    (0x80 - sum_bytes(bytes)) & 0x7F
+}
+
+pub(crate) fn sum_bytes(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) & 0x7F
+}
+
+pub(crate) fn xor_bytes(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |checksum, b| checksum ^ b) & 0x7F
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        let gs_reset = U7::try_from_bytes(&[0x40, 0x00, 0x7F, 0x00]).unwrap();
+        assert_eq!(sum_to_zero(gs_reset), U7(0x41));
+        assert_eq!(sum(gs_reset), U7(0x3F));
+        assert_eq!(xor(gs_reset), U7(0x3F));
+        let data = U7::try_from_bytes(&[0x40, 0x01, 0x30, 0x00]).unwrap();
+        assert_eq!(sum_to_zero(data), U7(0x0F));
+        assert!(!is_sum_to_zero(data, U7(0x10)));
+        assert_eq!(
+            xor(U7::try_from_bytes(&[0x7E, 0x55, 0x2A]).unwrap()),
+            U7(0x01)
+        );
+        assert_eq!(sum_to_zero(&[]), U7::MIN);
+        assert_eq!(sum(&[U7::MAX, U7(2)]), U7(1));
+    }
+}
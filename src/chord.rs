@@ -0,0 +1,218 @@
+use crate::Note;
+
+/// The quality of a `Chord`, independent of its root.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    /// A triad with the 3rd replaced by a major 2nd.
+    Suspended2,
+    /// A triad with the 3rd replaced by a perfect 4th.
+    Suspended4,
+    Dominant7,
+    Major7,
+    Minor7,
+    /// A diminished triad with a minor 7th.
+    HalfDiminished7,
+    /// A diminished triad with a diminished 7th.
+    Diminished7,
+    /// A minor triad with a major 7th.
+    MinorMajor7,
+}
+
+impl ChordQuality {
+    /// All qualities, in the order they are tried by `Chord::detect`.
+    const ALL: [ChordQuality; 12] = [
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Diminished,
+        ChordQuality::Augmented,
+        ChordQuality::Suspended4,
+        ChordQuality::Suspended2,
+        ChordQuality::Dominant7,
+        ChordQuality::Major7,
+        ChordQuality::Minor7,
+        ChordQuality::HalfDiminished7,
+        ChordQuality::Diminished7,
+        ChordQuality::MinorMajor7,
+    ];
+
+    /// The chord tones as half steps above the root, ordered by stacking: root, 3rd (or
+    /// suspension), 5th, and 7th.
+    pub fn intervals(self) -> &'static [u8] {
+        match self {
+            ChordQuality::Major => &[0, 4, 7],
+            ChordQuality::Minor => &[0, 3, 7],
+            ChordQuality::Diminished => &[0, 3, 6],
+            ChordQuality::Augmented => &[0, 4, 8],
+            ChordQuality::Suspended2 => &[0, 2, 7],
+            ChordQuality::Suspended4 => &[0, 5, 7],
+            ChordQuality::Dominant7 => &[0, 4, 7, 10],
+            ChordQuality::Major7 => &[0, 4, 7, 11],
+            ChordQuality::Minor7 => &[0, 3, 7, 10],
+            ChordQuality::HalfDiminished7 => &[0, 3, 6, 10],
+            ChordQuality::Diminished7 => &[0, 3, 6, 9],
+            ChordQuality::MinorMajor7 => &[0, 3, 7, 11],
+        }
+    }
+
+    /// The conventional chord symbol suffix. For example: `"m7"` for `Minor7`.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            ChordQuality::Major => "",
+            ChordQuality::Minor => "m",
+            ChordQuality::Diminished => "dim",
+            ChordQuality::Augmented => "aug",
+            ChordQuality::Suspended2 => "sus2",
+            ChordQuality::Suspended4 => "sus4",
+            ChordQuality::Dominant7 => "7",
+            ChordQuality::Major7 => "maj7",
+            ChordQuality::Minor7 => "m7",
+            ChordQuality::HalfDiminished7 => "m7b5",
+            ChordQuality::Diminished7 => "dim7",
+            ChordQuality::MinorMajor7 => "mMaj7",
+        }
+    }
+
+    fn pitch_class_mask(self) -> u16 {
+        self.intervals()
+            .iter()
+            .fold(0, |mask, interval| mask | 1 << interval)
+    }
+}
+
+/// Which chord tone is in the bass.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Inversion {
+    /// The root is in the bass.
+    RootPosition,
+    /// The 3rd is in the bass.
+    First,
+    /// The 5th is in the bass.
+    Second,
+    /// The 7th is in the bass.
+    Third,
+}
+
+/// A chord identified from a set of notes.
+///
+/// # Example
+/// ```
+/// use wmidi::{Chord, ChordQuality, Inversion, Note};
+/// let chord = Chord::detect(&[Note::E3, Note::G3, Note::C4]).unwrap();
+/// assert_eq!(chord.root, Note::C4);
+/// assert_eq!(chord.quality, ChordQuality::Major);
+/// assert_eq!(chord.inversion, Inversion::First);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Chord {
+    /// The lowest held note whose pitch class is the root of the chord.
+    pub root: Note,
+    /// The quality of the chord.
+    pub quality: ChordQuality,
+    /// The chord tone in the bass.
+    pub inversion: Inversion,
+}
+
+impl Chord {
+    /// Identify the chord formed by `notes`. The order of `notes` does not matter and doubled
+    /// notes (in any octave) are ignored. Returns `None` if the pitch classes do not form one of
+    /// the chords in `ChordQuality`.
+    ///
+    /// Some chords are ambiguous; for example, every note of an augmented triad could be its
+    /// root. In that case the chord rooted on the bass note is preferred.
+    pub fn detect(notes: &[Note]) -> Option<Chord> {
+        let bass = u8::from(*notes.iter().min()?);
+        let mask = notes
+            .iter()
+            .fold(0u16, |mask, note| mask | 1 << (u8::from(*note) % 12));
+        // Try the bass first so that symmetrical chords are rooted on it.
+        for offset in 0..12 {
+            let root_pitch_class = (bass + offset) % 12;
+            if mask & (1 << root_pitch_class) == 0 {
+                continue;
+            }
+            let relative_mask =
+                ((mask >> root_pitch_class) | (mask << (12 - root_pitch_class))) & 0x0FFF;
+            let quality = match ChordQuality::ALL
+                .iter()
+                .find(|q| q.pitch_class_mask() == relative_mask)
+            {
+                Some(q) => *q,
+                None => continue,
+            };
+            let bass_interval = (bass + 12 - root_pitch_class) % 12;
+            let inversion = match quality.intervals().iter().position(|i| *i == bass_interval) {
+                Some(0) => Inversion::RootPosition,
+                Some(1) => Inversion::First,
+                Some(2) => Inversion::Second,
+                _ => Inversion::Third,
+            };
+            let root = notes
+                .iter()
+                .copied()
+                .filter(|n| u8::from(*n) % 12 == root_pitch_class)
+                .min()?;
+            return Some(Chord {
+                root,
+                quality,
+                inversion,
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect_triads() {
+        let chord = Chord::detect(&[Note::A3, Note::C4, Note::E4]).unwrap();
+        assert_eq!(
+            (chord.root, chord.quality, chord.inversion),
+            (Note::A3, ChordQuality::Minor, Inversion::RootPosition)
+        );
+        let chord = Chord::detect(&[Note::F5, Note::B3, Note::D4]).unwrap();
+        assert_eq!(
+            (chord.root, chord.quality, chord.inversion),
+            (Note::B3, ChordQuality::Diminished, Inversion::RootPosition)
+        );
+        let chord = Chord::detect(&[Note::D3, Note::G3, Note::B3, Note::G4]).unwrap();
+        assert_eq!(
+            (chord.root, chord.quality, chord.inversion),
+            (Note::G3, ChordQuality::Major, Inversion::Second)
+        );
+    }
+
+    #[test]
+    fn detect_sevenths() {
+        let chord = Chord::detect(&[Note::F3, Note::G3, Note::B3, Note::D4]).unwrap();
+        assert_eq!(
+            (chord.root, chord.quality, chord.inversion),
+            (Note::G3, ChordQuality::Dominant7, Inversion::Third)
+        );
+        let chord = Chord::detect(&[Note::C4, Note::E4, Note::G4, Note::B4]).unwrap();
+        assert_eq!(chord.quality, ChordQuality::Major7);
+    }
+
+    #[test]
+    fn symmetrical_chords_are_rooted_on_the_bass() {
+        let chord = Chord::detect(&[Note::E3, Note::Ab3, Note::C4]).unwrap();
+        assert_eq!(
+            (chord.root, chord.quality, chord.inversion),
+            (Note::E3, ChordQuality::Augmented, Inversion::RootPosition)
+        );
+    }
+
+    #[test]
+    fn unrecognized_sets() {
+        assert_eq!(Chord::detect(&[]), None);
+        assert_eq!(Chord::detect(&[Note::C4]), None);
+        assert_eq!(Chord::detect(&[Note::C4, Note::G4]), None);
+        assert_eq!(Chord::detect(&[Note::C4, Note::Db4, Note::D4]), None);
+    }
+}
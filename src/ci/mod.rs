@@ -0,0 +1,205 @@
+//! MIDI Capability Inquiry (MIDI-CI) messages, which let devices discover and configure each
+//! other's capabilities.
+//!
+//! Every MIDI-CI message is a Universal Non-Real Time SysEx message that starts with a `Header`,
+//! which holds the version of the message format and the MUIDs of the sender and receiver.
+//!
+//! Documents referred to in this module:
+//! * [MIDI-CI]: MIDI Capability Inquiry (MIDI-CI), version 1.2
+
+use crate::{Channel, U7};
+
+mod profile;
+mod property;
+
+pub use self::profile::{ProfileId, ProfileIds, ProfileIdsIter, ProfileMessage};
+#[cfg(feature = "std")]
+pub use self::property::{Property, PropertyReassembler};
+pub use self::property::{
+    PropertyCapabilities, PropertyChunk, PropertyChunks, PropertyKind, PropertyMessage,
+    ReassemblyError,
+};
+
+/// [MIDI-CI] The number of bytes in the header of a SysEx message, including the start byte.
+pub(crate) const HEADER_SIZE: usize = 14;
+
+/// [MIDI-CI] A MIDI Unique Identifier, a random 28 bit number that identifies a device in MIDI-CI
+/// transactions.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Muid(u32);
+
+impl Muid {
+    /// The MUID that addresses every device.
+    pub const BROADCAST: Muid = Muid(0x0FFF_FFFF);
+
+    /// Create a MUID, or return `None` if `value` does not fit in 28 bits.
+    pub fn new(value: u32) -> Option<Muid> {
+        if value > 0x0FFF_FFFF {
+            return None;
+        }
+        Some(Muid(value))
+    }
+
+    /// Create a MUID from the lower 28 bits of `value`.
+    pub const fn from_u32_lossy(value: u32) -> Muid {
+        Muid(value & 0x0FFF_FFFF)
+    }
+
+    /// Returns `true` for MUIDs reserved by [MIDI-CI], including `Muid::BROADCAST`. Devices must
+    /// not pick a reserved MUID for themselves.
+    pub fn is_reserved(self) -> bool {
+        self.0 >= 0x0FFF_FF00
+    }
+
+    fn copy_to_slice(self, slice: &mut [u8]) {
+        for (i, byte) in slice[..4].iter_mut().enumerate() {
+            *byte = (self.0 >> (7 * i)) as u8 & 0x7F;
+        }
+    }
+
+    fn from_data(data: &[u8]) -> Muid {
+        Muid(
+            data[..4]
+                .iter()
+                .enumerate()
+                .fold(0, |value, (i, byte)| value | u32::from(*byte) << (7 * i)),
+        )
+    }
+}
+
+impl From<Muid> for u32 {
+    fn from(muid: Muid) -> u32 {
+        muid.0
+    }
+}
+
+/// [MIDI-CI] The part of a device that a message is for, given by the device ID byte.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Address {
+    /// A single channel, device ID 0x00 to 0x0F.
+    Channel(Channel),
+    /// Every channel of the group, device ID 0x7E.
+    Group,
+    /// The whole function block, device ID 0x7F.
+    FunctionBlock,
+}
+
+impl Address {
+    /// The device ID byte of the address.
+    pub fn device_id(self) -> U7 {
+        match self {
+            Address::Channel(channel) => U7(channel.index()),
+            Address::Group => U7(0x7E),
+            Address::FunctionBlock => U7(0x7F),
+        }
+    }
+
+    /// The address given by `device_id`, or `None` if it is not defined.
+    pub fn from_device_id(device_id: U7) -> Option<Address> {
+        match u8::from(device_id) {
+            0x7E => Some(Address::Group),
+            0x7F => Some(Address::FunctionBlock),
+            index => Channel::from_index(index).ok().map(Address::Channel),
+        }
+    }
+}
+
+/// [MIDI-CI] The fields shared by every MIDI-CI message.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Header {
+    /// The part of the receiver that the message is for.
+    pub address: Address,
+    /// The version of the message format. Receivers should accept messages with a newer version
+    /// and ignore the fields they do not know.
+    pub version: U7,
+    /// The MUID of the sender.
+    pub source: Muid,
+    /// The MUID of the receiver, or `Muid::BROADCAST`.
+    pub destination: Muid,
+}
+
+impl Header {
+    /// The version of the message format written by this module, for MIDI-CI 1.2.
+    pub const VERSION: U7 = U7(0x02);
+
+    /// Create a header with the current `Header::VERSION`.
+    pub fn new(address: Address, source: Muid, destination: Muid) -> Header {
+        Header {
+            address,
+            version: Header::VERSION,
+            source,
+            destination,
+        }
+    }
+
+    /// Write the start byte and header of a message with `sub_id2` to `slice`, which must hold
+    /// at least `HEADER_SIZE` bytes.
+    pub(crate) fn copy_to_slice(&self, sub_id2: u8, slice: &mut [u8]) {
+        slice[..6].copy_from_slice(&[
+            0xF0,
+            0x7E,
+            u8::from(self.address.device_id()),
+            0x0D,
+            sub_id2,
+            u8::from(self.version),
+        ]);
+        self.source.copy_to_slice(&mut slice[6..10]);
+        self.destination.copy_to_slice(&mut slice[10..14]);
+    }
+
+    /// Parse the header at the start of the payload of a `MidiMessage::SysEx`. Returns the
+    /// header, the sub-ID #2 of the message, and the rest of the payload.
+    pub(crate) fn from_sysex(payload: &[U7]) -> Option<(Header, u8, &[U7])> {
+        match U7::data_to_bytes(payload) {
+            [0x7E, device_id, 0x0D, sub_id2, version, muids @ ..] if muids.len() >= 8 => {
+                let header = Header {
+                    address: Address::from_device_id(U7(*device_id))?,
+                    version: U7(*version),
+                    source: Muid::from_data(&muids[..4]),
+                    destination: Muid::from_data(&muids[4..8]),
+                };
+                Some((header, *sub_id2, &payload[HEADER_SIZE - 1..]))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn header() {
+        let header = Header::new(
+            Address::Channel(Channel::Ch3),
+            Muid::from_u32_lossy(0x0123_4567),
+            Muid::BROADCAST,
+        );
+        let mut bytes = [0u8; HEADER_SIZE];
+        header.copy_to_slice(0x20, &mut bytes);
+        assert_eq!(
+            bytes,
+            [0xF0, 0x7E, 0x02, 0x0D, 0x20, 0x02, 0x67, 0x0A, 0x0D, 0x09, 0x7F, 0x7F, 0x7F, 0x7F]
+        );
+        let payload = U7::try_from_bytes(&bytes[1..]).unwrap();
+        assert_eq!(
+            Header::from_sysex(payload),
+            Some((header, 0x20, &payload[13..]))
+        );
+        assert_eq!(Header::from_sysex(&payload[..12]), None);
+    }
+
+    #[test]
+    fn muid() {
+        assert_eq!(Muid::new(0x1000_0000), None);
+        assert_eq!(Muid::from_u32_lossy(0x1000_0001), Muid(1));
+        assert!(Muid::BROADCAST.is_reserved());
+        assert!(!Muid(0x0FFF_FEFF).is_reserved());
+        assert_eq!(Address::from_device_id(U7(0x10)), None);
+        assert_eq!(
+            Address::from_device_id(U7(0x0F)),
+            Some(Address::Channel(Channel::Ch16))
+        );
+    }
+}
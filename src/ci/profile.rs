@@ -0,0 +1,448 @@
+use super::{Address, Header, HEADER_SIZE};
+use crate::{ToSliceError, U14, U7};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MIDI-CI] The ID of a profile, a set of behaviours that a device can enable, such as those of
+/// a drawbar organ.
+///
+/// Profiles defined by the MIDI Association start with 0x7E. Other profiles start with the
+/// manufacturer ID of the company that defined them.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProfileId(pub [U7; 5]);
+
+impl ProfileId {
+    /// The ID of a profile defined by the MIDI Association, with its bank, number, version, and
+    /// level.
+    pub fn standard(bank: U7, number: U7, version: U7, level: U7) -> ProfileId {
+        ProfileId([U7(0x7E), bank, number, version, level])
+    }
+
+    /// Returns `true` if the profile is defined by the MIDI Association.
+    pub fn is_standard(&self) -> bool {
+        self.0[0] == U7(0x7E)
+    }
+
+    fn from_data(data: &[U7]) -> ProfileId {
+        ProfileId([data[0], data[1], data[2], data[3], data[4]])
+    }
+}
+
+/// [MIDI-CI] A list of profiles in a `ProfileMessage::Reply`, either given as a slice or parsed
+/// from a message.
+#[derive(Copy, Clone)]
+pub struct ProfileIds<'a>(Ids<'a>);
+
+#[derive(Copy, Clone)]
+enum Ids<'a> {
+    Ids(&'a [ProfileId]),
+    // A multiple of 5 data bytes.
+    Data(&'a [U7]),
+}
+
+impl<'a> ProfileIds<'a> {
+    /// The profiles in `ids`.
+    pub fn new(ids: &'a [ProfileId]) -> ProfileIds<'a> {
+        ProfileIds(Ids::Ids(ids))
+    }
+
+    /// The number of profiles.
+    pub fn len(&self) -> usize {
+        match self.0 {
+            Ids::Ids(ids) => ids.len(),
+            Ids::Data(data) => data.len() / 5,
+        }
+    }
+
+    /// Returns `true` if there are no profiles.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// An iterator over the profiles.
+    pub fn iter(&self) -> ProfileIdsIter<'a> {
+        ProfileIdsIter(self.0)
+    }
+
+    fn bytes_size(&self) -> usize {
+        2 + 5 * self.len()
+    }
+
+    fn copy_to_slice(&self, slice: &mut [u8]) {
+        let len = self.len();
+        slice[..2].copy_from_slice(&[len as u8 & 0x7F, (len >> 7) as u8 & 0x7F]);
+        for (dst, id) in slice[2..].chunks_exact_mut(5).zip(self.iter()) {
+            dst.copy_from_slice(U7::data_to_bytes(&id.0));
+        }
+    }
+
+    // Parse a count and the IDs that follow it, returning the IDs and the rest of `data`.
+    fn from_data(data: &'a [U7]) -> Option<(ProfileIds<'a>, &'a [U7])> {
+        let count = match U7::data_to_bytes(data) {
+            [lsb, msb, ..] => usize::from(*lsb) | usize::from(*msb) << 7,
+            _ => return None,
+        };
+        let ids = data.get(2..2 + 5 * count)?;
+        Some((ProfileIds(Ids::Data(ids)), &data[2 + 5 * count..]))
+    }
+}
+
+impl<'a> From<&'a [ProfileId]> for ProfileIds<'a> {
+    fn from(ids: &'a [ProfileId]) -> ProfileIds<'a> {
+        ProfileIds::new(ids)
+    }
+}
+
+impl<'a> IntoIterator for ProfileIds<'a> {
+    type Item = ProfileId;
+    type IntoIter = ProfileIdsIter<'a>;
+
+    fn into_iter(self) -> ProfileIdsIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> PartialEq for ProfileIds<'a> {
+    fn eq(&self, other: &ProfileIds<'a>) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a> Eq for ProfileIds<'a> {}
+
+impl<'a> Hash for ProfileIds<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for id in self.iter() {
+            id.hash(state);
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ProfileIds<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the profiles of `ProfileIds`.
+#[derive(Clone)]
+pub struct ProfileIdsIter<'a>(Ids<'a>);
+
+impl<'a> Iterator for ProfileIdsIter<'a> {
+    type Item = ProfileId;
+
+    fn next(&mut self) -> Option<ProfileId> {
+        match &mut self.0 {
+            Ids::Ids(ids) => {
+                let (id, rest) = ids.split_first()?;
+                *ids = rest;
+                Some(*id)
+            }
+            Ids::Data(data) => {
+                if data.len() < 5 {
+                    return None;
+                }
+                let (id, rest) = data.split_at(5);
+                *data = rest;
+                Some(ProfileId::from_data(id))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = ProfileIds(self.0).len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for ProfileIdsIter<'a> {}
+
+impl<'a> fmt::Debug for ProfileIdsIter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProfileIdsIter")
+            .field(&ProfileIds(self.0))
+            .finish()
+    }
+}
+
+/// [MIDI-CI] The messages that find and configure the profiles of a device.
+///
+/// The profiles of a channel are found and set by addressing the channel in the `Header`, and
+/// those of a whole group or function block by addressing the group or function block. Since
+/// version 0x02 of the message format, a profile may span several channels starting at the
+/// addressed one.
+///
+/// # Example
+/// ```
+/// use wmidi::ci::{Address, Header, Muid, ProfileId, ProfileMessage};
+/// use wmidi::{Channel, U14, U7};
+/// let header = Header::new(
+///     Address::Channel(Channel::Ch1),
+///     Muid::from_u32_lossy(0x0123_4567),
+///     Muid::from_u32_lossy(0x0765_4321),
+/// );
+/// let organ = ProfileId::standard(
+///     U7::from_u8_lossy(0x21),
+///     U7::MIN,
+///     U7::from_u8_lossy(0x01),
+///     U7::MIN,
+/// );
+/// let message = ProfileMessage::SetOn {
+///     profile: organ,
+///     channels: U14::from_u16_lossy(1),
+/// };
+/// let mut bytes = [0u8; 32];
+/// let size = message.copy_sysex_to_slice(&header, &mut bytes).unwrap();
+/// assert_eq!(size, message.bytes_size(&header));
+/// let payload = U7::try_from_bytes(&bytes[1..size - 1]).unwrap();
+/// assert_eq!(ProfileMessage::from_sysex(payload), Some((header, message)));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ProfileMessage<'a> {
+    /// Ask which profiles the receiver supports at the address.
+    Inquiry,
+    /// The reply to an `Inquiry`, listing the enabled and disabled profiles at the address.
+    Reply {
+        enabled: ProfileIds<'a>,
+        disabled: ProfileIds<'a>,
+    },
+    /// Ask the receiver to enable a profile on `channels` channels, or 0 for a profile on a group
+    /// or function block. The receiver replies with an `Enabled` report.
+    SetOn { profile: ProfileId, channels: U14 },
+    /// Ask the receiver to disable a profile. The receiver replies with a `Disabled` report.
+    SetOff { profile: ProfileId },
+    /// Reports that a profile has been enabled on `channels` channels.
+    Enabled { profile: ProfileId, channels: U14 },
+    /// Reports that a profile has been disabled on `channels` channels.
+    Disabled { profile: ProfileId, channels: U14 },
+}
+
+impl<'a> ProfileMessage<'a> {
+    fn sub_id2(&self) -> u8 {
+        match self {
+            ProfileMessage::Inquiry => 0x20,
+            ProfileMessage::Reply { .. } => 0x21,
+            ProfileMessage::SetOn { .. } => 0x22,
+            ProfileMessage::SetOff { .. } => 0x23,
+            ProfileMessage::Enabled { .. } => 0x24,
+            ProfileMessage::Disabled { .. } => 0x25,
+        }
+    }
+
+    /// The number of bytes in the message, including the start and end bytes. This depends on
+    /// the version in `header`.
+    pub fn bytes_size(&self, header: &Header) -> usize {
+        let body = match self {
+            ProfileMessage::Inquiry => 0,
+            ProfileMessage::Reply { enabled, disabled } => {
+                enabled.bytes_size() + disabled.bytes_size()
+            }
+            _ if header.version < Header::VERSION => 5,
+            _ => 7,
+        };
+        HEADER_SIZE + body + 1
+    }
+
+    /// Write the SysEx message to `slice`, including the start and end bytes. Messages with a
+    /// `header` version before 0x02 are written without the number of channels. Returns the
+    /// number of bytes written.
+    pub fn copy_sysex_to_slice(
+        &self,
+        header: &Header,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size(header);
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        header.copy_to_slice(self.sub_id2(), slice);
+        let body = &mut slice[HEADER_SIZE..size - 1];
+        match self {
+            ProfileMessage::Inquiry => {}
+            ProfileMessage::Reply { enabled, disabled } => {
+                let (first, second) = body.split_at_mut(enabled.bytes_size());
+                enabled.copy_to_slice(first);
+                disabled.copy_to_slice(second);
+            }
+            ProfileMessage::SetOn { profile, channels }
+            | ProfileMessage::Enabled { profile, channels }
+            | ProfileMessage::Disabled { profile, channels } => {
+                body[..5].copy_from_slice(U7::data_to_bytes(&profile.0));
+                if body.len() > 5 {
+                    let channels = u16::from(*channels);
+                    body[5..].copy_from_slice(&[channels as u8 & 0x7F, (channels >> 7) as u8]);
+                }
+            }
+            ProfileMessage::SetOff { profile } => {
+                body[..5].copy_from_slice(U7::data_to_bytes(&profile.0));
+                for b in body[5..].iter_mut() {
+                    *b = 0;
+                }
+            }
+        }
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The SysEx message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, header: &Header) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size(header)];
+        self.copy_sysex_to_slice(header, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the header and message, or `None` if the payload is not a profile message.
+    ///
+    /// Fields added by newer versions of the message format are ignored. Messages without the
+    /// number of channels are for a single channel, or 0 channels for a group or function block.
+    pub fn from_sysex(payload: &'a [U7]) -> Option<(Header, ProfileMessage<'a>)> {
+        let (header, sub_id2, body) = Header::from_sysex(payload)?;
+        let profile = || body.get(..5).map(ProfileId::from_data);
+        let channels = || match U7::data_to_bytes(body) {
+            [_, _, _, _, _, lsb, msb, ..] => {
+                U14::from_u16_lossy(u16::from(*lsb) | u16::from(*msb) << 7)
+            }
+            _ => match header.address {
+                Address::Channel(_) => U14::from_u16_lossy(1),
+                _ => U14::MIN,
+            },
+        };
+        let message = match sub_id2 {
+            0x20 => ProfileMessage::Inquiry,
+            0x21 => {
+                let (enabled, rest) = ProfileIds::from_data(body)?;
+                let (disabled, _) = ProfileIds::from_data(rest)?;
+                ProfileMessage::Reply { enabled, disabled }
+            }
+            0x22 => ProfileMessage::SetOn {
+                profile: profile()?,
+                channels: channels(),
+            },
+            0x23 => ProfileMessage::SetOff {
+                profile: profile()?,
+            },
+            0x24 => ProfileMessage::Enabled {
+                profile: profile()?,
+                channels: channels(),
+            },
+            0x25 => ProfileMessage::Disabled {
+                profile: profile()?,
+                channels: channels(),
+            },
+            _ => return None,
+        };
+        Some((header, message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ci::Muid;
+    use crate::{Channel, MidiMessage};
+    use core::convert::TryFrom;
+
+    fn round_trip(header: &Header, message: ProfileMessage) -> ([u8; 64], usize) {
+        let mut bytes = [0u8; 64];
+        let size = message.copy_sysex_to_slice(header, &mut bytes).unwrap();
+        assert_eq!(size, message.bytes_size(header));
+        let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+            MidiMessage::SysEx(payload) => payload,
+            _ => panic!("expected SysEx"),
+        };
+        assert_eq!(
+            ProfileMessage::from_sysex(payload),
+            Some((*header, message))
+        );
+        (bytes, size)
+    }
+
+    #[test]
+    fn messages() {
+        let header = Header::new(Address::Group, Muid(1), Muid(2));
+        let organ = ProfileId::standard(U7(0x21), U7(0x00), U7(0x01), U7(0x00));
+        let vendor = ProfileId([U7(0x41), U7(0), U7(0), U7(0x10), U7(0x01)]);
+        assert!(organ.is_standard());
+        assert!(!vendor.is_standard());
+        for message in [
+            ProfileMessage::Inquiry,
+            ProfileMessage::SetOn {
+                profile: organ,
+                channels: U14::MIN,
+            },
+            ProfileMessage::SetOff { profile: organ },
+            ProfileMessage::Enabled {
+                profile: vendor,
+                channels: U14::from_u16_lossy(200),
+            },
+            ProfileMessage::Disabled {
+                profile: vendor,
+                channels: U14::from_u16_lossy(1),
+            },
+        ]
+        .iter()
+        {
+            round_trip(&header, *message);
+        }
+        let (bytes, size) = round_trip(&header, ProfileMessage::SetOff { profile: organ });
+        assert_eq!(
+            &bytes[14..size],
+            [0x7E, 0x21, 0x00, 0x01, 0x00, 0x00, 0x00, 0xF7]
+        );
+    }
+
+    #[test]
+    fn reply() {
+        let header = Header::new(Address::FunctionBlock, Muid(1), Muid(2));
+        let organ = ProfileId::standard(U7(0x21), U7(0x00), U7(0x01), U7(0x00));
+        let vendor = ProfileId([U7(0x41), U7(0), U7(0), U7(0x10), U7(0x01)]);
+        let enabled = [organ, vendor];
+        let message = ProfileMessage::Reply {
+            enabled: ProfileIds::new(&enabled),
+            disabled: ProfileIds::new(&[]),
+        };
+        let (bytes, size) = round_trip(&header, message);
+        assert_eq!(size, 14 + 2 + 10 + 2 + 1);
+        assert_eq!(bytes[14..16], [0x02, 0x00]);
+        assert_eq!(bytes[26..28], [0x00, 0x00]);
+        let payload = U7::try_from_bytes(&bytes[1..size - 1]).unwrap();
+        match ProfileMessage::from_sysex(payload) {
+            Some((_, ProfileMessage::Reply { enabled, disabled })) => {
+                assert!(enabled.iter().eq([organ, vendor].iter().copied()));
+                assert!(disabled.is_empty());
+            }
+            _ => panic!("expected Reply"),
+        }
+        assert_eq!(
+            ProfileMessage::from_sysex(&payload[..payload.len() - 1]),
+            None
+        );
+    }
+
+    #[test]
+    fn version_1() {
+        let header = Header {
+            address: Address::Channel(Channel::Ch2),
+            version: U7(0x01),
+            source: Muid(1),
+            destination: Muid::BROADCAST,
+        };
+        let profile = ProfileId::standard(U7(0x21), U7(0x00), U7(0x01), U7(0x00));
+        let message = ProfileMessage::Enabled {
+            profile,
+            channels: U14::from_u16_lossy(1),
+        };
+        assert_eq!(message.bytes_size(&header), 14 + 5 + 1);
+        round_trip(&header, message);
+        assert_eq!(
+            message.copy_sysex_to_slice(&header, &mut [0u8; 19]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+    }
+}
@@ -0,0 +1,662 @@
+use super::{Header, HEADER_SIZE};
+use crate::{ToSliceError, U14, U7};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use super::Muid;
+#[cfg(feature = "std")]
+use std::{error, vec::Vec};
+
+/// [MIDI-CI] The Property Exchange features of a device, sent in both directions before
+/// properties are exchanged.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct PropertyCapabilities {
+    /// The number of requests the sender can have in progress at once.
+    pub simultaneous_requests: U7,
+    /// The major version of Property Exchange supported by the sender.
+    pub major_version: U7,
+    /// The minor version of Property Exchange supported by the sender.
+    pub minor_version: U7,
+}
+
+/// [MIDI-CI] The kind of a Property Exchange transaction message.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum PropertyKind {
+    /// Ask for the value of the property named in the header.
+    Get,
+    /// The reply to `Get`, holding the value.
+    GetReply,
+    /// Set the property named in the header to the data.
+    Set,
+    /// The reply to `Set`.
+    SetReply,
+    /// Start or end a subscription to changes of a property, or send a change to a subscriber.
+    Subscription,
+    /// The reply to `Subscription`.
+    SubscriptionReply,
+    /// A notification about a transaction, such as one that was cancelled.
+    Notify,
+}
+
+impl PropertyKind {
+    fn sub_id2(self) -> u8 {
+        match self {
+            PropertyKind::Get => 0x34,
+            PropertyKind::GetReply => 0x35,
+            PropertyKind::Set => 0x36,
+            PropertyKind::SetReply => 0x37,
+            PropertyKind::Subscription => 0x38,
+            PropertyKind::SubscriptionReply => 0x39,
+            PropertyKind::Notify => 0x3F,
+        }
+    }
+
+    fn from_sub_id2(sub_id2: u8) -> Option<PropertyKind> {
+        match sub_id2 {
+            0x34 => Some(PropertyKind::Get),
+            0x35 => Some(PropertyKind::GetReply),
+            0x36 => Some(PropertyKind::Set),
+            0x37 => Some(PropertyKind::SetReply),
+            0x38 => Some(PropertyKind::Subscription),
+            0x39 => Some(PropertyKind::SubscriptionReply),
+            0x3F => Some(PropertyKind::Notify),
+            _ => None,
+        }
+    }
+}
+
+/// [MIDI-CI] One message of a Property Exchange transaction.
+///
+/// A transaction has a JSON header, which names the property and holds the status of replies,
+/// and optional property data, usually JSON as well. Both must be ASCII or otherwise encoded as 7
+/// bit data. Data too long for a single SysEx message is split into chunks with
+/// `PropertyChunks`, and joined again with `PropertyReassembler`. The header is only sent in
+/// the first chunk. The header and data of a chunk can each hold at most
+/// `PropertyChunk::MAX_LEN` bytes.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct PropertyChunk<'a> {
+    /// The kind of the transaction.
+    pub kind: PropertyKind,
+    /// Identifies the transaction among those in progress between two devices. Replies use the
+    /// ID of the request.
+    pub request_id: U7,
+    /// The JSON header, or nothing after the first chunk.
+    pub header: &'a [U7],
+    /// The number of chunks in the transaction.
+    pub chunk_count: U14,
+    /// The number of this chunk, starting at 1.
+    pub chunk_number: U14,
+    /// The part of the property data in this chunk.
+    pub data: &'a [U7],
+}
+
+impl<'a> PropertyChunk<'a> {
+    /// The largest header or data in a chunk, in bytes.
+    pub const MAX_LEN: usize = 0x3FFF;
+
+    /// The number of bytes in a SysEx message holding a chunk with `header_len` bytes of header
+    /// and `data_len` bytes of data, including the start and end bytes.
+    pub fn bytes_size_for(header_len: usize, data_len: usize) -> usize {
+        HEADER_SIZE + 9 + header_len + data_len + 1
+    }
+
+    /// Returns `true` if this is the last chunk of the transaction.
+    pub fn is_last(&self) -> bool {
+        self.chunk_number >= self.chunk_count
+    }
+}
+
+/// [MIDI-CI] The Property Exchange messages.
+///
+/// # Example
+/// ```
+/// use wmidi::ci::{Address, Header, Muid, PropertyChunk, PropertyKind, PropertyMessage};
+/// use wmidi::{U14, U7};
+/// let header = Header::new(
+///     Address::FunctionBlock,
+///     Muid::from_u32_lossy(0x0123_4567),
+///     Muid::from_u32_lossy(0x0765_4321),
+/// );
+/// let request = PropertyMessage::Chunk(PropertyChunk {
+///     kind: PropertyKind::Get,
+///     request_id: U7::from_u8_lossy(1),
+///     header: U7::try_from_bytes(br#"{"resource":"DeviceInfo"}"#).unwrap(),
+///     chunk_count: U14::from_u16_lossy(1),
+///     chunk_number: U14::from_u16_lossy(1),
+///     data: &[],
+/// });
+/// let mut bytes = [0u8; 64];
+/// let size = request.copy_sysex_to_slice(&header, &mut bytes).unwrap();
+/// let payload = U7::try_from_bytes(&bytes[1..size - 1]).unwrap();
+/// assert_eq!(PropertyMessage::from_sysex(payload), Some((header, request)));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum PropertyMessage<'a> {
+    /// Ask which Property Exchange features the receiver supports.
+    CapabilitiesInquiry(PropertyCapabilities),
+    /// The reply to a `CapabilitiesInquiry`.
+    CapabilitiesReply(PropertyCapabilities),
+    /// A chunk of a transaction.
+    Chunk(PropertyChunk<'a>),
+}
+
+impl<'a> PropertyMessage<'a> {
+    /// The number of bytes in the message, including the start and end bytes. This depends on
+    /// the version in `header`.
+    pub fn bytes_size(&self, header: &Header) -> usize {
+        match self {
+            PropertyMessage::CapabilitiesInquiry(_) | PropertyMessage::CapabilitiesReply(_) => {
+                let body = if header.version < Header::VERSION {
+                    1
+                } else {
+                    3
+                };
+                HEADER_SIZE + body + 1
+            }
+            PropertyMessage::Chunk(chunk) => {
+                PropertyChunk::bytes_size_for(chunk.header.len(), chunk.data.len())
+            }
+        }
+    }
+
+    /// Write the SysEx message to `slice`, including the start and end bytes. Capabilities with
+    /// a `header` version before 0x02 are written without the Property Exchange version. Returns
+    /// the number of bytes written.
+    ///
+    /// Returns `ToSliceError::InvalidMessage` if the header or data of a chunk is longer than
+    /// `PropertyChunk::MAX_LEN`.
+    pub fn copy_sysex_to_slice(
+        &self,
+        header: &Header,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        if let PropertyMessage::Chunk(chunk) = self {
+            if chunk.header.len() > PropertyChunk::MAX_LEN
+                || chunk.data.len() > PropertyChunk::MAX_LEN
+            {
+                return Err(ToSliceError::InvalidMessage);
+            }
+        }
+        let size = self.bytes_size(header);
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let body = &mut slice[HEADER_SIZE..size - 1];
+        let sub_id2 = match self {
+            PropertyMessage::CapabilitiesInquiry(capabilities)
+            | PropertyMessage::CapabilitiesReply(capabilities) => {
+                let bytes = [
+                    u8::from(capabilities.simultaneous_requests),
+                    u8::from(capabilities.major_version),
+                    u8::from(capabilities.minor_version),
+                ];
+                body.copy_from_slice(&bytes[..body.len()]);
+                match self {
+                    PropertyMessage::CapabilitiesInquiry(_) => 0x30,
+                    _ => 0x31,
+                }
+            }
+            PropertyMessage::Chunk(chunk) => {
+                let header_end = 3 + chunk.header.len();
+                body[0] = u8::from(chunk.request_id);
+                copy_u14(chunk.header.len() as u16, &mut body[1..3]);
+                body[3..header_end].copy_from_slice(U7::data_to_bytes(chunk.header));
+                copy_u14(u16::from(chunk.chunk_count), &mut body[header_end..]);
+                copy_u14(u16::from(chunk.chunk_number), &mut body[header_end + 2..]);
+                copy_u14(chunk.data.len() as u16, &mut body[header_end + 4..]);
+                body[header_end + 6..].copy_from_slice(U7::data_to_bytes(chunk.data));
+                chunk.kind.sub_id2()
+            }
+        };
+        header.copy_to_slice(sub_id2, slice);
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The SysEx message, including the start and end bytes.
+    ///
+    /// # Panics
+    /// Panics if the header or data of a chunk is longer than `PropertyChunk::MAX_LEN`.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, header: &Header) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size(header)];
+        self.copy_sysex_to_slice(header, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the header and message, or `None` if the payload is not a Property Exchange
+    /// message. Capabilities without the Property Exchange version have a version of 0.0.
+    pub fn from_sysex(payload: &'a [U7]) -> Option<(Header, PropertyMessage<'a>)> {
+        let (header, sub_id2, body) = Header::from_sysex(payload)?;
+        if let 0x30 | 0x31 = sub_id2 {
+            let capabilities = PropertyCapabilities {
+                simultaneous_requests: *body.first()?,
+                major_version: body.get(1).copied().unwrap_or(U7::MIN),
+                minor_version: body.get(2).copied().unwrap_or(U7::MIN),
+            };
+            let message = match sub_id2 {
+                0x30 => PropertyMessage::CapabilitiesInquiry(capabilities),
+                _ => PropertyMessage::CapabilitiesReply(capabilities),
+            };
+            return Some((header, message));
+        }
+        let kind = PropertyKind::from_sub_id2(sub_id2)?;
+        let request_id = *body.first()?;
+        let header_len = usize::from(u16::from(read_u14(body.get(1..3)?)));
+        let json = body.get(3..3 + header_len)?;
+        let rest = &body[3 + header_len..];
+        let data_len = usize::from(u16::from(read_u14(rest.get(4..6)?)));
+        let chunk = PropertyChunk {
+            kind,
+            request_id,
+            header: json,
+            chunk_count: read_u14(&rest[..2]),
+            chunk_number: read_u14(&rest[2..4]),
+            data: rest.get(6..6 + data_len)?,
+        };
+        Some((header, PropertyMessage::Chunk(chunk)))
+    }
+}
+
+fn copy_u14(value: u16, slice: &mut [u8]) {
+    slice[..2].copy_from_slice(&[value as u8 & 0x7F, (value >> 7) as u8 & 0x7F]);
+}
+
+fn read_u14(data: &[U7]) -> U14 {
+    U14::from_u16_lossy(u16::from(u8::from(data[0])) | u16::from(u8::from(data[1])) << 7)
+}
+
+/// [MIDI-CI] Splits the property data of a transaction into chunks of at most `max_data_len`
+/// bytes. The header is sent with the first chunk, and a transaction without data is sent as a
+/// single chunk.
+///
+/// The receiver gives the largest SysEx message it accepts during discovery, which limits the
+/// data in each chunk to that size minus `PropertyChunk::bytes_size_for(header_len, 0)`.
+///
+/// # Example
+/// ```
+/// use wmidi::ci::{PropertyChunks, PropertyKind};
+/// use wmidi::U7;
+/// let header = U7::try_from_bytes(br#"{"resource":"ProgramList"}"#).unwrap();
+/// let data = U7::try_from_bytes(&[b'x'; 100]).unwrap();
+/// let chunks = PropertyChunks::new(PropertyKind::GetReply, U7::MIN, header, data, 40);
+/// let sizes: Vec<_> = chunks.map(|c| (c.header.len(), c.data.len())).collect();
+/// assert_eq!(sizes, [(26, 40), (0, 40), (0, 20)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PropertyChunks<'a> {
+    kind: PropertyKind,
+    request_id: U7,
+    header: &'a [U7],
+    data: &'a [U7],
+    max_data_len: usize,
+    chunk_count: u16,
+    chunk_number: u16,
+}
+
+impl<'a> PropertyChunks<'a> {
+    /// Split `data` into chunks of at most `max_data_len` bytes, which is clamped between 1 and
+    /// `PropertyChunk::MAX_LEN`. There can be at most 16383 chunks, so longer data is truncated.
+    pub fn new(
+        kind: PropertyKind,
+        request_id: U7,
+        header: &'a [U7],
+        data: &'a [U7],
+        max_data_len: usize,
+    ) -> PropertyChunks<'a> {
+        let max_data_len = max_data_len.clamp(1, PropertyChunk::MAX_LEN);
+        let chunk_count = data.len().div_ceil(max_data_len).clamp(1, 0x3FFF);
+        PropertyChunks {
+            kind,
+            request_id,
+            header,
+            data: &data[..data.len().min(chunk_count * max_data_len)],
+            max_data_len,
+            chunk_count: chunk_count as u16,
+            chunk_number: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PropertyChunks<'a> {
+    type Item = PropertyChunk<'a>;
+
+    fn next(&mut self) -> Option<PropertyChunk<'a>> {
+        if self.chunk_number == self.chunk_count {
+            return None;
+        }
+        self.chunk_number += 1;
+        let (data, rest) = self.data.split_at(self.max_data_len.min(self.data.len()));
+        self.data = rest;
+        let header = match self.chunk_number {
+            1 => self.header,
+            _ => &[],
+        };
+        Some(PropertyChunk {
+            kind: self.kind,
+            request_id: self.request_id,
+            header,
+            chunk_count: U14::from_u16_lossy(self.chunk_count),
+            chunk_number: U14::from_u16_lossy(self.chunk_number),
+            data,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.chunk_count - self.chunk_number);
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for PropertyChunks<'a> {}
+
+/// [MIDI-CI] A Property Exchange transaction joined from its chunks.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Property {
+    /// The kind of the transaction.
+    pub kind: PropertyKind,
+    /// The MUID of the sender.
+    pub source: Muid,
+    /// The ID of the transaction.
+    pub request_id: U7,
+    /// The JSON header.
+    pub header: Vec<U7>,
+    /// The property data.
+    pub data: Vec<U7>,
+}
+
+/// An error joining the chunks of a Property Exchange transaction. The transaction is dropped.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ReassemblyError {
+    /// A chunk did not follow the previous chunk of its transaction, or a transaction did not
+    /// start with chunk 1.
+    ChunkOutOfOrder,
+    /// The header and data of a transaction are longer than the allowed maximum.
+    TooLong,
+    /// There are already as many transactions in progress as allowed.
+    TooManyTransactions,
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ReassemblyError {}
+
+impl fmt::Display for ReassemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// [MIDI-CI] Joins the chunks of Property Exchange transactions, which may be interleaved when
+/// several are in progress.
+///
+/// Transactions are told apart by the MUID of the sender and the request ID. The number of
+/// transactions in progress and the length of each are limited, so a broken or malicious sender
+/// cannot use unbounded memory.
+///
+/// # Example
+/// ```
+/// use wmidi::ci::{
+///     Address, Header, Muid, PropertyChunks, PropertyKind, PropertyMessage, PropertyReassembler,
+/// };
+/// use wmidi::U7;
+/// let header = Header::new(
+///     Address::FunctionBlock,
+///     Muid::from_u32_lossy(1),
+///     Muid::from_u32_lossy(2),
+/// );
+/// let json = U7::try_from_bytes(br#"{"status":200}"#).unwrap();
+/// let data = U7::try_from_bytes(&[b'x'; 100]).unwrap();
+///
+/// let mut reassembler = PropertyReassembler::new(4, 4096);
+/// let mut joined = None;
+/// for chunk in PropertyChunks::new(PropertyKind::GetReply, U7::MIN, json, data, 40) {
+///     let bytes = PropertyMessage::Chunk(chunk).to_sysex_vec(&header);
+///     let payload = U7::try_from_bytes(&bytes[1..bytes.len() - 1]).unwrap();
+///     match PropertyMessage::from_sysex(payload) {
+///         Some((header, PropertyMessage::Chunk(chunk))) => {
+///             joined = reassembler.push(&header, &chunk).unwrap();
+///         }
+///         _ => panic!("expected a chunk"),
+///     }
+/// }
+/// let property = joined.unwrap();
+/// assert_eq!(property.header, json);
+/// assert_eq!(property.data, data);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct PropertyReassembler {
+    max_transactions: usize,
+    max_len: usize,
+    pending: Vec<Pending>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+struct Pending {
+    property: Property,
+    next_chunk: u16,
+}
+
+#[cfg(feature = "std")]
+impl PropertyReassembler {
+    /// Create a reassembler for at most `max_transactions` transactions at once, each with at
+    /// most `max_len` bytes of header and data.
+    pub fn new(max_transactions: usize, max_len: usize) -> PropertyReassembler {
+        PropertyReassembler {
+            max_transactions,
+            max_len,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The number of transactions with chunks still to come.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no transaction is in progress.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Add a chunk sent with `header`. Returns the transaction once its last chunk is added.
+    pub fn push(
+        &mut self,
+        header: &Header,
+        chunk: &PropertyChunk,
+    ) -> Result<Option<Property>, ReassemblyError> {
+        let index = self.pending.iter().position(|pending| {
+            pending.property.source == header.source
+                && pending.property.request_id == chunk.request_id
+        });
+        let chunk_number = u16::from(chunk.chunk_number);
+        let mut pending = match index {
+            Some(index) => {
+                let pending = self.pending.swap_remove(index);
+                if chunk_number != pending.next_chunk || chunk.kind != pending.property.kind {
+                    return Err(ReassemblyError::ChunkOutOfOrder);
+                }
+                pending
+            }
+            None if chunk_number != 1 => return Err(ReassemblyError::ChunkOutOfOrder),
+            None if self.pending.len() >= self.max_transactions && !chunk.is_last() => {
+                return Err(ReassemblyError::TooManyTransactions)
+            }
+            None => Pending {
+                property: Property {
+                    kind: chunk.kind,
+                    source: header.source,
+                    request_id: chunk.request_id,
+                    header: Vec::new(),
+                    data: Vec::new(),
+                },
+                next_chunk: 1,
+            },
+        };
+        let property = &mut pending.property;
+        if property.header.len() + property.data.len() + chunk.header.len() + chunk.data.len()
+            > self.max_len
+        {
+            return Err(ReassemblyError::TooLong);
+        }
+        property.header.extend_from_slice(chunk.header);
+        property.data.extend_from_slice(chunk.data);
+        if chunk.is_last() {
+            return Ok(Some(pending.property));
+        }
+        pending.next_chunk += 1;
+        self.pending.push(pending);
+        Ok(None)
+    }
+
+    /// Drop the transaction from `source` with `request_id`, for example when it is cancelled by
+    /// a `PropertyKind::Notify` message.
+    pub fn cancel(&mut self, source: Muid, request_id: U7) {
+        self.pending.retain(|pending| {
+            pending.property.source != source || pending.property.request_id != request_id
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ci::{Address, Muid};
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    fn round_trip(header: &Header, message: PropertyMessage) {
+        let mut bytes = [0u8; 128];
+        let size = message.copy_sysex_to_slice(header, &mut bytes).unwrap();
+        assert_eq!(size, message.bytes_size(header));
+        let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+            MidiMessage::SysEx(payload) => payload,
+            _ => panic!("expected SysEx"),
+        };
+        assert_eq!(
+            PropertyMessage::from_sysex(payload),
+            Some((*header, message))
+        );
+    }
+
+    #[test]
+    fn messages() {
+        let header = Header::new(Address::FunctionBlock, Muid(1), Muid(2));
+        let capabilities = PropertyCapabilities {
+            simultaneous_requests: U7(4),
+            major_version: U7(0),
+            minor_version: U7(0),
+        };
+        round_trip(&header, PropertyMessage::CapabilitiesInquiry(capabilities));
+        round_trip(&header, PropertyMessage::CapabilitiesReply(capabilities));
+        let old = Header {
+            version: U7(0x01),
+            ..header
+        };
+        assert_eq!(
+            PropertyMessage::CapabilitiesReply(capabilities).bytes_size(&old),
+            16
+        );
+        round_trip(&old, PropertyMessage::CapabilitiesReply(capabilities));
+
+        let json = U7::try_from_bytes(br#"{"resource":"ResourceList"}"#).unwrap();
+        let chunk = PropertyChunk {
+            kind: PropertyKind::Notify,
+            request_id: U7(0x7F),
+            header: json,
+            chunk_count: U14::from_u16_lossy(300),
+            chunk_number: U14::from_u16_lossy(2),
+            data: &[U7(1), U7(2)],
+        };
+        round_trip(&header, PropertyMessage::Chunk(chunk));
+        let mut bytes = [0u8; 128];
+        let size = PropertyMessage::Chunk(chunk)
+            .copy_sysex_to_slice(&header, &mut bytes)
+            .unwrap();
+        assert_eq!(bytes[4], 0x3F);
+        assert_eq!(bytes[14..17], [0x7F, 27, 0]);
+        assert_eq!(bytes[44..50], [0x2C, 0x02, 0x02, 0x00, 0x02, 0x00]);
+        let payload = U7::try_from_bytes(&bytes[1..size - 2]).unwrap();
+        assert_eq!(PropertyMessage::from_sysex(payload), None);
+    }
+
+    #[test]
+    fn chunks() {
+        let json = [U7(b'{'), U7(b'}')];
+        let mut chunks = PropertyChunks::new(PropertyKind::Set, U7(1), &json, &[], 10);
+        assert_eq!(chunks.len(), 1);
+        let chunk = chunks.next().unwrap();
+        assert_eq!(chunk.header, &json);
+        assert!(chunk.is_last());
+        assert_eq!(chunks.next(), None);
+
+        let data = [U7(0); 20];
+        let chunks = PropertyChunks::new(PropertyKind::Set, U7(1), &json, &data, 10)
+            .map(|chunk| (chunk.header.len(), chunk.data.len(), chunk.is_last()));
+        assert!(chunks.eq([(2, 10, false), (0, 10, true)].iter().copied()));
+
+        let data = [U7(0); PropertyChunk::MAX_LEN + 1];
+        let mut chunks = PropertyChunks::new(PropertyKind::Set, U7(1), &[], &data, usize::MAX);
+        assert_eq!(chunks.len(), 2);
+        let chunk = chunks.next().unwrap();
+        assert_eq!(chunk.data.len(), PropertyChunk::MAX_LEN);
+        let header = Header::new(Address::FunctionBlock, Muid(1), Muid(2));
+        let too_long = PropertyChunk {
+            data: &data,
+            ..chunk
+        };
+        assert_eq!(
+            PropertyMessage::Chunk(too_long).copy_sysex_to_slice(&header, &mut [0u8; 64]),
+            Err(ToSliceError::InvalidMessage)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reassemble() {
+        let a = Header::new(Address::FunctionBlock, Muid(1), Muid(3));
+        let b = Header::new(Address::FunctionBlock, Muid(2), Muid(3));
+        let data: Vec<U7> = (0..50).map(U7).collect();
+        let mut reassembler = PropertyReassembler::new(2, 100);
+        let mut from_a = PropertyChunks::new(PropertyKind::GetReply, U7(1), &[], &data, 20);
+        let mut from_b = PropertyChunks::new(PropertyKind::GetReply, U7(1), &[], &data, 30);
+        assert_eq!(reassembler.push(&a, &from_a.next().unwrap()), Ok(None));
+        assert_eq!(reassembler.push(&b, &from_b.next().unwrap()), Ok(None));
+        assert_eq!(reassembler.len(), 2);
+        let c = Header::new(Address::FunctionBlock, Muid(4), Muid(3));
+        let first = PropertyChunks::new(PropertyKind::GetReply, U7(1), &[], &data, 20)
+            .next()
+            .unwrap();
+        assert_eq!(
+            reassembler.push(&c, &first),
+            Err(ReassemblyError::TooManyTransactions)
+        );
+        let property = reassembler
+            .push(&b, &from_b.next().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(property.source, Muid(2));
+        assert_eq!(property.data, data);
+        assert_eq!(
+            reassembler.push(&a, &from_a.nth(1).unwrap()),
+            Err(ReassemblyError::ChunkOutOfOrder)
+        );
+        assert!(reassembler.is_empty());
+
+        let mut reassembler = PropertyReassembler::new(2, 40);
+        let mut chunks = PropertyChunks::new(PropertyKind::GetReply, U7(1), &[], &data, 20);
+        assert_eq!(reassembler.push(&a, &chunks.next().unwrap()), Ok(None));
+        assert_eq!(reassembler.push(&a, &chunks.next().unwrap()), Ok(None));
+        assert_eq!(
+            reassembler.push(&a, &chunks.next().unwrap()),
+            Err(ReassemblyError::TooLong)
+        );
+        let mut chunks = PropertyChunks::new(PropertyKind::GetReply, U7(2), &[], &data, 20);
+        assert_eq!(reassembler.push(&a, &chunks.next().unwrap()), Ok(None));
+        reassembler.cancel(Muid(1), U7(2));
+        assert!(reassembler.is_empty());
+    }
+}
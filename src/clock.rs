@@ -0,0 +1,333 @@
+use crate::{MidiMessage, U14};
+
+/// Follows an external MIDI clock, estimating its tempo and tracking the transport position.
+///
+/// Pass every message to `handle` with the time it was received in microseconds. The tempo is
+/// estimated from the time between `TimingClock` messages, smoothed with an exponential moving
+/// average so that jitter in the arrival times does not make it fluctuate. A gap of more than a
+/// second between clocks restarts the estimate.
+///
+/// The position counts the `TimingClock` messages received while running, 24 per quarter note.
+/// `Start` moves it to the beginning of the song, `SongPositionPointer` moves it to the given
+/// number of sixteenth notes, and `Continue` resumes from where it is.
+///
+/// # Example
+/// ```
+/// use wmidi::{ClockFollower, MidiMessage};
+/// let mut clock = ClockFollower::new(0.5);
+/// clock.handle(0, &MidiMessage::Start);
+/// // 120 BPM is 24 clocks every 500 ms.
+/// for i in 0..48 {
+///     clock.handle(i * 500_000 / 24, &MidiMessage::TimingClock);
+/// }
+/// assert!((clock.bpm().unwrap() - 120.0).abs() < 0.1);
+/// assert_eq!(clock.position(), 48);
+/// assert_eq!(clock.beats(), 2.0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ClockFollower {
+    smoothing: f32,
+    interval: Option<f32>,
+    last_clock: Option<u64>,
+    running: bool,
+    position: u32,
+}
+
+impl ClockFollower {
+    /// The number of `TimingClock` messages per quarter note.
+    pub const CLOCKS_PER_BEAT: u32 = 24;
+
+    /// The longest time between clocks, in microseconds, that is used for the tempo estimate.
+    const MAX_INTERVAL: u64 = 1_000_000;
+
+    /// Create a follower that has not received a clock. `smoothing` is the weight, between 0.0
+    /// and 1.0, given to the previous estimate when a new clock arrives. 0.0 uses only the latest
+    /// interval.
+    pub fn new(smoothing: f32) -> ClockFollower {
+        ClockFollower {
+            smoothing: smoothing.clamp(0.0, 1.0),
+            interval: None,
+            last_clock: None,
+            running: false,
+            position: 0,
+        }
+    }
+
+    /// Update the tempo and transport from `message`, received at `now` microseconds.
+    pub fn handle(&mut self, now: u64, message: &MidiMessage) {
+        match message {
+            MidiMessage::TimingClock => {
+                self.update_interval(now);
+                if self.running {
+                    self.position = self.position.wrapping_add(1);
+                }
+            }
+            MidiMessage::Start => {
+                self.running = true;
+                self.position = 0;
+            }
+            MidiMessage::Continue => self.running = true,
+            MidiMessage::Stop => self.running = false,
+            MidiMessage::SongPositionPointer(position) => {
+                self.position = u32::from(u16::from(*position)) * 6;
+            }
+            MidiMessage::Reset => *self = ClockFollower::new(self.smoothing),
+            _ => {}
+        }
+    }
+
+    fn update_interval(&mut self, now: u64) {
+        let last = self.last_clock.replace(now);
+        let elapsed = match last.map(|l| now.saturating_sub(l)) {
+            Some(e) if e <= ClockFollower::MAX_INTERVAL => e as f32,
+            _ => {
+                self.interval = None;
+                return;
+            }
+        };
+        self.interval = Some(match self.interval {
+            Some(i) => i * self.smoothing + elapsed * (1.0 - self.smoothing),
+            None => elapsed,
+        });
+    }
+
+    /// The estimated tempo in quarter notes per minute, or `None` if not enough clocks have been
+    /// received.
+    pub fn bpm(&self) -> Option<f32> {
+        let interval = self.interval?;
+        if interval <= 0.0 {
+            return None;
+        }
+        Some(60_000_000.0 / (interval * ClockFollower::CLOCKS_PER_BEAT as f32))
+    }
+
+    /// Returns `true` between `Start` or `Continue` and `Stop`.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// The number of clocks since the start of the song.
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    /// The number of quarter notes since the start of the song.
+    pub fn beats(&self) -> f32 {
+        self.position as f32 / ClockFollower::CLOCKS_PER_BEAT as f32
+    }
+
+    /// The position as the number of sixteenth notes since the start of the song, rounded down,
+    /// as sent in `SongPositionPointer`.
+    pub fn song_position(&self) -> U14 {
+        U14::from_u16_lossy((self.position / 6).min(u32::from(u16::from(U14::MAX))) as u16)
+    }
+}
+
+/// Smooths the arrival times of `TimingClock` messages with a phase locked loop, giving a stable
+/// tempo and phase.
+///
+/// Clocks from USB interfaces often arrive a millisecond or more away from when they were sent,
+/// which is too noisy to drive an LFO or a delay time directly. The smoother predicts when each
+/// clock should arrive and corrects the prediction by a fraction of the error: `phase_gain` of
+/// the error moves the clock time, and `period_gain` of the error adjusts the interval between
+/// clocks. Smaller gains reject more jitter, but follow tempo changes more slowly. A `phase_gain`
+/// of around 0.1 and a `period_gain` of around 0.005 work well for most interfaces.
+///
+/// Times are in microseconds. A gap of more than a second between clocks restarts the loop.
+///
+/// # Example
+/// ```
+/// use wmidi::ClockSmoother;
+/// let mut smoother = ClockSmoother::new(0.1, 0.005);
+/// // 120 BPM with up to 1 ms of jitter.
+/// for i in 0..480u64 {
+///     let jitter = [0, 1000, 300, 700][i as usize % 4];
+///     smoother.clock(i * 500_000 / 24 + jitter);
+/// }
+/// assert!((smoother.bpm().unwrap() - 120.0).abs() < 0.5);
+/// // 480 clocks is 20 beats.
+/// let beats = smoother.beats_at(480 * 500_000 / 24).unwrap();
+/// assert!((beats - 20.0).abs() < 0.05);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ClockSmoother {
+    phase_gain: f64,
+    period_gain: f64,
+    last_arrival: Option<u64>,
+    period: Option<f64>,
+    time: f64,
+    clocks: u64,
+}
+
+impl ClockSmoother {
+    /// Create a smoother that has not received a clock. The gains are clamped between 0.0 and
+    /// 1.0.
+    pub fn new(phase_gain: f64, period_gain: f64) -> ClockSmoother {
+        ClockSmoother {
+            phase_gain: phase_gain.clamp(0.0, 1.0),
+            period_gain: period_gain.clamp(0.0, 1.0),
+            last_arrival: None,
+            period: None,
+            time: 0.0,
+            clocks: 0,
+        }
+    }
+
+    /// Forget all clocks, keeping the gains.
+    pub fn reset(&mut self) {
+        *self = ClockSmoother::new(self.phase_gain, self.period_gain);
+    }
+
+    /// Update the loop with a `TimingClock` received at `now` microseconds.
+    pub fn clock(&mut self, now: u64) {
+        let last = self.last_arrival.replace(now);
+        let elapsed = match last.map(|l| now.saturating_sub(l)) {
+            Some(e) if e <= ClockFollower::MAX_INTERVAL => e as f64,
+            _ => {
+                self.period = None;
+                self.time = now as f64;
+                self.clocks = 0;
+                return;
+            }
+        };
+        self.clocks += 1;
+        let period = match self.period {
+            Some(p) => p,
+            None => {
+                // The first interval is the best guess available. Two clocks with the same
+                // timestamp would give a period of zero, so clamp it like the update below.
+                self.period = Some(elapsed.max(1.0));
+                self.time = now as f64;
+                return;
+            }
+        };
+        let predicted = self.time + period;
+        let error = now as f64 - predicted;
+        self.time = predicted + error * self.phase_gain;
+        self.period = Some((period + error * self.period_gain).max(1.0));
+    }
+
+    /// Update the loop from `message`, received at `now` microseconds. `Reset` restarts the loop
+    /// and other messages besides `TimingClock` are ignored.
+    pub fn handle(&mut self, now: u64, message: &MidiMessage) {
+        match message {
+            MidiMessage::TimingClock => self.clock(now),
+            MidiMessage::Reset => self.reset(),
+            _ => {}
+        }
+    }
+
+    /// The smoothed time between clocks in microseconds, or `None` if fewer than two clocks have
+    /// been received.
+    pub fn period(&self) -> Option<f64> {
+        self.period
+    }
+
+    /// The smoothed tempo in quarter notes per minute, or `None` if fewer than two clocks have
+    /// been received.
+    pub fn bpm(&self) -> Option<f64> {
+        Some(60_000_000.0 / (self.period? * f64::from(ClockFollower::CLOCKS_PER_BEAT)))
+    }
+
+    /// The smoothed number of clocks between the first clock received and `now` microseconds,
+    /// including the fraction of the current clock. Returns `None` if fewer than two clocks have
+    /// been received.
+    pub fn clocks_at(&self, now: u64) -> Option<f64> {
+        let period = self.period?;
+        Some(self.clocks as f64 + (now as f64 - self.time) / period)
+    }
+
+    /// The smoothed number of quarter notes between the first clock received and `now`
+    /// microseconds.
+    pub fn beats_at(&self, now: u64) -> Option<f64> {
+        Some(self.clocks_at(now)? / f64::from(ClockFollower::CLOCKS_PER_BEAT))
+    }
+
+    /// The position within the current quarter note at `now` microseconds, between 0.0
+    /// inclusive and 1.0 exclusive.
+    pub fn phase_at(&self, now: u64) -> Option<f64> {
+        let phase = self.beats_at(now)? % 1.0;
+        Some(if phase < 0.0 { phase + 1.0 } else { phase })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn transport() {
+        let mut clock = ClockFollower::new(0.0);
+        clock.handle(0, &MidiMessage::TimingClock);
+        assert_eq!(clock.position(), 0);
+        assert_eq!(clock.bpm(), None);
+        clock.handle(0, &MidiMessage::SongPositionPointer(U14::from_u16_lossy(4)));
+        clock.handle(0, &MidiMessage::Continue);
+        clock.handle(10_000, &MidiMessage::TimingClock);
+        assert_eq!(clock.position(), 25);
+        assert_eq!(clock.song_position(), U14::from_u16_lossy(4));
+        assert_eq!(clock.bpm(), Some(250.0));
+        clock.handle(10_000, &MidiMessage::Stop);
+        clock.handle(20_000, &MidiMessage::TimingClock);
+        assert_eq!(clock.position(), 25);
+        assert!(!clock.is_running());
+    }
+
+    #[test]
+    fn long_gap_restarts_estimate() {
+        let mut clock = ClockFollower::new(0.9);
+        clock.handle(0, &MidiMessage::TimingClock);
+        clock.handle(10_000, &MidiMessage::TimingClock);
+        clock.handle(5_000_000, &MidiMessage::TimingClock);
+        assert_eq!(clock.bpm(), None);
+        clock.handle(5_020_000, &MidiMessage::TimingClock);
+        assert_eq!(clock.bpm(), Some(125.0));
+    }
+
+    #[test]
+    fn smoother_rejects_jitter() {
+        let mut smoother = ClockSmoother::new(0.1, 0.005);
+        smoother.clock(0);
+        assert_eq!(smoother.bpm(), None);
+        let mut seed = 1u32;
+        for i in 1..2400u64 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let jitter = u64::from(seed >> 16) % 2000;
+            smoother.clock(i * 20_000 + jitter);
+        }
+        // The raw intervals vary by up to 10%, the smoothed period by much less.
+        let period = smoother.period().unwrap();
+        assert!((period - 20_000.0).abs() < 100.0, "{}", period);
+        let clocks = smoother.clocks_at(2400 * 20_000).unwrap();
+        assert!((clocks - 2400.0).abs() < 0.1, "{}", clocks);
+        smoother.clock(60_000_000);
+        assert_eq!(smoother.period(), None);
+    }
+
+    #[test]
+    fn smoother_follows_tempo_change() {
+        let mut smoother = ClockSmoother::new(0.1, 0.005);
+        for i in 0..100 {
+            smoother.clock(i * 20_000);
+        }
+        assert_eq!(smoother.bpm(), Some(125.0));
+        assert_eq!(smoother.phase_at(108 * 20_000), Some(0.5));
+        let start = 99 * 20_000;
+        for i in 1..2000 {
+            smoother.clock(start + i * 25_000);
+        }
+        assert!((smoother.bpm().unwrap() - 100.0).abs() < 0.1);
+        smoother.handle(0, &MidiMessage::Reset);
+        assert_eq!(smoother.bpm(), None);
+    }
+
+    #[test]
+    fn smoother_clamps_zero_first_interval() {
+        let mut smoother = ClockSmoother::new(0.1, 0.01);
+        smoother.clock(1000);
+        smoother.clock(1000);
+        assert_eq!(smoother.period(), Some(1.0));
+        assert!(smoother.bpm().unwrap().is_finite());
+        assert!(smoother.clocks_at(2000).unwrap().is_finite());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{ControlFunction, ControlValue, U7};
+
+/// The meaning of a `ControlChange` value, based on the standard assignment of its controller.
+///
+/// # Example
+/// ```
+/// use wmidi::{ControlFunction, ControlValueSemantics, U7};
+/// let pan = ControlValueSemantics::interpret(ControlFunction::PAN, U7::MIN);
+/// assert_eq!(pan, ControlValueSemantics::Bipolar(-64));
+///
+/// let sustain = ControlValueSemantics::interpret(ControlFunction::DAMPER_PEDAL, U7::MAX);
+/// assert_eq!(sustain.as_switch(), Some(true));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ControlValueSemantics {
+    /// An on/off switch such as the damper pedal. Values of 64 and above are on.
+    Switch(bool),
+    /// A value centered around 64, such as pan or balance. The value ranges from -64 (hard
+    /// left) to 63 (hard right).
+    Bipolar(i8),
+    /// A send level such as reverb or chorus depth, between 0.0 and 100.0 percent inclusive.
+    Percent(f32),
+    /// A continuous controller. 14 bit controllers use this for the MSB or LSB alone.
+    Continuous(U7),
+    /// A controller whose value has no standard interpretation, such as an undefined
+    /// controller, a parameter number, or a channel mode message.
+    Raw(U7),
+}
+
+impl ControlValueSemantics {
+    /// Interpret `value` as sent to `function`.
+    pub fn interpret(function: ControlFunction, value: ControlValue) -> ControlValueSemantics {
+        let raw = u8::from(value);
+        match function {
+            ControlFunction::BALANCE | ControlFunction::PAN => {
+                ControlValueSemantics::Bipolar(raw as i8 - 64)
+            }
+            ControlFunction::EFFECTS_1_DEPTH
+            | ControlFunction::EFFECTS_2_DEPTH
+            | ControlFunction::EFFECTS_3_DEPTH
+            | ControlFunction::EFFECTS_4_DEPTH
+            | ControlFunction::EFFECTS_5_DEPTH => {
+                ControlValueSemantics::Percent(f32::from(raw) * 100.0 / 127.0)
+            }
+            ControlFunction::LOCAL_CONTROL => ControlValueSemantics::Switch(raw >= 64),
+            f if f.is_switch() => ControlValueSemantics::Switch(raw >= 64),
+            ControlFunction::DATA_INCREMENT
+            | ControlFunction::DATA_DECREMENT
+            | ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB
+            | ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB
+            | ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB
+            | ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB => ControlValueSemantics::Raw(value),
+            f if f.is_undefined() || f.is_channel_mode() => ControlValueSemantics::Raw(value),
+            _ => ControlValueSemantics::Continuous(value),
+        }
+    }
+
+    /// The state of the switch, or `None` if the value is not a switch.
+    pub fn as_switch(self) -> Option<bool> {
+        match self {
+            ControlValueSemantics::Switch(on) => Some(on),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn interpret() {
+        let interpret = |f, v| ControlValueSemantics::interpret(f, U7(v));
+        assert_eq!(
+            interpret(ControlFunction::SOFT_PEDAL, 63),
+            ControlValueSemantics::Switch(false)
+        );
+        assert_eq!(
+            interpret(ControlFunction::SOFT_PEDAL, 64),
+            ControlValueSemantics::Switch(true)
+        );
+        assert_eq!(
+            interpret(ControlFunction::BALANCE, 64),
+            ControlValueSemantics::Bipolar(0)
+        );
+        assert_eq!(
+            interpret(ControlFunction::PAN, 127),
+            ControlValueSemantics::Bipolar(63)
+        );
+        assert_eq!(
+            interpret(ControlFunction::EFFECTS_1_DEPTH, 127),
+            ControlValueSemantics::Percent(100.0)
+        );
+        assert_eq!(
+            interpret(ControlFunction::CHANNEL_VOLUME, 100),
+            ControlValueSemantics::Continuous(U7(100))
+        );
+        assert_eq!(
+            interpret(ControlFunction::UNDEFINED_3, 1),
+            ControlValueSemantics::Raw(U7(1))
+        );
+        assert_eq!(
+            interpret(ControlFunction::MONO_OPERATION, 1),
+            ControlValueSemantics::Raw(U7(1))
+        );
+        assert_eq!(interpret(ControlFunction::PAN, 0).as_switch(), None);
+    }
+}
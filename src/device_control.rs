@@ -0,0 +1,149 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+//! * [CA-025]: Master Fine/Coarse Tuning
+
+use crate::{Cents, MasterTuning, ToSliceError, U14, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MIDI 1.0] [CA-025] The Device Control Universal Real Time SysEx messages, which set the
+/// overall volume, balance, and tuning of a device.
+///
+/// # Example
+/// ```
+/// use wmidi::{DeviceControl, U14, U7};
+/// let mut bytes = [0u8; 8];
+/// DeviceControl::MasterVolume(U14::MAX).copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7F, 0x7F, 0x04, 0x01, 0x7F, 0x7F, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DeviceControl {
+    /// The volume of the device, from silent at 0 to full volume at `U14::MAX`.
+    MasterVolume(U14),
+    /// The balance of the device, from hard left at 0 to hard right at `U14::MAX`. The center is
+    /// `DeviceControl::BALANCE_CENTER`.
+    MasterBalance(U14),
+    /// The Master Fine Tuning or Master Coarse Tuning message.
+    MasterTuning(MasterTuning),
+}
+
+impl DeviceControl {
+    /// The number of bytes in the SysEx message, including the start and end bytes.
+    pub const SYSEX_BYTES_SIZE: usize = 8;
+
+    /// The balance value that leaves the left and right levels unchanged.
+    pub const BALANCE_CENTER: U14 = U14::from_u16_lossy(0x2000);
+
+    /// The tuning offset, or `None` if the message is not a tuning message.
+    pub fn cents(self) -> Option<Cents> {
+        match self {
+            DeviceControl::MasterTuning(tuning) => Some(tuning.cents()),
+            _ => None,
+        }
+    }
+
+    /// Write the Universal Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let (sub_id, value) = match self {
+            DeviceControl::MasterVolume(v) => (0x01, v),
+            DeviceControl::MasterBalance(v) => (0x02, v),
+            DeviceControl::MasterTuning(tuning) => {
+                return tuning.copy_sysex_to_slice(device_id, slice)
+            }
+        };
+        if slice.len() < DeviceControl::SYSEX_BYTES_SIZE {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let value = u16::from(value);
+        slice[..DeviceControl::SYSEX_BYTES_SIZE].copy_from_slice(&[
+            0xF0,
+            0x7F,
+            u8::from(device_id),
+            0x04,
+            sub_id,
+            (value & 0x7F) as u8,
+            (value >> 7) as u8,
+            0xF7,
+        ]);
+        Ok(DeviceControl::SYSEX_BYTES_SIZE)
+    }
+
+    /// The Universal Real Time SysEx message, including the start and end bytes. A `device_id`
+    /// of 0x7F addresses all devices.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; DeviceControl::SYSEX_BYTES_SIZE];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a Device Control
+    /// message.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, DeviceControl)> {
+        match U7::data_to_bytes(payload) {
+            [0x7F, device_id, 0x04, sub_id @ (0x01 | 0x02), lsb, msb] => {
+                let value = U14::from_u16_lossy(u16::from(*msb) << 7 | u16::from(*lsb));
+                let message = if *sub_id == 0x01 {
+                    DeviceControl::MasterVolume(value)
+                } else {
+                    DeviceControl::MasterBalance(value)
+                };
+                Some((U7(*device_id), message))
+            }
+            _ => MasterTuning::from_sysex(payload)
+                .map(|(device_id, tuning)| (device_id, DeviceControl::MasterTuning(tuning))),
+        }
+    }
+}
+
+impl From<MasterTuning> for DeviceControl {
+    fn from(tuning: MasterTuning) -> DeviceControl {
+        DeviceControl::MasterTuning(tuning)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let messages = [
+            DeviceControl::MasterVolume(U14::from_u16_lossy(0x1234)),
+            DeviceControl::MasterBalance(DeviceControl::BALANCE_CENTER),
+            DeviceControl::from(MasterTuning::fine(Cents(25.0))),
+            DeviceControl::from(MasterTuning::coarse(-12)),
+        ];
+        for message in messages.iter() {
+            let mut bytes = [0u8; 8];
+            assert_eq!(message.copy_sysex_to_slice(U7(0x01), &mut bytes), Ok(8));
+            let payload = match MidiMessage::try_from(&bytes[..]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(
+                DeviceControl::from_sysex(payload),
+                Some((U7(0x01), *message))
+            );
+        }
+        assert_eq!(messages[0].cents(), None);
+        assert_eq!(messages[3].cents(), Some(Cents(-1200.0)));
+        assert_eq!(
+            messages[1].copy_sysex_to_slice(U7::MAX, &mut [0u8; 7]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+        assert_eq!(
+            DeviceControl::from_sysex(&[U7(0x7F), U7(0x7F), U7(0x04), U7(0x05), U7(0), U7(0)]),
+            None
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::{ManufacturerId, ToSliceError, UniversalKind, UniversalSysEx, U14, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MIDI 1.0] The identity of a device, sent in reply to an Identity Request.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Identity {
+    /// The manufacturer of the device.
+    pub manufacturer: ManufacturerId,
+    /// The product family, as defined by the manufacturer.
+    pub family: U14,
+    /// The product within the family, as defined by the manufacturer.
+    pub member: U14,
+    /// The software revision, in a format defined by the manufacturer.
+    pub version: [U7; 4],
+}
+
+/// [MIDI 1.0] The Device Inquiry messages of the General Information Universal Non-Real Time
+/// SysEx messages, used to find out which devices are connected.
+///
+/// A host sends an Identity Request, usually to all devices with a device ID of 0x7F, and each
+/// device answers with an Identity Reply holding its own device ID.
+///
+/// # Example
+/// ```
+/// use wmidi::{DeviceInquiry, U14, U7};
+/// let mut bytes = [0u8; 6];
+/// DeviceInquiry::Request.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]);
+///
+/// let reply = [
+///     0x7E, 0x10, 0x06, 0x02, 0x41, 0x0B, 0x02, 0x00, 0x00, 0x00, 0x01, 0x02, 0x00,
+/// ];
+/// let payload = U7::try_from_bytes(&reply).unwrap();
+/// let (device_id, inquiry) = DeviceInquiry::from_sysex(payload).unwrap();
+/// assert_eq!(device_id, U7::from_u8_lossy(0x10));
+/// match inquiry {
+///     DeviceInquiry::Reply(identity) => {
+///         assert_eq!(identity.manufacturer.name(), Some("Roland"));
+///         assert_eq!(identity.family, U14::from_u16_lossy(0x010B));
+///     }
+///     _ => panic!(),
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DeviceInquiry {
+    /// Asks devices to send an Identity Reply.
+    Request,
+    /// The identity of the device that sent the message.
+    Reply(Identity),
+}
+
+impl DeviceInquiry {
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        match self {
+            DeviceInquiry::Request => 6,
+            DeviceInquiry::Reply(identity) => 14 + identity.manufacturer.bytes_size(),
+        }
+    }
+
+    /// Write the Universal Non-Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let mut data = [U7::MIN; 11];
+        let (sub_id2, len) = match self {
+            DeviceInquiry::Request => (0x01, 0),
+            DeviceInquiry::Reply(identity) => {
+                let mut id = [0u8; 3];
+                let id_len = identity.manufacturer.copy_to_slice(&mut id)?;
+                let family = u16::from(identity.family);
+                let member = u16::from(identity.member);
+                let mut i = 0;
+                for byte in id[..id_len]
+                    .iter()
+                    .copied()
+                    .chain([family as u8, (family >> 7) as u8])
+                    .chain([member as u8, (member >> 7) as u8])
+                    .chain(identity.version.iter().map(|v| u8::from(*v)))
+                {
+                    data[i] = U7::from_u8_lossy(byte);
+                    i += 1;
+                }
+                (0x02, i)
+            }
+        };
+        UniversalSysEx {
+            kind: UniversalKind::NonRealTime,
+            device_id,
+            sub_id1: U7(0x06),
+            sub_id2: Some(U7(sub_id2)),
+            data: &data[..len],
+        }
+        .copy_sysex_to_slice(slice)
+    }
+
+    /// The Universal Non-Real Time SysEx message, including the start and end bytes. A
+    /// `device_id` of 0x7F addresses all devices.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a Device Inquiry
+    /// message.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, DeviceInquiry)> {
+        let message = UniversalSysEx::from_sysex(payload)?;
+        if message.kind != UniversalKind::NonRealTime || message.sub_id1 != U7(0x06) {
+            return None;
+        }
+        let inquiry = match (message.sub_id2.map(u8::from), message.data) {
+            (Some(0x01), []) => DeviceInquiry::Request,
+            (Some(0x02), data) => {
+                let (manufacturer, rest) = ManufacturerId::from_sysex(data)?;
+                match U7::data_to_bytes(rest) {
+                    [family_lsb, family_msb, member_lsb, member_msb, a, b, c, d] => {
+                        DeviceInquiry::Reply(Identity {
+                            manufacturer,
+                            family: combine(*family_lsb, *family_msb),
+                            member: combine(*member_lsb, *member_msb),
+                            version: [U7(*a), U7(*b), U7(*c), U7(*d)],
+                        })
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        Some((message.device_id, inquiry))
+    }
+}
+
+fn combine(lsb: u8, msb: u8) -> U14 {
+    U14::from_u16_lossy(u16::from(msb) << 7 | u16::from(lsb))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let messages = [
+            DeviceInquiry::Request,
+            DeviceInquiry::Reply(Identity {
+                manufacturer: ManufacturerId::Standard(U7(0x43)),
+                family: U14::from_u16_lossy(0x1234),
+                member: U14::MAX,
+                version: [U7(1), U7(2), U7(3), U7(4)],
+            }),
+            DeviceInquiry::Reply(Identity {
+                manufacturer: ManufacturerId::Extended(U7(0x20), U7(0x29)),
+                family: U14::MIN,
+                member: U14::from_u16_lossy(0x0101),
+                version: [U7::MIN; 4],
+            }),
+        ];
+        for inquiry in messages.iter() {
+            let mut bytes = [0u8; 17];
+            let size = inquiry.copy_sysex_to_slice(U7(0x10), &mut bytes).unwrap();
+            assert_eq!(size, inquiry.bytes_size());
+            let message = MidiMessage::try_from(&bytes[..size]).unwrap();
+            let payload = match message {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(
+                DeviceInquiry::from_sysex(payload),
+                Some((U7(0x10), *inquiry))
+            );
+        }
+        assert_eq!(
+            messages[1].copy_sysex_to_slice(U7::MAX, &mut [0u8; 14]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn from_sysex_rejects_other_messages() {
+        let payloads: [&[u8]; 3] = [
+            &[0x7F, 0x7F, 0x06, 0x01],
+            &[0x7E, 0x7F, 0x06, 0x01, 0x00],
+            &[0x7E, 0x7F, 0x06, 0x02, 0x41, 0x00, 0x00],
+        ];
+        for payload in payloads.iter() {
+            let payload = U7::try_from_bytes(payload).unwrap();
+            assert_eq!(DeviceInquiry::from_sysex(payload), None);
+        }
+    }
+}
@@ -17,6 +17,9 @@ pub enum FromBytesError {
     /// Not enough data bytes for the specified MIDI message.
     NotEnoughBytes,
 
+    /// A SysEx message has more data bytes than the allowed maximum.
+    SysExTooLong,
+
     /// Found a SysEx end byte, but there was no start byte.
     UnexpectedEndSysExByte,
 
@@ -54,6 +57,10 @@ impl fmt::Display for FromBytesError {
 pub enum ToSliceError {
     /// The destination buffer cannot fit all the bytes.
     BufferTooSmall,
+
+    /// A field of the message cannot be encoded, so writing it would produce a different or
+    /// corrupt message.
+    InvalidMessage,
 }
 
 #[cfg(feature = "std")]
@@ -63,6 +70,85 @@ impl fmt::Display for ToSliceError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ToSliceError::BufferTooSmall => write!(f, "buffer size too small"),
+            ToSliceError::InvalidMessage => write!(f, "message cannot be encoded"),
         }
     }
 }
+
+/// The name does not match any controller, when parsing a `ControlFunction`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ParseControlFunctionError;
+
+#[cfg(feature = "std")]
+impl error::Error for ParseControlFunctionError {}
+
+impl fmt::Display for ParseControlFunctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown control function")
+    }
+}
+
+/// The controller is not the MSB or LSB of a 14 bit controller, between 0 and 63 inclusive.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct NotHighResControllerError;
+
+#[cfg(feature = "std")]
+impl error::Error for NotHighResControllerError {}
+
+impl fmt::Display for NotHighResControllerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not a high resolution controller")
+    }
+}
+
+/// An MPE zone must have between 0 and 15 member channels inclusive.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MpeMemberCountError;
+
+#[cfg(feature = "std")]
+impl error::Error for MpeMemberCountError {}
+
+impl fmt::Display for MpeMemberCountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mpe member count out of range")
+    }
+}
+
+/// The fields of a SMPTE time are out of range for its frame rate.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SmpteTimeError;
+
+#[cfg(feature = "std")]
+impl error::Error for SmpteTimeError {}
+
+impl fmt::Display for SmpteTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "smpte time out of range")
+    }
+}
+
+/// A key signature must have between 7 flats and 7 sharps inclusive, and be major or minor.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct KeySignatureError;
+
+#[cfg(feature = "std")]
+impl error::Error for KeySignatureError {}
+
+impl fmt::Display for KeySignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key signature out of range")
+    }
+}
+
+/// A time signature must have at least 1 beat, and a beat value that is a power of 2.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TimeSignatureError;
+
+#[cfg(feature = "std")]
+impl error::Error for TimeSignatureError {}
+
+impl fmt::Display for TimeSignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "time signature out of range")
+    }
+}
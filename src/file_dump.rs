@@ -0,0 +1,363 @@
+//! The File Dump Standard, which transfers files such as Standard MIDI Files between devices.
+//!
+//! A transfer is a `Header` naming the file, followed by `DataPacket`s holding up to 98 bytes of
+//! the file each, and is ended with an end of file `Handshake`. File bytes are 8 bits, so each
+//! group of 7 is sent as a byte holding their most significant bits followed by their lower 7
+//! bits. The receiver controls the flow of packets with handshakes, as in the `sds` module.
+//!
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::packing::{self, MsbOrder};
+use crate::{Handshake, ToSliceError, U7};
+use core::str;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MIDI 1.0] The four character type of a file.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FileType(pub [u8; 4]);
+
+impl FileType {
+    /// A Standard MIDI File.
+    pub const MIDI: FileType = FileType(*b"MIDI");
+    /// A MIDIEX file.
+    pub const MIEX: FileType = FileType(*b"MIEX");
+    /// An Ensoniq sequence file.
+    pub const ESEQ: FileType = FileType(*b"ESEQ");
+    /// A text file.
+    pub const TEXT: FileType = FileType(*b"TEXT");
+    /// A binary file.
+    pub const BIN: FileType = FileType(*b"BIN ");
+    /// A Macintosh file, with MacBinary header.
+    pub const MAC: FileType = FileType(*b"MAC ");
+
+    /// The type as a string, or `None` if it is not printable ASCII.
+    pub fn as_str(&self) -> Option<&str> {
+        if !self.0.iter().all(|b| (0x20..0x7F).contains(b)) {
+            return None;
+        }
+        str::from_utf8(&self.0).ok()
+    }
+}
+
+/// [MIDI 1.0] Starts a file dump.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Header<'a> {
+    /// The device ID of the sender, so the receiver can address its handshakes.
+    pub sender: U7,
+    /// The type of the file.
+    pub file_type: FileType,
+    /// The length of the file in bytes, up to 28 bits.
+    pub length: u32,
+    /// The name of the file, in ASCII. May be empty.
+    pub name: &'a str,
+}
+
+/// [MIDI 1.0] Asks a device to dump a file.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Request<'a> {
+    /// The device ID of the requester, which the file is sent to.
+    pub requester: U7,
+    /// The type of the file.
+    pub file_type: FileType,
+    /// The name of the file, in ASCII.
+    pub name: &'a str,
+}
+
+/// [MIDI 1.0] Up to 112 bytes of encoded file data.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DataPacket {
+    /// The number of the packet, counting from 0 and wrapping after 127.
+    pub number: U7,
+    len: u8,
+    data: [U7; DataPacket::DATA_SIZE],
+}
+
+impl DataPacket {
+    /// The most encoded bytes a packet holds.
+    pub const DATA_SIZE: usize = 112;
+
+    /// The most file bytes a packet holds.
+    pub const MAX_BYTES: usize = 98;
+
+    /// Encode as many of `bytes` as fit into a packet. Returns the packet and the number of bytes
+    /// used, which is 0 only if `bytes` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::file_dump::DataPacket;
+    /// use wmidi::U7;
+    /// let file = [0x4D, 0x54, 0x68, 0x64, 0x80, 0xFF];
+    /// let (packet, used) = DataPacket::from_bytes(U7::MIN, &file);
+    /// assert_eq!(used, 6);
+    /// assert_eq!(U7::data_to_bytes(packet.data()), [0x06, 0x4D, 0x54, 0x68, 0x64, 0x00, 0x7F]);
+    /// assert!(packet.bytes().eq(file.iter().copied()));
+    /// ```
+    pub fn from_bytes(number: U7, bytes: &[u8]) -> (DataPacket, usize) {
+        let used = bytes.len().min(DataPacket::MAX_BYTES);
+        let mut data = [U7::MIN; DataPacket::DATA_SIZE];
+        let len = packing::encode_7in8(&bytes[..used], MsbOrder::FirstInBit6, &mut data).unwrap();
+        let packet = DataPacket {
+            number,
+            len: len as u8,
+            data,
+        };
+        (packet, used)
+    }
+
+    /// The encoded data, as sent.
+    pub fn data(&self) -> &[U7] {
+        &self.data[..usize::from(self.len)]
+    }
+
+    /// The file bytes held by the packet.
+    pub fn bytes(&self) -> impl '_ + Iterator<Item = u8> {
+        self.data().chunks(8).flat_map(|group| {
+            let msbs = u8::from(group[0]);
+            group[1..]
+                .iter()
+                .enumerate()
+                .map(move |(i, byte)| u8::from(*byte) | (msbs >> (6 - i) & 1) << 7)
+        })
+    }
+
+    /// The checksum sent after the data: the exclusive or of the bytes after the start byte.
+    pub fn checksum(&self, device_id: U7) -> U7 {
+        let byte_count = self.len.saturating_sub(1);
+        let header = 0x7E ^ u8::from(device_id) ^ 0x07 ^ 0x02 ^ u8::from(self.number) ^ byte_count;
+        let checksum = self
+            .data()
+            .iter()
+            .fold(header, |checksum, byte| checksum ^ u8::from(*byte));
+        U7::from_u8_lossy(checksum)
+    }
+}
+
+/// [MIDI 1.0] A File Dump message.
+///
+/// # Example
+/// ```
+/// use wmidi::file_dump::{FileType, Message, Request};
+/// use wmidi::U7;
+/// let request = Message::Request(Request {
+///     requester: U7::from_u8_lossy(0x10),
+///     file_type: FileType::MIDI,
+///     name: "SONG",
+/// });
+/// let mut bytes = [0u8; 15];
+/// request.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(
+///     bytes,
+///     [0xF0, 0x7E, 0x7F, 0x07, 0x03, 0x10, b'M', b'I', b'D', b'I', b'S', b'O', b'N', b'G', 0xF7]
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Message<'a> {
+    /// Starts a dump.
+    Header(Header<'a>),
+    /// File data.
+    Packet(DataPacket),
+    /// Asks the device to dump a file.
+    Request(Request<'a>),
+    /// Controls the flow of packets, or ends the dump.
+    Handshake(Handshake),
+}
+
+impl<'a> Message<'a> {
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        match self {
+            Message::Header(header) => 15 + header.name.len(),
+            Message::Packet(packet) => 9 + usize::from(packet.len),
+            Message::Request(request) => 11 + request.name.len(),
+            Message::Handshake(_) => Handshake::SYSEX_BYTES_SIZE,
+        }
+    }
+
+    /// Write the Universal Non-Real Time SysEx message to `slice`, including the start and end
+    /// bytes. Returns the number of bytes written. Names and types that are not ASCII are written
+    /// with their bytes masked to 7 bits.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..4].copy_from_slice(&[0xF0, 0x7E, u8::from(device_id), 0x07]);
+        match self {
+            Message::Header(header) => {
+                slice[4] = 0x01;
+                slice[5] = u8::from(header.sender);
+                copy_ascii(&header.file_type.0, &mut slice[6..10]);
+                for (i, byte) in slice[10..14].iter_mut().enumerate() {
+                    *byte = (header.length >> (i * 7)) as u8 & 0x7F;
+                }
+                copy_ascii(header.name.as_bytes(), &mut slice[14..size - 1]);
+            }
+            Message::Packet(packet) => {
+                slice[4..7].copy_from_slice(&[
+                    0x02,
+                    u8::from(packet.number),
+                    packet.len.saturating_sub(1),
+                ]);
+                slice[7..size - 2].copy_from_slice(U7::data_to_bytes(packet.data()));
+                slice[size - 2] = u8::from(packet.checksum(device_id));
+            }
+            Message::Request(request) => {
+                slice[4] = 0x03;
+                slice[5] = u8::from(request.requester);
+                copy_ascii(&request.file_type.0, &mut slice[6..10]);
+                copy_ascii(request.name.as_bytes(), &mut slice[10..size - 1]);
+            }
+            Message::Handshake(handshake) => {
+                return handshake.copy_sysex_to_slice(device_id, slice);
+            }
+        }
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal Non-Real Time SysEx message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a File Dump message.
+    /// Data packets with an incorrect checksum are not returned.
+    pub fn from_sysex(payload: &'a [U7]) -> Option<(U7, Message<'a>)> {
+        if let Some((device_id, handshake)) = Handshake::from_sysex(payload) {
+            return Some((device_id, Message::Handshake(handshake)));
+        }
+        let (device_id, message) = match U7::data_to_bytes(payload) {
+            [0x7E, device_id, 0x07, 0x01, sender, t0, t1, t2, t3, l0, l1, l2, l3, name @ ..] => {
+                let length = [l0, l1, l2, l3]
+                    .iter()
+                    .enumerate()
+                    .fold(0u32, |length, (i, byte)| {
+                        length | u32::from(**byte) << (i * 7)
+                    });
+                let header = Header {
+                    sender: U7(*sender),
+                    file_type: FileType([*t0, *t1, *t2, *t3]),
+                    length,
+                    name: str::from_utf8(name).ok()?,
+                };
+                (device_id, Message::Header(header))
+            }
+            [0x7E, device_id, 0x07, 0x02, number, byte_count, data @ .., checksum]
+                if !data.is_empty()
+                    && data.len() <= DataPacket::DATA_SIZE
+                    && usize::from(*byte_count) + 1 == data.len() =>
+            {
+                let mut packet = DataPacket {
+                    number: U7(*number),
+                    len: data.len() as u8,
+                    data: [U7::MIN; DataPacket::DATA_SIZE],
+                };
+                packet.data[..data.len()].copy_from_slice(&payload[6..6 + data.len()]);
+                if packet.checksum(U7(*device_id)) != U7(*checksum) {
+                    return None;
+                }
+                (device_id, Message::Packet(packet))
+            }
+            [0x7E, device_id, 0x07, 0x03, requester, t0, t1, t2, t3, name @ ..] => {
+                let request = Request {
+                    requester: U7(*requester),
+                    file_type: FileType([*t0, *t1, *t2, *t3]),
+                    name: str::from_utf8(name).ok()?,
+                };
+                (device_id, Message::Request(request))
+            }
+            _ => return None,
+        };
+        Some((U7(*device_id), message))
+    }
+}
+
+fn copy_ascii(bytes: &[u8], slice: &mut [u8]) {
+    for (dst, src) in slice.iter_mut().zip(bytes) {
+        *dst = src & 0x7F;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{HandshakeKind, MidiMessage};
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let file: [u8; 200] = core::array::from_fn(|i| (i * 37) as u8);
+        let (packet, used) = DataPacket::from_bytes(U7(3), &file);
+        assert_eq!(used, DataPacket::MAX_BYTES);
+        assert_eq!(packet.data().len(), DataPacket::DATA_SIZE);
+        let (last, used) = DataPacket::from_bytes(U7(4), &file[196..]);
+        assert_eq!(used, 4);
+        assert_eq!(last.data().len(), 5);
+        let messages = [
+            Message::Header(Header {
+                sender: U7(0x01),
+                file_type: FileType::BIN,
+                length: 0x0FFF_FFFF,
+                name: "data.bin",
+            }),
+            Message::Header(Header {
+                sender: U7(0x01),
+                file_type: FileType::TEXT,
+                length: 0,
+                name: "",
+            }),
+            Message::Packet(packet),
+            Message::Packet(last),
+            Message::Request(Request {
+                requester: U7(0x02),
+                file_type: FileType::MIDI,
+                name: "song.mid",
+            }),
+            Message::Handshake(Handshake {
+                kind: HandshakeKind::EndOfFile,
+                packet: U7(4),
+            }),
+        ];
+        for message in messages.iter() {
+            let mut bytes = [0u8; 256];
+            let size = message.copy_sysex_to_slice(U7(0x05), &mut bytes).unwrap();
+            assert_eq!(size, message.bytes_size());
+            let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(Message::from_sysex(payload), Some((U7(0x05), *message)));
+        }
+        assert!(packet.bytes().eq(file[..98].iter().copied()));
+        assert!(last.bytes().eq(file[196..].iter().copied()));
+    }
+
+    #[test]
+    fn bad_checksum() {
+        let (packet, _) = DataPacket::from_bytes(U7::MIN, b"MThd");
+        let mut bytes = [0u8; 256];
+        let size = Message::Packet(packet)
+            .copy_sysex_to_slice(U7::MIN, &mut bytes)
+            .unwrap();
+        bytes[size - 2] ^= 0x01;
+        let payload = U7::try_from_bytes(&bytes[1..size - 1]).unwrap();
+        assert_eq!(Message::from_sysex(payload), None);
+    }
+
+    #[test]
+    fn file_type() {
+        assert_eq!(FileType::BIN.as_str(), Some("BIN "));
+        assert_eq!(FileType([0x00, 0x41, 0x42, 0x43]).as_str(), None);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{ChannelSet, ControlFunctionSet, MessageKind, MessageKindSet, MidiMessage, Note};
+
+/// Decides which messages to pass on, for example when echoing an input to an output (soft thru)
+/// or choosing the messages an input responds to.
+///
+/// A message is allowed if its kind is in `kinds`, and where they apply, its channel is in
+/// `channels`, its controller is in `controllers`, and its note is between `lowest_note` and
+/// `highest_note` inclusive.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ChannelSet, Filter, MessageKindSet, MidiMessage, Note, U7};
+/// let filter = Filter {
+///     kinds: MessageKindSet::CHANNEL,
+///     channels: ChannelSet::from(Channel::Ch1),
+///     lowest_note: Note::C4,
+///     ..Filter::new()
+/// };
+/// assert!(filter.allows(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX)));
+/// assert!(!filter.allows(&MidiMessage::NoteOn(Channel::Ch1, Note::B3, U7::MAX)));
+/// assert!(!filter.allows(&MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7::MAX)));
+/// assert!(!filter.allows(&MidiMessage::TimingClock));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Filter {
+    /// The kinds of message to allow.
+    pub kinds: MessageKindSet,
+    /// The channels to allow channel messages on.
+    pub channels: ChannelSet,
+    /// The controllers to allow `ControlChange` messages for.
+    pub controllers: ControlFunctionSet,
+    /// The lowest note to allow `NoteOn`, `NoteOff`, and `PolyphonicKeyPressure` messages for.
+    pub lowest_note: Note,
+    /// The highest note to allow `NoteOn`, `NoteOff`, and `PolyphonicKeyPressure` messages for.
+    pub highest_note: Note,
+}
+
+impl Filter {
+    /// The filter that allows every message.
+    pub const ALLOW_ALL: Filter = Filter {
+        kinds: MessageKindSet::ALL,
+        channels: ChannelSet::ALL,
+        controllers: ControlFunctionSet::ALL,
+        lowest_note: Note::LOWEST_NOTE,
+        highest_note: Note::HIGHEST_NOTE,
+    };
+
+    /// Create a filter that allows every message.
+    pub fn new() -> Filter {
+        Filter::ALLOW_ALL
+    }
+
+    /// Returns `true` if `message` passes the filter.
+    pub fn allows(&self, message: &MidiMessage) -> bool {
+        if !self.kinds.contains(message.kind()) {
+            return false;
+        }
+        if let Some(channel) = message.channel() {
+            if !self.channels.contains(channel) {
+                return false;
+            }
+        }
+        match message {
+            MidiMessage::NoteOn(_, note, _)
+            | MidiMessage::NoteOff(_, note, _)
+            | MidiMessage::PolyphonicKeyPressure(_, note, _) => {
+                self.lowest_note <= *note && *note <= self.highest_note
+            }
+            MidiMessage::ControlChange(_, function, _) => self.controllers.contains(*function),
+            _ => true,
+        }
+    }
+
+    /// Call `emit` with `message` if it passes the filter.
+    pub fn handle<'a, F: FnMut(MidiMessage<'a>)>(&self, message: &MidiMessage<'a>, mut emit: F) {
+        if self.allows(message) {
+            emit(message.clone());
+        }
+    }
+
+    /// Iterate over the messages of `messages` that pass the filter.
+    pub fn filter_iter<'a, I: IntoIterator<Item = MidiMessage<'a>>>(
+        self,
+        messages: I,
+    ) -> impl Iterator<Item = MidiMessage<'a>> {
+        messages.into_iter().filter(move |m| self.allows(m))
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter::ALLOW_ALL
+    }
+}
+
+impl From<MessageKindSet> for Filter {
+    /// A filter that allows messages with a kind in `kinds`.
+    fn from(kinds: MessageKindSet) -> Filter {
+        Filter {
+            kinds,
+            ..Filter::ALLOW_ALL
+        }
+    }
+}
+
+impl From<MessageKind> for Filter {
+    /// A filter that allows messages of `kind`.
+    fn from(kind: MessageKind) -> Filter {
+        Filter::from(MessageKindSet::from(kind))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Channel, ControlFunction, U7};
+
+    #[test]
+    fn allow_all() {
+        let messages = [
+            MidiMessage::TimingClock,
+            MidiMessage::SysEx(&[]),
+            MidiMessage::NoteOn(Channel::Ch16, Note::G9, U7(1)),
+            MidiMessage::ControlChange(Channel::Ch1, ControlFunction(U7(127)), U7(0)),
+        ];
+        assert_eq!(
+            Filter::new().filter_iter(messages.iter().cloned()).count(),
+            4
+        );
+    }
+
+    #[test]
+    fn controllers() {
+        let filter = Filter {
+            controllers: !ControlFunctionSet::from(ControlFunction::MODULATION_WHEEL),
+            ..Filter::new()
+        };
+        let mut count = 0;
+        for function in [ControlFunction::MODULATION_WHEEL, ControlFunction::PAN].iter() {
+            filter.handle(
+                &MidiMessage::ControlChange(Channel::Ch1, *function, U7(0)),
+                |_| count += 1,
+            );
+        }
+        assert_eq!(count, 1);
+        // Other kinds of messages are not affected by the controller set.
+        assert!(filter.allows(&MidiMessage::ProgramChange(Channel::Ch1, U7(0))));
+    }
+
+    #[test]
+    fn kinds() {
+        let filter = Filter::from(MessageKind::SysEx);
+        assert!(filter.allows(&MidiMessage::SysEx(&[U7(1)])));
+        assert!(!filter.allows(&MidiMessage::Start));
+        assert!(!filter.allows(&MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0))));
+    }
+
+    #[test]
+    fn sysex_passes_unchanged() {
+        let message = MidiMessage::SysEx(&[U7(0x7D), U7(1)]);
+        let mut count = 0;
+        Filter::new().handle(&message, |m| {
+            assert_eq!(m, message);
+            count += 1;
+        });
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,135 @@
+/// An unsigned fixed point number with 16 integer bits and 16 fractional bits (Q16.16).
+///
+/// Intended for targets without a floating point unit.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct UFix32(pub u32);
+
+impl UFix32 {
+    /// The number of fractional bits.
+    pub const FRAC_BITS: u32 = 16;
+    /// The smallest representable value, `0.0`.
+    pub const MIN: UFix32 = UFix32(0);
+    /// The largest representable value, just below `65536.0`.
+    pub const MAX: UFix32 = UFix32(u32::MAX);
+    /// The value `1.0`.
+    pub const ONE: UFix32 = UFix32(1 << UFix32::FRAC_BITS);
+
+    /// Create a value with no fractional part.
+    #[inline(always)]
+    pub const fn from_int(integer: u16) -> UFix32 {
+        UFix32((integer as u32) << UFix32::FRAC_BITS)
+    }
+
+    /// The integer part, rounded down.
+    #[inline(always)]
+    pub const fn integer(self) -> u16 {
+        (self.0 >> UFix32::FRAC_BITS) as u16
+    }
+
+    /// The fractional part, in 65536ths.
+    #[inline(always)]
+    pub const fn fraction(self) -> u16 {
+        self.0 as u16
+    }
+
+    /// Convert to a floating point number.
+    #[inline(always)]
+    pub fn to_f32(self) -> f32 {
+        self.0 as f32 / UFix32::ONE.0 as f32
+    }
+
+    /// Convert to a floating point number.
+    #[inline(always)]
+    pub fn to_f64(self) -> f64 {
+        f64::from(self.0) / f64::from(UFix32::ONE.0)
+    }
+}
+
+/// The frequencies of notes 120 (C9) through 132 (C10) with the standard 440Hz tuning. Lower
+/// octaves are derived by halving.
+const TOP_OCTAVE_FREQS: [u32; 13] = [
+    0x20b4_04a2,
+    0x22a5_d81d,
+    0x24b5_45c7,
+    0x26e4_1040,
+    0x2934_14f2,
+    0x2ba7_4dac,
+    0x2e3f_d250,
+    0x30ff_da9d,
+    0x33e9_c015,
+    0x3700_0000,
+    0x3a45_3d89,
+    0x3dbc_4401,
+    0x4168_0943,
+];
+
+/// The frequency in Hz for a fractional note number with the standard 440Hz tuning. For
+/// example, `69.5` is a quarter tone above A440.
+///
+/// Frequencies between semitones are linearly interpolated, which is accurate to within 1 cent.
+/// Pitches above `131.0` (B9) are treated as `131.0`.
+///
+/// # Example
+/// ```
+/// use wmidi::{pitch_to_freq_fixed, UFix32};
+/// let a440 = pitch_to_freq_fixed(UFix32::from_int(69));
+/// assert_eq!(a440, UFix32::from_int(440));
+/// ```
+pub fn pitch_to_freq_fixed(pitch: UFix32) -> UFix32 {
+    let (semitone, fraction) = if pitch.integer() > 131 {
+        (131, 0)
+    } else {
+        (u32::from(pitch.integer()), u64::from(pitch.fraction()))
+    };
+    let lo = TOP_OCTAVE_FREQS[(semitone % 12) as usize];
+    let hi = TOP_OCTAVE_FREQS[(semitone % 12) as usize + 1];
+    let top_octave_freq = lo + ((u64::from(hi - lo) * fraction) >> UFix32::FRAC_BITS) as u32;
+    let shift = 10 - semitone / 12;
+    if shift == 0 {
+        UFix32(top_octave_freq)
+    } else {
+        // Round to nearest.
+        UFix32((top_octave_freq + (1 << (shift - 1))) >> shift)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn accessors() {
+        let value = UFix32(0x0003_8000);
+        assert_eq!(value.integer(), 3);
+        assert_eq!(value.fraction(), 0x8000);
+        assert_eq!(value.to_f64(), 3.5);
+        assert_eq!(UFix32::from_int(1), UFix32::ONE);
+    }
+
+    #[test]
+    fn pitch_to_freq_matches_octaves() {
+        assert_eq!(pitch_to_freq_fixed(UFix32::from_int(57)).0, 220 << 16);
+        assert_eq!(pitch_to_freq_fixed(UFix32::from_int(9)).0, 0x000D_C000);
+        assert_eq!(
+            pitch_to_freq_fixed(UFix32::from_int(200)),
+            pitch_to_freq_fixed(UFix32::from_int(131))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn pitch_to_freq_interpolation_is_within_a_cent() {
+        for step in 0..(128 * 16) {
+            let pitch = UFix32(step << 12);
+            let expected = 440.0 * 2f64.powf((pitch.to_f64() - 69.0) / 12.0);
+            let actual = pitch_to_freq_fixed(pitch).to_f64();
+            let cents = 1200.0 * (actual / expected).log2();
+            assert!(
+                cents.abs() < 1.0,
+                "{} cents off at {}",
+                cents,
+                pitch.to_f64()
+            );
+        }
+    }
+}
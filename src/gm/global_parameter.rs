@@ -0,0 +1,217 @@
+use crate::{ToSliceError, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [GM2] The reverb types selected by `GlobalParameter::ReverbType`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReverbType {
+    SmallRoom = 0,
+    MediumRoom = 1,
+    LargeRoom = 2,
+    MediumHall = 3,
+    LargeHall = 4,
+    Plate = 8,
+}
+
+impl ReverbType {
+    /// Get the reverb type for `value`, or `None` if GM2 does not define one.
+    pub fn from_value(value: U7) -> Option<ReverbType> {
+        match u8::from(value) {
+            0 => Some(ReverbType::SmallRoom),
+            1 => Some(ReverbType::MediumRoom),
+            2 => Some(ReverbType::LargeRoom),
+            3 => Some(ReverbType::MediumHall),
+            4 => Some(ReverbType::LargeHall),
+            8 => Some(ReverbType::Plate),
+            _ => None,
+        }
+    }
+}
+
+/// [GM2] The chorus types selected by `GlobalParameter::ChorusType`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChorusType {
+    Chorus1 = 0,
+    Chorus2 = 1,
+    Chorus3 = 2,
+    Chorus4 = 3,
+    FeedbackChorus = 4,
+    Flanger = 5,
+}
+
+impl ChorusType {
+    /// Get the chorus type for `value`, or `None` if GM2 does not define one.
+    pub fn from_value(value: U7) -> Option<ChorusType> {
+        match u8::from(value) {
+            0 => Some(ChorusType::Chorus1),
+            1 => Some(ChorusType::Chorus2),
+            2 => Some(ChorusType::Chorus3),
+            3 => Some(ChorusType::Chorus4),
+            4 => Some(ChorusType::FeedbackChorus),
+            5 => Some(ChorusType::Flanger),
+            _ => None,
+        }
+    }
+}
+
+/// [GM2] A reverb or chorus parameter set with the Global Parameter Control Universal Real Time
+/// SysEx message.
+///
+/// GM2 messages address a single slot (reverb is 0x0101, chorus is 0x0102) with one byte
+/// parameter numbers and values. Messages that set several parameters at once, or use wider
+/// parameters, are not parsed.
+///
+/// # Example
+/// ```
+/// use wmidi::gm::{GlobalParameter, ReverbType};
+/// use wmidi::U7;
+/// let mut bytes = [0u8; 13];
+/// let parameter = GlobalParameter::ReverbType(ReverbType::Plate);
+/// parameter.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(
+///     bytes,
+///     [0xF0, 0x7F, 0x7F, 0x04, 0x05, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0x08, 0xF7]
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum GlobalParameter {
+    /// The reverb algorithm.
+    ReverbType(ReverbType),
+    /// The reverb time. The time in seconds is `exp((value - 40) * 0.025)`.
+    ReverbTime(U7),
+    /// The chorus algorithm.
+    ChorusType(ChorusType),
+    /// The modulation frequency, `value * 0.122` Hz.
+    ChorusModRate(U7),
+    /// The modulation depth, `(value + 1) / 3.2` ms.
+    ChorusModDepth(U7),
+    /// The feedback level, `value * 0.763` percent.
+    ChorusFeedback(U7),
+    /// The level of chorus sent to the reverb, `value * 0.787` percent.
+    ChorusSendToReverb(U7),
+}
+
+impl GlobalParameter {
+    /// The number of bytes in the SysEx message, including the start and end bytes.
+    pub const SYSEX_BYTES_SIZE: usize = 13;
+
+    /// The slot path, parameter number, and value of the parameter.
+    fn encode(self) -> ([u8; 2], u8, u8) {
+        const REVERB: [u8; 2] = [0x01, 0x01];
+        const CHORUS: [u8; 2] = [0x01, 0x02];
+        match self {
+            GlobalParameter::ReverbType(t) => (REVERB, 0x00, t as u8),
+            GlobalParameter::ReverbTime(v) => (REVERB, 0x01, u8::from(v)),
+            GlobalParameter::ChorusType(t) => (CHORUS, 0x00, t as u8),
+            GlobalParameter::ChorusModRate(v) => (CHORUS, 0x01, u8::from(v)),
+            GlobalParameter::ChorusModDepth(v) => (CHORUS, 0x02, u8::from(v)),
+            GlobalParameter::ChorusFeedback(v) => (CHORUS, 0x03, u8::from(v)),
+            GlobalParameter::ChorusSendToReverb(v) => (CHORUS, 0x04, u8::from(v)),
+        }
+    }
+
+    /// Write the Universal Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        if slice.len() < GlobalParameter::SYSEX_BYTES_SIZE {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let ([slot_msb, slot_lsb], parameter, value) = self.encode();
+        slice[..GlobalParameter::SYSEX_BYTES_SIZE].copy_from_slice(&[
+            0xF0,
+            0x7F,
+            u8::from(device_id),
+            0x04,
+            0x05,
+            0x01,
+            0x01,
+            0x01,
+            slot_msb,
+            slot_lsb,
+            parameter,
+            value,
+            0xF7,
+        ]);
+        Ok(GlobalParameter::SYSEX_BYTES_SIZE)
+    }
+
+    /// The Universal Real Time SysEx message, including the start and end bytes. A `device_id`
+    /// of 0x7F addresses all devices.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; GlobalParameter::SYSEX_BYTES_SIZE];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and parameter, or `None` if the payload is not a GM2 Global
+    /// Parameter Control message or the value is not defined.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, GlobalParameter)> {
+        let (device_id, slot, parameter, value) = match U7::data_to_bytes(payload) {
+            [0x7F, device_id, 0x04, 0x05, 0x01, 0x01, 0x01, msb, lsb, parameter, value] => {
+                (*device_id, [*msb, *lsb], *parameter, U7(*value))
+            }
+            _ => return None,
+        };
+        let message = match (slot, parameter) {
+            ([0x01, 0x01], 0x00) => GlobalParameter::ReverbType(ReverbType::from_value(value)?),
+            ([0x01, 0x01], 0x01) => GlobalParameter::ReverbTime(value),
+            ([0x01, 0x02], 0x00) => GlobalParameter::ChorusType(ChorusType::from_value(value)?),
+            ([0x01, 0x02], 0x01) => GlobalParameter::ChorusModRate(value),
+            ([0x01, 0x02], 0x02) => GlobalParameter::ChorusModDepth(value),
+            ([0x01, 0x02], 0x03) => GlobalParameter::ChorusFeedback(value),
+            ([0x01, 0x02], 0x04) => GlobalParameter::ChorusSendToReverb(value),
+            _ => return None,
+        };
+        Some((U7(device_id), message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let parameters = [
+            GlobalParameter::ReverbType(ReverbType::LargeHall),
+            GlobalParameter::ReverbTime(U7(64)),
+            GlobalParameter::ChorusType(ChorusType::Flanger),
+            GlobalParameter::ChorusModRate(U7(3)),
+            GlobalParameter::ChorusModDepth(U7(19)),
+            GlobalParameter::ChorusFeedback(U7(8)),
+            GlobalParameter::ChorusSendToReverb(U7::MAX),
+        ];
+        for parameter in parameters.iter() {
+            let mut bytes = [0u8; 13];
+            assert_eq!(parameter.copy_sysex_to_slice(U7(0x10), &mut bytes), Ok(13));
+            let payload = match MidiMessage::try_from(&bytes[..]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(
+                GlobalParameter::from_sysex(payload),
+                Some((U7(0x10), *parameter))
+            );
+        }
+    }
+
+    #[test]
+    fn undefined_values() {
+        assert_eq!(ReverbType::from_value(U7(5)), None);
+        assert_eq!(ChorusType::from_value(U7(6)), None);
+        let payload = U7::try_from_bytes(&[
+            0x7F, 0x7F, 0x04, 0x05, 0x01, 0x01, 0x01, 0x01, 0x02, 0x05, 0x00,
+        ])
+        .unwrap();
+        assert_eq!(GlobalParameter::from_sysex(payload), None);
+    }
+}
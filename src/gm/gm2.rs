@@ -0,0 +1,355 @@
+use super::Program;
+use crate::{Channel, ControlFunction, MidiMessage, ProgramNumber, U7};
+use core::fmt;
+
+/// [GM2] The Bank Select MSB of the melodic sound set.
+pub const MELODIC_BANK_MSB: U7 = U7(0x79);
+
+/// [GM2] The Bank Select MSB of the rhythm (drum kit) sound set.
+pub const RHYTHM_BANK_MSB: U7 = U7(0x78);
+
+/// [GM2] Variation sounds of the melodic bank, as `(program, bank LSB, name)`. Bank LSB `0` is the
+/// GM1 capital tone and is not listed. Sorted by program, then by bank LSB.
+const VARIATIONS: &[(u8, u8, &str)] = &[
+    (0, 1, "Acoustic Grand Piano (wide)"),
+    (0, 2, "Acoustic Grand Piano (dark)"),
+    (1, 1, "Bright Acoustic Piano (wide)"),
+    (2, 1, "Electric Grand Piano (wide)"),
+    (3, 1, "Honky-tonk Piano (wide)"),
+    (4, 1, "Detuned Electric Piano 1"),
+    (4, 2, "Electric Piano 1 (velocity mix)"),
+    (4, 3, "60's Electric Piano"),
+    (5, 1, "Detuned Electric Piano 2"),
+    (5, 2, "Electric Piano 2 (velocity mix)"),
+    (5, 3, "EP Legend"),
+    (5, 4, "EP Phase"),
+    (6, 1, "Harpsichord (octave mix)"),
+    (6, 2, "Harpsichord (wide)"),
+    (6, 3, "Harpsichord (with key off)"),
+    (7, 1, "Pulse Clavi"),
+    (11, 1, "Vibraphone (wide)"),
+    (12, 1, "Marimba (wide)"),
+    (14, 1, "Church Bell"),
+    (14, 2, "Carillon"),
+    (16, 1, "Detuned Drawbar Organ"),
+    (16, 2, "Italian 60's Organ"),
+    (16, 3, "Drawbar Organ 2"),
+    (17, 1, "Detuned Percussive Organ"),
+    (17, 2, "Percussive Organ 2"),
+    (19, 1, "Church Organ (octave mix)"),
+    (19, 2, "Detuned Church Organ"),
+    (20, 1, "Puff Organ"),
+    (21, 1, "Accordion 2"),
+    (24, 1, "Ukulele"),
+    (24, 2, "Acoustic Guitar (nylon + key off)"),
+    (24, 3, "Acoustic Guitar (nylon 2)"),
+    (25, 1, "12-Strings Guitar"),
+    (25, 2, "Mandolin"),
+    (25, 3, "Steel Guitar with Body Sound"),
+    (26, 1, "Electric Guitar (pedal steel)"),
+    (27, 1, "Electric Guitar (detuned clean)"),
+    (27, 2, "Mid Tone Guitar"),
+    (28, 1, "Electric Guitar (funky cutting)"),
+    (28, 2, "Electric Guitar (muted velo-sw)"),
+    (28, 3, "Jazz Man"),
+    (29, 1, "Guitar Pinch"),
+    (30, 1, "Distortion Guitar (with feedback)"),
+    (30, 2, "Distorted Rhythm Guitar"),
+    (31, 1, "Guitar Feedback"),
+    (33, 1, "Finger Slap Bass"),
+    (38, 1, "Synth Bass (warm)"),
+    (38, 2, "Synth Bass 3 (resonance)"),
+    (38, 3, "Clavi Bass"),
+    (38, 4, "Hammer"),
+    (39, 1, "Synth Bass 4 (attack)"),
+    (39, 2, "Synth Bass (rubber)"),
+    (39, 3, "Attack Pulse"),
+    (40, 1, "Violin (slow attack)"),
+    (46, 1, "Yang Chin"),
+    (48, 1, "Strings and Brass"),
+    (48, 2, "60s Strings"),
+    (50, 1, "Synth Strings 3"),
+    (52, 1, "Choir Aahs 2"),
+    (53, 1, "Humming"),
+    (54, 1, "Analog Voice"),
+    (55, 1, "Bass Hit Plus"),
+    (55, 2, "6th Hit"),
+    (55, 3, "Euro Hit"),
+    (56, 1, "Dark Trumpet Soft"),
+    (57, 1, "Trombone 2"),
+    (57, 2, "Bright Trombone"),
+    (59, 1, "Muted Trumpet 2"),
+    (60, 1, "French Horn 2 (warm)"),
+    (61, 1, "Brass Section 2 (octave mix)"),
+    (62, 1, "Synth Brass 3"),
+    (62, 2, "Analog Synth Brass 1"),
+    (62, 3, "Jump Brass"),
+    (63, 1, "Synth Brass 4"),
+    (63, 2, "Analog Synth Brass 2"),
+    (80, 1, "Square Wave"),
+    (80, 2, "Sine Wave"),
+    (81, 1, "Saw Wave"),
+    (81, 2, "Doctor Solo"),
+    (81, 3, "Natural Lead"),
+    (81, 4, "Sequenced Saw"),
+    (84, 1, "Wire Lead"),
+    (87, 1, "Soft Wrl"),
+    (89, 1, "Sine Pad"),
+    (91, 1, "Itopia"),
+    (98, 1, "Synth Mallet"),
+    (102, 1, "Echo Bell"),
+    (102, 2, "Echo Pan"),
+    (104, 1, "Sitar 2 (bend)"),
+    (107, 1, "Taisho Koto"),
+    (115, 1, "Castanets"),
+    (116, 1, "Concert Bass Drum"),
+    (117, 1, "Melodic Tom 2 (power)"),
+    (118, 1, "Rhythm Box Tom"),
+    (118, 2, "Electric Drum"),
+    (120, 1, "Guitar Cutting Noise"),
+    (120, 2, "Acoustic Bass String Slap"),
+    (121, 1, "Flute Key Click"),
+    (122, 1, "Rain"),
+    (122, 2, "Thunder"),
+    (122, 3, "Wind"),
+    (122, 4, "Stream"),
+    (122, 5, "Bubble"),
+    (123, 1, "Dog"),
+    (123, 2, "Horse Gallop"),
+    (123, 3, "Bird Tweet 2"),
+    (124, 1, "Telephone Ring 2"),
+    (124, 2, "Door Creaking"),
+    (124, 3, "Door"),
+    (124, 4, "Scratch"),
+    (124, 5, "Wind Chime"),
+    (125, 1, "Car Engine"),
+    (125, 2, "Car Stop"),
+    (125, 3, "Car Pass"),
+    (125, 4, "Car Crash"),
+    (125, 5, "Siren"),
+    (125, 6, "Train"),
+    (125, 7, "Jetplane"),
+    (125, 8, "Starship"),
+    (125, 9, "Burst Noise"),
+    (126, 1, "Laughing"),
+    (126, 2, "Screaming"),
+    (126, 3, "Punch"),
+    (126, 4, "Heart Beat"),
+    (126, 5, "Footsteps"),
+    (127, 1, "Machine Gun"),
+    (127, 2, "Lasergun"),
+    (127, 3, "Explosion"),
+];
+
+/// [GM2] A drum kit from the rhythm bank. The discriminant of each variant is its program number.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DrumKit {
+    Standard = 0,
+    Room = 8,
+    Power = 16,
+    Electronic = 24,
+    Analog = 25,
+    Jazz = 32,
+    Brush = 40,
+    Orchestra = 48,
+    Sfx = 56,
+}
+
+impl DrumKit {
+    /// Get the drum kit selected by `program` on the rhythm bank, or `None` if GM2 does not
+    /// define one.
+    pub fn from_program(program: ProgramNumber) -> Option<DrumKit> {
+        match u8::from(program) {
+            0 => Some(DrumKit::Standard),
+            8 => Some(DrumKit::Room),
+            16 => Some(DrumKit::Power),
+            24 => Some(DrumKit::Electronic),
+            25 => Some(DrumKit::Analog),
+            32 => Some(DrumKit::Jazz),
+            40 => Some(DrumKit::Brush),
+            48 => Some(DrumKit::Orchestra),
+            56 => Some(DrumKit::Sfx),
+            _ => None,
+        }
+    }
+
+    /// The program number that selects this kit.
+    pub fn program(self) -> ProgramNumber {
+        U7(self as u8)
+    }
+
+    /// Get the GM2 name of the kit. For example: `"Standard Set"`.
+    pub fn to_str(self) -> &'static str {
+        match self {
+            DrumKit::Standard => "Standard Set",
+            DrumKit::Room => "Room Set",
+            DrumKit::Power => "Power Set",
+            DrumKit::Electronic => "Electronic Set",
+            DrumKit::Analog => "Analog Set",
+            DrumKit::Jazz => "Jazz Set",
+            DrumKit::Brush => "Brush Set",
+            DrumKit::Orchestra => "Orchestra Set",
+            DrumKit::Sfx => "SFX Set",
+        }
+    }
+}
+
+/// [GM2] A sound selected by a bank select and program change combination.
+///
+/// # Example
+/// ```
+/// use wmidi::gm::{DrumKit, Gm2Sound, MELODIC_BANK_MSB, RHYTHM_BANK_MSB};
+/// use wmidi::U7;
+/// let ukulele = Gm2Sound::resolve(MELODIC_BANK_MSB, U7::new(1).unwrap(), U7::new(24).unwrap());
+/// assert_eq!(ukulele.map(|s| s.name()), Some("Ukulele"));
+/// let jazz_kit = Gm2Sound::resolve(RHYTHM_BANK_MSB, U7::MIN, U7::new(32).unwrap());
+/// assert_eq!(jazz_kit, Some(Gm2Sound::Rhythm(DrumKit::Jazz)));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Gm2Sound {
+    /// A sound from the melodic bank. A `variation` of `0` is the GM1 sound for `program`.
+    Melodic {
+        program: Program,
+        variation: U7,
+        name: &'static str,
+    },
+    /// A drum kit from the rhythm bank.
+    Rhythm(DrumKit),
+}
+
+impl Gm2Sound {
+    /// Resolve a bank MSB (`ControlFunction::BANK_SELECT`), bank LSB
+    /// (`ControlFunction::BANK_SELECT_LSB`), and program number into a named sound. Returns `None`
+    /// if the combination is not part of the GM2 sound set.
+    pub fn resolve(bank_msb: U7, bank_lsb: U7, program: ProgramNumber) -> Option<Gm2Sound> {
+        match bank_msb {
+            MELODIC_BANK_MSB => Gm2Sound::melodic(Program::from(program), bank_lsb),
+            RHYTHM_BANK_MSB if bank_lsb == U7::MIN => {
+                DrumKit::from_program(program).map(Gm2Sound::Rhythm)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the sound for a variation of `program` in the melodic bank, or `None` if GM2 does not
+    /// define the variation.
+    pub fn melodic(program: Program, variation: U7) -> Option<Gm2Sound> {
+        let name = if variation == U7::MIN {
+            program.to_str()
+        } else {
+            let key = (program as u8, u8::from(variation));
+            let i = VARIATIONS
+                .binary_search_by_key(&key, |(p, v, _)| (*p, *v))
+                .ok()?;
+            VARIATIONS[i].2
+        };
+        Some(Gm2Sound::Melodic {
+            program,
+            variation,
+            name,
+        })
+    }
+
+    /// The value for `ControlFunction::BANK_SELECT`.
+    pub fn bank_msb(&self) -> U7 {
+        match self {
+            Gm2Sound::Melodic { .. } => MELODIC_BANK_MSB,
+            Gm2Sound::Rhythm(_) => RHYTHM_BANK_MSB,
+        }
+    }
+
+    /// The value for `ControlFunction::BANK_SELECT_LSB`.
+    pub fn bank_lsb(&self) -> U7 {
+        match self {
+            Gm2Sound::Melodic { variation, .. } => *variation,
+            Gm2Sound::Rhythm(_) => U7::MIN,
+        }
+    }
+
+    /// The program number for `MidiMessage::ProgramChange`.
+    pub fn program(&self) -> ProgramNumber {
+        match self {
+            Gm2Sound::Melodic { program, .. } => program.number(),
+            Gm2Sound::Rhythm(kit) => kit.program(),
+        }
+    }
+
+    /// The GM2 name of the sound.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gm2Sound::Melodic { name, .. } => name,
+            Gm2Sound::Rhythm(kit) => kit.to_str(),
+        }
+    }
+
+    /// The bank select MSB, bank select LSB, and program change messages that select this sound
+    /// on `channel`.
+    pub fn messages(&self, channel: Channel) -> [MidiMessage<'static>; 3] {
+        [
+            MidiMessage::ControlChange(channel, ControlFunction::BANK_SELECT, self.bank_msb()),
+            MidiMessage::ControlChange(channel, ControlFunction::BANK_SELECT_LSB, self.bank_lsb()),
+            MidiMessage::ProgramChange(channel, self.program()),
+        ]
+    }
+}
+
+impl fmt::Display for Gm2Sound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn variations_are_sorted() {
+        for pair in VARIATIONS.windows(2) {
+            assert!(
+                (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1),
+                "{:?}",
+                pair
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_melodic() {
+        let sound = Gm2Sound::resolve(MELODIC_BANK_MSB, U7::MIN, U7::MIN).unwrap();
+        assert_eq!(sound.name(), "Acoustic Grand Piano");
+        let sound = Gm2Sound::resolve(MELODIC_BANK_MSB, U7(9), U7(125)).unwrap();
+        assert_eq!(sound.name(), "Burst Noise");
+        assert_eq!(Gm2Sound::resolve(MELODIC_BANK_MSB, U7(10), U7(125)), None);
+        assert_eq!(Gm2Sound::resolve(U7::MIN, U7::MIN, U7::MIN), None);
+    }
+
+    #[test]
+    fn resolve_rhythm() {
+        assert_eq!(
+            Gm2Sound::resolve(RHYTHM_BANK_MSB, U7::MIN, U7(56)),
+            Some(Gm2Sound::Rhythm(DrumKit::Sfx))
+        );
+        assert_eq!(Gm2Sound::resolve(RHYTHM_BANK_MSB, U7::MIN, U7(57)), None);
+        assert_eq!(Gm2Sound::resolve(RHYTHM_BANK_MSB, U7(1), U7(56)), None);
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        let sound = Gm2Sound::melodic(Program::Seashore, U7(3)).unwrap();
+        let messages = sound.messages(Channel::Ch2);
+        assert_eq!(
+            messages[0],
+            MidiMessage::ControlChange(
+                Channel::Ch2,
+                ControlFunction::BANK_SELECT,
+                MELODIC_BANK_MSB
+            )
+        );
+        assert_eq!(
+            Gm2Sound::resolve(sound.bank_msb(), sound.bank_lsb(), sound.program()),
+            Some(sound)
+        );
+    }
+}
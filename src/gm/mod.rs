@@ -0,0 +1,23 @@
+//! General MIDI instrument and percussion maps.
+//!
+//! Documents referred to in this module:
+//! * [GM1]: General MIDI System Level 1
+//! * [GM2]: General MIDI 2, version 1.2a
+
+use crate::Channel;
+
+mod global_parameter;
+mod gm2;
+mod percussion;
+mod program;
+mod system;
+
+pub use self::global_parameter::{ChorusType, GlobalParameter, ReverbType};
+pub use self::gm2::{DrumKit, Gm2Sound, MELODIC_BANK_MSB, RHYTHM_BANK_MSB};
+pub use self::percussion::Percussion;
+pub use self::program::{Program, ProgramFamily};
+pub use self::system::SystemMessage;
+
+/// [GM1] The channel reserved for percussion. Notes on this channel select a `Percussion` sound
+/// instead of a pitch.
+pub const PERCUSSION_CHANNEL: Channel = Channel::Ch10;
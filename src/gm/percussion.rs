@@ -0,0 +1,185 @@
+use crate::Note;
+use core::fmt;
+
+/// A General MIDI Level 1 percussion sound.
+///
+/// On the percussion channel (`gm::PERCUSSION_CHANNEL`), each note plays a different drum instead
+/// of a pitch. The discriminant of each variant is its note number.
+///
+/// # Example
+/// ```
+/// use wmidi::gm::Percussion;
+/// use wmidi::Note;
+/// assert_eq!(Note::from(Percussion::AcousticSnare), Note::D2);
+/// assert_eq!(Percussion::from_note(Note::C2), Some(Percussion::BassDrum1));
+/// assert_eq!(Percussion::from_note(Note::C4), Some(Percussion::HiBongo));
+/// assert_eq!(Percussion::from_note(Note::C8), None);
+/// ```
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Percussion {
+    AcousticBassDrum = 35,
+    BassDrum1 = 36,
+    SideStick = 37,
+    AcousticSnare = 38,
+    HandClap = 39,
+    ElectricSnare = 40,
+    LowFloorTom = 41,
+    ClosedHiHat = 42,
+    HighFloorTom = 43,
+    PedalHiHat = 44,
+    LowTom = 45,
+    OpenHiHat = 46,
+    LowMidTom = 47,
+    HiMidTom = 48,
+    CrashCymbal1 = 49,
+    HighTom = 50,
+    RideCymbal1 = 51,
+    ChineseCymbal = 52,
+    RideBell = 53,
+    Tambourine = 54,
+    SplashCymbal = 55,
+    Cowbell = 56,
+    CrashCymbal2 = 57,
+    Vibraslap = 58,
+    RideCymbal2 = 59,
+    HiBongo = 60,
+    LowBongo = 61,
+    MuteHiConga = 62,
+    OpenHiConga = 63,
+    LowConga = 64,
+    HighTimbale = 65,
+    LowTimbale = 66,
+    HighAgogo = 67,
+    LowAgogo = 68,
+    Cabasa = 69,
+    Maracas = 70,
+    ShortWhistle = 71,
+    LongWhistle = 72,
+    ShortGuiro = 73,
+    LongGuiro = 74,
+    Claves = 75,
+    HiWoodBlock = 76,
+    LowWoodBlock = 77,
+    MuteCuica = 78,
+    OpenCuica = 79,
+    MuteTriangle = 80,
+    OpenTriangle = 81,
+}
+
+impl Percussion {
+    /// The lowest note with a General MIDI percussion sound.
+    pub const LOWEST_NOTE: Note = Note::B1;
+
+    /// The highest note with a General MIDI percussion sound.
+    pub const HIGHEST_NOTE: Note = Note::A5;
+
+    /// Get the percussion sound for `note`, or `None` if General MIDI does not define one.
+    pub fn from_note(note: Note) -> Option<Percussion> {
+        if Percussion::LOWEST_NOTE <= note && note <= Percussion::HIGHEST_NOTE {
+            Some(unsafe { core::mem::transmute::<u8, Percussion>(u8::from(note)) })
+        } else {
+            None
+        }
+    }
+
+    /// The note that plays this sound.
+    pub fn note(self) -> Note {
+        Note::from(self)
+    }
+
+    /// Get the General MIDI name of the sound. For example: `"Acoustic Bass Drum"`.
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Percussion::AcousticBassDrum => "Acoustic Bass Drum",
+            Percussion::BassDrum1 => "Bass Drum 1",
+            Percussion::SideStick => "Side Stick",
+            Percussion::AcousticSnare => "Acoustic Snare",
+            Percussion::HandClap => "Hand Clap",
+            Percussion::ElectricSnare => "Electric Snare",
+            Percussion::LowFloorTom => "Low Floor Tom",
+            Percussion::ClosedHiHat => "Closed Hi-Hat",
+            Percussion::HighFloorTom => "High Floor Tom",
+            Percussion::PedalHiHat => "Pedal Hi-Hat",
+            Percussion::LowTom => "Low Tom",
+            Percussion::OpenHiHat => "Open Hi-Hat",
+            Percussion::LowMidTom => "Low-Mid Tom",
+            Percussion::HiMidTom => "Hi-Mid Tom",
+            Percussion::CrashCymbal1 => "Crash Cymbal 1",
+            Percussion::HighTom => "High Tom",
+            Percussion::RideCymbal1 => "Ride Cymbal 1",
+            Percussion::ChineseCymbal => "Chinese Cymbal",
+            Percussion::RideBell => "Ride Bell",
+            Percussion::Tambourine => "Tambourine",
+            Percussion::SplashCymbal => "Splash Cymbal",
+            Percussion::Cowbell => "Cowbell",
+            Percussion::CrashCymbal2 => "Crash Cymbal 2",
+            Percussion::Vibraslap => "Vibraslap",
+            Percussion::RideCymbal2 => "Ride Cymbal 2",
+            Percussion::HiBongo => "Hi Bongo",
+            Percussion::LowBongo => "Low Bongo",
+            Percussion::MuteHiConga => "Mute Hi Conga",
+            Percussion::OpenHiConga => "Open Hi Conga",
+            Percussion::LowConga => "Low Conga",
+            Percussion::HighTimbale => "High Timbale",
+            Percussion::LowTimbale => "Low Timbale",
+            Percussion::HighAgogo => "High Agogo",
+            Percussion::LowAgogo => "Low Agogo",
+            Percussion::Cabasa => "Cabasa",
+            Percussion::Maracas => "Maracas",
+            Percussion::ShortWhistle => "Short Whistle",
+            Percussion::LongWhistle => "Long Whistle",
+            Percussion::ShortGuiro => "Short Guiro",
+            Percussion::LongGuiro => "Long Guiro",
+            Percussion::Claves => "Claves",
+            Percussion::HiWoodBlock => "Hi Wood Block",
+            Percussion::LowWoodBlock => "Low Wood Block",
+            Percussion::MuteCuica => "Mute Cuica",
+            Percussion::OpenCuica => "Open Cuica",
+            Percussion::MuteTriangle => "Mute Triangle",
+            Percussion::OpenTriangle => "Open Triangle",
+        }
+    }
+}
+
+impl From<Percussion> for Note {
+    #[inline(always)]
+    fn from(percussion: Percussion) -> Note {
+        unsafe { Note::from_u8_unchecked(percussion as u8) }
+    }
+}
+
+impl fmt::Display for Percussion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn note_round_trip() {
+        for note in Note::all() {
+            if let Some(percussion) = Percussion::from_note(note) {
+                assert_eq!(percussion.note(), note);
+            }
+        }
+        assert_eq!(Note::all().filter_map(Percussion::from_note).count(), 47);
+    }
+
+    #[test]
+    fn range() {
+        assert_eq!(
+            Percussion::from_note(Percussion::LOWEST_NOTE),
+            Some(Percussion::AcousticBassDrum)
+        );
+        assert_eq!(
+            Percussion::from_note(Percussion::HIGHEST_NOTE),
+            Some(Percussion::OpenTriangle)
+        );
+        assert_eq!(Percussion::from_note(Note::Bb1), None);
+        assert_eq!(Percussion::from_note(Note::Bb5), None);
+    }
+}
@@ -0,0 +1,380 @@
+use crate::ProgramNumber;
+use core::fmt;
+
+/// A General MIDI Level 1 melodic instrument.
+///
+/// The discriminant of each variant is its zero-based `ProgramNumber`. Note that the GM1
+/// specification and most user interfaces number programs from 1 to 128.
+///
+/// # Example
+/// ```
+/// use wmidi::gm::Program;
+/// use wmidi::U7;
+/// let program = Program::from(U7::MIN);
+/// assert_eq!(program, Program::AcousticGrandPiano);
+/// assert_eq!(program.to_string(), "Acoustic Grand Piano");
+/// ```
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Program {
+    AcousticGrandPiano = 0,
+    BrightAcousticPiano = 1,
+    ElectricGrandPiano = 2,
+    HonkyTonkPiano = 3,
+    ElectricPiano1 = 4,
+    ElectricPiano2 = 5,
+    Harpsichord = 6,
+    Clavi = 7,
+    Celesta = 8,
+    Glockenspiel = 9,
+    MusicBox = 10,
+    Vibraphone = 11,
+    Marimba = 12,
+    Xylophone = 13,
+    TubularBells = 14,
+    Dulcimer = 15,
+    DrawbarOrgan = 16,
+    PercussiveOrgan = 17,
+    RockOrgan = 18,
+    ChurchOrgan = 19,
+    ReedOrgan = 20,
+    Accordion = 21,
+    Harmonica = 22,
+    TangoAccordion = 23,
+    AcousticGuitarNylon = 24,
+    AcousticGuitarSteel = 25,
+    ElectricGuitarJazz = 26,
+    ElectricGuitarClean = 27,
+    ElectricGuitarMuted = 28,
+    OverdrivenGuitar = 29,
+    DistortionGuitar = 30,
+    GuitarHarmonics = 31,
+    AcousticBass = 32,
+    ElectricBassFinger = 33,
+    ElectricBassPick = 34,
+    FretlessBass = 35,
+    SlapBass1 = 36,
+    SlapBass2 = 37,
+    SynthBass1 = 38,
+    SynthBass2 = 39,
+    Violin = 40,
+    Viola = 41,
+    Cello = 42,
+    Contrabass = 43,
+    TremoloStrings = 44,
+    PizzicatoStrings = 45,
+    OrchestralHarp = 46,
+    Timpani = 47,
+    StringEnsemble1 = 48,
+    StringEnsemble2 = 49,
+    SynthStrings1 = 50,
+    SynthStrings2 = 51,
+    ChoirAahs = 52,
+    VoiceOohs = 53,
+    SynthVoice = 54,
+    OrchestraHit = 55,
+    Trumpet = 56,
+    Trombone = 57,
+    Tuba = 58,
+    MutedTrumpet = 59,
+    FrenchHorn = 60,
+    BrassSection = 61,
+    SynthBrass1 = 62,
+    SynthBrass2 = 63,
+    SopranoSax = 64,
+    AltoSax = 65,
+    TenorSax = 66,
+    BaritoneSax = 67,
+    Oboe = 68,
+    EnglishHorn = 69,
+    Bassoon = 70,
+    Clarinet = 71,
+    Piccolo = 72,
+    Flute = 73,
+    Recorder = 74,
+    PanFlute = 75,
+    BlownBottle = 76,
+    Shakuhachi = 77,
+    Whistle = 78,
+    Ocarina = 79,
+    Lead1Square = 80,
+    Lead2Sawtooth = 81,
+    Lead3Calliope = 82,
+    Lead4Chiff = 83,
+    Lead5Charang = 84,
+    Lead6Voice = 85,
+    Lead7Fifths = 86,
+    Lead8BassAndLead = 87,
+    Pad1NewAge = 88,
+    Pad2Warm = 89,
+    Pad3Polysynth = 90,
+    Pad4Choir = 91,
+    Pad5Bowed = 92,
+    Pad6Metallic = 93,
+    Pad7Halo = 94,
+    Pad8Sweep = 95,
+    Fx1Rain = 96,
+    Fx2Soundtrack = 97,
+    Fx3Crystal = 98,
+    Fx4Atmosphere = 99,
+    Fx5Brightness = 100,
+    Fx6Goblins = 101,
+    Fx7Echoes = 102,
+    Fx8SciFi = 103,
+    Sitar = 104,
+    Banjo = 105,
+    Shamisen = 106,
+    Koto = 107,
+    Kalimba = 108,
+    BagPipe = 109,
+    Fiddle = 110,
+    Shanai = 111,
+    TinkleBell = 112,
+    Agogo = 113,
+    SteelDrums = 114,
+    Woodblock = 115,
+    TaikoDrum = 116,
+    MelodicTom = 117,
+    SynthDrum = 118,
+    ReverseCymbal = 119,
+    GuitarFretNoise = 120,
+    BreathNoise = 121,
+    Seashore = 122,
+    BirdTweet = 123,
+    TelephoneRing = 124,
+    Helicopter = 125,
+    Applause = 126,
+    Gunshot = 127,
+}
+
+impl Program {
+    /// The zero-based program number used in `MidiMessage::ProgramChange`.
+    #[inline(always)]
+    pub fn number(self) -> ProgramNumber {
+        ProgramNumber::from(self)
+    }
+
+    /// The group of 8 related instruments that this program belongs to.
+    pub fn family(self) -> ProgramFamily {
+        match self as u8 / 8 {
+            0 => ProgramFamily::Piano,
+            1 => ProgramFamily::ChromaticPercussion,
+            2 => ProgramFamily::Organ,
+            3 => ProgramFamily::Guitar,
+            4 => ProgramFamily::Bass,
+            5 => ProgramFamily::Strings,
+            6 => ProgramFamily::Ensemble,
+            7 => ProgramFamily::Brass,
+            8 => ProgramFamily::Reed,
+            9 => ProgramFamily::Pipe,
+            10 => ProgramFamily::SynthLead,
+            11 => ProgramFamily::SynthPad,
+            12 => ProgramFamily::SynthEffects,
+            13 => ProgramFamily::Ethnic,
+            14 => ProgramFamily::Percussive,
+            _ => ProgramFamily::SoundEffects,
+        }
+    }
+
+    /// Get the General MIDI name of the instrument. For example: `"Acoustic Grand Piano"`.
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Program::AcousticGrandPiano => "Acoustic Grand Piano",
+            Program::BrightAcousticPiano => "Bright Acoustic Piano",
+            Program::ElectricGrandPiano => "Electric Grand Piano",
+            Program::HonkyTonkPiano => "Honky-tonk Piano",
+            Program::ElectricPiano1 => "Electric Piano 1",
+            Program::ElectricPiano2 => "Electric Piano 2",
+            Program::Harpsichord => "Harpsichord",
+            Program::Clavi => "Clavi",
+            Program::Celesta => "Celesta",
+            Program::Glockenspiel => "Glockenspiel",
+            Program::MusicBox => "Music Box",
+            Program::Vibraphone => "Vibraphone",
+            Program::Marimba => "Marimba",
+            Program::Xylophone => "Xylophone",
+            Program::TubularBells => "Tubular Bells",
+            Program::Dulcimer => "Dulcimer",
+            Program::DrawbarOrgan => "Drawbar Organ",
+            Program::PercussiveOrgan => "Percussive Organ",
+            Program::RockOrgan => "Rock Organ",
+            Program::ChurchOrgan => "Church Organ",
+            Program::ReedOrgan => "Reed Organ",
+            Program::Accordion => "Accordion",
+            Program::Harmonica => "Harmonica",
+            Program::TangoAccordion => "Tango Accordion",
+            Program::AcousticGuitarNylon => "Acoustic Guitar (nylon)",
+            Program::AcousticGuitarSteel => "Acoustic Guitar (steel)",
+            Program::ElectricGuitarJazz => "Electric Guitar (jazz)",
+            Program::ElectricGuitarClean => "Electric Guitar (clean)",
+            Program::ElectricGuitarMuted => "Electric Guitar (muted)",
+            Program::OverdrivenGuitar => "Overdriven Guitar",
+            Program::DistortionGuitar => "Distortion Guitar",
+            Program::GuitarHarmonics => "Guitar harmonics",
+            Program::AcousticBass => "Acoustic Bass",
+            Program::ElectricBassFinger => "Electric Bass (finger)",
+            Program::ElectricBassPick => "Electric Bass (pick)",
+            Program::FretlessBass => "Fretless Bass",
+            Program::SlapBass1 => "Slap Bass 1",
+            Program::SlapBass2 => "Slap Bass 2",
+            Program::SynthBass1 => "Synth Bass 1",
+            Program::SynthBass2 => "Synth Bass 2",
+            Program::Violin => "Violin",
+            Program::Viola => "Viola",
+            Program::Cello => "Cello",
+            Program::Contrabass => "Contrabass",
+            Program::TremoloStrings => "Tremolo Strings",
+            Program::PizzicatoStrings => "Pizzicato Strings",
+            Program::OrchestralHarp => "Orchestral Harp",
+            Program::Timpani => "Timpani",
+            Program::StringEnsemble1 => "String Ensemble 1",
+            Program::StringEnsemble2 => "String Ensemble 2",
+            Program::SynthStrings1 => "SynthStrings 1",
+            Program::SynthStrings2 => "SynthStrings 2",
+            Program::ChoirAahs => "Choir Aahs",
+            Program::VoiceOohs => "Voice Oohs",
+            Program::SynthVoice => "Synth Voice",
+            Program::OrchestraHit => "Orchestra Hit",
+            Program::Trumpet => "Trumpet",
+            Program::Trombone => "Trombone",
+            Program::Tuba => "Tuba",
+            Program::MutedTrumpet => "Muted Trumpet",
+            Program::FrenchHorn => "French Horn",
+            Program::BrassSection => "Brass Section",
+            Program::SynthBrass1 => "SynthBrass 1",
+            Program::SynthBrass2 => "SynthBrass 2",
+            Program::SopranoSax => "Soprano Sax",
+            Program::AltoSax => "Alto Sax",
+            Program::TenorSax => "Tenor Sax",
+            Program::BaritoneSax => "Baritone Sax",
+            Program::Oboe => "Oboe",
+            Program::EnglishHorn => "English Horn",
+            Program::Bassoon => "Bassoon",
+            Program::Clarinet => "Clarinet",
+            Program::Piccolo => "Piccolo",
+            Program::Flute => "Flute",
+            Program::Recorder => "Recorder",
+            Program::PanFlute => "Pan Flute",
+            Program::BlownBottle => "Blown Bottle",
+            Program::Shakuhachi => "Shakuhachi",
+            Program::Whistle => "Whistle",
+            Program::Ocarina => "Ocarina",
+            Program::Lead1Square => "Lead 1 (square)",
+            Program::Lead2Sawtooth => "Lead 2 (sawtooth)",
+            Program::Lead3Calliope => "Lead 3 (calliope)",
+            Program::Lead4Chiff => "Lead 4 (chiff)",
+            Program::Lead5Charang => "Lead 5 (charang)",
+            Program::Lead6Voice => "Lead 6 (voice)",
+            Program::Lead7Fifths => "Lead 7 (fifths)",
+            Program::Lead8BassAndLead => "Lead 8 (bass + lead)",
+            Program::Pad1NewAge => "Pad 1 (new age)",
+            Program::Pad2Warm => "Pad 2 (warm)",
+            Program::Pad3Polysynth => "Pad 3 (polysynth)",
+            Program::Pad4Choir => "Pad 4 (choir)",
+            Program::Pad5Bowed => "Pad 5 (bowed)",
+            Program::Pad6Metallic => "Pad 6 (metallic)",
+            Program::Pad7Halo => "Pad 7 (halo)",
+            Program::Pad8Sweep => "Pad 8 (sweep)",
+            Program::Fx1Rain => "FX 1 (rain)",
+            Program::Fx2Soundtrack => "FX 2 (soundtrack)",
+            Program::Fx3Crystal => "FX 3 (crystal)",
+            Program::Fx4Atmosphere => "FX 4 (atmosphere)",
+            Program::Fx5Brightness => "FX 5 (brightness)",
+            Program::Fx6Goblins => "FX 6 (goblins)",
+            Program::Fx7Echoes => "FX 7 (echoes)",
+            Program::Fx8SciFi => "FX 8 (sci-fi)",
+            Program::Sitar => "Sitar",
+            Program::Banjo => "Banjo",
+            Program::Shamisen => "Shamisen",
+            Program::Koto => "Koto",
+            Program::Kalimba => "Kalimba",
+            Program::BagPipe => "Bag pipe",
+            Program::Fiddle => "Fiddle",
+            Program::Shanai => "Shanai",
+            Program::TinkleBell => "Tinkle Bell",
+            Program::Agogo => "Agogo",
+            Program::SteelDrums => "Steel Drums",
+            Program::Woodblock => "Woodblock",
+            Program::TaikoDrum => "Taiko Drum",
+            Program::MelodicTom => "Melodic Tom",
+            Program::SynthDrum => "Synth Drum",
+            Program::ReverseCymbal => "Reverse Cymbal",
+            Program::GuitarFretNoise => "Guitar Fret Noise",
+            Program::BreathNoise => "Breath Noise",
+            Program::Seashore => "Seashore",
+            Program::BirdTweet => "Bird Tweet",
+            Program::TelephoneRing => "Telephone Ring",
+            Program::Helicopter => "Helicopter",
+            Program::Applause => "Applause",
+            Program::Gunshot => "Gunshot",
+        }
+    }
+}
+
+impl From<ProgramNumber> for Program {
+    #[inline(always)]
+    fn from(program: ProgramNumber) -> Program {
+        // All 128 program numbers have a corresponding variant.
+        unsafe { core::mem::transmute::<u8, Program>(u8::from(program)) }
+    }
+}
+
+impl From<Program> for ProgramNumber {
+    #[inline(always)]
+    fn from(program: Program) -> ProgramNumber {
+        unsafe { ProgramNumber::from_unchecked(program as u8) }
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
+/// The General MIDI Level 1 instrument groups. Each family holds 8 consecutive programs.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProgramFamily {
+    Piano,
+    ChromaticPercussion,
+    Organ,
+    Guitar,
+    Bass,
+    Strings,
+    Ensemble,
+    Brass,
+    Reed,
+    Pipe,
+    SynthLead,
+    SynthPad,
+    SynthEffects,
+    Ethnic,
+    Percussive,
+    SoundEffects,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U7;
+
+    #[test]
+    fn program_number_round_trip() {
+        for n in 0..128 {
+            let number = U7::new(n).unwrap();
+            assert_eq!(Program::from(number).number(), number);
+        }
+        assert_eq!(Program::from(U7::MAX), Program::Gunshot);
+    }
+
+    #[test]
+    fn family() {
+        assert_eq!(Program::AcousticGrandPiano.family(), ProgramFamily::Piano);
+        assert_eq!(
+            Program::Dulcimer.family(),
+            ProgramFamily::ChromaticPercussion
+        );
+        assert_eq!(Program::Lead8BassAndLead.family(), ProgramFamily::SynthLead);
+        assert_eq!(Program::Gunshot.family(), ProgramFamily::SoundEffects);
+    }
+}
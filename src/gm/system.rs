@@ -0,0 +1,122 @@
+use crate::{ToSliceError, UniversalKind, UniversalSysEx, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [GM1] [GM2] The General MIDI Universal Non-Real Time SysEx messages, which switch a device in
+/// or out of General MIDI mode.
+///
+/// Modules reset their sounds and controllers when they receive one of these, so they are
+/// usually sent once before playing a General MIDI file.
+///
+/// # Example
+/// ```
+/// use wmidi::gm::SystemMessage;
+/// use wmidi::U7;
+/// let mut bytes = [0u8; 6];
+/// SystemMessage::Gm1On.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7E, 0x7F, 0x09, 0x01, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum SystemMessage {
+    /// GM1 System On.
+    Gm1On,
+    /// GM System Off, which returns the device to its own mode.
+    Off,
+    /// GM2 System On.
+    Gm2On,
+}
+
+impl SystemMessage {
+    /// The number of bytes in the SysEx message, including the start and end bytes.
+    pub const SYSEX_BYTES_SIZE: usize = 6;
+
+    fn sub_id2(self) -> u8 {
+        match self {
+            SystemMessage::Gm1On => 0x01,
+            SystemMessage::Off => 0x02,
+            SystemMessage::Gm2On => 0x03,
+        }
+    }
+
+    /// Write the Universal Non-Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        UniversalSysEx {
+            kind: UniversalKind::NonRealTime,
+            device_id,
+            sub_id1: U7(0x09),
+            sub_id2: Some(U7(self.sub_id2())),
+            data: &[],
+        }
+        .copy_sysex_to_slice(slice)
+    }
+
+    /// The Universal Non-Real Time SysEx message, including the start and end bytes. A
+    /// `device_id` of 0x7F addresses all devices.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; SystemMessage::SYSEX_BYTES_SIZE];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a General MIDI
+    /// system message.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, SystemMessage)> {
+        match U7::data_to_bytes(payload) {
+            [0x7E, device_id, 0x09, sub_id2] => {
+                let message = match sub_id2 {
+                    0x01 => SystemMessage::Gm1On,
+                    0x02 => SystemMessage::Off,
+                    0x03 => SystemMessage::Gm2On,
+                    _ => return None,
+                };
+                Some((U7(*device_id), message))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        for message in [
+            SystemMessage::Gm1On,
+            SystemMessage::Off,
+            SystemMessage::Gm2On,
+        ]
+        .iter()
+        {
+            let mut bytes = [0u8; 6];
+            assert_eq!(message.copy_sysex_to_slice(U7(0x10), &mut bytes), Ok(6));
+            let payload = match MidiMessage::try_from(&bytes[..]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(
+                SystemMessage::from_sysex(payload),
+                Some((U7(0x10), *message))
+            );
+        }
+        assert_eq!(
+            SystemMessage::from_sysex(&[U7(0x7E), U7(0x7F), U7(0x09), U7(0x04)]),
+            None
+        );
+        assert_eq!(
+            SystemMessage::Off.copy_sysex_to_slice(U7::MAX, &mut [0u8; 5]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::{ToSliceError, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MIDI 1.0] The kind of a handshaking message.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandshakeKind {
+    /// The transfer of a file has ended. Sub-ID #1 0x7B.
+    EndOfFile,
+    /// The receiver is busy and the sender should pause until the next handshake. Sub-ID #1
+    /// 0x7C.
+    Wait,
+    /// The transfer should be stopped. Sub-ID #1 0x7D.
+    Cancel,
+    /// The packet was not received correctly and should be sent again. Sub-ID #1 0x7E.
+    Nak,
+    /// The packet was received correctly. Sub-ID #1 0x7F.
+    Ack,
+}
+
+impl HandshakeKind {
+    fn sub_id1(self) -> u8 {
+        match self {
+            HandshakeKind::EndOfFile => 0x7B,
+            HandshakeKind::Wait => 0x7C,
+            HandshakeKind::Cancel => 0x7D,
+            HandshakeKind::Nak => 0x7E,
+            HandshakeKind::Ack => 0x7F,
+        }
+    }
+}
+
+/// [MIDI 1.0] A handshaking Universal Non-Real Time SysEx message, used by the Sample Dump and File
+/// Dump transfers to control the flow of data packets.
+///
+/// # Example
+/// ```
+/// use wmidi::{Handshake, HandshakeKind, U7};
+/// let ack = Handshake {
+///     kind: HandshakeKind::Ack,
+///     packet: U7::from_u8_lossy(3),
+/// };
+/// let mut bytes = [0u8; 6];
+/// ack.copy_sysex_to_slice(U7::MIN, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7E, 0x00, 0x7F, 0x03, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Handshake {
+    /// The kind of handshake.
+    pub kind: HandshakeKind,
+    /// The number of the packet the handshake refers to, counting from 0 and wrapping after 127.
+    pub packet: U7,
+}
+
+impl Handshake {
+    /// The number of bytes in the SysEx message, including the start and end bytes.
+    pub const SYSEX_BYTES_SIZE: usize = 6;
+
+    /// Write the Universal Non-Real Time SysEx message to `slice`, including the start and end
+    /// bytes. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        if slice.len() < Handshake::SYSEX_BYTES_SIZE {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..Handshake::SYSEX_BYTES_SIZE].copy_from_slice(&[
+            0xF0,
+            0x7E,
+            u8::from(device_id),
+            self.kind.sub_id1(),
+            u8::from(self.packet),
+            0xF7,
+        ]);
+        Ok(Handshake::SYSEX_BYTES_SIZE)
+    }
+
+    /// The Universal Non-Real Time SysEx message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; Handshake::SYSEX_BYTES_SIZE];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and handshake, or `None` if the payload is not a handshake.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, Handshake)> {
+        match U7::data_to_bytes(payload) {
+            [0x7E, device_id, sub_id1, packet] => {
+                let kind = match sub_id1 {
+                    0x7B => HandshakeKind::EndOfFile,
+                    0x7C => HandshakeKind::Wait,
+                    0x7D => HandshakeKind::Cancel,
+                    0x7E => HandshakeKind::Nak,
+                    0x7F => HandshakeKind::Ack,
+                    _ => return None,
+                };
+                let packet = U7(*packet);
+                Some((U7(*device_id), Handshake { kind, packet }))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let kinds = [
+            HandshakeKind::EndOfFile,
+            HandshakeKind::Wait,
+            HandshakeKind::Cancel,
+            HandshakeKind::Nak,
+            HandshakeKind::Ack,
+        ];
+        for kind in kinds.iter() {
+            let handshake = Handshake {
+                kind: *kind,
+                packet: U7(0x55),
+            };
+            let mut bytes = [0u8; 6];
+            assert_eq!(handshake.copy_sysex_to_slice(U7(0x01), &mut bytes), Ok(6));
+            let payload = U7::try_from_bytes(&bytes[1..5]).unwrap();
+            assert_eq!(Handshake::from_sysex(payload), Some((U7(0x01), handshake)));
+        }
+        assert_eq!(
+            Handshake::from_sysex(&[U7(0x7E), U7(0x00), U7(0x06), U7(0x01)]),
+            None
+        );
+    }
+}
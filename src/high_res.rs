@@ -0,0 +1,215 @@
+use crate::{
+    Channel, ChannelMap, ControlFunction, MidiMessage, NotHighResControllerError, U14, U7,
+};
+
+/// Combines the MSB (controllers 0-31) and LSB (controllers 32-63) `ControlChange` messages of
+/// each channel into 14 bit values.
+///
+/// [MIDI 1.0] Receiving an MSB resets the LSB of the controller to 0, so senders may omit the
+/// LSB when the finer resolution is not needed.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ControlFunction, HighResControllers, MidiMessage, U7, U14};
+/// # use std::convert::TryFrom;
+/// let mut controllers = HighResControllers::new();
+/// let msb = MidiMessage::ControlChange(Channel::Ch1, ControlFunction::MODULATION_WHEEL, U7::try_from(1).unwrap());
+/// let lsb = MidiMessage::ControlChange(Channel::Ch1, ControlFunction::MODULATION_WHEEL_LSB, U7::try_from(2).unwrap());
+/// assert_eq!(
+///     controllers.handle(&msb),
+///     Some((Channel::Ch1, ControlFunction::MODULATION_WHEEL, U14::try_from(128).unwrap()))
+/// );
+/// assert_eq!(
+///     controllers.handle(&lsb),
+///     Some((Channel::Ch1, ControlFunction::MODULATION_WHEEL, U14::try_from(130).unwrap()))
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HighResControllers {
+    values: ChannelMap<[U14; 32]>,
+}
+
+impl HighResControllers {
+    /// Create a tracker where every controller is 0.
+    pub fn new() -> HighResControllers {
+        HighResControllers::default()
+    }
+
+    /// Update the state from `message`. If `message` is the MSB or LSB of a 14 bit controller,
+    /// the channel, MSB controller, and new combined value are returned.
+    pub fn handle(&mut self, message: &MidiMessage) -> Option<(Channel, ControlFunction, U14)> {
+        let (channel, function, value) = match message {
+            MidiMessage::ControlChange(c, f, v) => (*c, *f, u16::from(u8::from(*v))),
+            _ => return None,
+        };
+        let (msb_function, combined) = if function.is_msb() {
+            (function, value << 7)
+        } else if let Some(msb_function) = function.msb_counterpart() {
+            let msb = u16::from(self.value(channel, msb_function)?) & !0x7F;
+            (msb_function, msb | value)
+        } else {
+            return None;
+        };
+        let combined = unsafe { U14::from_unchecked(combined) };
+        self.values[channel][usize::from(u8::from(msb_function))] = combined;
+        Some((channel, msb_function, combined))
+    }
+
+    /// The current value of a 14 bit controller. `function` may be either the MSB or the LSB
+    /// controller. Returns `None` if `function` is not between 0 and 63 inclusive.
+    pub fn value(&self, channel: Channel, function: ControlFunction) -> Option<U14> {
+        let msb_function = if function.is_msb() {
+            function
+        } else {
+            function.msb_counterpart()?
+        };
+        Some(self.values[channel][usize::from(u8::from(msb_function))])
+    }
+
+    /// Set every controller on `channel` back to 0.
+    pub fn reset(&mut self, channel: Channel) {
+        self.values[channel] = [U14::MIN; 32];
+    }
+}
+
+/// Whether `encode_high_res` sends the LSB of a 14 bit controller.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum LsbMode {
+    /// Always send the LSB after the MSB.
+    Always,
+    /// Only send the LSB when it is not 0. Receivers reset the LSB to 0 when the MSB is
+    /// received, so this produces the same result with fewer messages.
+    OmitZero,
+}
+
+/// Emit the `ControlChange` messages that set the 14 bit controller `function` to `value`. The
+/// MSB is sent first, followed by the LSB as determined by `lsb_mode`. `function` may be either
+/// the MSB or the LSB controller.
+///
+/// An error is returned if `function` is not between 0 and 63 inclusive.
+///
+/// # Example
+/// ```
+/// use wmidi::{encode_high_res, Channel, ControlFunction, LsbMode, MidiMessage, U14};
+/// # use std::convert::TryFrom;
+/// let mut messages = Vec::new();
+/// let value = U14::try_from(0x2000).unwrap();
+/// encode_high_res(Channel::Ch1, ControlFunction::PAN, value, LsbMode::OmitZero, |m| messages.push(m)).unwrap();
+/// assert_eq!(messages.len(), 1);
+/// ```
+pub fn encode_high_res<F: FnMut(MidiMessage<'static>)>(
+    channel: Channel,
+    function: ControlFunction,
+    value: U14,
+    lsb_mode: LsbMode,
+    mut emit: F,
+) -> Result<(), NotHighResControllerError> {
+    let msb_function = if function.is_msb() {
+        function
+    } else {
+        function
+            .msb_counterpart()
+            .ok_or(NotHighResControllerError)?
+    };
+    let value = u16::from(value);
+    let msb = U7((value >> 7) as u8);
+    let lsb = U7((value & 0x7F) as u8);
+    emit(MidiMessage::ControlChange(channel, msb_function, msb));
+    if lsb_mode == LsbMode::Always || lsb != U7::MIN {
+        // Checked above that `msb_function` is an MSB controller.
+        let lsb_function = msb_function.lsb_counterpart().unwrap();
+        emit(MidiMessage::ControlChange(channel, lsb_function, lsb));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::convert::TryFrom;
+
+    fn cc(channel: Channel, function: ControlFunction, value: u8) -> MidiMessage<'static> {
+        MidiMessage::ControlChange(channel, function, U7(value))
+    }
+
+    #[test]
+    fn msb_resets_lsb() {
+        let mut controllers = HighResControllers::new();
+        controllers.handle(&cc(Channel::Ch2, ControlFunction::CHANNEL_VOLUME, 100));
+        controllers.handle(&cc(Channel::Ch2, ControlFunction::CHANNEL_VOLUME_LSB, 5));
+        assert_eq!(
+            controllers.value(Channel::Ch2, ControlFunction::CHANNEL_VOLUME),
+            Some(U14::try_from(100 * 128 + 5).unwrap())
+        );
+        controllers.handle(&cc(Channel::Ch2, ControlFunction::CHANNEL_VOLUME, 90));
+        assert_eq!(
+            controllers.value(Channel::Ch2, ControlFunction::CHANNEL_VOLUME_LSB),
+            Some(U14::try_from(90 * 128).unwrap())
+        );
+        assert_eq!(
+            controllers.value(Channel::Ch1, ControlFunction::CHANNEL_VOLUME),
+            Some(U14::MIN)
+        );
+        controllers.reset(Channel::Ch2);
+        assert_eq!(
+            controllers.value(Channel::Ch2, ControlFunction::CHANNEL_VOLUME),
+            Some(U14::MIN)
+        );
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let mut controllers = HighResControllers::new();
+        for raw in [0, 1, 0x7F, 0x80, 0x2001, 0x3FFF].iter() {
+            let value = U14::try_from(*raw).unwrap();
+            for mode in [LsbMode::Always, LsbMode::OmitZero].iter() {
+                let mut count = 0;
+                encode_high_res(
+                    Channel::Ch5,
+                    ControlFunction::FOOT_CONTROLLER_LSB,
+                    value,
+                    *mode,
+                    |m| {
+                        controllers.handle(&m);
+                        count += 1;
+                    },
+                )
+                .unwrap();
+                assert_eq!(
+                    controllers.value(Channel::Ch5, ControlFunction::FOOT_CONTROLLER),
+                    Some(value)
+                );
+                let expected = if *mode == LsbMode::OmitZero && raw & 0x7F == 0 {
+                    1
+                } else {
+                    2
+                };
+                assert_eq!(count, expected);
+            }
+        }
+        assert_eq!(
+            encode_high_res(
+                Channel::Ch1,
+                ControlFunction::DAMPER_PEDAL,
+                U14::MAX,
+                LsbMode::Always,
+                |_| panic!()
+            ),
+            Err(NotHighResControllerError)
+        );
+    }
+
+    #[test]
+    fn ignores_other_messages() {
+        let mut controllers = HighResControllers::new();
+        assert_eq!(
+            controllers.handle(&cc(Channel::Ch1, ControlFunction::DAMPER_PEDAL, 127)),
+            None
+        );
+        assert_eq!(controllers.handle(&MidiMessage::TimingClock), None);
+        assert_eq!(
+            controllers.value(Channel::Ch1, ControlFunction::DAMPER_PEDAL),
+            None
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use crate::{Accidental, Error, KeySignatureError, Letter, Note, Scale, ScaleKind};
+use core::convert::TryFrom;
+use core::fmt;
+
+/// Whether a key is major or minor.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum KeyMode {
+    Major,
+    Minor,
+}
+
+/// A musical key, made up of a spelled tonic and a mode.
+///
+/// The key is used to choose the enharmonic spelling of notes. For example, the same `Note` is
+/// spelled `Bb` in F major and `A#` in B major.
+///
+/// # Example
+/// ```
+/// use wmidi::{Accidental, Key, KeyMode, Letter};
+/// let key = Key::new(Letter::E, Accidental::Flat, KeyMode::Major);
+/// assert_eq!(key.sharps(), -3);
+/// assert_eq!(format!("{}", key), "Eb major");
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Key {
+    letter: Letter,
+    accidental: Accidental,
+    mode: KeyMode,
+}
+
+impl Key {
+    /// Create a new key with the tonic `letter` altered by `accidental`.
+    pub fn new(letter: Letter, accidental: Accidental, mode: KeyMode) -> Key {
+        Key {
+            letter,
+            accidental,
+            mode,
+        }
+    }
+
+    /// Create a new major key.
+    pub fn major(letter: Letter, accidental: Accidental) -> Key {
+        Key::new(letter, accidental, KeyMode::Major)
+    }
+
+    /// Create a new minor key.
+    pub fn minor(letter: Letter, accidental: Accidental) -> Key {
+        Key::new(letter, accidental, KeyMode::Minor)
+    }
+
+    /// The letter of the tonic.
+    pub fn letter(self) -> Letter {
+        self.letter
+    }
+
+    /// The accidental of the tonic.
+    pub fn accidental(self) -> Accidental {
+        self.accidental
+    }
+
+    /// Whether the key is major or minor.
+    pub fn mode(self) -> KeyMode {
+        self.mode
+    }
+
+    /// The pitch class of the tonic, between 0 and 11 inclusive. `C` is `0`.
+    pub fn tonic_pitch_class(self) -> u8 {
+        (self.letter.pitch_class() as i8 + self.accidental.semitones()).rem_euclid(12) as u8
+    }
+
+    /// The number of sharps in the key signature. Flats are returned as a negative number. Keys
+    /// that would need double sharps or double flats in their signature return values outside of
+    /// `-7..=7`.
+    pub fn sharps(self) -> i8 {
+        let tonic = self.letter.fifths() + 7 * self.accidental.semitones();
+        match self.mode {
+            KeyMode::Major => tonic,
+            KeyMode::Minor => tonic - 3,
+        }
+    }
+
+    /// The diatonic scale of the key. Minor keys use the natural minor scale.
+    pub fn scale(self) -> Scale {
+        let kind = match self.mode {
+            KeyMode::Major => ScaleKind::Major,
+            KeyMode::Minor => ScaleKind::NaturalMinor,
+        };
+        Scale::new(Note::from_u8_lossy(self.tonic_pitch_class()), kind)
+    }
+
+    /// Returns `true` if `note` is one of the 7 diatonic notes of the key, in any octave.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Accidental, Key, Letter, Note};
+    /// let g_major = Key::major(Letter::G, Accidental::Natural);
+    /// assert!(g_major.contains(Note::FSharp3));
+    /// assert!(!g_major.contains(Note::F3));
+    /// ```
+    pub fn contains(self, note: Note) -> bool {
+        self.scale().contains(note)
+    }
+
+    /// The scale degree of `note`, between 1 (the tonic) and 7 inclusive. Returns `None` if the
+    /// note is not in the key.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Accidental, Key, Letter, Note};
+    /// let a_minor = Key::minor(Letter::A, Accidental::Natural);
+    /// assert_eq!(a_minor.degree_of(Note::A2), Some(1));
+    /// assert_eq!(a_minor.degree_of(Note::E5), Some(5));
+    /// assert_eq!(a_minor.degree_of(Note::GSharp4), None);
+    /// ```
+    pub fn degree_of(self, note: Note) -> Option<u8> {
+        let interval = (u8::from(note) + 12 - self.tonic_pitch_class()) % 12;
+        self.scale_intervals()
+            .iter()
+            .position(|i| *i == interval)
+            .map(|i| i as u8 + 1)
+    }
+
+    /// The note for scale `degree`, counting from the tonic in `octave`. Degree 1 is the tonic,
+    /// degree 8 is the tonic an octave higher, and so on. The octave follows the tonic's letter,
+    /// so the tonic of B# major in octave 3 is `C4`.
+    ///
+    /// An error is returned if `degree` is 0 or the note is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Accidental, Key, Letter, Note};
+    /// let d_major = Key::major(Letter::D, Accidental::Natural);
+    /// assert_eq!(d_major.note_for_degree(3, 4), Ok(Note::FSharp4));
+    /// assert_eq!(d_major.note_for_degree(9, 4), Ok(Note::E5));
+    /// ```
+    pub fn note_for_degree(self, degree: u8, octave: i8) -> Result<Note, Error> {
+        if degree == 0 {
+            return Err(Error::NoteOutOfRange);
+        }
+        let index = usize::from(degree - 1);
+        let tonic = (i16::from(octave) + 1) * 12
+            + i16::from(self.letter.pitch_class())
+            + i16::from(self.accidental.semitones());
+        let raw_note =
+            tonic + 12 * (index / 7) as i16 + i16::from(self.scale_intervals()[index % 7]);
+        if Note::LOWEST_NOTE as i16 <= raw_note && raw_note <= Note::HIGHEST_NOTE as i16 {
+            Ok(unsafe { Note::from_u8_unchecked(raw_note as u8) })
+        } else {
+            Err(Error::NoteOutOfRange)
+        }
+    }
+
+    fn scale_intervals(self) -> &'static [u8] {
+        match self.mode {
+            KeyMode::Major => ScaleKind::Major.intervals(),
+            KeyMode::Minor => ScaleKind::NaturalMinor.intervals(),
+        }
+    }
+
+    /// The range of line of fifths positions that notes in this key are spelled with. Diatonic
+    /// notes fall in the middle of the range, chromatic notes on the edges.
+    pub(crate) fn spelling_window_start(self) -> i8 {
+        match self.mode {
+            // The major scale spans `sharps - 1..=sharps + 5`.
+            KeyMode::Major => self.sharps() - 3,
+            // Favor the raised leading tone over the lowered tonic in minor keys.
+            KeyMode::Minor => self.sharps() - 2,
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self.mode {
+            KeyMode::Major => "major",
+            KeyMode::Minor => "minor",
+        };
+        write!(f, "{}{} {}", self.letter, self.accidental, mode)
+    }
+}
+
+/// A key signature: the number of sharps or flats and whether the key is major or minor, as
+/// stored in the Standard MIDI File key signature meta event.
+///
+/// # Example
+/// ```
+/// use wmidi::{Accidental, Key, KeyMode, KeySignature, Letter};
+/// let signature = KeySignature::from_bytes([0xFD, 0x01]).unwrap();
+/// assert_eq!(signature.sharps(), -3);
+/// assert_eq!(signature.mode(), KeyMode::Minor);
+/// assert_eq!(signature.key(), Key::minor(Letter::C, Accidental::Natural));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct KeySignature {
+    sharps: i8,
+    mode: KeyMode,
+}
+
+impl KeySignature {
+    /// Create a key signature with `sharps` sharps, or flats if negative. An error is returned if
+    /// `sharps` is not between -7 and 7 inclusive.
+    pub fn new(sharps: i8, mode: KeyMode) -> Result<KeySignature, KeySignatureError> {
+        if (-7..=7).contains(&sharps) {
+            Ok(KeySignature { sharps, mode })
+        } else {
+            Err(KeySignatureError)
+        }
+    }
+
+    /// The number of sharps. Flats are returned as a negative number.
+    pub fn sharps(self) -> i8 {
+        self.sharps
+    }
+
+    /// Whether the key is major or minor.
+    pub fn mode(self) -> KeyMode {
+        self.mode
+    }
+
+    /// The key with this signature.
+    pub fn key(self) -> Key {
+        let fifths = match self.mode {
+            KeyMode::Major => self.sharps,
+            KeyMode::Minor => self.sharps + 3,
+        };
+        let letter = Letter::from_fifths(fifths);
+        let accidental =
+            Accidental::from_semitones((fifths + 1).div_euclid(7)).unwrap_or(Accidental::Natural);
+        Key::new(letter, accidental, self.mode)
+    }
+
+    /// Parse the 2 data bytes of a Standard MIDI File key signature meta event: the number of
+    /// sharps as a signed byte, then 0 for major or 1 for minor.
+    pub fn from_bytes(bytes: [u8; 2]) -> Result<KeySignature, KeySignatureError> {
+        let mode = match bytes[1] {
+            0 => KeyMode::Major,
+            1 => KeyMode::Minor,
+            _ => return Err(KeySignatureError),
+        };
+        KeySignature::new(bytes[0] as i8, mode)
+    }
+
+    /// The 2 data bytes of a Standard MIDI File key signature meta event.
+    pub fn to_bytes(self) -> [u8; 2] {
+        let mode = match self.mode {
+            KeyMode::Major => 0,
+            KeyMode::Minor => 1,
+        };
+        [self.sharps as u8, mode]
+    }
+}
+
+impl TryFrom<Key> for KeySignature {
+    type Error = KeySignatureError;
+
+    /// The signature of `key`. An error is returned for keys that would need double sharps or
+    /// double flats, such as G# major.
+    fn try_from(key: Key) -> Result<KeySignature, KeySignatureError> {
+        KeySignature::new(key.sharps(), key.mode())
+    }
+}
+
+impl From<KeySignature> for Key {
+    fn from(signature: KeySignature) -> Key {
+        signature.key()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sharps() {
+        assert_eq!(Key::major(Letter::C, Accidental::Natural).sharps(), 0);
+        assert_eq!(Key::major(Letter::B, Accidental::Natural).sharps(), 5);
+        assert_eq!(Key::major(Letter::F, Accidental::Natural).sharps(), -1);
+        assert_eq!(Key::major(Letter::C, Accidental::Sharp).sharps(), 7);
+        assert_eq!(Key::major(Letter::C, Accidental::Flat).sharps(), -7);
+        assert_eq!(Key::minor(Letter::A, Accidental::Natural).sharps(), 0);
+        assert_eq!(Key::minor(Letter::D, Accidental::Natural).sharps(), -1);
+        assert_eq!(Key::minor(Letter::G, Accidental::Sharp).sharps(), 5);
+    }
+
+    #[test]
+    fn degrees_round_trip() {
+        let key = Key::minor(Letter::E, Accidental::Flat);
+        for degree in 1..=7 {
+            let note = key.note_for_degree(degree, 3).unwrap();
+            assert!(key.contains(note));
+            assert_eq!(key.degree_of(note), Some(degree));
+        }
+        assert_eq!(key.note_for_degree(0, 3), Err(Error::NoteOutOfRange));
+        assert_eq!(key.note_for_degree(255, 3), Err(Error::NoteOutOfRange));
+    }
+
+    #[test]
+    fn note_for_degree_follows_tonic_letter() {
+        let c_flat_major = Key::major(Letter::C, Accidental::Flat);
+        assert_eq!(c_flat_major.note_for_degree(1, 4), Ok(Note::B3));
+        assert_eq!(c_flat_major.note_for_degree(2, 4), Ok(Note::Db4));
+        assert_eq!(
+            c_flat_major.note_for_degree(1, -1),
+            Err(Error::NoteOutOfRange)
+        );
+    }
+
+    #[test]
+    fn tonic_pitch_class() {
+        assert_eq!(
+            Key::major(Letter::B, Accidental::Sharp).tonic_pitch_class(),
+            0
+        );
+        assert_eq!(
+            Key::minor(Letter::C, Accidental::Flat).tonic_pitch_class(),
+            11
+        );
+    }
+
+    #[test]
+    fn key_signature() {
+        for sharps in -7..=7 {
+            for mode in [KeyMode::Major, KeyMode::Minor].iter() {
+                let signature = KeySignature::new(sharps, *mode).unwrap();
+                assert_eq!(
+                    KeySignature::from_bytes(signature.to_bytes()),
+                    Ok(signature)
+                );
+                assert_eq!(KeySignature::try_from(signature.key()), Ok(signature));
+            }
+        }
+        assert_eq!(
+            KeySignature::new(7, KeyMode::Minor).unwrap().key(),
+            Key::minor(Letter::A, Accidental::Sharp)
+        );
+        assert_eq!(
+            KeySignature::try_from(Key::major(Letter::G, Accidental::Sharp)),
+            Err(KeySignatureError)
+        );
+        assert_eq!(KeySignature::from_bytes([0, 2]), Err(KeySignatureError));
+    }
+}
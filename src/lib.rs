@@ -3,19 +3,148 @@
 #[macro_use]
 extern crate std;
 
+mod active_sensing;
 mod byte;
 mod cc;
+mod cc_kind;
+mod cc_set;
+mod cents;
+mod channel_map;
+mod channel_mode;
+mod channel_set;
+mod channel_state;
+pub mod checksum;
+mod chord;
+pub mod ci;
+mod clock;
+mod control_value;
+mod device_control;
+mod device_inquiry;
 mod error;
+pub mod file_dump;
+mod filter;
+mod fixed;
+pub mod gm;
+mod handshake;
+mod high_res;
+mod key;
+mod manufacturer;
+mod master_tuning;
+mod merger;
+mod message_buffer;
+mod message_kind;
 mod midi_message;
+pub mod mmc;
+mod mpe;
+pub mod msc;
+mod mtc;
+mod mts;
+mod notation;
 mod note;
+mod note_tracker;
+pub mod packing;
+mod panic;
+mod pitch_bend;
+mod polyphony;
+mod position;
+mod redundancy;
+mod remapper;
+pub mod roland;
+#[cfg(feature = "std")]
+mod router;
+mod rpn;
+mod scale;
+pub mod sds;
+mod smpte;
+mod spelled_note;
+mod splitter;
+mod stuck_notes;
+mod sustain;
+#[cfg(feature = "std")]
+mod sysex_builder;
+mod sysex_pacer;
+mod sysex_view;
+mod temperament;
+mod time_base;
+mod timed;
+pub mod ump;
+mod universal;
+mod velocity;
+mod vendor_reset;
 
-pub use byte::{U14, U7};
-pub use cc::ControlFunction;
-pub use error::{FromBytesError, ToSliceError};
+pub use active_sensing::ActiveSensingSender;
+pub use byte::{U14, U4, U7};
+pub use cc::{ControlFunction, ControlFunctions};
+pub use cc_kind::ControlFunctionKind;
+pub use cc_set::ControlFunctionSet;
+pub use cents::Cents;
+pub use channel_map::ChannelMap;
+pub use channel_mode::ChannelModeState;
+pub use channel_set::{ChannelSet, ChannelSetIter};
+pub use channel_state::ChannelState;
+pub use chord::{Chord, ChordQuality, Inversion};
+pub use clock::{ClockFollower, ClockSmoother};
+pub use control_value::ControlValueSemantics;
+pub use device_control::DeviceControl;
+pub use device_inquiry::{DeviceInquiry, Identity};
+pub use error::{
+    FromBytesError, KeySignatureError, MpeMemberCountError, NotHighResControllerError,
+    ParseControlFunctionError, SmpteTimeError, TimeSignatureError, ToSliceError,
+};
+pub use filter::Filter;
+pub use fixed::{pitch_to_freq_fixed, UFix32};
+pub use handshake::{Handshake, HandshakeKind};
+pub use high_res::{encode_high_res, HighResControllers, LsbMode};
+pub use key::{Key, KeyMode, KeySignature};
+pub use manufacturer::ManufacturerId;
+pub use master_tuning::MasterTuning;
+pub use merger::Merger;
+pub use message_buffer::{MessageBuffer, MessageBufferIter, PushError};
+pub use message_kind::{MessageKind, MessageKindSet};
 pub use midi_message::{
-    Channel, ControlValue, MidiMessage, PitchBend, ProgramNumber, Song, SongPosition, Velocity,
+    Channel, Channels, ControlValue, MidiMessage, ProgramNumber, Song, SongPosition, Velocity,
+};
+pub use mpe::{
+    MpeAllocation, MpeAllocator, MpeConfigurationParser, MpeEvent, MpeReceiver, MpeZone,
+    MpeZoneKind, NoteExpression,
+};
+pub use mtc::{
+    FrameType, MtcSysEx, QuarterFrame, QuarterFrameAssembler, QuarterFrameGenerator, UserBits,
 };
-pub use note::Note;
+pub use mts::{
+    BulkTuningDump, BulkTuningRequest, MtsFrequency, NoteTuning, NoteTunings, ScaleOctaveOffsets,
+    ScaleOctaveTuning, SingleNoteTuning,
+};
+pub use notation::{BarMarker, Notation};
+pub use note::{Note, NoteRange, Rounding};
+pub use note_tracker::{ActiveNotes, NoteTracker};
+pub use panic::{panic_messages, panic_messages_for, PanicMessages};
+pub use pitch_bend::{PitchBend, PitchBendSensitivity};
+pub use polyphony::{PolyphonyLimiter, StealPolicy};
+pub use position::{MusicalPosition, TimeSignature};
+pub use redundancy::RedundancyFilter;
+pub use remapper::Remapper;
+#[cfg(feature = "std")]
+pub use router::Router;
+pub use rpn::{encode_parameter, ModulationDepthRange, ParameterNumber};
+pub use scale::{Scale, ScaleKind, ScaleNotes};
+pub use smpte::{FrameRate, SmpteTime};
+pub use spelled_note::{Accidental, Letter, SpelledNote};
+pub use splitter::{Splitter, SystemRouting};
+pub use stuck_notes::StuckNoteDetector;
+pub use sustain::SustainTracker;
+#[cfg(feature = "std")]
+pub use sysex_builder::SysExBuilder;
+pub use sysex_pacer::{Pacing, SysExPacer};
+pub use sysex_view::SysExView;
+pub use temperament::{EqualTemperament, Temperament, TuningTable};
+pub use time_base::TimeBase;
+pub use timed::Timed;
+#[cfg(feature = "std")]
+pub use timed::TimedQueue;
+pub use universal::{UniversalCategory, UniversalKind, UniversalSysEx};
+pub use velocity::{VelocityCurve, VelocityScaler};
+pub use vendor_reset::VendorReset;
 
 /// Use `FromBytesError` instead.
 pub type Error = FromBytesError;
@@ -0,0 +1,268 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::{ToSliceError, U7};
+use core::fmt;
+
+/// [MIDI 1.0] The manufacturer ID at the start of a System Exclusive message.
+///
+/// IDs are either a single byte, or three bytes starting with 0x00. 0x7D is reserved for
+/// non-commercial use, and 0x7E and 0x7F start Universal Non-Real Time and Real Time messages.
+///
+/// # Example
+/// ```
+/// use wmidi::{ManufacturerId, MidiMessage, U7};
+/// let bytes = [0xF0, 0x00, 0x20, 0x29, 0x02, 0xF7];
+/// let message = MidiMessage::from_bytes(&bytes).unwrap();
+/// let id = message.sysex_manufacturer().unwrap();
+/// assert_eq!(id, ManufacturerId::Extended(U7::from_u8_lossy(0x20), U7::from_u8_lossy(0x29)));
+/// assert_eq!(id.bytes_size(), 3);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ManufacturerId {
+    /// A one byte ID. 0x00 is not a valid one byte ID, since it starts an extended ID, and
+    /// `copy_to_slice` returns an error for it.
+    Standard(U7),
+    /// A three byte ID, 0x00 followed by these two bytes.
+    Extended(U7, U7),
+}
+
+impl ManufacturerId {
+    /// The ID reserved for non-commercial and educational use.
+    pub const NON_COMMERCIAL: ManufacturerId = ManufacturerId::Standard(U7(0x7D));
+    /// The ID of Universal Non-Real Time SysEx messages.
+    pub const UNIVERSAL_NON_REAL_TIME: ManufacturerId = ManufacturerId::Standard(U7(0x7E));
+    /// The ID of Universal Real Time SysEx messages.
+    pub const UNIVERSAL_REAL_TIME: ManufacturerId = ManufacturerId::Standard(U7(0x7F));
+
+    /// Parse the ID at the start of the payload of a `MidiMessage::SysEx`, which excludes the
+    /// start byte. Returns the ID and the rest of the payload, or `None` if the payload is too
+    /// short to hold an ID.
+    pub fn from_sysex(payload: &[U7]) -> Option<(ManufacturerId, &[U7])> {
+        match payload {
+            [U7(0x00), a, b, rest @ ..] => Some((ManufacturerId::Extended(*a, *b), rest)),
+            [U7(0x00), ..] | [] => None,
+            [id, rest @ ..] => Some((ManufacturerId::Standard(*id), rest)),
+        }
+    }
+
+    /// The number of bytes in the ID.
+    pub fn bytes_size(self) -> usize {
+        match self {
+            ManufacturerId::Standard(_) => 1,
+            ManufacturerId::Extended(..) => 3,
+        }
+    }
+
+    /// Write the ID to `slice`. Returns the number of bytes written, or
+    /// `ToSliceError::InvalidMessage` for `Standard(0x00)`.
+    pub fn copy_to_slice(self, slice: &mut [u8]) -> Result<usize, ToSliceError> {
+        if self == ManufacturerId::Standard(U7(0x00)) {
+            return Err(ToSliceError::InvalidMessage);
+        }
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        match self {
+            ManufacturerId::Standard(id) => slice[0] = u8::from(id),
+            ManufacturerId::Extended(a, b) => {
+                slice[..3].copy_from_slice(&[0x00, u8::from(a), u8::from(b)])
+            }
+        }
+        Ok(size)
+    }
+
+    /// The name registered for the ID, or `None` if it is not in the table. The table holds the
+    /// one byte IDs and the most common three byte IDs.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{ManufacturerId, U7};
+    /// assert_eq!(ManufacturerId::Standard(U7::from_u8_lossy(0x41)).name(), Some("Roland"));
+    /// ```
+    pub fn name(self) -> Option<&'static str> {
+        let name = match self {
+            ManufacturerId::Standard(id) => match u8::from(id) {
+                0x01 => "Sequential Circuits",
+                0x02 => "Big Briar",
+                0x03 => "Octave / Plateau",
+                0x04 => "Moog",
+                0x05 => "Passport Designs",
+                0x06 => "Lexicon",
+                0x07 => "Kurzweil",
+                0x08 => "Fender",
+                0x09 => "Gulbransen",
+                0x0A => "AKG Acoustics",
+                0x0B => "Voyce Music",
+                0x0C => "Waveframe",
+                0x0D => "ADA Signal Processors",
+                0x0E => "Garfield Electronics",
+                0x0F => "Ensoniq",
+                0x10 => "Oberheim",
+                0x11 => "Apple",
+                0x12 => "Grey Matter Response",
+                0x13 => "Digidesign",
+                0x14 => "Palmtree Instruments",
+                0x15 => "JLCooper Electronics",
+                0x16 => "Lowrey",
+                0x17 => "Adams-Smith",
+                0x18 => "E-mu",
+                0x19 => "Harmony Systems",
+                0x1A => "ART",
+                0x1B => "Baldwin",
+                0x1C => "Eventide",
+                0x1D => "Inventronics",
+                0x1E => "Key Concepts",
+                0x1F => "Clarity",
+                0x20 => "Passac",
+                0x21 => "SIEL",
+                0x22 => "Synthaxe",
+                0x23 => "Stepp",
+                0x24 => "Hohner",
+                0x25 => "Twister",
+                0x26 => "Solton",
+                0x27 => "Jellinghaus MS",
+                0x28 => "Southworth Music Systems",
+                0x29 => "PPG",
+                0x2A => "JEN",
+                0x2B => "Solid State Logic",
+                0x2C => "Audio Veritrieb",
+                0x2D => "Hinton Instruments",
+                0x2E => "Soundtracs",
+                0x2F => "Elka",
+                0x30 => "Dynacord",
+                0x31 => "Viscount",
+                0x32 => "Drawmer",
+                0x33 => "Clavia",
+                0x34 => "Audio Architecture",
+                0x35 => "GeneralMusic",
+                0x36 => "Cheetah Marketing",
+                0x37 => "C.T.M.",
+                0x38 => "Simmons",
+                0x39 => "Soundcraft Electronics",
+                0x3A => "Steinberg",
+                0x3B => "Wersi",
+                0x3C => "AVAB Niethammer",
+                0x3D => "Digigram",
+                0x3E => "Waldorf",
+                0x3F => "Quasimidi",
+                0x40 => "Kawai",
+                0x41 => "Roland",
+                0x42 => "Korg",
+                0x43 => "Yamaha",
+                0x44 => "Casio",
+                0x46 => "Kamiya Studio",
+                0x47 => "Akai",
+                0x48 => "Victor",
+                0x4B => "Fujitsu",
+                0x4C => "Sony",
+                0x4E => "Teac",
+                0x50 => "Matsushita Electric",
+                0x51 => "Fostex",
+                0x52 => "Zoom",
+                0x54 => "Matsushita Communication",
+                0x55 => "Suzuki",
+                0x56 => "Fuji Sound",
+                0x57 => "Acoustic Technical Laboratory",
+                0x7D => "Non-Commercial",
+                0x7E => "Universal Non-Real Time",
+                0x7F => "Universal Real Time",
+                _ => return None,
+            },
+            ManufacturerId::Extended(a, b) => match (u8::from(a), u8::from(b)) {
+                (0x00, 0x01) => "Time/Warner Interactive",
+                (0x00, 0x0E) => "Alesis",
+                (0x00, 0x1B) => "Peavey Electronics",
+                (0x00, 0x3B) => "Mark of the Unicorn",
+                (0x00, 0x41) => "Microsoft",
+                (0x00, 0x66) => "Mackie Designs",
+                (0x01, 0x05) => "M-Audio",
+                (0x20, 0x13) => "Kenton Electronics",
+                (0x20, 0x1F) => "TC Electronic",
+                (0x20, 0x29) => "Focusrite / Novation",
+                (0x20, 0x32) => "Behringer",
+                (0x20, 0x33) => "Access Music",
+                (0x20, 0x3C) => "Elektron",
+                (0x20, 0x6B) => "Arturia",
+                (0x20, 0x76) => "Teenage Engineering",
+                (0x21, 0x09) => "Native Instruments",
+                (0x21, 0x10) => "ROLI",
+                _ => return None,
+            },
+        };
+        Some(name)
+    }
+
+    /// Returns `true` for the IDs of Universal Non-Real Time and Real Time messages.
+    pub fn is_universal(self) -> bool {
+        self == ManufacturerId::UNIVERSAL_NON_REAL_TIME
+            || self == ManufacturerId::UNIVERSAL_REAL_TIME
+    }
+}
+
+impl fmt::Display for ManufacturerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManufacturerId::Standard(id) => write!(f, "{:02X}", u8::from(*id)),
+            ManufacturerId::Extended(a, b) => {
+                write!(f, "00 {:02X} {:02X}", u8::from(*a), u8::from(*b))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_sysex() {
+        let payload = U7::try_from_bytes(&[0x41, 0x10, 0x42]).unwrap();
+        assert_eq!(
+            ManufacturerId::from_sysex(payload),
+            Some((ManufacturerId::Standard(U7(0x41)), &payload[1..]))
+        );
+        let payload = U7::try_from_bytes(&[0x00, 0x00, 0x0E]).unwrap();
+        assert_eq!(
+            ManufacturerId::from_sysex(payload),
+            Some((ManufacturerId::Extended(U7(0x00), U7(0x0E)), &[][..]))
+        );
+        assert_eq!(ManufacturerId::from_sysex(&[U7(0x00), U7(0x20)]), None);
+        assert_eq!(ManufacturerId::from_sysex(&[]), None);
+    }
+
+    #[test]
+    fn copy_to_slice() {
+        let mut bytes = [0u8; 3];
+        let id = ManufacturerId::Extended(U7(0x20), U7(0x29));
+        assert_eq!(id.copy_to_slice(&mut bytes), Ok(3));
+        assert_eq!(bytes, [0x00, 0x20, 0x29]);
+        assert_eq!(
+            id.copy_to_slice(&mut bytes[..2]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+        assert_eq!(
+            ManufacturerId::Standard(U7(0x43)).copy_to_slice(&mut bytes),
+            Ok(1)
+        );
+        assert_eq!(bytes[0], 0x43);
+        assert_eq!(
+            ManufacturerId::Standard(U7(0x00)).copy_to_slice(&mut bytes),
+            Err(ToSliceError::InvalidMessage)
+        );
+        assert!(ManufacturerId::UNIVERSAL_REAL_TIME.is_universal());
+        assert!(!ManufacturerId::NON_COMMERCIAL.is_universal());
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(ManufacturerId::Standard(U7(0x42)).name(), Some("Korg"));
+        assert_eq!(
+            ManufacturerId::Extended(U7(0x00), U7(0x0E)).name(),
+            Some("Alesis")
+        );
+        assert_eq!(ManufacturerId::Standard(U7(0x45)).name(), None);
+        assert_eq!(ManufacturerId::Extended(U7(0x7F), U7(0x7F)).name(), None);
+    }
+}
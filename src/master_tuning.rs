@@ -0,0 +1,220 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+//! * [CA-025]: Master Fine/Coarse Tuning
+
+use crate::{
+    encode_parameter, Cents, Channel, LsbMode, MidiMessage, ParameterNumber, ToSliceError, U14, U7,
+};
+use core::convert::TryFrom;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [CA-025] A global tuning offset, sent with the Universal Real Time SysEx Master Fine Tuning
+/// and Master Coarse Tuning messages.
+///
+/// [MIDI 1.0] The same values are used by the Channel Fine Tuning (RPN 00/01) and Channel Coarse
+/// Tuning (RPN 00/02) parameters to tune a single channel.
+///
+/// # Example
+/// ```
+/// use wmidi::{Cents, MasterTuning, U7};
+/// let tuning = MasterTuning::fine(Cents(-50.0));
+/// let mut bytes = [0u8; 8];
+/// tuning.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7F, 0x7F, 0x04, 0x03, 0x00, 0x20, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MasterTuning {
+    /// An offset in 100/8192 cent steps, centered on 0x2000. The range is -100 to just under
+    /// +100 cents.
+    Fine(U14),
+    /// An offset in semitones, centered on 64. The range is -64 to +63 semitones.
+    Coarse(U7),
+}
+
+impl MasterTuning {
+    /// The number of bytes in the SysEx message, including the start and end bytes.
+    pub const SYSEX_BYTES_SIZE: usize = 8;
+
+    /// The closest fine tuning to `offset`. Offsets are clamped to the fine tuning range.
+    pub fn fine(offset: Cents) -> MasterTuning {
+        let raw = 0x2000 as f32 + offset.0 * 8192.0 / 100.0;
+        let clamped = if raw.is_nan() {
+            0x2000 as f32
+        } else {
+            raw.clamp(0.0, 0x3FFF as f32)
+        };
+        MasterTuning::Fine(U14::from_u16_lossy((clamped + 0.5) as u16))
+    }
+
+    /// A coarse tuning of `semitones`. Offsets are clamped to the coarse tuning range.
+    pub fn coarse(semitones: i8) -> MasterTuning {
+        MasterTuning::Coarse(U7(semitones.clamp(-64, 63).wrapping_add(64) as u8))
+    }
+
+    /// The tuning offset.
+    pub fn cents(self) -> Cents {
+        match self {
+            MasterTuning::Fine(v) => Cents((f32::from(u16::from(v)) - 8192.0) * 100.0 / 8192.0),
+            MasterTuning::Coarse(v) => Cents::from_semitones(f32::from(u8::from(v)) - 64.0),
+        }
+    }
+
+    /// The channel tuning parameter with the same meaning: `ParameterNumber::FINE_TUNING` or
+    /// `ParameterNumber::COARSE_TUNING`.
+    pub fn parameter(self) -> ParameterNumber {
+        match self {
+            MasterTuning::Fine(_) => ParameterNumber::FINE_TUNING,
+            MasterTuning::Coarse(_) => ParameterNumber::COARSE_TUNING,
+        }
+    }
+
+    /// The RPN value for `self.parameter()`. Coarse tuning is sent in the MSB.
+    pub fn parameter_value(self) -> U14 {
+        match self {
+            MasterTuning::Fine(v) => v,
+            MasterTuning::Coarse(v) => U14::from_u16_lossy(u16::from(u8::from(v)) << 7),
+        }
+    }
+
+    /// Decode a channel tuning RPN. Returns `None` if `parameter` is not a tuning parameter.
+    pub fn from_parameter(parameter: ParameterNumber, value: U14) -> Option<MasterTuning> {
+        match parameter {
+            ParameterNumber::FINE_TUNING => Some(MasterTuning::Fine(value)),
+            ParameterNumber::COARSE_TUNING => {
+                Some(MasterTuning::Coarse(U7((u16::from(value) >> 7) as u8)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Emit the `ControlChange` messages that set the channel fine or coarse tuning of
+    /// `channel`.
+    pub fn encode_rpn<F: FnMut(MidiMessage<'static>)>(self, channel: Channel, emit: F) {
+        encode_parameter(
+            channel,
+            self.parameter(),
+            self.parameter_value(),
+            LsbMode::OmitZero,
+            emit,
+        );
+    }
+
+    /// Write the Universal Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        if slice.len() < MasterTuning::SYSEX_BYTES_SIZE {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let (sub_id, lsb, msb) = match self {
+            MasterTuning::Fine(v) => {
+                let v = u16::from(v);
+                (0x03, (v & 0x7F) as u8, (v >> 7) as u8)
+            }
+            MasterTuning::Coarse(v) => (0x04, 0x00, u8::from(v)),
+        };
+        slice[..MasterTuning::SYSEX_BYTES_SIZE].copy_from_slice(&[
+            0xF0,
+            0x7F,
+            u8::from(device_id),
+            0x04,
+            sub_id,
+            lsb,
+            msb,
+            0xF7,
+        ]);
+        Ok(MasterTuning::SYSEX_BYTES_SIZE)
+    }
+
+    /// The Universal Real Time SysEx message, including the start and end bytes. A `device_id`
+    /// of 0x7F addresses all devices.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; MasterTuning::SYSEX_BYTES_SIZE];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and tuning, or `None` if the payload is not a master tuning
+    /// message.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, MasterTuning)> {
+        let bytes = U7::data_to_bytes(payload);
+        match bytes {
+            [0x7F, device_id, 0x04, 0x03, lsb, msb] => {
+                let value = u16::from(*msb) << 7 | u16::from(*lsb);
+                Some((
+                    U7(*device_id),
+                    MasterTuning::Fine(U14::try_from(value).ok()?),
+                ))
+            }
+            [0x7F, device_id, 0x04, 0x04, _, msb] => {
+                Some((U7(*device_id), MasterTuning::Coarse(U7(*msb))))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for MasterTuning {
+    fn default() -> MasterTuning {
+        MasterTuning::Fine(U14::from_u16_lossy(0x2000))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cents() {
+        assert_eq!(MasterTuning::fine(Cents::ZERO).cents(), Cents::ZERO);
+        assert_eq!(MasterTuning::fine(Cents(-100.0)).cents(), Cents(-100.0));
+        assert_eq!(
+            MasterTuning::fine(Cents(200.0)),
+            MasterTuning::Fine(U14::MAX)
+        );
+        assert_eq!(MasterTuning::default().cents(), Cents::ZERO);
+        assert_eq!(MasterTuning::coarse(-12).cents(), Cents(-1200.0));
+        assert_eq!(MasterTuning::coarse(i8::MAX), MasterTuning::Coarse(U7::MAX));
+        assert_eq!(MasterTuning::coarse(i8::MIN), MasterTuning::Coarse(U7::MIN));
+    }
+
+    #[test]
+    fn sysex_round_trip() {
+        for tuning in [MasterTuning::fine(Cents(12.5)), MasterTuning::coarse(7)].iter() {
+            let mut bytes = [0u8; 8];
+            assert_eq!(tuning.copy_sysex_to_slice(U7(0x10), &mut bytes), Ok(8));
+            let message = MidiMessage::try_from(&bytes[..]).unwrap();
+            let payload = match message {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(MasterTuning::from_sysex(payload), Some((U7(0x10), *tuning)));
+        }
+        assert_eq!(
+            MasterTuning::coarse(0).copy_sysex_to_slice(U7::MAX, &mut [0u8; 7]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+        assert_eq!(MasterTuning::from_sysex(&[U7(0x7E), U7(0x7F)]), None);
+    }
+
+    #[test]
+    fn rpn_round_trip() {
+        for tuning in [MasterTuning::fine(Cents(-3.0)), MasterTuning::coarse(-2)].iter() {
+            assert_eq!(
+                MasterTuning::from_parameter(tuning.parameter(), tuning.parameter_value()),
+                Some(*tuning)
+            );
+        }
+        assert_eq!(
+            MasterTuning::from_parameter(ParameterNumber::NULL, U14::MIN),
+            None
+        );
+    }
+}
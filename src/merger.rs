@@ -0,0 +1,218 @@
+/// Combines `N` byte streams into one, like a MIDI merge box.
+///
+/// Once an input starts a message, the output belongs to that input until the message is
+/// complete. Bytes pushed by other inputs in the meantime are refused and must be pushed again
+/// later. A SysEx message holds the output until its end byte, so it is never split. System
+/// Real-Time bytes are always passed through immediately, even in the middle of another message.
+///
+/// Running status is followed on each input. The status byte is written again whenever the
+/// output switches to a message with a different status, so a run of messages from one input may
+/// be interleaved with messages from other inputs without changing its meaning.
+///
+/// # Example
+/// ```
+/// use wmidi::Merger;
+/// let mut merger: Merger<2> = Merger::new();
+/// let mut out = Vec::new();
+/// assert!(merger.push(0, 0x90, |b| out.push(b)));
+/// // Input 1 must wait until the NoteOn from input 0 is complete.
+/// assert!(!merger.push(1, 0xC0, |b| out.push(b)));
+/// // System Real-Time messages are never blocked.
+/// assert!(merger.push(1, 0xF8, |b| out.push(b)));
+/// assert!(merger.push(0, 60, |b| out.push(b)));
+/// assert!(merger.push(0, 100, |b| out.push(b)));
+/// assert!(merger.push(1, 0xC0, |b| out.push(b)));
+/// assert_eq!(out, [0x90, 0xF8, 60, 100, 0xC0]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Merger<const N: usize> {
+    inputs: [Input; N],
+    owner: Option<usize>,
+    output_status: Option<u8>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Input {
+    // The running status of the input.
+    status: Option<u8>,
+    // The number of data bytes left in the current message.
+    remaining: u8,
+    in_sysex: bool,
+}
+
+impl<const N: usize> Merger<N> {
+    /// Create a merger with no message in progress.
+    pub fn new() -> Merger<N> {
+        Merger {
+            inputs: [Input::default(); N],
+            owner: None,
+            output_status: None,
+        }
+    }
+
+    /// The input that is in the middle of a message, if any.
+    pub fn owner(&self) -> Option<usize> {
+        self.owner
+    }
+
+    /// Push `byte` from `input`, calling `emit` with the bytes to write to the output. Returns
+    /// `false` if the byte was refused because another input is in the middle of a message.
+    ///
+    /// # Panics
+    /// Panics if `input` is not less than `N`.
+    pub fn push<F: FnMut(u8)>(&mut self, input: usize, byte: u8, mut emit: F) -> bool {
+        assert!(input < N, "input {} out of range for {} inputs", input, N);
+        if byte >= 0xF8 {
+            emit(byte);
+            return true;
+        }
+        if self.owner.is_some_and(|owner| owner != input) {
+            return false;
+        }
+        let state = &mut self.inputs[input];
+        if byte & 0x80 != 0 {
+            if state.in_sysex {
+                // Any status byte ends a SysEx message.
+                state.in_sysex = false;
+                emit(0xF7);
+                self.owner = None;
+                if byte == 0xF7 {
+                    return true;
+                }
+            }
+            match byte {
+                0x80..=0xEF => {
+                    state.status = Some(byte);
+                    state.remaining = channel_data_len(byte);
+                    self.output_status = Some(byte);
+                }
+                0xF0 => {
+                    state.status = None;
+                    state.in_sysex = true;
+                    self.output_status = None;
+                }
+                // A stray SysEx end byte.
+                0xF7 => return true,
+                _ => {
+                    state.status = None;
+                    state.remaining = match byte {
+                        0xF1 | 0xF3 => 1,
+                        0xF2 => 2,
+                        _ => 0,
+                    };
+                    self.output_status = None;
+                }
+            }
+            emit(byte);
+        } else if state.in_sysex {
+            emit(byte);
+        } else if state.remaining > 0 {
+            state.remaining -= 1;
+            emit(byte);
+        } else if let Some(status) = state.status {
+            // A new message using running status.
+            if self.output_status != Some(status) {
+                emit(status);
+                self.output_status = Some(status);
+            }
+            state.remaining = channel_data_len(status) - 1;
+            emit(byte);
+        } else {
+            // A data byte with no status to belong to.
+            return true;
+        }
+        let state = &self.inputs[input];
+        self.owner = if state.in_sysex || state.remaining > 0 {
+            Some(input)
+        } else {
+            None
+        };
+        true
+    }
+}
+
+impl<const N: usize> Default for Merger<N> {
+    fn default() -> Merger<N> {
+        Merger::new()
+    }
+}
+
+/// The number of data bytes that follow a channel message status byte.
+fn channel_data_len(status: u8) -> u8 {
+    match status & 0xF0 {
+        0xC0 | 0xD0 => 1,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Push `bytes` as `input`, returning the number of bytes accepted.
+    fn push_all(
+        merger: &mut Merger<2>,
+        input: usize,
+        bytes: &[u8],
+        out: &mut [u8; 32],
+        len: &mut usize,
+    ) -> usize {
+        bytes
+            .iter()
+            .take_while(|b| {
+                merger.push(input, **b, |o| {
+                    out[*len] = o;
+                    *len += 1;
+                })
+            })
+            .count()
+    }
+
+    #[test]
+    fn sysex_is_not_split() {
+        let mut merger: Merger<2> = Merger::new();
+        let mut out = [0u8; 32];
+        let mut len = 0;
+        assert_eq!(
+            push_all(&mut merger, 0, &[0xF0, 1, 2], &mut out, &mut len),
+            3
+        );
+        assert_eq!(merger.owner(), Some(0));
+        assert_eq!(
+            push_all(&mut merger, 1, &[0x90, 60, 1], &mut out, &mut len),
+            0
+        );
+        assert_eq!(push_all(&mut merger, 0, &[3, 0xF7], &mut out, &mut len), 2);
+        assert_eq!(merger.owner(), None);
+        assert_eq!(
+            push_all(&mut merger, 1, &[0x90, 60, 1], &mut out, &mut len),
+            3
+        );
+        assert_eq!(&out[..len], &[0xF0, 1, 2, 3, 0xF7, 0x90, 60, 1]);
+    }
+
+    #[test]
+    fn running_status_is_restored() {
+        let mut merger: Merger<2> = Merger::new();
+        let mut out = [0u8; 32];
+        let mut len = 0;
+        push_all(&mut merger, 0, &[0x90, 60, 1], &mut out, &mut len);
+        push_all(&mut merger, 1, &[0xB0, 7, 100], &mut out, &mut len);
+        // Input 0 continues its run without a status byte.
+        push_all(&mut merger, 0, &[62, 1, 64, 1], &mut out, &mut len);
+        assert_eq!(
+            &out[..len],
+            &[0x90, 60, 1, 0xB0, 7, 100, 0x90, 62, 1, 64, 1]
+        );
+    }
+
+    #[test]
+    fn status_ends_sysex() {
+        let mut merger: Merger<2> = Merger::new();
+        let mut out = [0u8; 32];
+        let mut len = 0;
+        push_all(&mut merger, 0, &[0xF0, 1, 0xC0, 5], &mut out, &mut len);
+        assert_eq!(&out[..len], &[0xF0, 1, 0xF7, 0xC0, 5]);
+        assert_eq!(merger.owner(), None);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{Error, MidiMessage, ToSliceError};
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+/// An error from `MessageBuffer::push_bytes`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PushError {
+    /// The bytes do not start with a complete, valid message.
+    Invalid(Error),
+    /// The message is valid, but there is not enough room for it in the buffer.
+    BufferFull,
+}
+
+impl From<Error> for PushError {
+    fn from(error: Error) -> PushError {
+        PushError::Invalid(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for PushError {}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A fixed capacity queue of messages stored as encoded bytes, for buffering messages without
+/// an allocator.
+///
+/// Messages are stored back to back in the same form as `MidiMessage::copy_to_slice`, with SysEx
+/// messages including their start and end bytes, so the contents can be written to a serial port
+/// with `MessageBuffer::as_bytes`. A stored `NoteOn` with a velocity of 0 is read back as a
+/// `NoteOff`, as it is by `MidiMessage::try_from`.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MessageBuffer, MidiMessage, Note, U7};
+/// let mut buffer: MessageBuffer<16> = MessageBuffer::new();
+/// buffer.push_back(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX)).unwrap();
+/// buffer.push_back(&MidiMessage::TimingClock).unwrap();
+/// assert_eq!(buffer.as_bytes(), &[0x90, 60, 127, 0xF8]);
+/// assert_eq!(buffer.pop_front(), Some(MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX)));
+/// assert_eq!(buffer.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MessageBuffer<const N: usize> {
+    bytes: [u8; N],
+    // The stored bytes are `bytes[start..end]`.
+    start: usize,
+    end: usize,
+    len: usize,
+}
+
+impl<const N: usize> MessageBuffer<N> {
+    /// Create an empty buffer.
+    pub fn new() -> MessageBuffer<N> {
+        MessageBuffer {
+            bytes: [0; N],
+            start: 0,
+            end: 0,
+            len: 0,
+        }
+    }
+
+    /// The number of messages in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer has no messages.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of bytes that can still be stored.
+    pub fn remaining_capacity(&self) -> usize {
+        N - (self.end - self.start)
+    }
+
+    /// The encoded messages, oldest first.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[self.start..self.end]
+    }
+
+    /// Add `message` to the back of the buffer. If there is not enough room,
+    /// `ToSliceError::BufferTooSmall` is returned and the buffer is unchanged.
+    pub fn push_back(&mut self, message: &MidiMessage) -> Result<(), ToSliceError> {
+        let size = message.bytes_size();
+        self.reserve(size)?;
+        let written = message.copy_to_slice(&mut self.bytes[self.end..])?;
+        self.end += written;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Decode the first message of `bytes` and add it to the back of the buffer. Returns the
+    /// number of bytes used from `bytes`. If there is not enough room, `PushError::BufferFull`
+    /// is returned and the buffer is unchanged, so the message can be pushed again later.
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Result<usize, PushError> {
+        let message = MidiMessage::try_from(bytes)?;
+        let size = message.bytes_size();
+        self.reserve(size).map_err(|_| PushError::BufferFull)?;
+        self.bytes[self.end..self.end + size].copy_from_slice(&bytes[..size]);
+        self.end += size;
+        self.len += 1;
+        Ok(size)
+    }
+
+    /// The message at the front of the buffer.
+    pub fn front(&self) -> Option<MidiMessage<'_>> {
+        if self.is_empty() {
+            return None;
+        }
+        MidiMessage::try_from(self.as_bytes()).ok()
+    }
+
+    /// Remove the message at the front of the buffer and return it. The message borrows from
+    /// the buffer until it is dropped.
+    pub fn pop_front(&mut self) -> Option<MidiMessage<'_>> {
+        let size = self.front()?.bytes_size();
+        let start = self.start;
+        self.start += size;
+        self.len -= 1;
+        if self.len == 0 {
+            self.start = 0;
+            self.end = 0;
+        }
+        MidiMessage::try_from(&self.bytes[start..start + size]).ok()
+    }
+
+    /// Iterate over the messages, oldest first.
+    pub fn iter(&self) -> MessageBufferIter<'_> {
+        MessageBufferIter {
+            bytes: self.as_bytes(),
+        }
+    }
+
+    /// Remove every message.
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.end = 0;
+        self.len = 0;
+    }
+
+    /// Make room for `size` contiguous bytes at the end, moving the stored bytes to the front of
+    /// the array if needed.
+    fn reserve(&mut self, size: usize) -> Result<(), ToSliceError> {
+        if size > self.remaining_capacity() {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        if self.end + size > N {
+            self.bytes.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for MessageBuffer<N> {
+    fn default() -> MessageBuffer<N> {
+        MessageBuffer::new()
+    }
+}
+
+/// An iterator over the messages in a `MessageBuffer`. Created with `MessageBuffer::iter`.
+#[derive(Clone, Debug)]
+pub struct MessageBufferIter<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for MessageBufferIter<'a> {
+    type Item = MidiMessage<'a>;
+
+    fn next(&mut self) -> Option<MidiMessage<'a>> {
+        let message = MidiMessage::try_from(self.bytes).ok()?;
+        self.bytes = &self.bytes[message.bytes_size()..];
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Channel, U7};
+
+    #[test]
+    fn sysex() {
+        let mut buffer: MessageBuffer<8> = MessageBuffer::new();
+        let data = [U7(1), U7(2), U7(3)];
+        buffer.push_back(&MidiMessage::SysEx(&data)).unwrap();
+        buffer.push_back(&MidiMessage::Start).unwrap();
+        assert_eq!(buffer.as_bytes(), &[0xF0, 1, 2, 3, 0xF7, 0xFA]);
+        assert!(buffer
+            .iter()
+            .eq([MidiMessage::SysEx(&data), MidiMessage::Start]
+                .iter()
+                .cloned()));
+        assert_eq!(buffer.push_back(&MidiMessage::SongSelect(U7(0))), Ok(()));
+        assert_eq!(
+            buffer.push_back(&MidiMessage::Stop),
+            Err(ToSliceError::BufferTooSmall)
+        );
+        assert_eq!(buffer.len(), 3);
+    }
+
+    #[test]
+    fn wraps_around() {
+        let mut buffer: MessageBuffer<5> = MessageBuffer::new();
+        for program in 0..20 {
+            let message = MidiMessage::ProgramChange(Channel::Ch1, U7(program));
+            buffer.push_back(&message).unwrap();
+            buffer.push_back(&message).unwrap();
+            assert_eq!(buffer.pop_front(), Some(message.clone()));
+            assert_eq!(buffer.len(), 1);
+            assert_eq!(buffer.pop_front(), Some(message));
+            assert!(buffer.is_empty());
+        }
+        buffer.push_back(&MidiMessage::Start).unwrap();
+        buffer.push_back(&MidiMessage::Stop).unwrap();
+        buffer.pop_front();
+        // 4 bytes are free, but not at the end of the array.
+        buffer
+            .push_back(&MidiMessage::ProgramChange(Channel::Ch1, U7(0)))
+            .unwrap();
+        buffer
+            .push_back(&MidiMessage::ProgramChange(Channel::Ch1, U7(1)))
+            .unwrap();
+        assert_eq!(buffer.as_bytes(), &[0xFC, 0xC0, 0, 0xC0, 1]);
+    }
+
+    #[test]
+    fn push_bytes() {
+        let mut buffer: MessageBuffer<4> = MessageBuffer::new();
+        assert_eq!(buffer.push_bytes(&[0x80, 60, 0, 0xF8]), Ok(3));
+        assert_eq!(
+            buffer.push_bytes(&[0x80, 60]),
+            Err(PushError::Invalid(Error::NotEnoughBytes))
+        );
+        assert_eq!(buffer.front().and_then(|m| m.channel()), Some(Channel::Ch1));
+    }
+
+    #[test]
+    fn push_bytes_when_full() {
+        let mut buffer: MessageBuffer<4> = MessageBuffer::new();
+        assert_eq!(buffer.push_bytes(&[0x90, 60, 127]), Ok(3));
+        assert_eq!(
+            buffer.push_bytes(&[0x80, 60, 0]),
+            Err(PushError::BufferFull)
+        );
+        assert_eq!(buffer.as_bytes(), &[0x90, 60, 127]);
+        assert_eq!(buffer.push_bytes(&[0xF8]), Ok(1));
+    }
+}
@@ -0,0 +1,187 @@
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, Not};
+
+/// The kind of a `MidiMessage` without its data. Created with `MidiMessage::kind`.
+///
+/// Each variant matches the `MidiMessage` variant of the same name. `MidiMessage::SysEx` and
+/// `MidiMessage::OwnedSysEx` are both `MessageKind::SysEx`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MessageKind {
+    NoteOff,
+    NoteOn,
+    PolyphonicKeyPressure,
+    ControlChange,
+    ProgramChange,
+    ChannelPressure,
+    PitchBendChange,
+    SysEx,
+    MidiTimeCode,
+    SongPositionPointer,
+    SongSelect,
+    Reserved,
+    TuneRequest,
+    TimingClock,
+    Start,
+    Continue,
+    Stop,
+    ActiveSensing,
+    Reset,
+}
+
+impl MessageKind {
+    /// Every kind, in the same order as the `MidiMessage` variants.
+    pub const ALL: [MessageKind; 19] = [
+        MessageKind::NoteOff,
+        MessageKind::NoteOn,
+        MessageKind::PolyphonicKeyPressure,
+        MessageKind::ControlChange,
+        MessageKind::ProgramChange,
+        MessageKind::ChannelPressure,
+        MessageKind::PitchBendChange,
+        MessageKind::SysEx,
+        MessageKind::MidiTimeCode,
+        MessageKind::SongPositionPointer,
+        MessageKind::SongSelect,
+        MessageKind::Reserved,
+        MessageKind::TuneRequest,
+        MessageKind::TimingClock,
+        MessageKind::Start,
+        MessageKind::Continue,
+        MessageKind::Stop,
+        MessageKind::ActiveSensing,
+        MessageKind::Reset,
+    ];
+
+    /// Returns `true` for the channel voice messages, which are sent on a channel.
+    pub fn is_channel(self) -> bool {
+        self <= MessageKind::PitchBendChange
+    }
+
+    /// Returns `true` for the System Real-Time messages, which may be sent between the bytes of
+    /// other messages. `Reserved` is not included since it may be a System Common message.
+    pub fn is_realtime(self) -> bool {
+        self >= MessageKind::TimingClock
+    }
+}
+
+/// A set of `MessageKind`.
+///
+/// # Example
+/// ```
+/// use wmidi::{MessageKind, MessageKindSet};
+/// let notes: MessageKindSet = [MessageKind::NoteOn, MessageKind::NoteOff].iter().copied().collect();
+/// assert!(notes.contains(MessageKind::NoteOff));
+/// assert!(!MessageKindSet::CHANNEL.contains(MessageKind::TimingClock));
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct MessageKindSet(u32);
+
+impl MessageKindSet {
+    /// The set with no kinds.
+    pub const EMPTY: MessageKindSet = MessageKindSet(0);
+    /// The set with every kind.
+    pub const ALL: MessageKindSet = MessageKindSet((1 << 19) - 1);
+    /// The channel voice messages.
+    pub const CHANNEL: MessageKindSet = MessageKindSet((1 << 7) - 1);
+    /// The System Real-Time messages.
+    pub const REALTIME: MessageKindSet = MessageKindSet(((1 << 19) - 1) & !((1 << 13) - 1));
+
+    /// Returns `true` if `kind` is in the set.
+    #[inline(always)]
+    pub fn contains(self, kind: MessageKind) -> bool {
+        self.0 & (1 << kind as u32) != 0
+    }
+
+    /// Add `kind` to the set.
+    #[inline(always)]
+    pub fn insert(&mut self, kind: MessageKind) {
+        self.0 |= 1 << kind as u32;
+    }
+
+    /// Remove `kind` from the set.
+    #[inline(always)]
+    pub fn remove(&mut self, kind: MessageKind) {
+        self.0 &= !(1 << kind as u32);
+    }
+
+    /// The number of kinds in the set.
+    #[inline(always)]
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns `true` if the set has no kinds.
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the kinds in the set in the order of `MessageKind::ALL`.
+    pub fn iter(self) -> impl Iterator<Item = MessageKind> {
+        MessageKind::ALL
+            .iter()
+            .copied()
+            .filter(move |k| self.contains(*k))
+    }
+}
+
+impl From<MessageKind> for MessageKindSet {
+    #[inline(always)]
+    fn from(kind: MessageKind) -> MessageKindSet {
+        MessageKindSet(1 << kind as u32)
+    }
+}
+
+impl FromIterator<MessageKind> for MessageKindSet {
+    fn from_iter<I: IntoIterator<Item = MessageKind>>(iter: I) -> MessageKindSet {
+        let mut set = MessageKindSet::EMPTY;
+        for kind in iter {
+            set.insert(kind);
+        }
+        set
+    }
+}
+
+impl BitOr for MessageKindSet {
+    type Output = MessageKindSet;
+
+    #[inline(always)]
+    fn bitor(self, rhs: MessageKindSet) -> MessageKindSet {
+        MessageKindSet(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for MessageKindSet {
+    type Output = MessageKindSet;
+
+    #[inline(always)]
+    fn bitand(self, rhs: MessageKindSet) -> MessageKindSet {
+        MessageKindSet(self.0 & rhs.0)
+    }
+}
+
+impl Not for MessageKindSet {
+    type Output = MessageKindSet;
+
+    #[inline(always)]
+    fn not(self) -> MessageKindSet {
+        MessageKindSet(!self.0 & MessageKindSet::ALL.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn predicates_match_constants() {
+        for kind in MessageKind::ALL.iter() {
+            assert_eq!(kind.is_channel(), MessageKindSet::CHANNEL.contains(*kind));
+            assert_eq!(kind.is_realtime(), MessageKindSet::REALTIME.contains(*kind));
+            assert!(MessageKindSet::ALL.contains(*kind));
+        }
+        assert_eq!(MessageKindSet::REALTIME.len(), 6);
+        assert_eq!(!MessageKindSet::ALL, MessageKindSet::EMPTY);
+        assert_eq!(MessageKindSet::ALL.iter().count(), 19);
+    }
+}
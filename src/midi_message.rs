@@ -1,5 +1,9 @@
-use crate::{ControlFunction, Error, Note, ToSliceError, U14, U7};
+use crate::{
+    ControlFunction, Error, ManufacturerId, MessageKind, Note, PitchBend, QuarterFrame,
+    ToSliceError, U14, U7,
+};
 use core::convert::TryFrom;
+use core::fmt;
 
 #[cfg(feature = "std")]
 use std::{io, vec::Vec};
@@ -55,9 +59,8 @@ pub enum MidiMessage<'a> {
     /// MIDI Time Code Quarter Frame.
     ///
     /// The data is in the format 0nnndddd where nnn is the Message Type and dddd is the Value.
-    ///
-    /// TODO: Interpret data instead of providing the raw format.
-    MidiTimeCode(U7),
+    /// Convert from and to the data byte with `QuarterFrame::from(U7)` and `U7::from(frame)`.
+    MidiTimeCode(QuarterFrame),
 
     /// This is an internal 14 bit value that holds the number of MIDI beats (1 beat = six MIDI clocks) since the start
     /// of the song.
@@ -130,11 +133,11 @@ impl<'a> TryFrom<&'a [u8]> for MidiMessage<'a> {
             0xD0 => Ok(MidiMessage::ChannelPressure(chan, data_a?)),
             0xE0 => Ok(MidiMessage::PitchBendChange(
                 chan,
-                combine_data(data_a?, data_b?),
+                PitchBend::from(combine_data(data_a?, data_b?)),
             )),
             0xF0 => match bytes[0] {
                 0xF0 => MidiMessage::new_sysex(bytes),
-                0xF1 => Ok(MidiMessage::MidiTimeCode(data_a?)),
+                0xF1 => Ok(MidiMessage::MidiTimeCode(QuarterFrame::from(data_a?))),
                 0xF2 => Ok(MidiMessage::SongPositionPointer(combine_data(
                     data_a?, data_b?,
                 ))),
@@ -163,6 +166,39 @@ impl<'a> MidiMessage<'a> {
         MidiMessage::try_from(bytes)
     }
 
+    /// Construct a midi message from bytes, allowing SysEx messages to hold at most
+    /// `max_sysex_len` data bytes. Longer SysEx messages return `Error::SysExTooLong`, which is
+    /// found without scanning past the limit, even if the end byte is missing.
+    ///
+    /// Parsing borrows `bytes` and does not allocate, so the limit only bounds the memory used
+    /// by later copies, such as `to_owned`. The APIs that accumulate SysEx data have their own
+    /// limits: `SysExBuilder::max_len`, `ump::SysEx7Assembler::new` and
+    /// `ump::SysEx8Assembler::new`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{FromBytesError, MidiMessage};
+    /// let bytes = [0xF0, 0x7D, 0x01, 0x02, 0xF7];
+    /// assert!(MidiMessage::from_bytes_with_limit(&bytes, 3).is_ok());
+    /// assert_eq!(
+    ///     MidiMessage::from_bytes_with_limit(&bytes, 2),
+    ///     Err(FromBytesError::SysExTooLong)
+    /// );
+    /// ```
+    pub fn from_bytes_with_limit(bytes: &'a [u8], max_sysex_len: usize) -> Result<Self, Error> {
+        if bytes.first() != Some(&0xF0) {
+            return MidiMessage::try_from(bytes);
+        }
+        let max_bytes_len = max_sysex_len.saturating_add(2);
+        if bytes.len() <= max_bytes_len {
+            return MidiMessage::new_sysex(bytes);
+        }
+        match MidiMessage::new_sysex(&bytes[..max_bytes_len]) {
+            Err(Error::NoSysExEndByte) => Err(Error::SysExTooLong),
+            result => result,
+        }
+    }
+
     /// Copies the message as bytes to slice. If slice does not have enough capacity to fit the
     /// message, then an error is returned. On success, the number of bytes written will be
     /// returned. This should be the same number obtained from `self.bytes_size()`.
@@ -192,7 +228,7 @@ impl<'a> MidiMessage<'a> {
                     slice.copy_from_slice(&[0xD0 | a.index(), u8::from(*b)]);
                 }
                 MidiMessage::PitchBendChange(a, b) => {
-                    let (b1, b2) = split_data(*b);
+                    let (b1, b2) = split_data(U14::from(*b));
                     slice.copy_from_slice(&[0xE0 | a.index(), b1, b2]);
                 }
                 MidiMessage::SysEx(b) => {
@@ -206,7 +242,9 @@ impl<'a> MidiMessage<'a> {
                     slice[1..1 + b.len()].copy_from_slice(U7::data_to_bytes(b));
                     slice[1 + b.len()] = 0xF7;
                 }
-                MidiMessage::MidiTimeCode(a) => slice.copy_from_slice(&[0xF1, u8::from(*a)]),
+                MidiMessage::MidiTimeCode(a) => {
+                    slice.copy_from_slice(&[0xF1, u8::from(U7::from(*a))])
+                }
                 MidiMessage::SongPositionPointer(a) => {
                     let (a1, a2) = split_data(*a);
                     slice.copy_from_slice(&[0xF2, a1, a2]);
@@ -339,6 +377,86 @@ impl<'a> MidiMessage<'a> {
         }
     }
 
+    /// The manufacturer ID at the start of a SysEx message, or `None` if the message is not
+    /// SysEx or is too short to hold an ID.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{ManufacturerId, MidiMessage, U7};
+    /// let bytes = [0xF0, 0x41, 0x10, 0xF7];
+    /// let message = MidiMessage::from_bytes(&bytes).unwrap();
+    /// assert_eq!(
+    ///     message.sysex_manufacturer(),
+    ///     Some(ManufacturerId::Standard(U7::from_u8_lossy(0x41)))
+    /// );
+    /// ```
+    pub fn sysex_manufacturer(&self) -> Option<ManufacturerId> {
+        let payload: &[U7] = match self {
+            MidiMessage::SysEx(b) => b,
+            #[cfg(feature = "std")]
+            MidiMessage::OwnedSysEx(b) => b,
+            _ => return None,
+        };
+        ManufacturerId::from_sysex(payload).map(|(id, _)| id)
+    }
+
+    /// The kind of the message.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{MessageKind, MidiMessage};
+    /// assert_eq!(MidiMessage::TimingClock.kind(), MessageKind::TimingClock);
+    /// ```
+    pub fn kind(&self) -> MessageKind {
+        match self {
+            MidiMessage::NoteOff(..) => MessageKind::NoteOff,
+            MidiMessage::NoteOn(..) => MessageKind::NoteOn,
+            MidiMessage::PolyphonicKeyPressure(..) => MessageKind::PolyphonicKeyPressure,
+            MidiMessage::ControlChange(..) => MessageKind::ControlChange,
+            MidiMessage::ProgramChange(..) => MessageKind::ProgramChange,
+            MidiMessage::ChannelPressure(..) => MessageKind::ChannelPressure,
+            MidiMessage::PitchBendChange(..) => MessageKind::PitchBendChange,
+            MidiMessage::SysEx(..) => MessageKind::SysEx,
+            #[cfg(feature = "std")]
+            MidiMessage::OwnedSysEx(..) => MessageKind::SysEx,
+            MidiMessage::MidiTimeCode(..) => MessageKind::MidiTimeCode,
+            MidiMessage::SongPositionPointer(..) => MessageKind::SongPositionPointer,
+            MidiMessage::SongSelect(..) => MessageKind::SongSelect,
+            MidiMessage::Reserved(..) => MessageKind::Reserved,
+            MidiMessage::TuneRequest => MessageKind::TuneRequest,
+            MidiMessage::TimingClock => MessageKind::TimingClock,
+            MidiMessage::Start => MessageKind::Start,
+            MidiMessage::Continue => MessageKind::Continue,
+            MidiMessage::Stop => MessageKind::Stop,
+            MidiMessage::ActiveSensing => MessageKind::ActiveSensing,
+            MidiMessage::Reset => MessageKind::Reset,
+        }
+    }
+
+    /// The same message sent on `channel`. Messages that are not channel messages are returned
+    /// unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Channel, MidiMessage, Note, U7};
+    /// let message = MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX);
+    /// assert_eq!(message.with_channel(Channel::Ch10).channel(), Some(Channel::Ch10));
+    /// ```
+    pub fn with_channel(self, channel: Channel) -> MidiMessage<'a> {
+        match self {
+            MidiMessage::NoteOff(_, n, v) => MidiMessage::NoteOff(channel, n, v),
+            MidiMessage::NoteOn(_, n, v) => MidiMessage::NoteOn(channel, n, v),
+            MidiMessage::PolyphonicKeyPressure(_, n, v) => {
+                MidiMessage::PolyphonicKeyPressure(channel, n, v)
+            }
+            MidiMessage::ControlChange(_, f, v) => MidiMessage::ControlChange(channel, f, v),
+            MidiMessage::ProgramChange(_, p) => MidiMessage::ProgramChange(channel, p),
+            MidiMessage::ChannelPressure(_, v) => MidiMessage::ChannelPressure(channel, v),
+            MidiMessage::PitchBendChange(_, b) => MidiMessage::PitchBendChange(channel, b),
+            m => m,
+        }
+    }
+
     #[inline(always)]
     fn new_sysex(bytes: &'a [u8]) -> Result<Self, Error> {
         debug_assert!(bytes[0] == 0xF0);
@@ -374,6 +492,7 @@ impl<'a> io::Read for MidiMessage<'a> {
         match self.copy_to_slice(buf) {
             Ok(n) => Ok(n),
             Err(ToSliceError::BufferTooSmall) => Ok(0),
+            Err(err) => Err(io::Error::new(io::ErrorKind::InvalidData, err)),
         }
     }
 }
@@ -387,9 +506,6 @@ pub type ControlValue = U7;
 /// Specifies a program. Sometimes known as patch.
 pub type ProgramNumber = U7;
 
-/// A 14bit value specifying the pitch bend. Neutral is 8192.
-pub type PitchBend = U14;
-
 /// 14 bit value that holds the number of MIDI beats (1 beat = six MIDI clocks) since the start of the song.
 pub type SongPosition = U14;
 
@@ -442,6 +558,21 @@ impl Channel {
         }
     }
 
+    /// Get a MIDI channel from a number that is between 1 and 16 inclusive.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::Channel;
+    /// assert_eq!(Channel::from_number(10), Ok(Channel::Ch10));
+    /// assert!(Channel::from_number(0).is_err());
+    /// ```
+    pub fn from_number(n: u8) -> Result<Channel, Error> {
+        match n {
+            0 => Err(Error::ChannelOutOfRange),
+            n => Channel::from_index(n - 1),
+        }
+    }
+
     /// The index of this midi channel. The returned value is between 0 and 15
     /// inclusive.
     pub fn index(self) -> u8 {
@@ -470,8 +601,127 @@ impl Channel {
     pub fn number(self) -> u8 {
         self.index() + 1
     }
+
+    /// The channel after `self`, or `None` for `Ch16`.
+    #[inline(always)]
+    pub fn next(self) -> Option<Channel> {
+        self.checked_offset(1)
+    }
+
+    /// The channel before `self`, or `None` for `Ch1`.
+    #[inline(always)]
+    pub fn prev(self) -> Option<Channel> {
+        self.checked_offset(-1)
+    }
+
+    /// The channel `offset` channels away from `self`, or `None` if it would be outside of
+    /// `Ch1` through `Ch16`.
+    pub fn checked_offset(self, offset: i8) -> Option<Channel> {
+        let index = i16::from(self.index()) + i16::from(offset);
+        if (0..16).contains(&index) {
+            Channel::from_index(index as u8).ok()
+        } else {
+            None
+        }
+    }
+
+    /// The channel `offset` channels away from `self`, wrapping around from `Ch16` to `Ch1`
+    /// and from `Ch1` to `Ch16`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::Channel;
+    /// assert_eq!(Channel::Ch15.wrapping_offset(3), Channel::Ch2);
+    /// assert_eq!(Channel::Ch1.wrapping_offset(-1), Channel::Ch16);
+    /// ```
+    pub fn wrapping_offset(self, offset: i8) -> Channel {
+        let index = (i16::from(self.index()) + i16::from(offset)).rem_euclid(16);
+        Channel::from_index(index as u8).unwrap()
+    }
+
+    /// Iterate over all 16 channels in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Channel, MidiMessage};
+    /// let all_notes_off: Vec<MidiMessage> = Channel::all()
+    ///     .map(|c| MidiMessage::ControlChange(c, wmidi::ControlFunction::ALL_NOTES_OFF, wmidi::U7::MIN))
+    ///     .collect();
+    /// assert_eq!(all_notes_off.len(), 16);
+    /// ```
+    #[inline(always)]
+    pub fn all() -> Channels {
+        Channels { next: 0, end: 16 }
+    }
 }
 
+/// Get a MIDI channel from an index that is between 0 and 15 inclusive.
+impl TryFrom<u8> for Channel {
+    type Error = Error;
+
+    #[inline(always)]
+    fn try_from(index: u8) -> Result<Channel, Error> {
+        Channel::from_index(index)
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ch{}", self.number())
+    }
+}
+
+/// An iterator over consecutive channels. Created with `Channel::all`.
+#[derive(Clone, Debug)]
+pub struct Channels {
+    next: u8,
+    // Exclusive, may be 16.
+    end: u8,
+}
+
+impl Channels {
+    /// Iterate over the channels with indices from `start` up to but excluding `end`.
+    #[inline(always)]
+    pub(crate) fn range(start: u8, end: u8) -> Channels {
+        Channels { next: start, end }
+    }
+}
+
+impl Iterator for Channels {
+    type Item = Channel;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Channel> {
+        if self.next < self.end {
+            let channel = Channel::from_index(self.next).ok();
+            self.next += 1;
+            channel
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.end - self.next);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Channels {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Channel> {
+        if self.next < self.end {
+            self.end -= 1;
+            Channel::from_index(self.end).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Channels {}
+
 #[inline(always)]
 fn combine_data(lower: U7, higher: U7) -> U14 {
     let raw = u16::from(u8::from(lower)) + 128 * u16::from(u8::from(higher));
@@ -593,12 +843,41 @@ mod test {
             MidiMessage::try_from([0xE4, 64, 100].as_ref()),
             Ok(MidiMessage::PitchBendChange(
                 Channel::Ch5,
-                U14::try_from(12864).unwrap()
+                PitchBend::try_from(12864).unwrap()
             )),
             "PitchBendChange is decoded.",
         );
     }
 
+    #[test]
+    fn from_bytes_with_limit() {
+        let bytes = [0xF0, 1, 2, 3, 0xF7];
+        assert_eq!(
+            MidiMessage::from_bytes_with_limit(&bytes, 3),
+            MidiMessage::from_bytes(&bytes)
+        );
+        assert_eq!(
+            MidiMessage::from_bytes_with_limit(&bytes, 2),
+            Err(Error::SysExTooLong)
+        );
+        assert_eq!(
+            MidiMessage::from_bytes_with_limit(&bytes[..4], 3),
+            Err(Error::NoSysExEndByte)
+        );
+        assert_eq!(
+            MidiMessage::from_bytes_with_limit(&[0xF0, 1, 0xF8, 0xF7], 1),
+            Err(Error::UnexpectedNonSysExEndByte(0xF8))
+        );
+        assert_eq!(
+            MidiMessage::from_bytes_with_limit(&[0xF0, 0xF7], 0),
+            Ok(MidiMessage::SysEx(&[]))
+        );
+        assert_eq!(
+            MidiMessage::from_bytes_with_limit(&[0xFA], 0),
+            Ok(MidiMessage::Start)
+        );
+    }
+
     #[test]
     fn copy_to_slice() {
         let b = {
@@ -685,6 +964,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn channel_conversions() {
+        assert_eq!(Channel::try_from(9), Ok(Channel::Ch10));
+        assert_eq!(Channel::try_from(16), Err(Error::ChannelOutOfRange));
+        assert_eq!(Channel::from_number(1), Ok(Channel::Ch1));
+        assert_eq!(Channel::from_number(16), Ok(Channel::Ch16));
+        assert_eq!(Channel::from_number(17), Err(Error::ChannelOutOfRange));
+        for channel in Channel::all() {
+            assert_eq!(Channel::from_number(channel.number()), Ok(channel));
+        }
+        assert_eq!(Channel::Ch1.next(), Some(Channel::Ch2));
+        assert_eq!(Channel::Ch16.next(), None);
+        assert_eq!(Channel::Ch16.prev(), Some(Channel::Ch15));
+        assert_eq!(Channel::Ch1.prev(), None);
+        assert_eq!(Channel::Ch8.checked_offset(8), Some(Channel::Ch16));
+        assert_eq!(Channel::Ch8.checked_offset(-8), None);
+        assert_eq!(Channel::Ch8.checked_offset(i8::MAX), None);
+        assert_eq!(Channel::Ch8.wrapping_offset(i8::MIN), Channel::Ch8);
+        assert_eq!(Channel::Ch16.wrapping_offset(1), Channel::Ch1);
+        assert_eq!(Channel::all().len(), 16);
+        assert_eq!(Channel::all().next(), Some(Channel::Ch1));
+        assert_eq!(Channel::all().next_back(), Some(Channel::Ch16));
+        for (index, channel) in Channel::all().enumerate() {
+            assert_eq!(usize::from(channel.index()), index);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn channel_display() {
+        assert_eq!(format!("{}", Channel::Ch10), "Ch10");
+    }
+
     #[test]
     fn channel() {
         assert_eq!(
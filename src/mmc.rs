@@ -0,0 +1,224 @@
+//! MIDI Machine Control, which controls the transport of recorders and other devices.
+//!
+//! Commands are sent as Universal Real Time SysEx messages with sub-ID #1 0x06. A device ID of
+//! 0x7F addresses all devices.
+//!
+//! Documents referred to in this module:
+//! * [MMC]: MIDI Machine Control 1.0, part of The Complete MIDI 1.0 Detailed Specification
+
+use crate::{FrameRate, SmpteTime, ToSliceError, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MMC] A MIDI Machine Control command.
+///
+/// Only messages holding a single command are parsed. Locate is supported with a time code
+/// target; locating to a stored field is not.
+///
+/// # Example
+/// ```
+/// use wmidi::mmc::Command;
+/// use wmidi::{FrameRate, SmpteTime, U7};
+/// let mut bytes = [0u8; 6];
+/// Command::Play.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7]);
+///
+/// let time = SmpteTime::new(1, 0, 0, 0, FrameRate::Fps25).unwrap();
+/// let locate = Command::Locate { time, subframes: 0 };
+/// let mut bytes = [0u8; 13];
+/// locate.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(
+///     bytes,
+///     [0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x21, 0x00, 0x00, 0x00, 0x00, 0xF7]
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Command {
+    Stop,
+    Play,
+    /// Play once the device has finished locating.
+    DeferredPlay,
+    FastForward,
+    Rewind,
+    /// Start recording if playing, or start playing and recording if stopped.
+    RecordStrobe,
+    RecordExit,
+    RecordPause,
+    Pause,
+    Eject,
+    Chase,
+    CommandErrorReset,
+    /// Reset the Machine Control state of the device.
+    Reset,
+    /// Move to `time`, with `subframes` hundredths of a frame.
+    Locate {
+        time: SmpteTime,
+        subframes: u8,
+    },
+}
+
+impl Command {
+    /// The number of bytes in a Locate message, including the start and end bytes.
+    pub const LOCATE_BYTES_SIZE: usize = 13;
+
+    /// The command number.
+    pub fn code(self) -> u8 {
+        match self {
+            Command::Stop => 0x01,
+            Command::Play => 0x02,
+            Command::DeferredPlay => 0x03,
+            Command::FastForward => 0x04,
+            Command::Rewind => 0x05,
+            Command::RecordStrobe => 0x06,
+            Command::RecordExit => 0x07,
+            Command::RecordPause => 0x08,
+            Command::Pause => 0x09,
+            Command::Eject => 0x0A,
+            Command::Chase => 0x0B,
+            Command::CommandErrorReset => 0x0C,
+            Command::Reset => 0x0D,
+            Command::Locate { .. } => 0x44,
+        }
+    }
+
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(self) -> usize {
+        match self {
+            Command::Locate { .. } => Command::LOCATE_BYTES_SIZE,
+            _ => 6,
+        }
+    }
+
+    /// Write the Universal Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..5].copy_from_slice(&[0xF0, 0x7F, u8::from(device_id), 0x06, self.code()]);
+        if let Command::Locate { time, subframes } = self {
+            slice[5..12].copy_from_slice(&[
+                0x06,
+                0x01,
+                time.rate.code() << 5 | (time.hours & 0x1F),
+                time.minutes & 0x7F,
+                time.seconds & 0x7F,
+                time.frames & 0x1F,
+                subframes.min(99),
+            ]);
+        }
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal Real Time SysEx message, including the start and end bytes. A `device_id`
+    /// of 0x7F addresses all devices.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and command, or `None` if the payload is not a supported MMC
+    /// command.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, Command)> {
+        let (device_id, command) = match U7::data_to_bytes(payload) {
+            [0x7F, device_id, 0x06, 0x44, 0x06, 0x01, hours, minutes, seconds, frames, sub] => {
+                let time = SmpteTime {
+                    hours: hours & 0x1F,
+                    minutes: *minutes,
+                    seconds: *seconds,
+                    frames: frames & 0x1F,
+                    rate: FrameRate::from_code(hours >> 5),
+                };
+                if !time.is_valid() || *sub > 99 {
+                    return None;
+                }
+                let command = Command::Locate {
+                    time,
+                    subframes: *sub,
+                };
+                (device_id, command)
+            }
+            [0x7F, device_id, 0x06, code] => {
+                let command = match code {
+                    0x01 => Command::Stop,
+                    0x02 => Command::Play,
+                    0x03 => Command::DeferredPlay,
+                    0x04 => Command::FastForward,
+                    0x05 => Command::Rewind,
+                    0x06 => Command::RecordStrobe,
+                    0x07 => Command::RecordExit,
+                    0x08 => Command::RecordPause,
+                    0x09 => Command::Pause,
+                    0x0A => Command::Eject,
+                    0x0B => Command::Chase,
+                    0x0C => Command::CommandErrorReset,
+                    0x0D => Command::Reset,
+                    _ => return None,
+                };
+                (device_id, command)
+            }
+            _ => return None,
+        };
+        Some((U7(*device_id), command))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let commands = [
+            Command::Stop,
+            Command::DeferredPlay,
+            Command::RecordStrobe,
+            Command::Reset,
+            Command::Locate {
+                time: SmpteTime::new(23, 59, 59, 29, FrameRate::Fps2997Drop).unwrap(),
+                subframes: 50,
+            },
+        ];
+        for command in commands.iter() {
+            let mut bytes = [0u8; 13];
+            let size = command.copy_sysex_to_slice(U7(0x01), &mut bytes).unwrap();
+            assert_eq!(size, command.bytes_size());
+            let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(Command::from_sysex(payload), Some((U7(0x01), *command)));
+        }
+        assert_eq!(
+            commands[4].copy_sysex_to_slice(U7::MAX, &mut [0u8; 12]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn from_sysex_rejects_unsupported() {
+        let payloads: [&[u8]; 3] = [
+            &[0x7F, 0x7F, 0x06, 0x47, 0x01, 0x00],
+            &[0x7F, 0x7F, 0x06, 0x01, 0x02],
+            &[
+                0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x00, 0x00, 0x00, 0x00, 0x64,
+            ],
+        ];
+        for payload in payloads.iter() {
+            let payload = U7::try_from_bytes(payload).unwrap();
+            assert_eq!(Command::from_sysex(payload), None);
+        }
+    }
+}
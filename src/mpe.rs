@@ -0,0 +1,785 @@
+//! Documents referred to in this module:
+//! * [MPE]: MIDI Polyphonic Expression, version 1.0
+
+use crate::{
+    encode_parameter, Channel, ChannelMap, Channels, ControlFunction, LsbMode, MidiMessage,
+    MpeMemberCountError, Note, NoteTracker, ParameterNumber, PitchBend, Velocity, U14, U7,
+};
+
+/// [MPE] Which end of the channel range a zone occupies.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MpeZoneKind {
+    /// The master channel is channel 1 and member channels count up from channel 2.
+    Lower,
+    /// The master channel is channel 16 and member channels count down from channel 15.
+    Upper,
+}
+
+/// [MPE] A zone made of a master channel and a number of member channels, configured with the
+/// MPE Configuration Message (MCM). A zone with no member channels is disabled.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MpeZone};
+/// let zone = MpeZone::lower(7).unwrap();
+/// assert_eq!(zone.master_channel(), Channel::Ch1);
+/// assert_eq!(zone.member_channels().next(), Some(Channel::Ch2));
+/// assert_eq!(zone.member_channels().last(), Some(Channel::Ch8));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MpeZone {
+    kind: MpeZoneKind,
+    member_count: u8,
+}
+
+impl MpeZone {
+    /// The largest number of member channels in a zone.
+    pub const MAX_MEMBER_COUNT: u8 = 15;
+
+    /// Create a zone. An error is returned if `member_count` is greater than 15.
+    pub fn new(kind: MpeZoneKind, member_count: u8) -> Result<MpeZone, MpeMemberCountError> {
+        if member_count > MpeZone::MAX_MEMBER_COUNT {
+            return Err(MpeMemberCountError);
+        }
+        Ok(MpeZone { kind, member_count })
+    }
+
+    /// Create a lower zone with master channel 1. An error is returned if `member_count` is
+    /// greater than 15.
+    pub fn lower(member_count: u8) -> Result<MpeZone, MpeMemberCountError> {
+        MpeZone::new(MpeZoneKind::Lower, member_count)
+    }
+
+    /// Create an upper zone with master channel 16. An error is returned if `member_count` is
+    /// greater than 15.
+    pub fn upper(member_count: u8) -> Result<MpeZone, MpeMemberCountError> {
+        MpeZone::new(MpeZoneKind::Upper, member_count)
+    }
+
+    /// Whether this is the lower or upper zone.
+    pub fn kind(self) -> MpeZoneKind {
+        self.kind
+    }
+
+    /// The number of member channels.
+    pub fn member_count(self) -> u8 {
+        self.member_count
+    }
+
+    /// Returns `true` if the zone has any member channels.
+    pub fn is_enabled(self) -> bool {
+        self.member_count > 0
+    }
+
+    /// The channel that carries messages affecting the whole zone.
+    pub fn master_channel(self) -> Channel {
+        match self.kind {
+            MpeZoneKind::Lower => Channel::Ch1,
+            MpeZoneKind::Upper => Channel::Ch16,
+        }
+    }
+
+    /// Iterate over the member channels in ascending order.
+    pub fn member_channels(self) -> Channels {
+        match self.kind {
+            MpeZoneKind::Lower => Channels::range(1, 1 + self.member_count),
+            MpeZoneKind::Upper => Channels::range(15 - self.member_count, 15),
+        }
+    }
+
+    /// Returns `true` if `channel` is one of the member channels.
+    pub fn is_member(self, channel: Channel) -> bool {
+        let index = channel.index();
+        match self.kind {
+            MpeZoneKind::Lower => index >= 1 && index < 1 + self.member_count,
+            MpeZoneKind::Upper => index >= 15 - self.member_count && index < 15,
+        }
+    }
+
+    /// Emit the MPE Configuration Message, RPN 00/06 on the master channel with the member
+    /// count in the Data Entry MSB.
+    pub fn encode<F: FnMut(MidiMessage<'static>)>(self, emit: F) {
+        encode_parameter(
+            self.master_channel(),
+            ParameterNumber::MPE_CONFIGURATION,
+            U14::from_u16_lossy(u16::from(self.member_count) << 7),
+            LsbMode::OmitZero,
+            emit,
+        );
+    }
+
+    /// Decode an MPE Configuration Message that set `parameter` to `value` on `channel`.
+    /// Returns `None` if the parameter is not the MCM or `channel` is not channel 1 or 16.
+    /// Member counts above 15 are clamped.
+    pub fn from_parameter(
+        channel: Channel,
+        parameter: ParameterNumber,
+        value: U14,
+    ) -> Option<MpeZone> {
+        if parameter != ParameterNumber::MPE_CONFIGURATION {
+            return None;
+        }
+        let kind = match channel {
+            Channel::Ch1 => MpeZoneKind::Lower,
+            Channel::Ch16 => MpeZoneKind::Upper,
+            _ => return None,
+        };
+        let member_count = ((u16::from(value) >> 7) as u8).min(MpeZone::MAX_MEMBER_COUNT);
+        Some(MpeZone { kind, member_count })
+    }
+}
+
+/// Recognizes MPE Configuration Messages in a stream of messages.
+///
+/// Only the RPN selection of channels 1 and 16 is tracked. A zone is reported when the Data Entry
+/// MSB is received while RPN 00/06 is selected.
+///
+/// # Example
+/// ```
+/// use wmidi::{MpeConfigurationParser, MpeZone};
+/// let mut parser = MpeConfigurationParser::new();
+/// let mut zones = Vec::new();
+/// MpeZone::upper(3).unwrap().encode(|m| zones.extend(parser.handle(&m)));
+/// assert_eq!(zones, [MpeZone::upper(3).unwrap()]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MpeConfigurationParser {
+    // The selected RPN MSB and LSB of channels 1 and 16. `None` if unknown or an NRPN is selected.
+    selected: [(Option<U7>, Option<U7>); 2],
+}
+
+impl MpeConfigurationParser {
+    /// Create a parser with no parameter selected.
+    pub fn new() -> MpeConfigurationParser {
+        MpeConfigurationParser::default()
+    }
+
+    /// Process `message`, returning the configured zone if it completes an MPE Configuration
+    /// Message.
+    pub fn handle(&mut self, message: &MidiMessage) -> Option<MpeZone> {
+        let (channel, function, value) = match message {
+            MidiMessage::ControlChange(c, f, v) => (*c, *f, *v),
+            _ => return None,
+        };
+        let selected = match channel {
+            Channel::Ch1 => &mut self.selected[0],
+            Channel::Ch16 => &mut self.selected[1],
+            _ => return None,
+        };
+        match function {
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB => selected.0 = Some(value),
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB => selected.1 = Some(value),
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB
+            | ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB => *selected = (None, None),
+            ControlFunction::DATA_ENTRY_MSB if *selected == (Some(U7(0)), Some(U7(6))) => {
+                let value = U14::from_u16_lossy(u16::from(u8::from(value)) << 7);
+                return MpeZone::from_parameter(channel, ParameterNumber::MPE_CONFIGURATION, value);
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// [MPE] The expression of a single note, sent on its member channel.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct NoteExpression {
+    /// The per-note pitch bend.
+    pub pitch_bend: PitchBend,
+    /// The third dimension of control, sent with CC 74.
+    pub timbre: U7,
+    /// The per-note pressure, sent with Channel Pressure.
+    pub pressure: U7,
+}
+
+impl NoteExpression {
+    /// [MPE] The expression of a member channel before any expression messages are received.
+    pub const DEFAULT: NoteExpression = NoteExpression {
+        pitch_bend: PitchBend::CENTER,
+        timbre: U7(64),
+        pressure: U7(0),
+    };
+}
+
+impl Default for NoteExpression {
+    fn default() -> NoteExpression {
+        NoteExpression::DEFAULT
+    }
+}
+
+/// [MPE] A note event produced by `MpeReceiver`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MpeEvent {
+    /// A note started with the expression that was sent on its channel before the `NoteOn`.
+    NoteOn {
+        channel: Channel,
+        note: Note,
+        velocity: Velocity,
+        expression: NoteExpression,
+    },
+    /// A note stopped.
+    NoteOff {
+        channel: Channel,
+        note: Note,
+        velocity: Velocity,
+    },
+    /// The expression of a sounding note changed.
+    Expression {
+        channel: Channel,
+        note: Note,
+        expression: NoteExpression,
+    },
+}
+
+/// [MPE] Turns the channel messages of a zone into per-note events.
+///
+/// Pitch bend, CC 74, and Channel Pressure on a member channel apply to the notes sounding on
+/// that channel. An `MpeEvent::Expression` is emitted for each of them. Expression received
+/// before a `NoteOn` becomes the initial expression of the note.
+///
+/// An MPE Configuration Message on the master channel of the zone changes the number of member
+/// channels and stops every note. All Sound Off and All Notes Off stop every note on the channel,
+/// or every note in the zone when sent on the master channel. Messages on channels outside of
+/// the zone and other messages on the master channel are ignored, and should be handled by the
+/// caller.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, MpeEvent, MpeReceiver, MpeZone, Note, PitchBend, U7};
+/// let mut receiver = MpeReceiver::new(MpeZone::lower(15).unwrap());
+/// let mut events = Vec::new();
+/// receiver.handle(&MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7::MAX), |e| events.push(e));
+/// receiver.handle(&MidiMessage::PitchBendChange(Channel::Ch2, PitchBend::MAX), |e| events.push(e));
+/// match events[1] {
+///     MpeEvent::Expression { note, expression, .. } => {
+///         assert_eq!(note, Note::C4);
+///         assert_eq!(expression.pitch_bend, PitchBend::MAX);
+///     }
+///     _ => panic!("expected expression"),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MpeReceiver {
+    zone: MpeZone,
+    configuration: MpeConfigurationParser,
+    notes: NoteTracker,
+    expression: ChannelMap<NoteExpression>,
+}
+
+impl MpeReceiver {
+    /// Create a receiver for `zone` with no sounding notes.
+    pub fn new(zone: MpeZone) -> MpeReceiver {
+        MpeReceiver {
+            zone,
+            configuration: MpeConfigurationParser::new(),
+            notes: NoteTracker::new(),
+            expression: ChannelMap::default(),
+        }
+    }
+
+    /// The current zone.
+    pub fn zone(&self) -> MpeZone {
+        self.zone
+    }
+
+    /// The current expression of `channel`.
+    pub fn expression(&self, channel: Channel) -> NoteExpression {
+        self.expression[channel]
+    }
+
+    /// The sounding notes.
+    pub fn notes(&self) -> &NoteTracker {
+        &self.notes
+    }
+
+    /// Process `message`, calling `emit` with the resulting note events.
+    pub fn handle<F: FnMut(MpeEvent)>(&mut self, message: &MidiMessage, mut emit: F) {
+        let channel = match message.channel() {
+            Some(c) => c,
+            None => return,
+        };
+        if channel == self.zone.master_channel() {
+            if let Some(zone) = self.configuration.handle(message) {
+                if zone.kind() == self.zone.kind() {
+                    self.stop_all(emit);
+                    self.zone = zone;
+                    self.expression = ChannelMap::default();
+                }
+                return;
+            }
+            if let MidiMessage::ControlChange(_, f, _) = message {
+                if *f == ControlFunction::ALL_SOUND_OFF || *f == ControlFunction::ALL_NOTES_OFF {
+                    self.stop_all(emit);
+                }
+            }
+            return;
+        }
+        if !self.zone.is_member(channel) {
+            return;
+        }
+        let expression = &mut self.expression[channel];
+        match message {
+            MidiMessage::NoteOn(_, note, velocity) if *velocity != Velocity::MIN => {
+                emit(MpeEvent::NoteOn {
+                    channel,
+                    note: *note,
+                    velocity: *velocity,
+                    expression: *expression,
+                });
+            }
+            MidiMessage::NoteOn(_, note, velocity) | MidiMessage::NoteOff(_, note, velocity)
+                if self.notes.is_active(channel, *note) =>
+            {
+                emit(MpeEvent::NoteOff {
+                    channel,
+                    note: *note,
+                    velocity: *velocity,
+                });
+            }
+            MidiMessage::ControlChange(_, f, _)
+                if *f == ControlFunction::ALL_SOUND_OFF || *f == ControlFunction::ALL_NOTES_OFF =>
+            {
+                for note in self.notes.notes(channel) {
+                    emit(MpeEvent::NoteOff {
+                        channel,
+                        note,
+                        velocity: Velocity::MIN,
+                    });
+                }
+            }
+            MidiMessage::PitchBendChange(_, bend) => {
+                expression.pitch_bend = *bend;
+                self.emit_expression(channel, emit);
+            }
+            MidiMessage::ControlChange(_, ControlFunction::SOUND_CONTROLLER_5, value) => {
+                expression.timbre = *value;
+                self.emit_expression(channel, emit);
+            }
+            MidiMessage::ChannelPressure(_, value) => {
+                expression.pressure = *value;
+                self.emit_expression(channel, emit);
+            }
+            _ => {}
+        }
+        self.notes.handle(message);
+    }
+
+    fn emit_expression<F: FnMut(MpeEvent)>(&self, channel: Channel, mut emit: F) {
+        for note in self.notes.notes(channel) {
+            emit(MpeEvent::Expression {
+                channel,
+                note,
+                expression: self.expression[channel],
+            });
+        }
+    }
+
+    /// Emit a `NoteOff` for every sounding note and forget them.
+    fn stop_all<F: FnMut(MpeEvent)>(&mut self, mut emit: F) {
+        for (channel, note) in self.notes.iter() {
+            emit(MpeEvent::NoteOff {
+                channel,
+                note,
+                velocity: Velocity::MIN,
+            });
+        }
+        self.notes.clear();
+    }
+}
+
+/// [MPE] How `MpeAllocator` chooses a member channel for a new note.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MpeAllocation {
+    /// Use the next free member channel after the last one used.
+    RoundRobin,
+    /// Use the free member channel that has been free for the longest time. This gives the
+    /// release of the previous note on the channel the most time to finish.
+    LeastRecentlyUsed,
+}
+
+/// [MPE] Assigns each new note its own member channel so that a stream of ordinary messages is
+/// sent as valid MPE.
+///
+/// Notes are moved to member channels and `PolyphonicKeyPressure` becomes `ChannelPressure` on
+/// the channel of its note. Every other channel message is sent on the master channel, and system
+/// messages are passed through. When every member channel is in use, the oldest note is stopped
+/// to make room. The channel of a sounding note can be found with `MpeAllocator::channel_of` to
+/// send per-note pitch bend and CC 74.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, MpeAllocation, MpeAllocator, MpeZone, Note, U7};
+/// let mut allocator = MpeAllocator::new(MpeZone::lower(15).unwrap(), MpeAllocation::RoundRobin);
+/// let mut out = Vec::new();
+/// allocator.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX), |m| out.push(m));
+/// allocator.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::E4, U7::MAX), |m| out.push(m));
+/// assert_eq!(out[1], MidiMessage::NoteOn(Channel::Ch3, Note::E4, U7::MAX));
+/// assert_eq!(allocator.channel_of(Note::C4), Some(Channel::Ch2));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MpeAllocator {
+    zone: MpeZone,
+    allocation: MpeAllocation,
+    notes: ChannelMap<Option<Note>>,
+    // The time of the last NoteOn or NoteOff on each channel.
+    stamps: ChannelMap<u32>,
+    clock: u32,
+    last: Option<Channel>,
+}
+
+impl MpeAllocator {
+    /// Create an allocator for `zone` with every member channel free.
+    pub fn new(zone: MpeZone, allocation: MpeAllocation) -> MpeAllocator {
+        MpeAllocator {
+            zone,
+            allocation,
+            notes: ChannelMap::default(),
+            stamps: ChannelMap::default(),
+            clock: 0,
+            last: None,
+        }
+    }
+
+    /// The zone that notes are allocated in.
+    pub fn zone(&self) -> MpeZone {
+        self.zone
+    }
+
+    /// The member channel that `note` is sounding on. If `note` is sounding more than once, the
+    /// oldest is returned.
+    pub fn channel_of(&self, note: Note) -> Option<Channel> {
+        self.zone
+            .member_channels()
+            .filter(|c| self.notes[*c] == Some(note))
+            .min_by_key(|c| self.stamps[*c])
+    }
+
+    /// Process `message`, calling `emit` with the rewritten messages.
+    pub fn handle<'a, F: FnMut(MidiMessage<'a>)>(
+        &mut self,
+        message: &MidiMessage<'a>,
+        mut emit: F,
+    ) {
+        let master = self.zone.master_channel();
+        match message {
+            MidiMessage::NoteOn(_, note, velocity) if *velocity != Velocity::MIN => {
+                let channel = match self.allocate(&mut emit) {
+                    Some(c) => c,
+                    None => return emit(MidiMessage::NoteOn(master, *note, *velocity)),
+                };
+                self.notes[channel] = Some(*note);
+                self.touch(channel);
+                self.last = Some(channel);
+                emit(MidiMessage::NoteOn(channel, *note, *velocity));
+            }
+            MidiMessage::NoteOn(_, note, velocity) | MidiMessage::NoteOff(_, note, velocity) => {
+                if !self.zone.is_enabled() {
+                    return emit(MidiMessage::NoteOff(master, *note, *velocity));
+                }
+                if let Some(channel) = self.channel_of(*note) {
+                    self.notes[channel] = None;
+                    self.touch(channel);
+                    emit(MidiMessage::NoteOff(channel, *note, *velocity));
+                }
+            }
+            MidiMessage::PolyphonicKeyPressure(_, note, pressure) if self.zone.is_enabled() => {
+                if let Some(channel) = self.channel_of(*note) {
+                    emit(MidiMessage::ChannelPressure(channel, *pressure));
+                }
+            }
+            MidiMessage::ControlChange(_, f, _)
+                if *f == ControlFunction::ALL_SOUND_OFF || *f == ControlFunction::ALL_NOTES_OFF =>
+            {
+                self.notes = ChannelMap::default();
+                emit(message.clone().with_channel(master));
+            }
+            _ => emit(message.clone().with_channel(master)),
+        }
+    }
+
+    /// Pick a member channel for a new note, stopping the oldest note if none are free. Returns
+    /// `None` if the zone is disabled.
+    fn allocate<'a, F: FnMut(MidiMessage<'a>)>(&mut self, emit: &mut F) -> Option<Channel> {
+        let members = self.zone.member_channels();
+        let free = match self.allocation {
+            MpeAllocation::RoundRobin => {
+                let last = self.last;
+                members
+                    .clone()
+                    .filter(|c| Some(*c) > last)
+                    .chain(members.clone())
+                    .find(|c| self.notes[*c].is_none())
+            }
+            MpeAllocation::LeastRecentlyUsed => members
+                .clone()
+                .filter(|c| self.notes[*c].is_none())
+                .min_by_key(|c| self.stamps[*c]),
+        };
+        if free.is_some() {
+            return free;
+        }
+        let oldest = members.min_by_key(|c| self.stamps[*c])?;
+        if let Some(note) = self.notes[oldest].take() {
+            emit(MidiMessage::NoteOff(oldest, note, Velocity::MIN));
+        }
+        Some(oldest)
+    }
+
+    fn touch(&mut self, channel: Channel) {
+        self.clock = self.clock.wrapping_add(1);
+        self.stamps[channel] = self.clock;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn member_channels() {
+        let upper = MpeZone::upper(2).unwrap();
+        assert_eq!(upper.master_channel(), Channel::Ch16);
+        assert!(upper
+            .member_channels()
+            .eq([Channel::Ch14, Channel::Ch15].iter().copied()));
+        assert!(upper.is_member(Channel::Ch14));
+        assert!(!upper.is_member(Channel::Ch16));
+        assert!(!upper.is_member(Channel::Ch13));
+
+        let lower = MpeZone::lower(15).unwrap();
+        assert_eq!(lower.member_channels().len(), 15);
+        assert!(lower.is_member(Channel::Ch16));
+        assert!(!lower.is_member(Channel::Ch1));
+
+        let disabled = MpeZone::lower(0).unwrap();
+        assert!(!disabled.is_enabled());
+        assert_eq!(disabled.member_channels().len(), 0);
+        assert_eq!(MpeZone::upper(16), Err(MpeMemberCountError));
+    }
+
+    #[test]
+    fn encode() {
+        let mut bytes = [0u8; 15];
+        let mut len = 0;
+        MpeZone::lower(5).unwrap().encode(|m| {
+            len += m.copy_to_slice(&mut bytes[len..]).unwrap();
+        });
+        assert_eq!(
+            bytes,
+            [0xB0, 101, 0, 0xB0, 100, 6, 0xB0, 6, 5, 0xB0, 101, 0x7F, 0xB0, 100, 0x7F]
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let mut parser = MpeConfigurationParser::new();
+        let cc = |c, f, v| MidiMessage::ControlChange(c, f, U7(v));
+        let messages = [
+            cc(
+                Channel::Ch16,
+                ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB,
+                0,
+            ),
+            cc(
+                Channel::Ch16,
+                ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB,
+                6,
+            ),
+            cc(Channel::Ch2, ControlFunction::DATA_ENTRY_MSB, 4),
+            cc(Channel::Ch16, ControlFunction::DATA_ENTRY_MSB, 20),
+        ];
+        let mut zone = None;
+        for message in messages.iter() {
+            zone = zone.or(parser.handle(message));
+        }
+        assert_eq!(zone, Some(MpeZone::upper(15).unwrap()));
+
+        parser.handle(&cc(
+            Channel::Ch16,
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB,
+            6,
+        ));
+        assert_eq!(
+            parser.handle(&cc(Channel::Ch16, ControlFunction::DATA_ENTRY_MSB, 1)),
+            None
+        );
+        assert_eq!(
+            MpeZone::from_parameter(Channel::Ch2, ParameterNumber::MPE_CONFIGURATION, U14::MIN),
+            None
+        );
+    }
+
+    fn collect(receiver: &mut MpeReceiver, message: MidiMessage) -> ([Option<MpeEvent>; 2], usize) {
+        let mut events = [None; 2];
+        let mut len = 0;
+        receiver.handle(&message, |e| {
+            events[len] = Some(e);
+            len += 1;
+        });
+        (events, len)
+    }
+
+    #[test]
+    fn receiver_tracks_expression_per_note() {
+        let mut receiver = MpeReceiver::new(MpeZone::lower(3).unwrap());
+        let (events, len) = collect(
+            &mut receiver,
+            MidiMessage::ControlChange(Channel::Ch3, ControlFunction::SOUND_CONTROLLER_5, U7(10)),
+        );
+        assert_eq!(len, 0);
+        let (events_on, _) = collect(
+            &mut receiver,
+            MidiMessage::NoteOn(Channel::Ch3, Note::A4, U7(100)),
+        );
+        let expression = NoteExpression {
+            timbre: U7(10),
+            ..NoteExpression::DEFAULT
+        };
+        assert_eq!(
+            events_on[0],
+            Some(MpeEvent::NoteOn {
+                channel: Channel::Ch3,
+                note: Note::A4,
+                velocity: U7(100),
+                expression,
+            })
+        );
+        assert_eq!(events, [None, None]);
+
+        let (events, len) = collect(
+            &mut receiver,
+            MidiMessage::ChannelPressure(Channel::Ch3, U7(50)),
+        );
+        assert_eq!(len, 1);
+        assert_eq!(
+            events[0],
+            Some(MpeEvent::Expression {
+                channel: Channel::Ch3,
+                note: Note::A4,
+                expression: NoteExpression {
+                    pressure: U7(50),
+                    ..expression
+                },
+            })
+        );
+
+        // Not a member channel.
+        let (_, len) = collect(
+            &mut receiver,
+            MidiMessage::NoteOn(Channel::Ch5, Note::A4, U7(100)),
+        );
+        assert_eq!(len, 0);
+
+        let (_, len) = collect(
+            &mut receiver,
+            MidiMessage::NoteOff(Channel::Ch3, Note::A4, U7(0)),
+        );
+        assert_eq!(len, 1);
+        assert!(receiver.notes().is_empty());
+    }
+
+    #[test]
+    fn receiver_reconfigures_zone() {
+        let mut receiver = MpeReceiver::new(MpeZone::lower(3).unwrap());
+        collect(
+            &mut receiver,
+            MidiMessage::NoteOn(Channel::Ch4, Note::A4, U7(100)),
+        );
+        let mut stopped = 0;
+        MpeZone::lower(1).unwrap().encode(|m| {
+            receiver.handle(&m, |e| {
+                assert!(matches!(e, MpeEvent::NoteOff { .. }));
+                stopped += 1;
+            })
+        });
+        assert_eq!(stopped, 1);
+        assert_eq!(receiver.zone(), MpeZone::lower(1).unwrap());
+        assert!(receiver.notes().is_empty());
+    }
+
+    #[test]
+    fn allocator_round_robin() {
+        let mut allocator =
+            MpeAllocator::new(MpeZone::upper(2).unwrap(), MpeAllocation::RoundRobin);
+        let mut out = [MidiMessage::Reset, MidiMessage::Reset];
+        let mut len = 0;
+        let notes = [Note::C4, Note::D4];
+        for note in notes.iter() {
+            allocator.handle(&MidiMessage::NoteOn(Channel::Ch1, *note, U7(1)), |m| {
+                out[len] = m;
+                len += 1;
+            });
+        }
+        assert_eq!(
+            out,
+            [
+                MidiMessage::NoteOn(Channel::Ch14, Note::C4, U7(1)),
+                MidiMessage::NoteOn(Channel::Ch15, Note::D4, U7(1)),
+            ]
+        );
+
+        // The oldest note is stopped to make room.
+        let mut len = 0;
+        allocator.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::E4, U7(1)), |m| {
+            out[len] = m;
+            len += 1;
+        });
+        assert_eq!(
+            out,
+            [
+                MidiMessage::NoteOff(Channel::Ch14, Note::C4, U7(0)),
+                MidiMessage::NoteOn(Channel::Ch14, Note::E4, U7(1)),
+            ]
+        );
+        assert_eq!(allocator.channel_of(Note::C4), None);
+
+        let mut len = 0;
+        allocator.handle(
+            &MidiMessage::PolyphonicKeyPressure(Channel::Ch1, Note::D4, U7(9)),
+            |m| {
+                out[len] = m;
+                len += 1;
+            },
+        );
+        allocator.handle(&MidiMessage::NoteOff(Channel::Ch1, Note::D4, U7(2)), |m| {
+            out[len] = m;
+            len += 1;
+        });
+        assert_eq!(
+            out,
+            [
+                MidiMessage::ChannelPressure(Channel::Ch15, U7(9)),
+                MidiMessage::NoteOff(Channel::Ch15, Note::D4, U7(2)),
+            ]
+        );
+        assert_eq!(allocator.channel_of(Note::D4), None);
+    }
+
+    #[test]
+    fn allocator_least_recently_used() {
+        let mut allocator =
+            MpeAllocator::new(MpeZone::lower(3).unwrap(), MpeAllocation::LeastRecentlyUsed);
+        for note in [Note::C4, Note::D4, Note::E4].iter() {
+            allocator.handle(&MidiMessage::NoteOn(Channel::Ch1, *note, U7(1)), |_| {});
+        }
+        allocator.handle(&MidiMessage::NoteOff(Channel::Ch1, Note::E4, U7(0)), |_| {});
+        allocator.handle(&MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0)), |_| {});
+        allocator.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::F4, U7(1)), |_| {});
+        // Channel 4 was freed before channel 2.
+        assert_eq!(allocator.channel_of(Note::F4), Some(Channel::Ch4));
+
+        let mut message = None;
+        allocator.handle(
+            &MidiMessage::ControlChange(Channel::Ch7, ControlFunction::MODULATION_WHEEL, U7(3)),
+            |m| message = Some(m),
+        );
+        assert_eq!(
+            message,
+            Some(MidiMessage::ControlChange(
+                Channel::Ch1,
+                ControlFunction::MODULATION_WHEEL,
+                U7(3)
+            ))
+        );
+    }
+}
@@ -0,0 +1,639 @@
+//! MIDI Show Control, which controls lighting, sound, machinery, and other show equipment.
+//!
+//! Commands are sent as Universal Real Time SysEx messages with sub-ID #1 0x02. The device ID is
+//! 0x00 to 0x6F for a single device, 0x70 to 0x7E for a group, or 0x7F for all devices.
+//!
+//! Documents referred to in this module:
+//! * [MSC]: MIDI Show Control 1.1, part of The Complete MIDI 1.0 Detailed Specification
+
+use crate::{FrameRate, SmpteTime, ToSliceError, U14, U7};
+use core::str;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MSC] The kind of equipment a command is for.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CommandFormat(pub U7);
+
+impl CommandFormat {
+    pub const LIGHTING: CommandFormat = CommandFormat(U7(0x01));
+    pub const MOVING_LIGHTS: CommandFormat = CommandFormat(U7(0x02));
+    pub const COLOR_CHANGERS: CommandFormat = CommandFormat(U7(0x03));
+    pub const STROBES: CommandFormat = CommandFormat(U7(0x04));
+    pub const LASERS: CommandFormat = CommandFormat(U7(0x05));
+    pub const CHASERS: CommandFormat = CommandFormat(U7(0x06));
+    pub const SOUND: CommandFormat = CommandFormat(U7(0x10));
+    pub const MUSIC: CommandFormat = CommandFormat(U7(0x11));
+    pub const CD_PLAYERS: CommandFormat = CommandFormat(U7(0x12));
+    pub const EPROM_PLAYBACK: CommandFormat = CommandFormat(U7(0x13));
+    pub const AUDIO_TAPE_MACHINES: CommandFormat = CommandFormat(U7(0x14));
+    pub const INTERCOMS: CommandFormat = CommandFormat(U7(0x15));
+    pub const AMPLIFIERS: CommandFormat = CommandFormat(U7(0x16));
+    pub const AUDIO_EFFECTS: CommandFormat = CommandFormat(U7(0x17));
+    pub const EQUALIZERS: CommandFormat = CommandFormat(U7(0x18));
+    pub const MACHINERY: CommandFormat = CommandFormat(U7(0x20));
+    pub const RIGGING: CommandFormat = CommandFormat(U7(0x21));
+    pub const FLYS: CommandFormat = CommandFormat(U7(0x22));
+    pub const LIFTS: CommandFormat = CommandFormat(U7(0x23));
+    pub const TURNTABLES: CommandFormat = CommandFormat(U7(0x24));
+    pub const VIDEO: CommandFormat = CommandFormat(U7(0x30));
+    pub const PROJECTION: CommandFormat = CommandFormat(U7(0x40));
+    pub const PROCESS_CONTROL: CommandFormat = CommandFormat(U7(0x50));
+    pub const PYRO: CommandFormat = CommandFormat(U7(0x60));
+    /// Addresses equipment of every kind.
+    pub const ALL_TYPES: CommandFormat = CommandFormat(U7(0x7F));
+}
+
+/// [MSC] Identifies a cue by its number, and optionally the cue list and cue path it is in.
+///
+/// Each part is written as ASCII digits with an optional decimal point, such as `"10.5"`. A
+/// `list` is only sent with a `number`, and a `path` only with a `list`. A command without a
+/// number applies to the next cue.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Cue<'a> {
+    pub number: Option<&'a str>,
+    pub list: Option<&'a str>,
+    pub path: Option<&'a str>,
+}
+
+impl<'a> Cue<'a> {
+    /// The cue with `number` in the current list.
+    pub fn number(number: &'a str) -> Cue<'a> {
+        Cue {
+            number: Some(number),
+            list: None,
+            path: None,
+        }
+    }
+
+    fn parts(&self) -> impl Iterator<Item = &'a str> {
+        let list = self.number.and(self.list);
+        let path = list.and(self.path);
+        self.number.into_iter().chain(list).chain(path)
+    }
+
+    fn bytes_size(&self) -> usize {
+        self.parts()
+            .map(|part| part.len() + 1)
+            .sum::<usize>()
+            .saturating_sub(1)
+    }
+
+    fn write(&self, writer: &mut Writer) {
+        for (i, part) in self.parts().enumerate() {
+            if i > 0 {
+                writer.push(0x00);
+            }
+            writer.extend(part.as_bytes());
+        }
+    }
+
+    fn parse(data: &'a [u8]) -> Option<Cue<'a>> {
+        let mut parts = data.split(|b| *b == 0x00).map(parse_number);
+        if data.is_empty() {
+            return Some(Cue::default());
+        }
+        let mut next = || match parts.next() {
+            Some(part) => part.map(Some),
+            None => Some(None),
+        };
+        let cue = Cue {
+            number: Some(next()??),
+            list: next()?,
+            path: next()?,
+        };
+        match parts.next() {
+            Some(_) => None,
+            None => Some(cue),
+        }
+    }
+}
+
+/// [MSC] A MIDI Show Control command.
+///
+/// Commands that refer to a cue list or cue path hold them as ASCII numbers, as in `Cue`. The
+/// optional time of `Set` is not supported.
+///
+/// # Example
+/// ```
+/// use wmidi::msc::{Command, CommandFormat, Cue};
+/// use wmidi::U7;
+/// let go = Command::Go(Cue {
+///     number: Some("23.5"),
+///     list: Some("1"),
+///     path: None,
+/// });
+/// let mut bytes = [0u8; 16];
+/// let size = go
+///     .copy_sysex_to_slice(U7::MAX, CommandFormat::LIGHTING, &mut bytes)
+///     .unwrap();
+/// let bytes = &bytes[..size];
+/// assert_eq!(bytes, b"\xF0\x7F\x7F\x02\x01\x0123.5\x001\xF7");
+/// let payload = U7::try_from_bytes(&bytes[1..bytes.len() - 1]).unwrap();
+/// assert_eq!(
+///     Command::from_sysex(payload),
+///     Some((U7::MAX, CommandFormat::LIGHTING, go))
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Command<'a> {
+    Go(Cue<'a>),
+    Stop(Cue<'a>),
+    Resume(Cue<'a>),
+    /// Go to the cue, taking `time` to complete the transition.
+    TimedGo {
+        time: SmpteTime,
+        subframes: u8,
+        cue: Cue<'a>,
+    },
+    /// Prepare the cue so that the next `Go` runs it.
+    Load(Cue<'a>),
+    /// Set a generic control to a value.
+    Set {
+        control: U14,
+        value: U14,
+    },
+    /// Run a macro.
+    Fire(U7),
+    AllOff,
+    Restore,
+    Reset,
+    GoOff(Cue<'a>),
+    GoJamClock(Cue<'a>),
+    StandbyPlus(Option<&'a str>),
+    StandbyMinus(Option<&'a str>),
+    SequencePlus(Option<&'a str>),
+    SequenceMinus(Option<&'a str>),
+    StartClock(Option<&'a str>),
+    StopClock(Option<&'a str>),
+    ZeroClock(Option<&'a str>),
+    SetClock {
+        time: SmpteTime,
+        subframes: u8,
+        list: Option<&'a str>,
+    },
+    MtcChaseOn(Option<&'a str>),
+    MtcChaseOff(Option<&'a str>),
+    OpenCueList(&'a str),
+    CloseCueList(&'a str),
+    OpenCuePath(&'a str),
+    CloseCuePath(&'a str),
+}
+
+impl<'a> Command<'a> {
+    /// The command number.
+    pub fn code(&self) -> u8 {
+        match self {
+            Command::Go(_) => 0x01,
+            Command::Stop(_) => 0x02,
+            Command::Resume(_) => 0x03,
+            Command::TimedGo { .. } => 0x04,
+            Command::Load(_) => 0x05,
+            Command::Set { .. } => 0x06,
+            Command::Fire(_) => 0x07,
+            Command::AllOff => 0x08,
+            Command::Restore => 0x09,
+            Command::Reset => 0x0A,
+            Command::GoOff(_) => 0x0B,
+            Command::GoJamClock(_) => 0x10,
+            Command::StandbyPlus(_) => 0x11,
+            Command::StandbyMinus(_) => 0x12,
+            Command::SequencePlus(_) => 0x13,
+            Command::SequenceMinus(_) => 0x14,
+            Command::StartClock(_) => 0x15,
+            Command::StopClock(_) => 0x16,
+            Command::ZeroClock(_) => 0x17,
+            Command::SetClock { .. } => 0x18,
+            Command::MtcChaseOn(_) => 0x19,
+            Command::MtcChaseOff(_) => 0x1A,
+            Command::OpenCueList(_) => 0x1B,
+            Command::CloseCueList(_) => 0x1C,
+            Command::OpenCuePath(_) => 0x1D,
+            Command::CloseCuePath(_) => 0x1E,
+        }
+    }
+
+    fn data_size(&self) -> usize {
+        match self {
+            Command::Go(cue)
+            | Command::Stop(cue)
+            | Command::Resume(cue)
+            | Command::Load(cue)
+            | Command::GoOff(cue)
+            | Command::GoJamClock(cue) => cue.bytes_size(),
+            Command::TimedGo { cue, .. } => {
+                5 + usize::from(cue.number.is_some()) + cue.bytes_size()
+            }
+            Command::Set { .. } => 4,
+            Command::Fire(_) => 1,
+            Command::AllOff | Command::Restore | Command::Reset => 0,
+            Command::StandbyPlus(list)
+            | Command::StandbyMinus(list)
+            | Command::SequencePlus(list)
+            | Command::SequenceMinus(list)
+            | Command::StartClock(list)
+            | Command::StopClock(list)
+            | Command::ZeroClock(list)
+            | Command::MtcChaseOn(list)
+            | Command::MtcChaseOff(list) => list.map_or(0, str::len),
+            Command::SetClock { list, .. } => 5 + list.map_or(0, |l| l.len() + 1),
+            Command::OpenCueList(number)
+            | Command::CloseCueList(number)
+            | Command::OpenCuePath(number)
+            | Command::CloseCuePath(number) => number.len(),
+        }
+    }
+
+    /// Returns `true` if every cue, list, and path number is ASCII digits and decimal points, so
+    /// that the message parses back to the same command.
+    fn has_valid_numbers(&self) -> bool {
+        let valid = |number: &str| parse_number(number.as_bytes()).is_some();
+        match self {
+            Command::Go(cue)
+            | Command::Stop(cue)
+            | Command::Resume(cue)
+            | Command::Load(cue)
+            | Command::GoOff(cue)
+            | Command::GoJamClock(cue)
+            | Command::TimedGo { cue, .. } => cue.parts().all(valid),
+            Command::StandbyPlus(list)
+            | Command::StandbyMinus(list)
+            | Command::SequencePlus(list)
+            | Command::SequenceMinus(list)
+            | Command::StartClock(list)
+            | Command::StopClock(list)
+            | Command::ZeroClock(list)
+            | Command::MtcChaseOn(list)
+            | Command::MtcChaseOff(list)
+            | Command::SetClock { list, .. } => list.is_none_or(valid),
+            Command::OpenCueList(number)
+            | Command::CloseCueList(number)
+            | Command::OpenCuePath(number)
+            | Command::CloseCuePath(number) => valid(number),
+            Command::Set { .. }
+            | Command::Fire(_)
+            | Command::AllOff
+            | Command::Restore
+            | Command::Reset => true,
+        }
+    }
+
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        7 + self.data_size()
+    }
+
+    /// Write the Universal Real Time SysEx message for equipment of kind `format` to `slice`,
+    /// including the start and end bytes. Returns the number of bytes written.
+    ///
+    /// Returns `ToSliceError::InvalidMessage` if a cue, list, or path number is empty or has a
+    /// character other than an ASCII digit or decimal point.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        format: CommandFormat,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        if !self.has_valid_numbers() {
+            return Err(ToSliceError::InvalidMessage);
+        }
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let mut writer = Writer { slice, len: 0 };
+        writer.extend(&[
+            0xF0,
+            0x7F,
+            u8::from(device_id),
+            0x02,
+            u8::from(format.0),
+            self.code(),
+        ]);
+        match self {
+            Command::Go(cue)
+            | Command::Stop(cue)
+            | Command::Resume(cue)
+            | Command::Load(cue)
+            | Command::GoOff(cue)
+            | Command::GoJamClock(cue) => cue.write(&mut writer),
+            Command::TimedGo {
+                time,
+                subframes,
+                cue,
+            } => {
+                write_time(&mut writer, time, *subframes);
+                if cue.number.is_some() {
+                    writer.push(0x00);
+                    cue.write(&mut writer);
+                }
+            }
+            Command::Set { control, value } => {
+                let control = u16::from(*control);
+                let value = u16::from(*value);
+                writer.extend(&[
+                    (control & 0x7F) as u8,
+                    (control >> 7) as u8,
+                    (value & 0x7F) as u8,
+                    (value >> 7) as u8,
+                ]);
+            }
+            Command::Fire(number) => writer.push(u8::from(*number)),
+            Command::AllOff | Command::Restore | Command::Reset => {}
+            Command::StandbyPlus(list)
+            | Command::StandbyMinus(list)
+            | Command::SequencePlus(list)
+            | Command::SequenceMinus(list)
+            | Command::StartClock(list)
+            | Command::StopClock(list)
+            | Command::ZeroClock(list)
+            | Command::MtcChaseOn(list)
+            | Command::MtcChaseOff(list) => writer.extend(list.unwrap_or("").as_bytes()),
+            Command::SetClock {
+                time,
+                subframes,
+                list,
+            } => {
+                write_time(&mut writer, time, *subframes);
+                if let Some(list) = list {
+                    writer.push(0x00);
+                    writer.extend(list.as_bytes());
+                }
+            }
+            Command::OpenCueList(number)
+            | Command::CloseCueList(number)
+            | Command::OpenCuePath(number)
+            | Command::CloseCuePath(number) => writer.extend(number.as_bytes()),
+        }
+        writer.push(0xF7);
+        Ok(size)
+    }
+
+    /// The Universal Real Time SysEx message for equipment of kind `format`, including the start
+    /// and end bytes.
+    ///
+    /// # Panics
+    /// Panics if a cue, list, or path number is not valid, as described in `copy_sysex_to_slice`.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7, format: CommandFormat) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, format, &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id, command format, and command, or `None` if the payload is not a
+    /// valid MIDI Show Control message.
+    pub fn from_sysex(payload: &'a [U7]) -> Option<(U7, CommandFormat, Command<'a>)> {
+        let (device_id, format, code, data) = match U7::data_to_bytes(payload) {
+            [0x7F, device_id, 0x02, format, code, data @ ..] => (*device_id, *format, *code, data),
+            _ => return None,
+        };
+        let list = || -> Option<Option<&'a str>> {
+            if data.is_empty() {
+                Some(None)
+            } else {
+                parse_number(data).map(Some)
+            }
+        };
+        let command = match code {
+            0x01 => Command::Go(Cue::parse(data)?),
+            0x02 => Command::Stop(Cue::parse(data)?),
+            0x03 => Command::Resume(Cue::parse(data)?),
+            0x04 => {
+                let (time, subframes) = parse_time(data)?;
+                let cue = match &data[5..] {
+                    [] => Cue::default(),
+                    [0x00, cue @ ..] if !cue.is_empty() => Cue::parse(cue)?,
+                    _ => return None,
+                };
+                Command::TimedGo {
+                    time,
+                    subframes,
+                    cue,
+                }
+            }
+            0x05 => Command::Load(Cue::parse(data)?),
+            0x06 => match data {
+                [control_lsb, control_msb, value_lsb, value_msb] => Command::Set {
+                    control: combine(*control_lsb, *control_msb),
+                    value: combine(*value_lsb, *value_msb),
+                },
+                _ => return None,
+            },
+            0x07 => match data {
+                [number] => Command::Fire(U7(*number)),
+                _ => return None,
+            },
+            0x08 if data.is_empty() => Command::AllOff,
+            0x09 if data.is_empty() => Command::Restore,
+            0x0A if data.is_empty() => Command::Reset,
+            0x0B => Command::GoOff(Cue::parse(data)?),
+            0x10 => Command::GoJamClock(Cue::parse(data)?),
+            0x11 => Command::StandbyPlus(list()?),
+            0x12 => Command::StandbyMinus(list()?),
+            0x13 => Command::SequencePlus(list()?),
+            0x14 => Command::SequenceMinus(list()?),
+            0x15 => Command::StartClock(list()?),
+            0x16 => Command::StopClock(list()?),
+            0x17 => Command::ZeroClock(list()?),
+            0x18 => {
+                let (time, subframes) = parse_time(data)?;
+                let list = match &data[5..] {
+                    [] => None,
+                    [0x00, list @ ..] => Some(parse_number(list)?),
+                    _ => return None,
+                };
+                Command::SetClock {
+                    time,
+                    subframes,
+                    list,
+                }
+            }
+            0x19 => Command::MtcChaseOn(list()?),
+            0x1A => Command::MtcChaseOff(list()?),
+            0x1B => Command::OpenCueList(parse_number(data)?),
+            0x1C => Command::CloseCueList(parse_number(data)?),
+            0x1D => Command::OpenCuePath(parse_number(data)?),
+            0x1E => Command::CloseCuePath(parse_number(data)?),
+            _ => return None,
+        };
+        Some((U7(device_id), CommandFormat(U7(format)), command))
+    }
+}
+
+/// Writes bytes to a slice that is known to be large enough.
+struct Writer<'s> {
+    slice: &'s mut [u8],
+    len: usize,
+}
+
+impl<'s> Writer<'s> {
+    fn push(&mut self, byte: u8) {
+        self.slice[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        self.slice[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+}
+
+fn write_time(writer: &mut Writer, time: &SmpteTime, subframes: u8) {
+    writer.extend(&[
+        time.rate.code() << 5 | (time.hours & 0x1F),
+        time.minutes & 0x7F,
+        time.seconds & 0x7F,
+        time.frames & 0x1F,
+        subframes.min(99),
+    ]);
+}
+
+fn parse_time(data: &[u8]) -> Option<(SmpteTime, u8)> {
+    match data {
+        [hours, minutes, seconds, frames, subframes, ..] => {
+            let time = SmpteTime {
+                hours: hours & 0x1F,
+                minutes: *minutes,
+                seconds: *seconds,
+                frames: frames & 0x1F,
+                rate: FrameRate::from_code(hours >> 5),
+            };
+            if !time.is_valid() || *subframes > 99 {
+                return None;
+            }
+            Some((time, *subframes))
+        }
+        _ => None,
+    }
+}
+
+/// Parse a cue, list, or path number, which is ASCII digits and decimal points.
+fn parse_number(data: &[u8]) -> Option<&str> {
+    if data.is_empty() || !data.iter().all(|b| b.is_ascii_digit() || *b == b'.') {
+        return None;
+    }
+    str::from_utf8(data).ok()
+}
+
+fn combine(lsb: u8, msb: u8) -> U14 {
+    U14::from_u16_lossy(u16::from(msb) << 7 | u16::from(lsb))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let time = SmpteTime::new(0, 1, 2, 3, FrameRate::Fps30).unwrap();
+        let commands = [
+            Command::Go(Cue::default()),
+            Command::Stop(Cue::number("1")),
+            Command::Load(Cue {
+                number: Some("5.1"),
+                list: Some("2"),
+                path: Some("3"),
+            }),
+            Command::TimedGo {
+                time,
+                subframes: 0,
+                cue: Cue::default(),
+            },
+            Command::TimedGo {
+                time,
+                subframes: 99,
+                cue: Cue::number("7"),
+            },
+            Command::Set {
+                control: U14::from_u16_lossy(0x200),
+                value: U14::MAX,
+            },
+            Command::Fire(U7(12)),
+            Command::AllOff,
+            Command::StandbyPlus(None),
+            Command::StartClock(Some("4")),
+            Command::SetClock {
+                time,
+                subframes: 1,
+                list: Some("2"),
+            },
+            Command::OpenCuePath("10"),
+        ];
+        for command in commands.iter() {
+            let mut bytes = [0u8; 32];
+            let size = command
+                .copy_sysex_to_slice(U7(0x01), CommandFormat::SOUND, &mut bytes)
+                .unwrap();
+            assert_eq!(size, command.bytes_size());
+            let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(
+                Command::from_sysex(payload),
+                Some((U7(0x01), CommandFormat::SOUND, *command))
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cue_parts_require_earlier_parts() {
+        let cue = Cue {
+            number: None,
+            list: Some("1"),
+            path: Some("2"),
+        };
+        assert_eq!(
+            Command::Go(cue).to_sysex_vec(U7::MAX, CommandFormat::ALL_TYPES),
+            [0xF0, 0x7F, 0x7F, 0x02, 0x7F, 0x01, 0xF7]
+        );
+    }
+
+    #[test]
+    fn invalid_numbers_are_not_encoded() {
+        let commands = [
+            Command::Go(Cue::number("1é")),
+            Command::Load(Cue {
+                number: Some("1"),
+                list: Some(""),
+                path: None,
+            }),
+            Command::StartClock(Some("a")),
+            Command::SetClock {
+                time: SmpteTime::new(0, 0, 0, 0, FrameRate::Fps25).unwrap(),
+                subframes: 0,
+                list: Some("-1"),
+            },
+            Command::OpenCuePath(""),
+        ];
+        for command in commands.iter() {
+            assert_eq!(
+                command.copy_sysex_to_slice(U7::MAX, CommandFormat::SOUND, &mut [0u8; 32]),
+                Err(ToSliceError::InvalidMessage)
+            );
+        }
+    }
+
+    #[test]
+    fn from_sysex_rejects_invalid_numbers() {
+        let payloads: [&[u8]; 4] = [
+            &[0x7F, 0x7F, 0x02, 0x01, 0x01, b'a'],
+            &[0x7F, 0x7F, 0x02, 0x01, 0x01, b'1', 0x00],
+            &[
+                0x7F, 0x7F, 0x02, 0x01, 0x01, b'1', 0x00, b'2', 0x00, b'3', 0x00, b'4',
+            ],
+            &[0x7F, 0x7F, 0x02, 0x01, 0x08, 0x00],
+        ];
+        for payload in payloads.iter() {
+            let payload = U7::try_from_bytes(payload).unwrap();
+            assert_eq!(Command::from_sysex(payload), None);
+        }
+    }
+}
@@ -0,0 +1,574 @@
+use crate::{FrameRate, MidiMessage, SmpteTime, ToSliceError, U4, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// The part of the time code carried by a MIDI Time Code quarter frame. The pieces are sent in
+/// this order while time moves forward.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FrameType {
+    /// The low 4 bits of the frame number.
+    FramesLow = 0,
+    /// The high bit of the frame number.
+    FramesHigh = 1,
+    /// The low 4 bits of the seconds.
+    SecondsLow = 2,
+    /// The high 2 bits of the seconds.
+    SecondsHigh = 3,
+    /// The low 4 bits of the minutes.
+    MinutesLow = 4,
+    /// The high 2 bits of the minutes.
+    MinutesHigh = 5,
+    /// The low 4 bits of the hours.
+    HoursLow = 6,
+    /// The high bit of the hours and the 2 bits of the frame rate.
+    HoursHigh = 7,
+}
+
+impl FrameType {
+    /// All pieces, in the order they are sent.
+    pub const ALL: [FrameType; 8] = [
+        FrameType::FramesLow,
+        FrameType::FramesHigh,
+        FrameType::SecondsLow,
+        FrameType::SecondsHigh,
+        FrameType::MinutesLow,
+        FrameType::MinutesHigh,
+        FrameType::HoursLow,
+        FrameType::HoursHigh,
+    ];
+
+    /// The piece with the position `index` in the sequence. Only the 3 least significant bits
+    /// are used.
+    pub fn from_index(index: u8) -> FrameType {
+        FrameType::ALL[usize::from(index & 0x07)]
+    }
+
+    /// The position of the piece in the sequence, from 0 to 7.
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+/// The payload of a `MidiMessage::MidiTimeCode` quarter frame message: one nibble of the time
+/// code and which part of the time code it is.
+///
+/// # Example
+/// ```
+/// use wmidi::{FrameType, MidiMessage, QuarterFrame, U4};
+/// let message = MidiMessage::MidiTimeCode(QuarterFrame::new(FrameType::SecondsHigh, U4::MAX));
+/// let mut bytes = [0u8; 2];
+/// message.copy_to_slice(&mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF1, 0x3F]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct QuarterFrame {
+    /// The part of the time code that `value` holds.
+    pub piece: FrameType,
+    /// The nibble of the time code.
+    pub value: U4,
+}
+
+impl QuarterFrame {
+    /// Create a quarter frame.
+    pub const fn new(piece: FrameType, value: U4) -> QuarterFrame {
+        QuarterFrame { piece, value }
+    }
+
+    /// The quarter frame that holds the `piece` of `time`.
+    pub fn from_time(time: &SmpteTime, piece: FrameType) -> QuarterFrame {
+        let value = match piece {
+            FrameType::FramesLow => time.frames,
+            FrameType::FramesHigh => time.frames >> 4,
+            FrameType::SecondsLow => time.seconds,
+            FrameType::SecondsHigh => time.seconds >> 4,
+            FrameType::MinutesLow => time.minutes,
+            FrameType::MinutesHigh => time.minutes >> 4,
+            FrameType::HoursLow => time.hours,
+            FrameType::HoursHigh => (time.hours >> 4 & 0x01) | time.rate.code() << 1,
+        };
+        QuarterFrame::new(piece, U4::from_u8_lossy(value))
+    }
+}
+
+impl From<U7> for QuarterFrame {
+    /// Decode the `0nnndddd` data byte, where `nnn` is the piece and `dddd` is the value.
+    fn from(data: U7) -> QuarterFrame {
+        let data = u8::from(data);
+        QuarterFrame {
+            piece: FrameType::from_index(data >> 4),
+            value: U4::from_u8_lossy(data),
+        }
+    }
+}
+
+impl From<QuarterFrame> for U7 {
+    /// Encode the quarter frame as a `0nnndddd` data byte.
+    fn from(frame: QuarterFrame) -> U7 {
+        U7((frame.piece.index() << 4) | u8::from(frame.value))
+    }
+}
+
+/// Assembles the quarter frames of MIDI Time Code into complete times.
+///
+/// A time is returned after all 8 pieces have been received in sequence, which takes 2 frames.
+/// Since the pieces describe the time when the sequence started, the returned time is 2 frames
+/// later when running forward, or 2 frames earlier when running in reverse, the pieces then
+/// arriving in reverse order. A piece out of sequence discards the pieces received so far.
+///
+/// # Example
+/// ```
+/// use wmidi::{FrameType, MidiMessage, QuarterFrame, QuarterFrameAssembler, U4};
+/// // 01:02:03:04 at 25 frames per second.
+/// let nibbles = [4, 0, 3, 0, 2, 0, 1, 2];
+/// let mut assembler = QuarterFrameAssembler::new();
+/// let mut time = None;
+/// for (piece, nibble) in FrameType::ALL.iter().zip(nibbles.iter()) {
+///     let frame = QuarterFrame::new(*piece, U4::new(*nibble).unwrap());
+///     time = assembler.handle(&MidiMessage::MidiTimeCode(frame));
+/// }
+/// let time = time.unwrap();
+/// assert_eq!((time.hours, time.minutes, time.seconds, time.frames), (1, 2, 3, 6));
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct QuarterFrameAssembler {
+    nibbles: [u8; 8],
+    received: u8,
+    last: Option<FrameType>,
+    reverse: bool,
+}
+
+impl QuarterFrameAssembler {
+    /// Create an assembler that has not received any pieces.
+    pub fn new() -> QuarterFrameAssembler {
+        QuarterFrameAssembler::default()
+    }
+
+    /// Returns `true` if the last pieces arrived in reverse order.
+    pub fn is_reverse(&self) -> bool {
+        self.reverse
+    }
+
+    /// Process `message`, returning the current time if it completes a sequence of quarter
+    /// frames or is a Full Message. A Full Message also discards the pieces received so far.
+    pub fn handle(&mut self, message: &MidiMessage) -> Option<SmpteTime> {
+        match message {
+            MidiMessage::MidiTimeCode(frame) => self.push(*frame),
+            MidiMessage::SysEx(payload) => self.handle_sysex(payload),
+            #[cfg(feature = "std")]
+            MidiMessage::OwnedSysEx(payload) => self.handle_sysex(payload),
+            _ => None,
+        }
+    }
+
+    fn handle_sysex(&mut self, payload: &[U7]) -> Option<SmpteTime> {
+        match MtcSysEx::from_sysex(payload) {
+            Some((_, MtcSysEx::Full(time))) => {
+                self.reset();
+                Some(time)
+            }
+            _ => None,
+        }
+    }
+
+    /// Add a quarter frame, returning the current time if it completes a sequence.
+    pub fn push(&mut self, frame: QuarterFrame) -> Option<SmpteTime> {
+        let index = frame.piece.index();
+        let last = self.last.replace(frame.piece).map(FrameType::index);
+        match last {
+            Some(l) if index == (l + 1) & 0x07 || index == l.wrapping_sub(1) & 0x07 => {
+                let reverse = index != (l + 1) & 0x07;
+                if reverse != self.reverse {
+                    // Keep only the previous piece, which starts the sequence in the new
+                    // direction.
+                    self.received &= 1 << l;
+                    self.reverse = reverse;
+                }
+            }
+            _ => self.received = 0,
+        }
+        self.nibbles[usize::from(index)] = u8::from(frame.value);
+        self.received |= 1 << index;
+        let end = if self.reverse {
+            FrameType::FramesLow
+        } else {
+            FrameType::HoursHigh
+        };
+        if frame.piece != end || self.received != 0xFF {
+            return None;
+        }
+        self.received = 0;
+        let n = &self.nibbles;
+        let time = SmpteTime {
+            frames: n[0] | (n[1] & 0x01) << 4,
+            seconds: n[2] | (n[3] & 0x03) << 4,
+            minutes: n[4] | (n[5] & 0x03) << 4,
+            hours: n[6] | (n[7] & 0x01) << 4,
+            rate: FrameRate::from_code(n[7] >> 1),
+        };
+        if !time.is_valid() {
+            return None;
+        }
+        Some(time.add_frames(if self.reverse { -2 } else { 2 }))
+    }
+
+    /// Discard the pieces received so far.
+    pub fn reset(&mut self) {
+        *self = QuarterFrameAssembler::new();
+    }
+}
+
+/// Generates the quarter frames of MIDI Time Code for a device acting as the time code source.
+///
+/// Each sequence of 8 quarter frames holds the time at which its first quarter frame is sent and
+/// takes 2 frames to send, so the time moves forward by 2 frames between sequences. Call `poll`
+/// regularly with the time since playback started from the start time, or take quarter frames
+/// with `next_frame` from a timer running every `quarter_frame_interval` microseconds.
+///
+/// # Example
+/// ```
+/// use wmidi::{FrameRate, FrameType, MidiMessage, QuarterFrameGenerator, SmpteTime};
+/// let start = SmpteTime {
+///     hours: 0,
+///     minutes: 0,
+///     seconds: 0,
+///     frames: 0,
+///     rate: FrameRate::Fps25,
+/// };
+/// let mut generator = QuarterFrameGenerator::new(start);
+/// assert_eq!(generator.quarter_frame_interval(), 10_000);
+/// assert!(generator.poll(0).is_some());
+/// assert!(generator.poll(9_999).is_none());
+/// match generator.poll(10_000) {
+///     Some(MidiMessage::MidiTimeCode(frame)) => assert_eq!(frame.piece, FrameType::FramesHigh),
+///     _ => panic!(),
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct QuarterFrameGenerator {
+    time: SmpteTime,
+    piece: u8,
+    sent: u64,
+}
+
+impl QuarterFrameGenerator {
+    /// Create a generator whose first sequence holds `start`.
+    pub fn new(start: SmpteTime) -> QuarterFrameGenerator {
+        QuarterFrameGenerator {
+            time: start,
+            piece: 0,
+            sent: 0,
+        }
+    }
+
+    /// The time held by the sequence being sent.
+    pub fn time(&self) -> SmpteTime {
+        self.time
+    }
+
+    /// Restart from `time`, for example after the user moves the playback position. The time
+    /// passed to `poll` is then measured from when playback restarts.
+    pub fn locate(&mut self, time: SmpteTime) {
+        *self = QuarterFrameGenerator::new(time);
+    }
+
+    /// The time between quarter frames in microseconds, rounded to the nearest microsecond.
+    pub fn quarter_frame_interval(&self) -> u64 {
+        let (frames, seconds) = self.time.rate.ratio();
+        (seconds * 1_000_000 + frames * 2) / (frames * 4)
+    }
+
+    /// The next quarter frame to send.
+    pub fn next_frame(&mut self) -> QuarterFrame {
+        let frame = QuarterFrame::from_time(&self.time, FrameType::from_index(self.piece));
+        self.piece += 1;
+        self.sent += 1;
+        if self.piece == 8 {
+            self.piece = 0;
+            self.time = self.time.add_frames(2);
+        }
+        frame
+    }
+
+    /// Returns the next quarter frame message if it is due `elapsed` microseconds after the
+    /// start. Quarter frames are due at exact multiples of the frame period, so no drift builds
+    /// up from rounding.
+    pub fn poll(&mut self, elapsed: u64) -> Option<MidiMessage<'static>> {
+        let (frames, seconds) = self.time.rate.ratio();
+        let due = self.sent * seconds * 1_000_000 / (frames * 4);
+        if elapsed < due {
+            return None;
+        }
+        Some(MidiMessage::MidiTimeCode(self.next_frame()))
+    }
+}
+
+/// The user bits of a SMPTE time code: 8 groups of 4 bits and 2 binary group flags, which say
+/// how the groups are used.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct UserBits {
+    /// The binary groups, in the order they are sent.
+    pub groups: [U4; 8],
+    /// The binary group flags. Only the 2 least significant bits are sent.
+    pub flags: u8,
+}
+
+/// A Universal Real Time SysEx message for MIDI Time Code.
+///
+/// # Example
+/// ```
+/// use wmidi::{FrameRate, MtcSysEx, SmpteTime, U7};
+/// let time = SmpteTime::new(1, 2, 3, 4, FrameRate::Fps30).unwrap();
+/// let mut bytes = [0u8; 10];
+/// MtcSysEx::Full(time).copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MtcSysEx {
+    /// The Full Message, which moves receivers to a time, for example when the source locates
+    /// to a new position while stopped.
+    Full(SmpteTime),
+    /// The user bits of the time code.
+    UserBits(UserBits),
+}
+
+impl MtcSysEx {
+    /// The number of bytes in a Full Message, including the start and end bytes.
+    pub const FULL_BYTES_SIZE: usize = 10;
+    /// The number of bytes in a User Bits message, including the start and end bytes.
+    pub const USER_BITS_BYTES_SIZE: usize = 15;
+
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        match self {
+            MtcSysEx::Full(_) => MtcSysEx::FULL_BYTES_SIZE,
+            MtcSysEx::UserBits(_) => MtcSysEx::USER_BITS_BYTES_SIZE,
+        }
+    }
+
+    /// Write the Universal Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..3].copy_from_slice(&[0xF0, 0x7F, u8::from(device_id)]);
+        match self {
+            MtcSysEx::Full(time) => slice[3..size].copy_from_slice(&[
+                0x01,
+                0x01,
+                time.rate.code() << 5 | (time.hours & 0x1F),
+                time.minutes & 0x7F,
+                time.seconds & 0x7F,
+                time.frames & 0x7F,
+                0xF7,
+            ]),
+            MtcSysEx::UserBits(bits) => {
+                slice[3..5].copy_from_slice(&[0x01, 0x02]);
+                for (byte, group) in slice[5..13].iter_mut().zip(bits.groups.iter()) {
+                    *byte = u8::from(*group);
+                }
+                slice[13..size].copy_from_slice(&[bits.flags & 0x03, 0xF7]);
+            }
+        }
+        Ok(size)
+    }
+
+    /// The Universal Real Time SysEx message, including the start and end bytes. A `device_id`
+    /// of 0x7F addresses all devices.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a MIDI Time Code
+    /// message or holds an invalid time.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, MtcSysEx)> {
+        match U7::data_to_bytes(payload) {
+            [0x7F, device_id, 0x01, 0x01, hours, minutes, seconds, frames] => {
+                let time = SmpteTime {
+                    hours: hours & 0x1F,
+                    minutes: *minutes,
+                    seconds: *seconds,
+                    frames: *frames,
+                    rate: FrameRate::from_code(hours >> 5),
+                };
+                if !time.is_valid() {
+                    return None;
+                }
+                Some((U7(*device_id), MtcSysEx::Full(time)))
+            }
+            [0x7F, device_id, 0x01, 0x02, groups @ .., flags] if groups.len() == 8 => {
+                let mut bits = UserBits {
+                    groups: [U4::MIN; 8],
+                    flags: flags & 0x03,
+                };
+                for (group, byte) in bits.groups.iter_mut().zip(groups.iter()) {
+                    *group = U4::from_u8_lossy(*byte);
+                }
+                Some((U7(*device_id), MtcSysEx::UserBits(bits)))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        for data in 0..128 {
+            let frame = QuarterFrame::from(U7(data));
+            assert_eq!(frame.piece.index(), data >> 4);
+            assert_eq!(U7::from(frame), U7(data));
+        }
+    }
+
+    fn pieces(time: SmpteTime) -> [QuarterFrame; 8] {
+        let mut generator = QuarterFrameGenerator::new(time);
+        core::array::from_fn(|_| generator.next_frame())
+    }
+
+    #[test]
+    fn assemble() {
+        let time = SmpteTime {
+            hours: 23,
+            minutes: 59,
+            seconds: 59,
+            frames: 28,
+            rate: FrameRate::Fps30,
+        };
+        let mut assembler = QuarterFrameAssembler::new();
+        let forward: Option<_> = pieces(time).iter().fold(None, |_, f| assembler.push(*f));
+        assert_eq!(forward.map(|t| (t.hours, t.frames)), Some((0, 0)));
+        assert!(!assembler.is_reverse());
+        let mut assembler = QuarterFrameAssembler::new();
+        let reverse = pieces(time)
+            .iter()
+            .rev()
+            .fold(None, |_, f| assembler.push(*f));
+        assert_eq!(reverse.map(|t| t.frames), Some(26));
+        assert!(assembler.is_reverse());
+    }
+
+    #[test]
+    fn out_of_sequence() {
+        let time = SmpteTime {
+            hours: 1,
+            minutes: 2,
+            seconds: 3,
+            frames: 4,
+            rate: FrameRate::Fps24,
+        };
+        let frames = pieces(time);
+        let mut assembler = QuarterFrameAssembler::new();
+        for frame in frames[..7].iter().chain(frames[3..].iter()) {
+            assert_eq!(assembler.push(*frame), None);
+        }
+        for frame in frames.iter() {
+            assert_eq!(
+                assembler.push(*frame).is_some(),
+                frame.piece == FrameType::HoursHigh
+            );
+        }
+    }
+
+    #[test]
+    fn generate() {
+        let start = SmpteTime {
+            hours: 0,
+            minutes: 0,
+            seconds: 59,
+            frames: 28,
+            rate: FrameRate::Fps2997Drop,
+        };
+        let mut generator = QuarterFrameGenerator::new(start);
+        let mut assembler = QuarterFrameAssembler::new();
+        let mut times = [None; 16];
+        for time in times.iter_mut() {
+            *time = assembler.push(generator.next_frame());
+        }
+        assert_eq!(
+            times[7].map(|t| (t.minutes, t.seconds, t.frames)),
+            Some((1, 0, 2))
+        );
+        assert_eq!(
+            times[15].map(|t| (t.minutes, t.seconds, t.frames)),
+            Some((1, 0, 4))
+        );
+        assert_eq!(generator.time().frames, 4);
+        // 4 quarter frames per frame at 29.97 frames per second.
+        let mut generator = QuarterFrameGenerator::new(start);
+        let count = (0..=1_000_000)
+            .filter(|t| generator.poll(*t).is_some())
+            .count();
+        assert_eq!(count, 120);
+    }
+
+    #[test]
+    fn sysex_round_trip() {
+        let time = SmpteTime::new(23, 59, 59, 29, FrameRate::Fps2997Drop).unwrap();
+        let mut groups = [U4::MIN; 8];
+        groups[7] = U4::MAX;
+        let messages = [
+            MtcSysEx::Full(time),
+            MtcSysEx::UserBits(UserBits { groups, flags: 2 }),
+        ];
+        for message in messages.iter() {
+            let mut bytes = [0u8; 15];
+            let size = message.copy_sysex_to_slice(U7(3), &mut bytes).unwrap();
+            let payload = match MidiMessage::try_from(&bytes[..size]) {
+                Ok(MidiMessage::SysEx(payload)) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(MtcSysEx::from_sysex(payload), Some((U7(3), *message)));
+        }
+        assert_eq!(
+            messages[1].copy_sysex_to_slice(U7(3), &mut [0u8; 14]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn full_message_resets_assembler() {
+        let time = SmpteTime::new(1, 0, 0, 0, FrameRate::Fps24).unwrap();
+        let frames = pieces(time);
+        let mut assembler = QuarterFrameAssembler::new();
+        for frame in frames[..4].iter() {
+            assembler.push(*frame);
+        }
+        let mut bytes = [0u8; 10];
+        MtcSysEx::Full(time)
+            .copy_sysex_to_slice(U7::MAX, &mut bytes)
+            .unwrap();
+        let full = MidiMessage::try_from(&bytes[..]).unwrap();
+        assert_eq!(assembler.handle(&full), Some(time));
+        for frame in frames[4..].iter() {
+            assert_eq!(assembler.push(*frame), None);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn owned_full_message() {
+        let time = SmpteTime::new(2, 30, 0, 12, FrameRate::Fps30).unwrap();
+        let mut assembler = QuarterFrameAssembler::new();
+        assembler.push(pieces(time)[0]);
+        let bytes = MtcSysEx::Full(time).to_sysex_vec(U7::MAX);
+        let full = MidiMessage::try_from(&bytes[..]).unwrap().to_owned();
+        assert!(matches!(full, MidiMessage::OwnedSysEx(_)));
+        assert_eq!(assembler.handle(&full), Some(time));
+        assert_eq!(assembler.push(pieces(time)[1]), None);
+    }
+}
@@ -0,0 +1,905 @@
+//! Documents referred to in this module:
+//! * [MTS]: MIDI Tuning Updated Specification (CA-020, CA-021, RP-020)
+
+use crate::checksum::xor_bytes as checksum;
+use crate::{
+    Cents, ChannelSet, Error, Note, Temperament, ToSliceError, UFix32, UniversalKind, U14, U7,
+};
+use core::convert::TryFrom;
+use core::str;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MTS] A frequency in the MIDI Tuning Standard 3 byte format: a note number, plus a 14 bit
+/// fraction of a half step above it. Each step of the fraction is 100/16384 cents (about 0.0061
+/// cents).
+///
+/// # Example
+/// ```
+/// use wmidi::{MtsFrequency, Note, U14};
+/// let quarter_tone = MtsFrequency::new(Note::A4, U14::try_from(8192).unwrap());
+/// assert_eq!(quarter_tone.to_data().len(), 3);
+/// # use std::convert::TryFrom;
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MtsFrequency {
+    note: Note,
+    fraction: U14,
+}
+
+impl MtsFrequency {
+    /// [MTS] The data that indicates that a note's tuning should not be changed.
+    pub const NO_CHANGE: [U7; 3] = [U7::MAX, U7::MAX, U7::MAX];
+
+    /// Create a frequency `fraction`/16384 of a half step above `note`.
+    ///
+    /// `G9` with the maximum fraction is encoded the same as `MtsFrequency::NO_CHANGE`, so the
+    /// fraction is reduced by one step in that case.
+    pub fn new(note: Note, fraction: U14) -> MtsFrequency {
+        let fraction = if note == Note::HIGHEST_NOTE && fraction == U14::MAX {
+            unsafe { U14::from_unchecked(u16::from(U14::MAX) - 1) }
+        } else {
+            fraction
+        };
+        MtsFrequency { note, fraction }
+    }
+
+    /// The nearest note at or below the frequency.
+    pub fn note(self) -> Note {
+        self.note
+    }
+
+    /// The distance above `self.note()`, in 16384ths of a half step.
+    pub fn fraction(self) -> U14 {
+        self.fraction
+    }
+
+    /// The distance above `self.note()`.
+    pub fn cents_above_note(self) -> Cents {
+        Cents(f32::from(u16::from(self.fraction)) * 100.0 / 16384.0)
+    }
+
+    /// Decode the 3 data bytes of the MTS frequency format. Returns `None` for
+    /// `MtsFrequency::NO_CHANGE`.
+    pub fn from_data(data: [U7; 3]) -> Option<MtsFrequency> {
+        if data == MtsFrequency::NO_CHANGE {
+            return None;
+        }
+        let fraction = u16::from(u8::from(data[1])) << 7 | u16::from(u8::from(data[2]));
+        Some(MtsFrequency {
+            note: Note::from(data[0]),
+            fraction: unsafe { U14::from_unchecked(fraction) },
+        })
+    }
+
+    /// Encode to the 3 data bytes of the MTS frequency format.
+    pub fn to_data(self) -> [U7; 3] {
+        let fraction = u16::from(self.fraction);
+        [
+            U7(u8::from(self.note)),
+            U7::from_u8_lossy((fraction >> 7) as u8),
+            U7::from_u8_lossy(fraction as u8),
+        ]
+    }
+
+    /// The frequency as a fractional note number in Q16.16 format, suitable for
+    /// `pitch_to_freq_fixed`.
+    pub fn to_pitch(self) -> UFix32 {
+        UFix32(u32::from(u8::from(self.note)) << 16 | u32::from(u16::from(self.fraction)) << 2)
+    }
+
+    /// Create a frequency from a fractional note number in Q16.16 format. The fraction is
+    /// truncated to 14 bits. An error is returned if the pitch is not below 128.0.
+    pub fn from_pitch(pitch: UFix32) -> Result<MtsFrequency, Error> {
+        let note = Note::try_from(pitch.integer().min(128) as u8)?;
+        let fraction = unsafe { U14::from_unchecked(pitch.fraction() >> 2) };
+        Ok(MtsFrequency::new(note, fraction))
+    }
+
+    /// The frequency in Hz, relative to the standard 440Hz tuning.
+    #[cfg(feature = "std")]
+    pub fn to_freq_f64(self) -> f64 {
+        let pitch = f64::from(u8::from(self.note)) + f64::from(u16::from(self.fraction)) / 16384.0;
+        440.0 * 2_f64.powf((pitch - 69.0) / 12.0)
+    }
+
+    /// The closest MTS frequency to `freq` Hz, relative to the standard 440Hz tuning. An error
+    /// is returned if `freq` is below `C-1` or not below `G#9`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{MtsFrequency, Note, U14};
+    /// let a440 = MtsFrequency::from_freq_f64(440.0).unwrap();
+    /// assert_eq!((a440.note(), a440.fraction()), (Note::A4, U14::MIN));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_freq_f64(freq: f64) -> Result<MtsFrequency, Error> {
+        let pitch = 69.0 + 12.0 * (freq / 440.0).log2();
+        let steps = (pitch * 16384.0).round();
+        if !(0.0..128.0 * 16384.0).contains(&steps) {
+            return Err(Error::NoteOutOfRange);
+        }
+        let steps = steps as u32;
+        let note = Note::from_u8_lossy((steps / 16384) as u8);
+        let fraction = unsafe { U14::from_unchecked((steps % 16384) as u16) };
+        Ok(MtsFrequency::new(note, fraction))
+    }
+}
+
+impl From<Note> for MtsFrequency {
+    fn from(note: Note) -> MtsFrequency {
+        MtsFrequency::new(note, U14::MIN)
+    }
+}
+
+/// [MTS] Asks a device to send a `BulkTuningDump` of a tuning program.
+///
+/// # Example
+/// ```
+/// use wmidi::{BulkTuningRequest, U7};
+/// let request = BulkTuningRequest {
+///     bank: None,
+///     program: U7::from_u8_lossy(5),
+/// };
+/// let mut bytes = [0u8; 7];
+/// request.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7E, 0x7F, 0x08, 0x00, 0x05, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BulkTuningRequest {
+    /// The tuning bank, or `None` for devices with a single bank.
+    pub bank: Option<U7>,
+    /// The tuning program within the bank.
+    pub program: U7,
+}
+
+impl BulkTuningRequest {
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(self) -> usize {
+        if self.bank.is_some() {
+            8
+        } else {
+            7
+        }
+    }
+
+    /// Write the Universal Non-Real Time SysEx message to `slice`, including the start and end
+    /// bytes. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..3].copy_from_slice(&[0xF0, 0x7E, u8::from(device_id)]);
+        match self.bank {
+            Some(bank) => slice[3..6].copy_from_slice(&[0x08, 0x03, u8::from(bank)]),
+            None => slice[3..5].copy_from_slice(&[0x08, 0x00]),
+        }
+        slice[size - 2] = u8::from(self.program);
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal Non-Real Time SysEx message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and request, or `None` if the payload is not a bulk tuning dump
+    /// request.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, BulkTuningRequest)> {
+        let (device_id, bank, program) = match U7::data_to_bytes(payload) {
+            [0x7E, device_id, 0x08, 0x00, program] => (device_id, None, program),
+            [0x7E, device_id, 0x08, 0x03, bank, program] => (device_id, Some(U7(*bank)), program),
+            _ => return None,
+        };
+        let request = BulkTuningRequest {
+            bank,
+            program: U7(*program),
+        };
+        Some((U7(*device_id), request))
+    }
+}
+
+/// [MTS] The tuning of every note in a tuning program.
+///
+/// # Example
+/// ```
+/// use wmidi::{BulkTuningDump, MtsFrequency, Note, U14, U7};
+/// let mut dump = BulkTuningDump::new(None, U7::MIN, "Quarter sharp");
+/// dump.frequencies[usize::from(u8::from(Note::A4))] =
+///     Some(MtsFrequency::new(Note::A4, U14::from_u16_lossy(8192)));
+/// let mut bytes = [0u8; BulkTuningDump::SYSEX_BYTES_SIZE];
+/// let size = dump.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(size, BulkTuningDump::SYSEX_BYTES_SIZE);
+/// let payload = U7::try_from_bytes(&bytes[1..size - 1]).unwrap();
+/// assert_eq!(BulkTuningDump::from_sysex(payload), Some((U7::MAX, dump)));
+/// assert_eq!(dump.name(), Some("Quarter sharp"));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BulkTuningDump {
+    /// The tuning bank, or `None` for devices with a single bank.
+    pub bank: Option<U7>,
+    /// The tuning program within the bank.
+    pub program: U7,
+    /// The name of the tuning in ASCII, padded with spaces.
+    pub name: [u8; 16],
+    /// The frequency of each note, indexed by note number. `None` leaves the note unchanged.
+    pub frequencies: [Option<MtsFrequency>; 128],
+}
+
+impl BulkTuningDump {
+    /// The number of bytes in a message without a bank, including the start and end bytes.
+    pub const SYSEX_BYTES_SIZE: usize = 408;
+
+    /// Create an equal tempered tuning. `name` is truncated to 16 bytes.
+    pub fn new(bank: Option<U7>, program: U7, name: &str) -> BulkTuningDump {
+        let mut name_bytes = [b' '; 16];
+        for (dst, src) in name_bytes.iter_mut().zip(name.bytes()) {
+            *dst = src;
+        }
+        let mut frequencies = [None; 128];
+        for (frequency, note) in frequencies.iter_mut().zip(Note::all()) {
+            *frequency = Some(MtsFrequency::from(note));
+        }
+        BulkTuningDump {
+            bank,
+            program,
+            name: name_bytes,
+            frequencies,
+        }
+    }
+
+    /// The name with trailing spaces removed, or `None` if it is not ASCII.
+    pub fn name(&self) -> Option<&str> {
+        if !self.name.is_ascii() {
+            return None;
+        }
+        str::from_utf8(&self.name).ok().map(str::trim_end)
+    }
+
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        BulkTuningDump::SYSEX_BYTES_SIZE + usize::from(self.bank.is_some())
+    }
+
+    /// Write the Universal Non-Real Time SysEx message to `slice`, including the start and end
+    /// bytes. Returns the number of bytes written. Name bytes that are not ASCII are masked to
+    /// 7 bits.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..3].copy_from_slice(&[0xF0, 0x7E, u8::from(device_id)]);
+        let start = match self.bank {
+            Some(bank) => {
+                slice[3..7].copy_from_slice(&[0x08, 0x04, u8::from(bank), u8::from(self.program)]);
+                7
+            }
+            None => {
+                slice[3..6].copy_from_slice(&[0x08, 0x01, u8::from(self.program)]);
+                6
+            }
+        };
+        for (dst, src) in slice[start..start + 16].iter_mut().zip(self.name.iter()) {
+            *dst = src & 0x7F;
+        }
+        let data = &mut slice[start + 16..size - 2];
+        for (chunk, frequency) in data.chunks_exact_mut(3).zip(self.frequencies.iter()) {
+            let frequency = frequency.map_or(MtsFrequency::NO_CHANGE, MtsFrequency::to_data);
+            chunk.copy_from_slice(U7::data_to_bytes(&frequency));
+        }
+        slice[size - 2] = checksum(&slice[1..size - 2]);
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal Non-Real Time SysEx message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and dump, or `None` if the payload is not a bulk tuning dump or
+    /// the checksum is incorrect.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, BulkTuningDump)> {
+        let bytes = U7::data_to_bytes(payload);
+        let (bank, program, rest) = match bytes {
+            [0x7E, _, 0x08, 0x01, program, rest @ ..] => (None, program, rest),
+            [0x7E, _, 0x08, 0x04, bank, program, rest @ ..] => (Some(U7(*bank)), program, rest),
+            _ => return None,
+        };
+        let (checksum_byte, rest) = rest.split_last()?;
+        if rest.len() != 16 + 128 * 3 || checksum(&bytes[..bytes.len() - 1]) != *checksum_byte {
+            return None;
+        }
+        let mut dump = BulkTuningDump {
+            bank,
+            program: U7(*program),
+            name: [0; 16],
+            frequencies: [None; 128],
+        };
+        dump.name.copy_from_slice(&rest[..16]);
+        for (frequency, chunk) in dump
+            .frequencies
+            .iter_mut()
+            .zip(payload[payload.len() - 385..payload.len() - 1].chunks_exact(3))
+        {
+            *frequency = MtsFrequency::from_data([chunk[0], chunk[1], chunk[2]]);
+        }
+        Some((payload[1], dump))
+    }
+}
+
+/// [MTS] A change to the tuning of a single note.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct NoteTuning {
+    /// The note to retune.
+    pub note: Note,
+    /// The new frequency of the note, or `None` to leave it unchanged.
+    pub frequency: Option<MtsFrequency>,
+}
+
+impl NoteTuning {
+    fn to_data(self) -> [U7; 4] {
+        let [a, b, c] = self
+            .frequency
+            .map_or(MtsFrequency::NO_CHANGE, MtsFrequency::to_data);
+        [U7(u8::from(self.note)), a, b, c]
+    }
+}
+
+/// [MTS] Retunes individual notes of a tuning program. The changes are passed separately when
+/// encoding, and returned as a `NoteTunings` iterator when decoding.
+///
+/// Messages without a bank are only defined as Real Time messages, so they are always sent as
+/// Real Time. Real Time changes apply to sounding notes immediately, while Non-Real Time changes
+/// apply to the next notes played.
+///
+/// # Example
+/// ```
+/// use wmidi::{MtsFrequency, Note, NoteTuning, SingleNoteTuning, U14, U7, UniversalKind};
+/// let tuning = SingleNoteTuning {
+///     kind: UniversalKind::RealTime,
+///     bank: None,
+///     program: U7::MIN,
+/// };
+/// let changes = [NoteTuning {
+///     note: Note::C4,
+///     frequency: Some(MtsFrequency::new(Note::C4, U14::from_u16_lossy(0x2000))),
+/// }];
+/// let mut bytes = [0u8; 12];
+/// tuning.copy_sysex_to_slice(U7::MAX, &changes, &mut bytes).unwrap();
+/// assert_eq!(
+///     bytes,
+///     [0xF0, 0x7F, 0x7F, 0x08, 0x02, 0x00, 0x01, 0x3C, 0x3C, 0x40, 0x00, 0xF7]
+/// );
+/// let payload = U7::try_from_bytes(&bytes[1..11]).unwrap();
+/// let (_, decoded, mut notes) = SingleNoteTuning::from_sysex(payload).unwrap();
+/// assert_eq!(decoded, tuning);
+/// assert_eq!(notes.next(), Some(changes[0]));
+/// assert_eq!(notes.next(), None);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SingleNoteTuning {
+    /// Whether the changes apply to sounding notes. Ignored if `bank` is `None`.
+    pub kind: UniversalKind,
+    /// The tuning bank, or `None` for devices with a single bank.
+    pub bank: Option<U7>,
+    /// The tuning program within the bank.
+    pub program: U7,
+}
+
+impl SingleNoteTuning {
+    /// The most changes a message holds.
+    pub const MAX_CHANGES: usize = 127;
+
+    /// The number of bytes in a message with `changes` changes, including the start and end
+    /// bytes. At most `SingleNoteTuning::MAX_CHANGES` changes are counted.
+    pub fn bytes_size(&self, changes: usize) -> usize {
+        let header = if self.bank.is_some() { 8 } else { 7 };
+        header + 4 * changes.min(SingleNoteTuning::MAX_CHANGES) + 1
+    }
+
+    /// Write the Universal SysEx message holding `changes` to `slice`, including the start and
+    /// end bytes. Only the first `SingleNoteTuning::MAX_CHANGES` changes are written. Returns
+    /// the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        changes: &[NoteTuning],
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let changes = &changes[..changes.len().min(SingleNoteTuning::MAX_CHANGES)];
+        let size = self.bytes_size(changes.len());
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let count = changes.len() as u8;
+        let start = match self.bank {
+            Some(bank) => {
+                let id = match self.kind {
+                    UniversalKind::NonRealTime => 0x7E,
+                    UniversalKind::RealTime => 0x7F,
+                };
+                slice[..8].copy_from_slice(&[
+                    0xF0,
+                    id,
+                    u8::from(device_id),
+                    0x08,
+                    0x07,
+                    u8::from(bank),
+                    u8::from(self.program),
+                    count,
+                ]);
+                8
+            }
+            None => {
+                slice[..7].copy_from_slice(&[
+                    0xF0,
+                    0x7F,
+                    u8::from(device_id),
+                    0x08,
+                    0x02,
+                    u8::from(self.program),
+                    count,
+                ]);
+                7
+            }
+        };
+        for (chunk, change) in slice[start..size - 1].chunks_exact_mut(4).zip(changes) {
+            chunk.copy_from_slice(U7::data_to_bytes(&change.to_data()));
+        }
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal SysEx message holding `changes`, including the start and end bytes. Only the
+    /// first `SingleNoteTuning::MAX_CHANGES` changes are included.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7, changes: &[NoteTuning]) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size(changes.len())];
+        self.copy_sysex_to_slice(device_id, changes, &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id, message, and changes, or `None` if the payload is not a single
+    /// note tuning change.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, SingleNoteTuning, NoteTunings<'_>)> {
+        let (tuning, count, data) = match U7::data_to_bytes(payload) {
+            [0x7F, _, 0x08, 0x02, program, count, ..] => {
+                let tuning = SingleNoteTuning {
+                    kind: UniversalKind::RealTime,
+                    bank: None,
+                    program: U7(*program),
+                };
+                (tuning, count, &payload[6..])
+            }
+            [id, _, 0x08, 0x07, bank, program, count, ..] => {
+                let tuning = SingleNoteTuning {
+                    kind: match id {
+                        0x7E => UniversalKind::NonRealTime,
+                        0x7F => UniversalKind::RealTime,
+                        _ => return None,
+                    },
+                    bank: Some(U7(*bank)),
+                    program: U7(*program),
+                };
+                (tuning, count, &payload[7..])
+            }
+            _ => return None,
+        };
+        if data.len() != 4 * usize::from(*count) {
+            return None;
+        }
+        Some((payload[1], tuning, NoteTunings { data }))
+    }
+}
+
+/// An iterator over the changes in a `SingleNoteTuning` message.
+#[derive(Clone, Debug)]
+pub struct NoteTunings<'a> {
+    data: &'a [U7],
+}
+
+impl<'a> Iterator for NoteTunings<'a> {
+    type Item = NoteTuning;
+
+    fn next(&mut self) -> Option<NoteTuning> {
+        if self.data.len() < 4 {
+            return None;
+        }
+        let (change, rest) = self.data.split_at(4);
+        self.data = rest;
+        Some(NoteTuning {
+            note: Note::from(change[0]),
+            frequency: MtsFrequency::from_data([change[1], change[2], change[3]]),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.data.len() / 4;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for NoteTunings<'a> {}
+
+/// [MTS] The detune of each pitch class in a `ScaleOctaveTuning` message, starting from C.
+///
+/// Offsets are relative to equal temperament and apply to every octave, so they can be used as
+/// a `Temperament`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ScaleOctaveOffsets {
+    /// -64 to +63 cents in 1 cent steps, where 0x40 is no detune.
+    OneByte([U7; 12]),
+    /// -100 to just under +100 cents in 100/8192 cent steps, where 0x2000 is no detune.
+    TwoByte([U14; 12]),
+}
+
+impl ScaleOctaveOffsets {
+    /// Encode `cents` in the 1 byte format, rounding to the nearest cent and saturating at the
+    /// ends of the range.
+    pub fn from_cents_one_byte(cents: [Cents; 12]) -> ScaleOctaveOffsets {
+        let mut offsets = [U7(0x40); 12];
+        for (offset, cents) in offsets.iter_mut().zip(cents.iter()) {
+            if !cents.0.is_nan() {
+                // Round half up. `as` truncates toward zero, so shift to positive values first.
+                *offset = U7((cents.0.clamp(-64.0, 63.0) + 64.5) as u8);
+            }
+        }
+        ScaleOctaveOffsets::OneByte(offsets)
+    }
+
+    /// Encode `cents` in the 2 byte format, rounding to the nearest step and saturating at the
+    /// ends of the range.
+    pub fn from_cents_two_byte(cents: [Cents; 12]) -> ScaleOctaveOffsets {
+        let mut offsets = [U14::from_u16_lossy(0x2000); 12];
+        for (offset, cents) in offsets.iter_mut().zip(cents.iter()) {
+            if !cents.0.is_nan() {
+                let steps = (cents.0 * 8192.0 / 100.0).clamp(-8192.0, 8191.0);
+                *offset = U14::from_u16_lossy((steps + 8192.5) as u16);
+            }
+        }
+        ScaleOctaveOffsets::TwoByte(offsets)
+    }
+
+    /// The detune of each pitch class, starting from C.
+    pub fn cents(&self) -> [Cents; 12] {
+        let mut cents = [Cents::ZERO; 12];
+        for (i, cents) in cents.iter_mut().enumerate() {
+            *cents = self.cents_at(i);
+        }
+        cents
+    }
+
+    fn cents_at(&self, pitch_class: usize) -> Cents {
+        match self {
+            ScaleOctaveOffsets::OneByte(offsets) => {
+                Cents(f32::from(u8::from(offsets[pitch_class])) - 64.0)
+            }
+            ScaleOctaveOffsets::TwoByte(offsets) => {
+                Cents((f32::from(u16::from(offsets[pitch_class])) - 8192.0) * 100.0 / 8192.0)
+            }
+        }
+    }
+}
+
+impl Temperament for ScaleOctaveOffsets {
+    fn offset(&self, note: Note) -> Cents {
+        self.cents_at(usize::from(u8::from(note) % 12))
+    }
+}
+
+/// [MTS] Detunes each pitch class on a set of channels. Real Time messages apply to sounding
+/// notes immediately, while Non-Real Time messages apply to the next notes played.
+///
+/// # Example
+/// ```
+/// use wmidi::{ChannelSet, Note, ScaleOctaveOffsets, ScaleOctaveTuning, TuningTable, U7};
+/// use wmidi::UniversalKind;
+/// let pythagorean = TuningTable::pythagorean(Note::C4);
+/// let tuning = ScaleOctaveTuning {
+///     kind: UniversalKind::RealTime,
+///     channels: ChannelSet::ALL,
+///     offsets: ScaleOctaveOffsets::from_cents_one_byte(*pythagorean.offsets()),
+/// };
+/// let mut bytes = [0u8; 32];
+/// tuning.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(&bytes[..10], &[0xF0, 0x7F, 0x7F, 0x08, 0x08, 0x03, 0x7F, 0x7F, 0x40, 0x36]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ScaleOctaveTuning {
+    /// Whether the offsets apply to sounding notes.
+    pub kind: UniversalKind,
+    /// The channels to retune.
+    pub channels: ChannelSet,
+    /// The detune of each pitch class.
+    pub offsets: ScaleOctaveOffsets,
+}
+
+impl ScaleOctaveTuning {
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        match self.offsets {
+            ScaleOctaveOffsets::OneByte(_) => 21,
+            ScaleOctaveOffsets::TwoByte(_) => 33,
+        }
+    }
+
+    /// Write the Universal SysEx message to `slice`, including the start and end bytes. Returns
+    /// the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let id = match self.kind {
+            UniversalKind::NonRealTime => 0x7E,
+            UniversalKind::RealTime => 0x7F,
+        };
+        let sub_id2 = match self.offsets {
+            ScaleOctaveOffsets::OneByte(_) => 0x08,
+            ScaleOctaveOffsets::TwoByte(_) => 0x09,
+        };
+        let channels = self.channels.bits();
+        slice[..8].copy_from_slice(&[
+            0xF0,
+            id,
+            u8::from(device_id),
+            0x08,
+            sub_id2,
+            (channels >> 14) as u8,
+            (channels >> 7) as u8 & 0x7F,
+            channels as u8 & 0x7F,
+        ]);
+        match &self.offsets {
+            ScaleOctaveOffsets::OneByte(offsets) => {
+                slice[8..20].copy_from_slice(U7::data_to_bytes(offsets));
+            }
+            ScaleOctaveOffsets::TwoByte(offsets) => {
+                for (chunk, offset) in slice[8..32].chunks_exact_mut(2).zip(offsets.iter()) {
+                    let offset = u16::from(*offset);
+                    chunk.copy_from_slice(&[(offset >> 7) as u8, offset as u8 & 0x7F]);
+                }
+            }
+        }
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal SysEx message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a scale/octave tuning
+    /// message.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, ScaleOctaveTuning)> {
+        let (id, sub_id2, channels, data) = match U7::data_to_bytes(payload) {
+            [id, _, 0x08, sub_id2, ff, gg, hh, data @ ..] => {
+                let channels = u16::from(*ff) << 14 | u16::from(*gg) << 7 | u16::from(*hh);
+                (id, sub_id2, channels, data)
+            }
+            _ => return None,
+        };
+        let kind = match id {
+            0x7E => UniversalKind::NonRealTime,
+            0x7F => UniversalKind::RealTime,
+            _ => return None,
+        };
+        let offsets = match (sub_id2, data.len()) {
+            (0x08, 12) => {
+                let mut offsets = [U7::MIN; 12];
+                offsets.copy_from_slice(&payload[7..]);
+                ScaleOctaveOffsets::OneByte(offsets)
+            }
+            (0x09, 24) => {
+                let mut offsets = [U14::MIN; 12];
+                for (offset, chunk) in offsets.iter_mut().zip(data.chunks_exact(2)) {
+                    *offset = U14::from_u16_lossy(u16::from(chunk[0]) << 7 | u16::from(chunk[1]));
+                }
+                ScaleOctaveOffsets::TwoByte(offsets)
+            }
+            _ => return None,
+        };
+        let tuning = ScaleOctaveTuning {
+            kind,
+            channels: ChannelSet::from_bits(channels),
+            offsets,
+        };
+        Some((payload[1], tuning))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn data_round_trip() {
+        let freq = MtsFrequency::new(Note::C4, U14::try_from(0x2ABC).unwrap());
+        let data = freq.to_data();
+        assert_eq!(U7::data_to_bytes(&data), &[60, 0x55, 0x3C]);
+        assert_eq!(MtsFrequency::from_data(data), Some(freq));
+        assert_eq!(MtsFrequency::from_data(MtsFrequency::NO_CHANGE), None);
+    }
+
+    #[test]
+    fn no_change_is_never_encoded() {
+        let freq = MtsFrequency::new(Note::G9, U14::MAX);
+        assert_ne!(freq.to_data(), MtsFrequency::NO_CHANGE);
+    }
+
+    #[test]
+    fn pitch_round_trip() {
+        let freq = MtsFrequency::new(Note::A4, U14::try_from(4096).unwrap());
+        assert_eq!(freq.to_pitch(), UFix32(69 << 16 | 0x4000));
+        assert_eq!(MtsFrequency::from_pitch(freq.to_pitch()), Ok(freq));
+        assert_eq!(
+            MtsFrequency::from_pitch(UFix32::from_int(128)),
+            Err(Error::NoteOutOfRange)
+        );
+        assert_eq!(freq.cents_above_note(), Cents(25.0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn freq_round_trip() {
+        for note in Note::all() {
+            let freq = MtsFrequency::from(note);
+            let hz = freq.to_freq_f64();
+            assert!((hz - note.to_freq_f64()).abs() < 1E-6);
+            assert_eq!(MtsFrequency::from_freq_f64(hz), Ok(freq));
+        }
+        assert_eq!(MtsFrequency::from_freq_f64(8.0), Err(Error::NoteOutOfRange));
+        assert_eq!(
+            MtsFrequency::from_freq_f64(14000.0),
+            Err(Error::NoteOutOfRange)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn bulk_dump_round_trip() {
+        let mut dump = BulkTuningDump::new(Some(U7(2)), U7(3), "Just C");
+        dump.frequencies[0] = None;
+        dump.frequencies[127] = Some(MtsFrequency::new(Note::G9, U14::MAX));
+        let mut bytes = dump.to_sysex_vec(U7(0x10));
+        assert_eq!(bytes.len(), BulkTuningDump::SYSEX_BYTES_SIZE + 1);
+        assert_eq!(&bytes[..7], &[0xF0, 0x7E, 0x10, 0x08, 0x04, 0x02, 0x03]);
+        let end = bytes.len() - 1;
+        let payload = U7::try_from_bytes(&bytes[1..end]).unwrap();
+        assert_eq!(BulkTuningDump::from_sysex(payload), Some((U7(0x10), dump)));
+
+        bytes[30] ^= 0x01;
+        let payload = U7::try_from_bytes(&bytes[1..end]).unwrap();
+        assert_eq!(BulkTuningDump::from_sysex(payload), None);
+
+        for request in [
+            BulkTuningRequest {
+                bank: None,
+                program: U7(1),
+            },
+            BulkTuningRequest {
+                bank: Some(U7(4)),
+                program: U7(1),
+            },
+        ]
+        .iter()
+        {
+            let bytes = request.to_sysex_vec(U7(0x10));
+            let payload = U7::try_from_bytes(&bytes[1..bytes.len() - 1]).unwrap();
+            assert_eq!(
+                BulkTuningRequest::from_sysex(payload),
+                Some((U7(0x10), *request))
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn single_note_round_trip() {
+        let tuning = SingleNoteTuning {
+            kind: UniversalKind::NonRealTime,
+            bank: Some(U7(1)),
+            program: U7(2),
+        };
+        let changes = [
+            NoteTuning {
+                note: Note::A4,
+                frequency: Some(MtsFrequency::new(Note::A4, U14::from_u16_lossy(100))),
+            },
+            NoteTuning {
+                note: Note::B4,
+                frequency: None,
+            },
+        ];
+        let bytes = tuning.to_sysex_vec(U7::MAX, &changes);
+        assert_eq!(bytes.len(), tuning.bytes_size(2));
+        assert_eq!(bytes[1], 0x7E);
+        let payload = U7::try_from_bytes(&bytes[1..bytes.len() - 1]).unwrap();
+        let (device_id, decoded, notes) = SingleNoteTuning::from_sysex(payload).unwrap();
+        assert_eq!((device_id, decoded), (U7::MAX, tuning));
+        assert!(notes.eq(changes.iter().copied()));
+
+        let payload = U7::try_from_bytes(&[0x7E, 0x7F, 0x08, 0x02, 0x00, 0x00]).unwrap();
+        assert!(SingleNoteTuning::from_sysex(payload).is_none());
+        let payload = U7::try_from_bytes(&[0x7F, 0x7F, 0x08, 0x02, 0x00, 0x01]).unwrap();
+        assert!(SingleNoteTuning::from_sysex(payload).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn scale_octave_round_trip() {
+        let cents = [
+            Cents(0.0),
+            Cents(-100.0),
+            Cents(99.99),
+            Cents(12.5),
+            Cents(-64.4),
+            Cents(63.0),
+            Cents(f32::NAN),
+            Cents(1.0),
+            Cents(-1.0),
+            Cents(200.0),
+            Cents(-200.0),
+            Cents(0.4),
+        ];
+        let one_byte = ScaleOctaveOffsets::from_cents_one_byte(cents);
+        assert_eq!(one_byte.cents()[3], Cents(13.0));
+        assert_eq!(one_byte.cents()[4], Cents(-64.0));
+        assert_eq!(one_byte.offset(Note::FSharp2), Cents::ZERO);
+        let two_byte = ScaleOctaveOffsets::from_cents_two_byte(cents);
+        assert_eq!(two_byte.cents()[1], Cents(-100.0));
+        assert!((two_byte.cents()[2].0 - 99.99).abs() < 0.01);
+        assert_eq!(two_byte.offset(Note::DSharp4), Cents(12.5));
+        for (kind, offsets) in [
+            (UniversalKind::RealTime, one_byte),
+            (UniversalKind::NonRealTime, two_byte),
+        ]
+        .iter()
+        {
+            let tuning = ScaleOctaveTuning {
+                kind: *kind,
+                channels: ChannelSet::from_bits(0x8421),
+                offsets: *offsets,
+            };
+            let bytes = tuning.to_sysex_vec(U7(0x10));
+            assert_eq!(bytes.len(), tuning.bytes_size());
+            assert_eq!(&bytes[5..8], &[0x02, 0x08, 0x21]);
+            let payload = U7::try_from_bytes(&bytes[1..bytes.len() - 1]).unwrap();
+            assert_eq!(
+                ScaleOctaveTuning::from_sysex(payload),
+                Some((U7(0x10), tuning))
+            );
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::{TimeSignature, ToSliceError, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MIDI 1.0] The bar reported by a bar marker message.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum BarMarker {
+    /// The sequence is stopped.
+    NotRunning,
+    /// The bar that is starting, from -8191 to 8190. Bars 0 and below are count-in bars, with 0
+    /// the last bar before the song starts.
+    Bar(i16),
+    /// The sequence is running, but the bar number is not known.
+    Running,
+}
+
+impl BarMarker {
+    fn to_value(self) -> u16 {
+        let value = match self {
+            BarMarker::NotRunning => -8192,
+            BarMarker::Bar(bar) => bar.clamp(-8191, 8190),
+            BarMarker::Running => 8191,
+        };
+        value as u16 & 0x3FFF
+    }
+
+    fn from_value(value: u16) -> BarMarker {
+        // Sign extend the 14 bit value.
+        match ((value << 2) as i16) >> 2 {
+            -8192 => BarMarker::NotRunning,
+            8191 => BarMarker::Running,
+            bar => BarMarker::Bar(bar),
+        }
+    }
+}
+
+/// [MIDI 1.0] A Notation Information Universal Real Time SysEx message, used by accompaniment
+/// and score following software to track the bar and time signature.
+///
+/// Only the first time signature of a compound time signature is supported; additional time
+/// signatures are ignored when parsing.
+///
+/// # Example
+/// ```
+/// use wmidi::{BarMarker, Notation, TimeSignature, U7};
+/// let mut bytes = [0u8; 8];
+/// Notation::BarMarker(BarMarker::Bar(-1))
+///     .copy_sysex_to_slice(U7::MAX, &mut bytes)
+///     .unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7F, 0x7F, 0x03, 0x01, 0x7F, 0x7F, 0xF7]);
+///
+/// let six_eight = Notation::TimeSignature {
+///     signature: TimeSignature::new(6, 8).unwrap(),
+///     thirty_seconds_per_quarter: U7::from_u8_lossy(8),
+///     delayed: true,
+/// };
+/// let mut bytes = [0u8; 10];
+/// six_eight.copy_sysex_to_slice(U7::MAX, &mut bytes).unwrap();
+/// assert_eq!(
+///     bytes,
+///     [0xF0, 0x7F, 0x7F, 0x03, 0x42, 0x03, 0x06, 0x03, 0x08, 0xF7]
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Notation {
+    /// Marks the start of a bar.
+    BarMarker(BarMarker),
+    /// Changes the time signature.
+    TimeSignature {
+        /// The new time signature.
+        signature: TimeSignature,
+        /// The number of notated 32nd notes in a MIDI quarter note of 24 clocks, normally 8.
+        thirty_seconds_per_quarter: U7,
+        /// If `true`, the change takes effect at the next bar marker. Otherwise it takes effect
+        /// immediately.
+        delayed: bool,
+    },
+}
+
+impl Notation {
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(self) -> usize {
+        match self {
+            Notation::BarMarker(_) => 8,
+            Notation::TimeSignature { .. } => 10,
+        }
+    }
+
+    /// Write the Universal Real Time SysEx message to `slice`, including the start and end
+    /// bytes. A `device_id` of 0x7F addresses all devices. Returns the number of bytes written.
+    ///
+    /// Returns `ToSliceError::InvalidMessage` for a time signature with more than 127 beats,
+    /// which does not fit in a data byte.
+    pub fn copy_sysex_to_slice(
+        self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        if let Notation::TimeSignature { signature, .. } = self {
+            if signature.numerator() > 0x7F {
+                return Err(ToSliceError::InvalidMessage);
+            }
+        }
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..4].copy_from_slice(&[0xF0, 0x7F, u8::from(device_id), 0x03]);
+        match self {
+            Notation::BarMarker(marker) => {
+                let value = marker.to_value();
+                slice[4..7].copy_from_slice(&[0x01, value as u8 & 0x7F, (value >> 7) as u8]);
+            }
+            Notation::TimeSignature {
+                signature,
+                thirty_seconds_per_quarter,
+                delayed,
+            } => {
+                slice[4..9].copy_from_slice(&[
+                    if delayed { 0x42 } else { 0x02 },
+                    0x03,
+                    signature.numerator(),
+                    signature.denominator().trailing_zeros() as u8,
+                    u8::from(thirty_seconds_per_quarter),
+                ]);
+            }
+        }
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal Real Time SysEx message, including the start and end bytes. A `device_id`
+    /// of 0x7F addresses all devices.
+    ///
+    /// # Panics
+    /// Panics if a time signature has more than 127 beats.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a Notation
+    /// Information message or holds an invalid time signature.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, Notation)> {
+        let (device_id, notation) = match U7::data_to_bytes(payload) {
+            [0x7F, device_id, 0x03, 0x01, lsb, msb] => {
+                let value = u16::from(*msb) << 7 | u16::from(*lsb);
+                (device_id, Notation::BarMarker(BarMarker::from_value(value)))
+            }
+            [0x7F, device_id, 0x03, sub_id2 @ (0x02 | 0x42), len, data @ ..]
+                if usize::from(*len) == data.len() && data.len() >= 3 && data.len() % 2 == 1 =>
+            {
+                let denominator = 1u8.checked_shl(u32::from(data[1]))?;
+                let notation = Notation::TimeSignature {
+                    signature: TimeSignature::new(data[0], denominator).ok()?,
+                    thirty_seconds_per_quarter: U7(data[2]),
+                    delayed: *sub_id2 == 0x42,
+                };
+                (device_id, notation)
+            }
+            _ => return None,
+        };
+        Some((U7(*device_id), notation))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let messages = [
+            Notation::BarMarker(BarMarker::NotRunning),
+            Notation::BarMarker(BarMarker::Bar(-8191)),
+            Notation::BarMarker(BarMarker::Bar(0)),
+            Notation::BarMarker(BarMarker::Bar(8190)),
+            Notation::BarMarker(BarMarker::Running),
+            Notation::TimeSignature {
+                signature: TimeSignature::COMMON_TIME,
+                thirty_seconds_per_quarter: U7(8),
+                delayed: false,
+            },
+            Notation::TimeSignature {
+                signature: TimeSignature::new(7, 128).unwrap(),
+                thirty_seconds_per_quarter: U7(12),
+                delayed: true,
+            },
+        ];
+        for message in messages.iter() {
+            let mut bytes = [0u8; 10];
+            let size = message.copy_sysex_to_slice(U7(0x01), &mut bytes).unwrap();
+            assert_eq!(size, message.bytes_size());
+            let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(Notation::from_sysex(payload), Some((U7(0x01), *message)));
+        }
+        let too_many_beats = Notation::TimeSignature {
+            signature: TimeSignature::new(200, 4).unwrap(),
+            thirty_seconds_per_quarter: U7(8),
+            delayed: false,
+        };
+        assert_eq!(
+            too_many_beats.copy_sysex_to_slice(U7(0x01), &mut [0u8; 10]),
+            Err(ToSliceError::InvalidMessage)
+        );
+    }
+
+    #[test]
+    fn bar_marker_values() {
+        let mut bytes = [0u8; 8];
+        Notation::BarMarker(BarMarker::NotRunning)
+            .copy_sysex_to_slice(U7::MAX, &mut bytes)
+            .unwrap();
+        assert_eq!(&bytes[5..7], &[0x00, 0x40]);
+        Notation::BarMarker(BarMarker::Running)
+            .copy_sysex_to_slice(U7::MAX, &mut bytes)
+            .unwrap();
+        assert_eq!(&bytes[5..7], &[0x7F, 0x3F]);
+        Notation::BarMarker(BarMarker::Bar(1))
+            .copy_sysex_to_slice(U7::MAX, &mut bytes)
+            .unwrap();
+        assert_eq!(&bytes[5..7], &[0x01, 0x00]);
+    }
+
+    #[test]
+    fn compound_time_signature() {
+        let payload =
+            U7::try_from_bytes(&[0x7F, 0x7F, 0x03, 0x02, 0x05, 0x03, 0x03, 0x08, 0x02, 0x02])
+                .unwrap();
+        assert_eq!(
+            Notation::from_sysex(payload),
+            Some((
+                U7::MAX,
+                Notation::TimeSignature {
+                    signature: TimeSignature::new(3, 8).unwrap(),
+                    thirty_seconds_per_quarter: U7(8),
+                    delayed: false,
+                }
+            ))
+        );
+        let payload =
+            U7::try_from_bytes(&[0x7F, 0x7F, 0x03, 0x02, 0x03, 0x04, 0x08, 0x08]).unwrap();
+        assert_eq!(Notation::from_sysex(payload), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::{Accidental, Error, Key, Letter, SpelledNote, UFix32};
 use core::convert::TryFrom;
 use core::fmt;
 
@@ -234,6 +234,34 @@ impl Note {
         Note::from(crate::U7::from_u8_lossy(note))
     }
 
+    /// Iterate over all 128 notes in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// assert_eq!(wmidi::Note::all().count(), 128);
+    /// ```
+    #[inline(always)]
+    pub fn all() -> NoteRange {
+        Note::range(Note::LOWEST_NOTE, Note::HIGHEST_NOTE)
+    }
+
+    /// Iterate over the notes between `lo` and `hi` inclusive in ascending order. The range is
+    /// empty if `lo` is greater than `hi`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::Note;
+    /// let notes: Vec<Note> = Note::range(Note::C4, Note::E4).collect();
+    /// assert_eq!(notes, [Note::C4, Note::Db4, Note::D4, Note::Eb4, Note::E4]);
+    /// ```
+    #[inline(always)]
+    pub fn range(lo: Note, hi: Note) -> NoteRange {
+        NoteRange {
+            next: lo as u8,
+            end: (hi as u8 + 1).max(lo as u8),
+        }
+    }
+
     /// The frequency using the standard 440Hz tuning.
     ///
     /// # Example
@@ -264,6 +292,68 @@ impl Note {
         2_f64.powf(exp)
     }
 
+    /// The frequency using `temperament`, relative to the standard 440Hz tuning.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Note, TuningTable};
+    /// let just = TuningTable::just_intonation(Note::A4);
+    /// assert!((Note::E5.to_freq_f32_with(&just) - 660.0).abs() < 0.01);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn to_freq_f32_with<T: crate::Temperament + ?Sized>(self, temperament: &T) -> f32 {
+        self.to_freq_f32() * temperament.offset(self).to_ratio()
+    }
+
+    /// The frequency using `temperament`, relative to the standard 440Hz tuning.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn to_freq_f64_with<T: crate::Temperament + ?Sized>(self, temperament: &T) -> f64 {
+        self.to_freq_f64() * f64::from(temperament.offset(self).to_ratio())
+    }
+
+    /// The note for `freq` Hz using the standard 440Hz tuning, quantized with `rounding`. An
+    /// error is returned if the rounded note is out of range, or if `freq` is not a positive
+    /// number.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Note, Rounding};
+    /// // About 30 cents above A2.
+    /// let freq = 112.0;
+    /// assert_eq!(Note::from_freq_with(freq, Rounding::Nearest), Ok(Note::A2));
+    /// assert_eq!(Note::from_freq_with(freq, Rounding::Floor), Ok(Note::A2));
+    /// assert_eq!(Note::from_freq_with(freq, Rounding::Ceil), Ok(Note::Bb2));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_freq_with(freq: f32, rounding: Rounding) -> Result<Note, Error> {
+        let pitch = 69.0 + 12.0 * (freq / 440.0).log2();
+        let rounded = match rounding {
+            Rounding::Nearest => pitch.round(),
+            Rounding::Floor => pitch.floor(),
+            Rounding::Ceil => pitch.ceil(),
+        };
+        if (Note::LOWEST_NOTE as u8 as f32..=Note::HIGHEST_NOTE as u8 as f32).contains(&rounded) {
+            Ok(unsafe { Note::from_u8_unchecked(rounded as u8) })
+        } else {
+            Err(Error::NoteOutOfRange)
+        }
+    }
+
+    /// The frequency in Hz using the standard 440Hz tuning, as a Q16.16 fixed point number.
+    /// Unlike `to_freq_f32`, this does not require floating point math or `std`.
+    ///
+    /// # Example
+    /// ```
+    /// let freq = wmidi::Note::A3.to_freq_fixed();
+    /// assert_eq!(freq.integer(), 220);
+    /// ```
+    #[inline(always)]
+    pub fn to_freq_fixed(self) -> UFix32 {
+        crate::pitch_to_freq_fixed(UFix32::from_int(u16::from(self as u8)))
+    }
+
     /// Get the note relative to `self`.
     ///
     /// # Example
@@ -284,6 +374,71 @@ impl Note {
         }
     }
 
+    /// Get the note `octaves` octaves above `self`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Error, Note};
+    /// assert_eq!(Note::C4.up_octave(2), Ok(Note::C6));
+    /// assert_eq!(Note::C4.up_octave(6), Err(Error::NoteOutOfRange));
+    /// ```
+    pub fn up_octave(self, octaves: u8) -> Result<Note, Error> {
+        self.step_octaves(i16::from(octaves))
+    }
+
+    /// Get the note `octaves` octaves below `self`.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Error, Note};
+    /// assert_eq!(Note::C4.down_octave(5), Ok(Note::CMinus1));
+    /// assert_eq!(Note::C4.down_octave(6), Err(Error::NoteOutOfRange));
+    /// ```
+    pub fn down_octave(self, octaves: u8) -> Result<Note, Error> {
+        self.step_octaves(-i16::from(octaves))
+    }
+
+    fn step_octaves(self, octaves: i16) -> Result<Note, Error> {
+        let raw_note = self as i16 + 12 * octaves;
+        if Note::LOWEST_NOTE as i16 <= raw_note && raw_note <= Note::HIGHEST_NOTE as i16 {
+            Ok(unsafe { Note::from_u8_unchecked(raw_note as u8) })
+        } else {
+            Err(Error::NoteOutOfRange)
+        }
+    }
+
+    /// Get the enharmonic spelling of the note that is most appropriate for `key`.
+    ///
+    /// Notes in the key are spelled as they appear in the scale. Notes outside of the key use
+    /// the spelling closest to the key signature, so sharp keys favor sharps and flat keys favor
+    /// flats.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Accidental, Key, Letter, Note};
+    /// let f_major = Key::major(Letter::F, Accidental::Natural);
+    /// let b_major = Key::major(Letter::B, Accidental::Natural);
+    /// assert_eq!(Note::Bb3.spelled_in(&f_major).to_string(), "Bb3");
+    /// assert_eq!(Note::Bb3.spelled_in(&b_major).to_string(), "A#3");
+    /// ```
+    pub fn spelled_in(self, key: &Key) -> SpelledNote {
+        // Fifths are 7 half steps and 7 * 7 = 1 (mod 12), so multiplying by 7 maps a pitch class
+        // to its position on the line of fifths.
+        let pitch_class = self as i8 % 12;
+        // Clamp the window so that theoretical keys never need more than double accidentals.
+        let start = key.spelling_window_start().clamp(-15, 8);
+        let fifths = start + (pitch_class * 7 - start).rem_euclid(12);
+        let letter = Letter::from_fifths(fifths);
+        let alteration = (fifths + 1).div_euclid(7);
+        let octave = (self as i8 - letter.pitch_class() as i8 - alteration) / 12 - 1;
+        SpelledNote {
+            letter,
+            // The clamped window keeps the alteration between a double flat and a double sharp.
+            accidental: Accidental::from_semitones(alteration).unwrap_or(Accidental::Natural),
+            octave,
+        }
+    }
+
     /// Get a `str` representation of the note. For example: `"C3"` or `"A#/Bb2"`.
     pub fn to_str(self) -> &'static str {
         match self {
@@ -419,6 +574,17 @@ impl Note {
     }
 }
 
+/// How to quantize a frequency that falls between two notes.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round to the closest note.
+    Nearest,
+    /// Round down to the closest note at or below the frequency.
+    Floor,
+    /// Round up to the closest note at or above the frequency.
+    Ceil,
+}
+
 /// Convert from a `u8` to a `Note`. The `u8` must be in the range [0, 127] inclusive.
 impl TryFrom<u8> for Note {
     type Error = Error;
@@ -466,6 +632,49 @@ impl From<Note> for u8 {
     }
 }
 
+/// An iterator over consecutive notes. Created with `Note::all` or `Note::range`.
+#[derive(Clone, Debug)]
+pub struct NoteRange {
+    next: u8,
+    // Exclusive, may be 128.
+    end: u8,
+}
+
+impl Iterator for NoteRange {
+    type Item = Note;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Note> {
+        if self.next < self.end {
+            let note = unsafe { Note::from_u8_unchecked(self.next) };
+            self.next += 1;
+            Some(note)
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.end - self.next);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for NoteRange {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Note> {
+        if self.next < self.end {
+            self.end -= 1;
+            Some(unsafe { Note::from_u8_unchecked(self.end) })
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for NoteRange {}
+
 impl fmt::Debug for Note {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}({})", self.to_str(), *self as u8)
@@ -492,6 +701,48 @@ mod test {
         assert!((a440_f32 - 440.0).abs() < 1E-10, "{} != 440", a440_f32);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn note_to_fixed_frequency() {
+        for note in Note::all() {
+            let expected = note.to_freq_f64();
+            let actual = note.to_freq_fixed().to_f64();
+            assert!(
+                (actual - expected).abs() < 1E-4,
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn frequency_to_note() {
+        for note in Note::all() {
+            let freq = note.to_freq_f32();
+            assert_eq!(Note::from_freq_with(freq, Rounding::Nearest), Ok(note));
+            assert_eq!(Note::from_freq_with(freq * 1.01, Rounding::Floor), Ok(note));
+            assert_eq!(Note::from_freq_with(freq * 0.99, Rounding::Ceil), Ok(note));
+        }
+        assert_eq!(
+            Note::from_freq_with(8.0, Rounding::Floor),
+            Err(Error::NoteOutOfRange)
+        );
+        assert_eq!(
+            Note::from_freq_with(8.0, Rounding::Nearest),
+            Ok(Note::CMinus1)
+        );
+        assert_eq!(
+            Note::from_freq_with(0.0, Rounding::Nearest),
+            Err(Error::NoteOutOfRange)
+        );
+        assert_eq!(
+            Note::from_freq_with(f32::NAN, Rounding::Nearest),
+            Err(Error::NoteOutOfRange)
+        );
+    }
+
     #[test]
     fn step() {
         assert_eq!(Note::CMinus1.step(12), Ok(Note::C0));
@@ -501,6 +752,101 @@ mod test {
         assert_eq!(Note::B3.step(-100), Err(Error::NoteOutOfRange));
     }
 
+    #[test]
+    fn octaves() {
+        assert_eq!(Note::G9.up_octave(0), Ok(Note::G9));
+        assert_eq!(Note::G8.up_octave(1), Ok(Note::G9));
+        assert_eq!(Note::Ab8.up_octave(1), Err(Error::NoteOutOfRange));
+        assert_eq!(Note::B0.down_octave(1), Ok(Note::BMinus1));
+        assert_eq!(Note::B0.down_octave(2), Err(Error::NoteOutOfRange));
+        assert_eq!(Note::C4.up_octave(u8::MAX), Err(Error::NoteOutOfRange));
+        assert_eq!(Note::C4.down_octave(u8::MAX), Err(Error::NoteOutOfRange));
+    }
+
+    #[test]
+    fn range() {
+        assert!(Note::all().map(u8::from).eq(0..128));
+        assert!(Note::all().rev().map(u8::from).eq((0..128).rev()));
+        assert_eq!(Note::range(Note::C4, Note::B3).count(), 0);
+        assert_eq!(Note::range(Note::G9, Note::G9).len(), 1);
+        let mut notes = Note::range(Note::C4, Note::D4);
+        assert_eq!(notes.next_back(), Some(Note::D4));
+        assert_eq!(notes.next(), Some(Note::C4));
+        assert_eq!(notes.next(), Some(Note::Db4));
+        assert_eq!(notes.next_back(), None);
+    }
+
+    #[test]
+    fn spelled_in() {
+        let c_major = Key::major(Letter::C, Accidental::Natural);
+        let f_major = Key::major(Letter::F, Accidental::Natural);
+        let b_major = Key::major(Letter::B, Accidental::Natural);
+        let d_minor = Key::minor(Letter::D, Accidental::Natural);
+        let spelled = |note: Note, key: &Key| {
+            let s = note.spelled_in(key);
+            (s.letter, s.accidental, s.octave)
+        };
+        assert_eq!(
+            spelled(Note::Bb3, &f_major),
+            (Letter::B, Accidental::Flat, 3)
+        );
+        assert_eq!(
+            spelled(Note::Bb3, &b_major),
+            (Letter::A, Accidental::Sharp, 3)
+        );
+        assert_eq!(
+            spelled(Note::Db4, &d_minor),
+            (Letter::C, Accidental::Sharp, 4)
+        );
+        assert_eq!(
+            spelled(Note::Gb4, &c_major),
+            (Letter::F, Accidental::Sharp, 4)
+        );
+        assert_eq!(
+            spelled(Note::CMinus1, &c_major),
+            (Letter::C, Accidental::Natural, -1)
+        );
+    }
+
+    #[test]
+    fn spelled_in_crosses_octave_boundaries() {
+        let c_sharp_major = Key::major(Letter::C, Accidental::Sharp);
+        let c_flat_major = Key::major(Letter::C, Accidental::Flat);
+        let b_sharp = Note::C4.spelled_in(&c_sharp_major);
+        assert_eq!((b_sharp.letter, b_sharp.octave), (Letter::B, 3));
+        assert_eq!(b_sharp.accidental, Accidental::Sharp);
+        let c_flat = Note::B3.spelled_in(&c_flat_major);
+        assert_eq!((c_flat.letter, c_flat.octave), (Letter::C, 4));
+        assert_eq!(c_flat.accidental, Accidental::Flat);
+    }
+
+    #[test]
+    fn spelled_in_theoretical_key_uses_at_most_double_accidentals() {
+        let g_sharp_major = Key::major(Letter::G, Accidental::Sharp);
+        let f_flat_major = Key::major(Letter::F, Accidental::Flat);
+        let f_double_sharp_major = Key::major(Letter::F, Accidental::DoubleSharp);
+        let spelled = |note: Note, key: &Key| {
+            let s = note.spelled_in(key);
+            (s.letter, s.accidental, s.octave)
+        };
+        assert_eq!(
+            spelled(Note::G4, &g_sharp_major),
+            (Letter::F, Accidental::DoubleSharp, 4)
+        );
+        assert_eq!(
+            spelled(Note::A3, &f_flat_major),
+            (Letter::B, Accidental::DoubleFlat, 3)
+        );
+        assert_eq!(
+            spelled(Note::G4, &f_double_sharp_major),
+            (Letter::F, Accidental::DoubleSharp, 4)
+        );
+        assert_eq!(
+            spelled(Note::Ab4, &f_double_sharp_major),
+            (Letter::G, Accidental::Sharp, 4)
+        );
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_debug() {
@@ -0,0 +1,196 @@
+use crate::{Channel, ChannelMap, ControlFunction, MidiMessage, Note, Velocity};
+
+/// Tracks the notes that are currently sounding on each channel.
+///
+/// A `NoteOn` with a velocity of 0 is treated as a `NoteOff`. All Sound Off, All Notes Off, and
+/// the Omni/Mono/Poly mode messages release every note on their channel, and `Reset` releases
+/// every note.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, Note, NoteTracker, U7};
+/// let mut tracker = NoteTracker::new();
+/// tracker.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX));
+/// tracker.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::E4, U7::MAX));
+/// tracker.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MIN));
+/// assert_eq!(tracker.notes(Channel::Ch1).collect::<Vec<_>>(), [Note::E4]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NoteTracker {
+    notes: ChannelMap<u128>,
+}
+
+impl NoteTracker {
+    /// Create a tracker with no sounding notes.
+    pub fn new() -> NoteTracker {
+        NoteTracker::default()
+    }
+
+    /// Update the sounding notes from `message`.
+    pub fn handle(&mut self, message: &MidiMessage) {
+        match message {
+            MidiMessage::NoteOn(c, n, v) if *v != Velocity::MIN => {
+                self.notes[*c] |= 1 << u8::from(*n);
+            }
+            MidiMessage::NoteOn(c, n, _) | MidiMessage::NoteOff(c, n, _) => {
+                self.notes[*c] &= !(1 << u8::from(*n));
+            }
+            MidiMessage::ControlChange(c, f, _)
+                if *f == ControlFunction::ALL_SOUND_OFF
+                    || u8::from(*f) >= u8::from(ControlFunction::ALL_NOTES_OFF) =>
+            {
+                self.notes[*c] = 0;
+            }
+            MidiMessage::Reset => self.clear(),
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if `note` is sounding on `channel`.
+    pub fn is_active(&self, channel: Channel, note: Note) -> bool {
+        self.notes[channel] & (1 << u8::from(note)) != 0
+    }
+
+    /// The number of notes sounding on `channel`.
+    pub fn active_count(&self, channel: Channel) -> usize {
+        self.notes[channel].count_ones() as usize
+    }
+
+    /// Returns `true` if no notes are sounding on any channel.
+    pub fn is_empty(&self) -> bool {
+        self.notes.values().iter().all(|n| *n == 0)
+    }
+
+    /// Iterate over the notes sounding on `channel` in ascending order.
+    pub fn notes(&self, channel: Channel) -> ActiveNotes {
+        ActiveNotes {
+            bits: self.notes[channel],
+        }
+    }
+
+    /// Iterate over every sounding note and its channel, ordered by channel and then note.
+    pub fn iter(&self) -> impl Iterator<Item = (Channel, Note)> + '_ {
+        self.notes
+            .iter()
+            .flat_map(|(c, bits)| ActiveNotes { bits: *bits }.map(move |n| (c, n)))
+    }
+
+    /// Forget all sounding notes without emitting any messages.
+    pub fn clear(&mut self) {
+        self.notes = ChannelMap::default();
+    }
+
+    /// Emit a `NoteOff` for every sounding note and forget them. This is useful for shutting
+    /// down a connection without leaving notes stuck on the receiver.
+    pub fn release_all<F: FnMut(MidiMessage<'static>)>(&mut self, mut emit: F) {
+        for (channel, note) in self.iter() {
+            emit(MidiMessage::NoteOff(channel, note, Velocity::MIN));
+        }
+        self.clear();
+    }
+}
+
+/// An iterator over the notes sounding on a channel. Created with `NoteTracker::notes`.
+#[derive(Clone, Debug)]
+pub struct ActiveNotes {
+    bits: u128,
+}
+
+impl Iterator for ActiveNotes {
+    type Item = Note;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Note> {
+        if self.bits == 0 {
+            return None;
+        }
+        let note = self.bits.trailing_zeros() as u8;
+        self.bits &= self.bits - 1;
+        Some(unsafe { Note::from_u8_unchecked(note) })
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ActiveNotes {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U7;
+
+    #[test]
+    fn note_on_and_off() {
+        let mut tracker = NoteTracker::new();
+        assert!(tracker.is_empty());
+        tracker.handle(&MidiMessage::NoteOn(Channel::Ch2, Note::G9, U7(1)));
+        tracker.handle(&MidiMessage::NoteOn(Channel::Ch2, Note::CMinus1, U7(1)));
+        tracker.handle(&MidiMessage::NoteOn(Channel::Ch3, Note::A4, U7(1)));
+        assert!(tracker.is_active(Channel::Ch2, Note::G9));
+        assert!(!tracker.is_active(Channel::Ch3, Note::G9));
+        assert_eq!(tracker.active_count(Channel::Ch2), 2);
+        assert!(tracker
+            .notes(Channel::Ch2)
+            .eq([Note::CMinus1, Note::G9].iter().copied()));
+        assert!(tracker.iter().eq([
+            (Channel::Ch2, Note::CMinus1),
+            (Channel::Ch2, Note::G9),
+            (Channel::Ch3, Note::A4)
+        ]
+        .iter()
+        .copied()));
+        tracker.handle(&MidiMessage::NoteOff(Channel::Ch2, Note::G9, U7(64)));
+        assert!(!tracker.is_active(Channel::Ch2, Note::G9));
+    }
+
+    #[test]
+    fn channel_mode_messages_release_notes() {
+        let mut tracker = NoteTracker::new();
+        for function in [
+            ControlFunction::ALL_SOUND_OFF,
+            ControlFunction::ALL_NOTES_OFF,
+            ControlFunction::POLY_OPERATION,
+        ]
+        .iter()
+        {
+            tracker.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(1)));
+            tracker.handle(&MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7(1)));
+            tracker.handle(&MidiMessage::ControlChange(Channel::Ch1, *function, U7(0)));
+            assert_eq!(tracker.active_count(Channel::Ch1), 0);
+            assert_eq!(tracker.active_count(Channel::Ch2), 1);
+        }
+        tracker.handle(&MidiMessage::ControlChange(
+            Channel::Ch2,
+            ControlFunction::RESET_ALL_CONTROLLERS,
+            U7(0),
+        ));
+        assert_eq!(tracker.active_count(Channel::Ch2), 1);
+        tracker.handle(&MidiMessage::Reset);
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn release_all() {
+        let mut tracker = NoteTracker::new();
+        tracker.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(1)));
+        tracker.handle(&MidiMessage::NoteOn(Channel::Ch16, Note::D4, U7(1)));
+        let mut released = [MidiMessage::Reset, MidiMessage::Reset];
+        let mut count = 0;
+        tracker.release_all(|m| {
+            released[count] = m;
+            count += 1;
+        });
+        assert_eq!(
+            released,
+            [
+                MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0)),
+                MidiMessage::NoteOff(Channel::Ch16, Note::D4, U7(0))
+            ]
+        );
+        assert!(tracker.is_empty());
+    }
+}
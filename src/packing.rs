@@ -0,0 +1,225 @@
+//! Encodings that carry 8 bit binary data in the 7 bit data bytes of SysEx messages.
+//!
+//! The 7-in-8 encoding sends each group of up to 7 bytes as a byte holding their most
+//! significant bits, followed by their lower 7 bits. It adds one byte for every 7, and is used
+//! by the File Dump Standard and many firmware updaters. The nibble encoding sends each byte as
+//! two bytes holding 4 bits each, which is simpler but doubles the size.
+//!
+//! # Example
+//! ```
+//! use wmidi::packing::{decode_7in8, encode_7in8, encoded_len_7in8, MsbOrder};
+//! use wmidi::U7;
+//! let firmware = [0xDE, 0xAD, 0xBE, 0xEF];
+//! let mut encoded = [U7::MIN; 5];
+//! assert_eq!(encoded_len_7in8(firmware.len()), 5);
+//! encode_7in8(&firmware, MsbOrder::FirstInBit6, &mut encoded).unwrap();
+//! assert_eq!(U7::data_to_bytes(&encoded), [0x78, 0x5E, 0x2D, 0x3E, 0x6F]);
+//!
+//! let mut decoded = [0u8; 4];
+//! decode_7in8(&encoded, MsbOrder::FirstInBit6, &mut decoded).unwrap();
+//! assert_eq!(decoded, firmware);
+//! ```
+
+use crate::{ToSliceError, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Where the 7-in-8 encoding stores the most significant bit of the first byte of a group.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MsbOrder {
+    /// Bit 6 holds the first byte and bit 0 the seventh, as in the File Dump Standard.
+    FirstInBit6,
+    /// Bit 0 holds the first byte and bit 6 the seventh, as in Korg and many firmware updaters.
+    FirstInBit0,
+}
+
+impl MsbOrder {
+    fn shift(self, index: usize) -> usize {
+        match self {
+            MsbOrder::FirstInBit6 => 6 - index,
+            MsbOrder::FirstInBit0 => index,
+        }
+    }
+}
+
+/// Which half of a byte the nibble encoding sends first.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum NibbleOrder {
+    /// The upper 4 bits are sent first.
+    HighFirst,
+    /// The lower 4 bits are sent first.
+    LowFirst,
+}
+
+/// The number of data bytes needed to 7-in-8 encode `len` bytes.
+pub fn encoded_len_7in8(len: usize) -> usize {
+    len + len.div_ceil(7)
+}
+
+/// The number of bytes held by `len` 7-in-8 encoded data bytes.
+pub fn decoded_len_7in8(len: usize) -> usize {
+    len - len.div_ceil(8)
+}
+
+/// Write the 7-in-8 encoding of `bytes` to `slice`. Returns the number of data bytes written.
+pub fn encode_7in8(bytes: &[u8], order: MsbOrder, slice: &mut [U7]) -> Result<usize, ToSliceError> {
+    let len = encoded_len_7in8(bytes.len());
+    if slice.len() < len {
+        return Err(ToSliceError::BufferTooSmall);
+    }
+    for (group, chunk) in bytes.chunks(7).zip(slice.chunks_mut(8)) {
+        let msbs = group
+            .iter()
+            .enumerate()
+            .fold(0, |msbs, (i, byte)| msbs | (byte >> 7) << order.shift(i));
+        chunk[0] = U7(msbs);
+        for (dst, byte) in chunk[1..].iter_mut().zip(group) {
+            *dst = U7(byte & 0x7F);
+        }
+    }
+    Ok(len)
+}
+
+/// Write the bytes held by the 7-in-8 encoded `data` to `slice`. Returns the number of bytes
+/// written.
+pub fn decode_7in8(data: &[U7], order: MsbOrder, slice: &mut [u8]) -> Result<usize, ToSliceError> {
+    let len = decoded_len_7in8(data.len());
+    if slice.len() < len {
+        return Err(ToSliceError::BufferTooSmall);
+    }
+    for (chunk, group) in data.chunks(8).zip(slice.chunks_mut(7)) {
+        let msbs = u8::from(chunk[0]);
+        for (i, (dst, byte)) in group.iter_mut().zip(&chunk[1..]).enumerate() {
+            *dst = u8::from(*byte) | (msbs >> order.shift(i) & 1) << 7;
+        }
+    }
+    Ok(len)
+}
+
+/// The 7-in-8 encoding of `bytes`.
+#[cfg(feature = "std")]
+pub fn encode_7in8_vec(bytes: &[u8], order: MsbOrder) -> Vec<U7> {
+    let mut data = vec![U7::MIN; encoded_len_7in8(bytes.len())];
+    encode_7in8(bytes, order, &mut data).unwrap();
+    data
+}
+
+/// The bytes held by the 7-in-8 encoded `data`.
+#[cfg(feature = "std")]
+pub fn decode_7in8_vec(data: &[U7], order: MsbOrder) -> Vec<u8> {
+    let mut bytes = vec![0; decoded_len_7in8(data.len())];
+    decode_7in8(data, order, &mut bytes).unwrap();
+    bytes
+}
+
+/// Write the nibble encoding of `bytes` to `slice`. Returns the number of data bytes written,
+/// which is twice the number of bytes.
+pub fn encode_nibbles(
+    bytes: &[u8],
+    order: NibbleOrder,
+    slice: &mut [U7],
+) -> Result<usize, ToSliceError> {
+    let len = bytes.len() * 2;
+    if slice.len() < len {
+        return Err(ToSliceError::BufferTooSmall);
+    }
+    for (pair, byte) in slice.chunks_exact_mut(2).zip(bytes) {
+        let (first, second) = match order {
+            NibbleOrder::HighFirst => (byte >> 4, byte & 0x0F),
+            NibbleOrder::LowFirst => (byte & 0x0F, byte >> 4),
+        };
+        pair.copy_from_slice(&[U7(first), U7(second)]);
+    }
+    Ok(len)
+}
+
+/// Write the bytes held by the nibble encoded `data` to `slice`. The upper 3 bits of each data
+/// byte are ignored, as is a final unpaired data byte. Returns the number of bytes written.
+pub fn decode_nibbles(
+    data: &[U7],
+    order: NibbleOrder,
+    slice: &mut [u8],
+) -> Result<usize, ToSliceError> {
+    let len = data.len() / 2;
+    if slice.len() < len {
+        return Err(ToSliceError::BufferTooSmall);
+    }
+    for (byte, pair) in slice.iter_mut().zip(data.chunks_exact(2)) {
+        let (first, second) = (u8::from(pair[0]) & 0x0F, u8::from(pair[1]) & 0x0F);
+        *byte = match order {
+            NibbleOrder::HighFirst => first << 4 | second,
+            NibbleOrder::LowFirst => second << 4 | first,
+        };
+    }
+    Ok(len)
+}
+
+/// The nibble encoding of `bytes`.
+#[cfg(feature = "std")]
+pub fn encode_nibbles_vec(bytes: &[u8], order: NibbleOrder) -> Vec<U7> {
+    let mut data = vec![U7::MIN; bytes.len() * 2];
+    encode_nibbles(bytes, order, &mut data).unwrap();
+    data
+}
+
+/// The bytes held by the nibble encoded `data`.
+#[cfg(feature = "std")]
+pub fn decode_nibbles_vec(data: &[U7], order: NibbleOrder) -> Vec<u8> {
+    let mut bytes = vec![0; data.len() / 2];
+    decode_nibbles(data, order, &mut bytes).unwrap();
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip_7in8() {
+        let bytes: [u8; 256] = core::array::from_fn(|i| i as u8);
+        for len in 0..20 {
+            for order in [MsbOrder::FirstInBit6, MsbOrder::FirstInBit0].iter() {
+                let input = &bytes[240 - len..240];
+                let mut encoded = [U7::MIN; 32];
+                let size = encode_7in8(input, *order, &mut encoded).unwrap();
+                assert_eq!(size, encoded_len_7in8(len));
+                let mut decoded = [0u8; 32];
+                let size = decode_7in8(&encoded[..size], *order, &mut decoded).unwrap();
+                assert_eq!(&decoded[..size], input);
+            }
+        }
+        let mut encoded = [U7::MIN; 2];
+        encode_7in8(&[0x80], MsbOrder::FirstInBit0, &mut encoded).unwrap();
+        assert_eq!(U7::data_to_bytes(&encoded), [0x01, 0x00]);
+        assert_eq!(
+            encode_7in8(&[0; 8], MsbOrder::FirstInBit6, &mut [U7::MIN; 9]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn round_trip_nibbles() {
+        let bytes = [0x00, 0x12, 0xAB, 0xFF];
+        let mut high_first = [U7::MIN; 8];
+        encode_nibbles(&bytes, NibbleOrder::HighFirst, &mut high_first).unwrap();
+        assert_eq!(
+            U7::data_to_bytes(&high_first),
+            [0x00, 0x00, 0x01, 0x02, 0x0A, 0x0B, 0x0F, 0x0F]
+        );
+        let mut low_first = [U7::MIN; 8];
+        encode_nibbles(&bytes, NibbleOrder::LowFirst, &mut low_first).unwrap();
+        assert_eq!(U7::data_to_bytes(&low_first)[2..4], [0x02, 0x01]);
+        let mut decoded = [0u8; 4];
+        decode_nibbles(&high_first, NibbleOrder::HighFirst, &mut decoded).unwrap();
+        assert_eq!(decoded, bytes);
+        decode_nibbles(&low_first, NibbleOrder::LowFirst, &mut decoded).unwrap();
+        assert_eq!(decoded, bytes);
+        let data = [U7(0x71), U7(0x02), U7(0x03)];
+        assert_eq!(
+            decode_nibbles(&data, NibbleOrder::HighFirst, &mut decoded),
+            Ok(1)
+        );
+        assert_eq!(decoded[0], 0x12);
+    }
+}
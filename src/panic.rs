@@ -0,0 +1,104 @@
+use crate::{Channel, ChannelSet, ControlFunction, MidiMessage, U7};
+
+/// The messages that silence every channel and return its controllers to their defaults, used to
+/// recover from stuck notes. Equivalent to `panic_messages_for(ChannelSet::ALL)`.
+///
+/// # Example
+/// ```
+/// let messages = wmidi::panic_messages();
+/// assert_eq!(messages.len(), 64);
+/// ```
+pub fn panic_messages() -> PanicMessages {
+    panic_messages_for(ChannelSet::ALL)
+}
+
+/// The messages that silence each channel in `channels` and return its controllers to their
+/// defaults.
+///
+/// For each channel in ascending order, the Damper Pedal is released first so that the notes it
+/// holds can stop, followed by All Sound Off, All Notes Off, and Reset All Controllers.
+///
+/// # Example
+/// ```
+/// use wmidi::{panic_messages_for, Channel, ChannelSet, ControlFunction, MidiMessage, U7};
+/// let mut messages = panic_messages_for(ChannelSet::from(Channel::Ch10));
+/// assert_eq!(
+///     messages.next(),
+///     Some(MidiMessage::ControlChange(Channel::Ch10, ControlFunction::DAMPER_PEDAL, U7::MIN))
+/// );
+/// assert_eq!(messages.len(), 3);
+/// ```
+pub fn panic_messages_for(channels: ChannelSet) -> PanicMessages {
+    PanicMessages { channels, step: 0 }
+}
+
+/// The functions sent to each channel by `panic_messages`, in order.
+const PANIC_FUNCTIONS: [ControlFunction; 4] = [
+    ControlFunction::DAMPER_PEDAL,
+    ControlFunction::ALL_SOUND_OFF,
+    ControlFunction::ALL_NOTES_OFF,
+    ControlFunction::RESET_ALL_CONTROLLERS,
+];
+
+/// An iterator over panic messages. Created with `panic_messages` or `panic_messages_for`.
+#[derive(Clone, Debug)]
+pub struct PanicMessages {
+    // The channels that have messages left, including the current one.
+    channels: ChannelSet,
+    // The index into `PANIC_FUNCTIONS` for the lowest channel in `channels`.
+    step: u8,
+}
+
+impl Iterator for PanicMessages {
+    type Item = MidiMessage<'static>;
+
+    fn next(&mut self) -> Option<MidiMessage<'static>> {
+        let channel: Channel = self.channels.iter().next()?;
+        let function = PANIC_FUNCTIONS[usize::from(self.step)];
+        self.step += 1;
+        if usize::from(self.step) == PANIC_FUNCTIONS.len() {
+            self.step = 0;
+            self.channels.remove(channel);
+        }
+        Some(MidiMessage::ControlChange(channel, function, U7::MIN))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len =
+            (self.channels.len() * PANIC_FUNCTIONS.len()).saturating_sub(usize::from(self.step));
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PanicMessages {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn panic_all_channels() {
+        let mut messages = panic_messages();
+        assert_eq!(messages.len(), 64);
+        for function in PANIC_FUNCTIONS.iter() {
+            assert_eq!(
+                messages.next(),
+                Some(MidiMessage::ControlChange(Channel::Ch1, *function, U7(0)))
+            );
+        }
+        assert_eq!(messages.len(), 60);
+        assert_eq!(
+            messages.next().and_then(|m| m.channel()),
+            Some(Channel::Ch2)
+        );
+        assert_eq!(
+            messages.last().and_then(|m| m.channel()),
+            Some(Channel::Ch16)
+        );
+    }
+
+    #[test]
+    fn panic_empty_set() {
+        assert_eq!(panic_messages_for(ChannelSet::EMPTY).next(), None);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{
+    encode_parameter, Cents, Channel, Error, LsbMode, MidiMessage, ParameterNumber, U14, U7,
+};
+use core::convert::TryFrom;
+
+/// The position of the pitch bender. The raw 14 bit value is centered on 8192, which is no bend.
+///
+/// # Example
+/// ```
+/// use wmidi::{PitchBend, PitchBendSensitivity};
+/// let bend = PitchBend::from_signed_clamped(-4096);
+/// assert_eq!(u16::from(bend), 4096);
+/// assert_eq!(bend.to_semitones_f32(PitchBendSensitivity::DEFAULT), -1.0);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PitchBend(U14);
+
+impl PitchBend {
+    /// The lowest position, a signed offset of -8192.
+    pub const MIN: PitchBend = PitchBend(U14::MIN);
+    /// The center position with no bend.
+    pub const CENTER: PitchBend = PitchBend(U14::from_u16_lossy(0x2000));
+    /// The highest position, a signed offset of 8191.
+    pub const MAX: PitchBend = PitchBend(U14::MAX);
+
+    /// Create a pitch bend from the raw 14 bit value.
+    #[inline(always)]
+    pub fn new(value: U14) -> PitchBend {
+        PitchBend(value)
+    }
+
+    /// The raw 14 bit value, as sent in a `PitchBendChange` message.
+    #[inline(always)]
+    pub fn value(self) -> U14 {
+        self.0
+    }
+
+    /// Create a pitch bend from an offset from the center. An error is returned if `offset` is
+    /// not between -8192 and 8191 inclusive.
+    pub fn from_signed(offset: i16) -> Result<PitchBend, Error> {
+        if (-0x2000..0x2000).contains(&offset) {
+            Ok(PitchBend::from_signed_clamped(offset))
+        } else {
+            Err(Error::U14OutOfRange)
+        }
+    }
+
+    /// Create a pitch bend from an offset from the center. Offsets outside of -8192 to 8191 are
+    /// clamped.
+    pub fn from_signed_clamped(offset: i16) -> PitchBend {
+        let raw = offset.clamp(-0x2000, 0x1FFF) + 0x2000;
+        PitchBend(U14::from_u16_lossy(raw as u16))
+    }
+
+    /// The offset from the center, between -8192 and 8191 inclusive.
+    pub fn signed(self) -> i16 {
+        u16::from(self.0) as i16 - 0x2000
+    }
+
+    /// The offset from the center scaled to between -1.0 and 1.0. The center maps to 0.0 and
+    /// `PitchBend::MIN` to -1.0. `PitchBend::MAX` maps to just below 1.0.
+    pub fn to_f32(self) -> f32 {
+        f32::from(self.signed()) / 8192.0
+    }
+
+    /// The pitch bend for an offset scaled to between -1.0 and 1.0. Values are rounded to the
+    /// nearest step and clamped to the pitch bend range.
+    pub fn from_f32_clamped(value: f32) -> PitchBend {
+        Cents(value).to_pitch_bend(Cents(1.0))
+    }
+
+    /// The pitch offset in semitones with the pitch bender range set to `sensitivity`.
+    pub fn to_semitones_f32(self, sensitivity: PitchBendSensitivity) -> f32 {
+        sensitivity.bend_to_semitones_f32(self)
+    }
+
+    /// The pitch bend that produces an offset of `semitones` with the pitch bender range set to
+    /// `sensitivity`. Values are rounded to the nearest step and saturate at the ends of the pitch
+    /// bend range.
+    pub fn from_semitones_f32(semitones: f32, sensitivity: PitchBendSensitivity) -> PitchBend {
+        sensitivity.semitones_to_bend_f32(semitones)
+    }
+}
+
+impl Default for PitchBend {
+    fn default() -> PitchBend {
+        PitchBend::CENTER
+    }
+}
+
+impl From<U14> for PitchBend {
+    #[inline(always)]
+    fn from(value: U14) -> PitchBend {
+        PitchBend(value)
+    }
+}
+
+impl From<PitchBend> for U14 {
+    #[inline(always)]
+    fn from(bend: PitchBend) -> U14 {
+        bend.0
+    }
+}
+
+impl From<PitchBend> for u16 {
+    #[inline(always)]
+    fn from(bend: PitchBend) -> u16 {
+        u16::from(bend.0)
+    }
+}
+
+impl TryFrom<u16> for PitchBend {
+    type Error = Error;
+
+    #[inline(always)]
+    fn try_from(value: u16) -> Result<PitchBend, Error> {
+        U14::try_from(value).map(PitchBend)
+    }
+}
+
+/// [MIDI 1.0] The range of the pitch bender in each direction, set with RPN 00/00
+/// (`ParameterNumber::PITCH_BEND_SENSITIVITY`).
+///
+/// # Example
+/// ```
+/// use wmidi::{PitchBend, PitchBendSensitivity};
+/// let sensitivity = PitchBendSensitivity::DEFAULT;
+/// assert_eq!(sensitivity.bend_to_semitones_f32(PitchBend::MIN), -2.0);
+/// assert_eq!(sensitivity.semitones_to_bend_f32(2.0), PitchBend::MAX);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct PitchBendSensitivity {
+    /// The whole semitones of the range, sent as the Data Entry MSB.
+    pub semitones: U7,
+    /// The additional cents of the range, sent as the Data Entry LSB.
+    pub cents: U7,
+}
+
+impl PitchBendSensitivity {
+    /// [GM1] The default range of +/-2 semitones.
+    pub const DEFAULT: PitchBendSensitivity = PitchBendSensitivity {
+        semitones: U7(2),
+        cents: U7(0),
+    };
+
+    /// Create a range of `semitones` plus `cents` in each direction.
+    pub fn new(semitones: U7, cents: U7) -> PitchBendSensitivity {
+        PitchBendSensitivity { semitones, cents }
+    }
+
+    /// The range in each direction.
+    pub fn range(self) -> Cents {
+        Cents(f32::from(u8::from(self.semitones)) * 100.0 + f32::from(u8::from(self.cents)))
+    }
+
+    /// The pitch offset in semitones produced by `bend`.
+    pub fn bend_to_semitones_f32(self, bend: PitchBend) -> f32 {
+        Cents::from_pitch_bend(bend, self.range()).semitones()
+    }
+
+    /// The pitch bend that produces an offset of `semitones`. Values are rounded to the nearest
+    /// step and saturate at the ends of the pitch bend range.
+    pub fn semitones_to_bend_f32(self, semitones: f32) -> PitchBend {
+        Cents::from_semitones(semitones).to_pitch_bend(self.range())
+    }
+
+    /// The RPN 00/00 value, with semitones in the MSB and cents in the LSB.
+    pub fn to_parameter_value(self) -> U14 {
+        let value = u16::from(u8::from(self.semitones)) << 7 | u16::from(u8::from(self.cents));
+        U14::from_u16_lossy(value)
+    }
+
+    /// Decode an RPN 00/00 value.
+    pub fn from_parameter_value(value: U14) -> PitchBendSensitivity {
+        let value = u16::from(value);
+        PitchBendSensitivity {
+            semitones: U7((value >> 7) as u8),
+            cents: U7((value & 0x7F) as u8),
+        }
+    }
+
+    /// Emit the `ControlChange` messages that set the pitch bend sensitivity of `channel`.
+    pub fn encode<F: FnMut(MidiMessage<'static>)>(self, channel: Channel, emit: F) {
+        encode_parameter(
+            channel,
+            ParameterNumber::PITCH_BEND_SENSITIVITY,
+            self.to_parameter_value(),
+            LsbMode::OmitZero,
+            emit,
+        );
+    }
+}
+
+impl Default for PitchBendSensitivity {
+    fn default() -> PitchBendSensitivity {
+        PitchBendSensitivity::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signed() {
+        assert_eq!(PitchBend::CENTER.signed(), 0);
+        assert_eq!(PitchBend::MIN.signed(), -8192);
+        assert_eq!(PitchBend::MAX.signed(), 8191);
+        assert_eq!(PitchBend::default(), PitchBend::CENTER);
+        for offset in -8192..8192 {
+            assert_eq!(PitchBend::from_signed(offset).unwrap().signed(), offset);
+        }
+        assert_eq!(PitchBend::from_signed(8192), Err(Error::U14OutOfRange));
+        assert_eq!(PitchBend::from_signed_clamped(i16::MIN), PitchBend::MIN);
+        assert_eq!(PitchBend::from_signed_clamped(i16::MAX), PitchBend::MAX);
+        assert_eq!(PitchBend::try_from(0x4000), Err(Error::U14OutOfRange));
+    }
+
+    #[test]
+    fn normalized() {
+        assert_eq!(PitchBend::MIN.to_f32(), -1.0);
+        assert_eq!(PitchBend::from_f32_clamped(0.5).signed(), 4096);
+        assert_eq!(PitchBend::from_f32_clamped(2.0), PitchBend::MAX);
+        assert_eq!(PitchBend::from_f32_clamped(f32::NAN), PitchBend::CENTER);
+    }
+
+    #[test]
+    fn semitones() {
+        let sensitivity = PitchBendSensitivity::new(U7(12), U7(50));
+        assert_eq!(sensitivity.range(), Cents(1250.0));
+        assert_eq!(sensitivity.bend_to_semitones_f32(PitchBend::CENTER), 0.0);
+        assert_eq!(sensitivity.bend_to_semitones_f32(PitchBend::MIN), -12.5);
+        assert_eq!(
+            sensitivity.semitones_to_bend_f32(6.25),
+            PitchBend::from_signed_clamped(4096)
+        );
+        assert_eq!(sensitivity.semitones_to_bend_f32(-24.0), PitchBend::MIN);
+        assert_eq!(
+            PitchBend::from_semitones_f32(-6.25, sensitivity).to_semitones_f32(sensitivity),
+            -6.25
+        );
+    }
+
+    #[test]
+    fn parameter_value() {
+        let sensitivity = PitchBendSensitivity::new(U7(24), U7(1));
+        let value = sensitivity.to_parameter_value();
+        assert_eq!(u16::from(value), 24 << 7 | 1);
+        assert_eq!(
+            PitchBendSensitivity::from_parameter_value(value),
+            sensitivity
+        );
+        let mut count = 0;
+        sensitivity.encode(Channel::Ch1, |_| count += 1);
+        assert_eq!(count, 6);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{Channel, ChannelMap, MidiMessage, Note, NoteTracker, Velocity};
+
+/// Which note `PolyphonyLimiter` stops to make room for a new note.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum StealPolicy {
+    /// Stop the note that started first.
+    Oldest,
+    /// Stop the note with the lowest velocity. Ties stop the oldest of them.
+    Quietest,
+    /// Stop the lowest note. This keeps melodies on top while limiting the bass.
+    Lowest,
+}
+
+/// Limits the number of notes sounding on each channel.
+///
+/// When a `NoteOn` would exceed `max_notes` on its channel, a `NoteOff` is emitted for the note
+/// chosen by the `StealPolicy` before the new note is passed on. A `NoteOn` for a note that is
+/// already sounding is passed on without stealing. All other messages, including the `NoteOff`
+/// for a stolen note, are passed on unchanged.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, Note, PolyphonyLimiter, StealPolicy, U7};
+/// let mut limiter = PolyphonyLimiter::new(1, StealPolicy::Oldest);
+/// let mut out = Vec::new();
+/// limiter.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX), |m| out.push(m));
+/// limiter.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::E4, U7::MAX), |m| out.push(m));
+/// assert_eq!(out[1], MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7::MIN));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolyphonyLimiter {
+    max_notes: usize,
+    policy: StealPolicy,
+    notes: NoteTracker,
+    started: ChannelMap<[u32; 128]>,
+    velocities: ChannelMap<[Velocity; 128]>,
+    clock: u32,
+}
+
+impl PolyphonyLimiter {
+    /// Create a limiter that allows `max_notes` notes per channel. A `max_notes` of 0 is treated
+    /// as 1.
+    pub fn new(max_notes: usize, policy: StealPolicy) -> PolyphonyLimiter {
+        PolyphonyLimiter {
+            max_notes: max_notes.max(1),
+            policy,
+            notes: NoteTracker::new(),
+            started: ChannelMap::new([[0; 128]; 16]),
+            velocities: ChannelMap::new([[Velocity::MIN; 128]; 16]),
+            clock: 0,
+        }
+    }
+
+    /// The maximum number of notes per channel.
+    pub fn max_notes(&self) -> usize {
+        self.max_notes
+    }
+
+    /// The notes that are sounding after limiting.
+    pub fn notes(&self) -> &NoteTracker {
+        &self.notes
+    }
+
+    /// Process `message`, calling `emit` with the messages to send on.
+    pub fn handle<'a, F: FnMut(MidiMessage<'a>)>(
+        &mut self,
+        message: &MidiMessage<'a>,
+        mut emit: F,
+    ) {
+        if let MidiMessage::NoteOn(channel, note, velocity) = message {
+            if *velocity != Velocity::MIN {
+                if !self.notes.is_active(*channel, *note)
+                    && self.notes.active_count(*channel) >= self.max_notes
+                {
+                    if let Some(victim) = self.victim(*channel) {
+                        let off = MidiMessage::NoteOff(*channel, victim, Velocity::MIN);
+                        self.notes.handle(&off);
+                        emit(off);
+                    }
+                }
+                self.clock = self.clock.wrapping_add(1);
+                let index = usize::from(u8::from(*note));
+                self.started[*channel][index] = self.clock;
+                self.velocities[*channel][index] = *velocity;
+            }
+        }
+        self.notes.handle(message);
+        emit(message.clone());
+    }
+
+    /// The note on `channel` to stop according to the policy.
+    fn victim(&self, channel: Channel) -> Option<Note> {
+        let started = &self.started[channel];
+        let age = |n: &Note| self.clock.wrapping_sub(started[usize::from(u8::from(*n))]);
+        let mut notes = self.notes.notes(channel);
+        match self.policy {
+            StealPolicy::Oldest => notes.max_by_key(age),
+            StealPolicy::Quietest => {
+                let velocities = &self.velocities[channel];
+                notes.min_by_key(|n| {
+                    (
+                        velocities[usize::from(u8::from(*n))],
+                        core::cmp::Reverse(age(n)),
+                    )
+                })
+            }
+            StealPolicy::Lowest => notes.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U7;
+
+    fn play(limiter: &mut PolyphonyLimiter, notes: &[(Note, u8)]) -> Option<Note> {
+        let mut stolen = None;
+        for (note, velocity) in notes.iter() {
+            limiter.handle(
+                &MidiMessage::NoteOn(Channel::Ch1, *note, U7(*velocity)),
+                |m| {
+                    if let MidiMessage::NoteOff(_, n, _) = m {
+                        stolen = Some(n);
+                    }
+                },
+            );
+        }
+        stolen
+    }
+
+    #[test]
+    fn policies() {
+        let notes = [
+            (Note::E4, 50),
+            (Note::C4, 100),
+            (Note::G4, 30),
+            (Note::B4, 90),
+        ];
+        let mut oldest = PolyphonyLimiter::new(3, StealPolicy::Oldest);
+        assert_eq!(play(&mut oldest, &notes), Some(Note::E4));
+        let mut quietest = PolyphonyLimiter::new(3, StealPolicy::Quietest);
+        assert_eq!(play(&mut quietest, &notes), Some(Note::G4));
+        let mut lowest = PolyphonyLimiter::new(3, StealPolicy::Lowest);
+        assert_eq!(play(&mut lowest, &notes), Some(Note::C4));
+        assert_eq!(lowest.notes().active_count(Channel::Ch1), 3);
+    }
+
+    #[test]
+    fn repeated_note_is_not_stolen() {
+        let mut limiter = PolyphonyLimiter::new(2, StealPolicy::Oldest);
+        let notes = [(Note::C4, 1), (Note::D4, 1), (Note::C4, 1)];
+        assert_eq!(play(&mut limiter, &notes), None);
+        // C4 was restarted, so D4 is now the oldest.
+        assert_eq!(play(&mut limiter, &[(Note::E4, 1)]), Some(Note::D4));
+    }
+
+    #[test]
+    fn channels_are_independent() {
+        let mut limiter = PolyphonyLimiter::new(1, StealPolicy::Oldest);
+        let mut count = 0;
+        for channel in [Channel::Ch1, Channel::Ch2].iter() {
+            limiter.handle(&MidiMessage::NoteOn(*channel, Note::C4, U7(1)), |_| {
+                count += 1
+            });
+        }
+        assert_eq!(count, 2);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{SongPosition, TimeSignatureError, U14};
+use core::fmt;
+
+/// A time signature: the number of beats in a bar and the note value of each beat.
+///
+/// # Example
+/// ```
+/// use wmidi::TimeSignature;
+/// let six_eight = TimeSignature::new(6, 8).unwrap();
+/// assert_eq!(six_eight.numerator(), 6);
+/// assert_eq!(six_eight.denominator(), 8);
+/// assert!(TimeSignature::new(3, 5).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TimeSignature {
+    numerator: u8,
+    denominator: u8,
+}
+
+impl TimeSignature {
+    /// 4 quarter notes per bar.
+    pub const COMMON_TIME: TimeSignature = TimeSignature {
+        numerator: 4,
+        denominator: 4,
+    };
+
+    /// Create a time signature with `numerator` beats of 1/`denominator` notes. An error is
+    /// returned if `numerator` is 0 or `denominator` is not a power of 2.
+    pub fn new(numerator: u8, denominator: u8) -> Result<TimeSignature, TimeSignatureError> {
+        if numerator == 0 || !denominator.is_power_of_two() {
+            return Err(TimeSignatureError);
+        }
+        Ok(TimeSignature {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// The number of beats in a bar.
+    pub fn numerator(self) -> u8 {
+        self.numerator
+    }
+
+    /// The note value of a beat, for example 4 for quarter notes.
+    pub fn denominator(self) -> u8 {
+        self.denominator
+    }
+
+    /// The number of ticks in a beat, given `division` ticks per quarter note. This is at
+    /// least 1.
+    pub fn ticks_per_beat(self, division: u16) -> u64 {
+        (u64::from(division) * 4 / u64::from(self.denominator)).max(1)
+    }
+
+    /// The number of ticks in a bar, given `division` ticks per quarter note.
+    pub fn ticks_per_bar(self, division: u16) -> u64 {
+        self.ticks_per_beat(division) * u64::from(self.numerator)
+    }
+}
+
+impl Default for TimeSignature {
+    fn default() -> TimeSignature {
+        TimeSignature::COMMON_TIME
+    }
+}
+
+/// A position in bars, beats, and ticks, counted from 0, for a song with a single time
+/// signature.
+///
+/// Ticks are counted at a `division` of ticks per quarter note, as in Standard MIDI Files.
+/// Positions are displayed counting bars and beats from 1, as on a transport display.
+///
+/// # Example
+/// ```
+/// use wmidi::{MusicalPosition, TimeSignature};
+/// let three_four = TimeSignature::new(3, 4).unwrap();
+/// // 4 bars of 3/4 and 1 beat, at 24 clocks per quarter note.
+/// let position = MusicalPosition::from_clocks(13 * 24, three_four, 480);
+/// assert_eq!(position, MusicalPosition { bar: 4, beat: 1, tick: 0 });
+/// assert_eq!(position.to_string(), "5.2.000");
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MusicalPosition {
+    /// The number of whole bars.
+    pub bar: u32,
+    /// The number of whole beats within the bar.
+    pub beat: u32,
+    /// The number of ticks within the beat.
+    pub tick: u32,
+}
+
+impl MusicalPosition {
+    /// The position `ticks` ticks from the start.
+    pub fn from_ticks(ticks: u64, signature: TimeSignature, division: u16) -> MusicalPosition {
+        let per_beat = signature.ticks_per_beat(division);
+        let beats = ticks / per_beat;
+        MusicalPosition {
+            bar: (beats / u64::from(signature.numerator)) as u32,
+            beat: (beats % u64::from(signature.numerator)) as u32,
+            tick: (ticks % per_beat) as u32,
+        }
+    }
+
+    /// The number of ticks from the start.
+    pub fn to_ticks(&self, signature: TimeSignature, division: u16) -> u64 {
+        u64::from(self.bar) * signature.ticks_per_bar(division)
+            + u64::from(self.beat) * signature.ticks_per_beat(division)
+            + u64::from(self.tick)
+    }
+
+    /// The position after `clocks` `TimingClock` messages, at 24 clocks per quarter note.
+    pub fn from_clocks(clocks: u32, signature: TimeSignature, division: u16) -> MusicalPosition {
+        let ticks = u64::from(clocks) * u64::from(division) / 24;
+        MusicalPosition::from_ticks(ticks, signature, division)
+    }
+
+    /// The number of `TimingClock` messages from the start, rounded down. Positions past the
+    /// largest `u32` are clamped to it.
+    pub fn to_clocks(&self, signature: TimeSignature, division: u16) -> u32 {
+        self.clocks(signature, division).min(u64::from(u32::MAX)) as u32
+    }
+
+    fn clocks(&self, signature: TimeSignature, division: u16) -> u64 {
+        self.to_ticks(signature, division) * 24 / u64::from(division.max(1))
+    }
+
+    /// The position of a `SongPositionPointer`, which counts sixteenth notes.
+    pub fn from_song_position(
+        position: SongPosition,
+        signature: TimeSignature,
+        division: u16,
+    ) -> MusicalPosition {
+        MusicalPosition::from_clocks(u32::from(u16::from(position)) * 6, signature, division)
+    }
+
+    /// The position to send in a `SongPositionPointer`, rounded down to a sixteenth note.
+    /// Positions past the largest pointer are clamped to it.
+    pub fn to_song_position(&self, signature: TimeSignature, division: u16) -> SongPosition {
+        let sixteenths = self.clocks(signature, division) / 6;
+        U14::from_u16_lossy(sixteenths.min(u64::from(u16::from(U14::MAX))) as u16)
+    }
+}
+
+impl fmt::Display for MusicalPosition {
+    /// Format as `bar.beat.tick`, counting bars and beats from 1.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{:03}", self.bar + 1, self.beat + 1, self.tick)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let six_eight = TimeSignature::new(6, 8).unwrap();
+        for ticks in (0..100_000).step_by(7) {
+            let position = MusicalPosition::from_ticks(ticks, six_eight, 96);
+            assert!(position.beat < 6 && position.tick < 48);
+            assert_eq!(position.to_ticks(six_eight, 96), ticks);
+        }
+        let position = MusicalPosition {
+            bar: 2,
+            beat: 5,
+            tick: 47,
+        };
+        assert_eq!(position.to_clocks(six_eight, 96), 2 * 72 + 5 * 12 + 11);
+        assert_eq!(
+            position.to_song_position(six_eight, 96),
+            U14::from_u16_lossy(35)
+        );
+        assert_eq!(
+            MusicalPosition::from_song_position(U14::from_u16_lossy(35), six_eight, 96),
+            MusicalPosition {
+                bar: 2,
+                beat: 5,
+                tick: 24
+            }
+        );
+    }
+
+    #[test]
+    fn clamped() {
+        let signature = TimeSignature::new(1, 128).unwrap();
+        assert_eq!(signature.ticks_per_beat(24), 1);
+        assert_eq!(TimeSignature::new(0, 4), Err(TimeSignatureError));
+        let far = MusicalPosition {
+            bar: u32::MAX,
+            beat: 0,
+            tick: 0,
+        };
+        assert_eq!(
+            far.to_song_position(TimeSignature::COMMON_TIME, 24),
+            U14::MAX
+        );
+        assert_eq!(far.to_clocks(TimeSignature::COMMON_TIME, 24), u32::MAX);
+    }
+}
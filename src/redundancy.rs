@@ -0,0 +1,193 @@
+use crate::{ChannelMap, ControlFunction, ControlFunctionSet, MidiMessage, PitchBend, U7};
+
+/// Drops `ControlChange`, `ChannelPressure`, and `PitchBendChange` messages that repeat the last
+/// value sent on their channel, to save bandwidth on dense controller streams.
+///
+/// With a `window`, a repeated value is only dropped if the same value was sent less than
+/// `window` ago, so that values are still refreshed periodically. The unit of time is up to the
+/// caller. Without a window, repeated values are always dropped.
+///
+/// Only controllers in `controllers` are deduplicated. By default this excludes data entry,
+/// parameter number selection, and channel mode messages, since repeating those has an effect.
+/// An MSB also resets its LSB on the receiver, so high resolution controllers that send only an
+/// MSB for some values should be removed as well. `Reset` and Reset All Controllers forget the
+/// values that were sent.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ControlFunction, MidiMessage, RedundancyFilter, U7};
+/// let mut filter = RedundancyFilter::new(None);
+/// let modulation = MidiMessage::ControlChange(Channel::Ch1, ControlFunction::MODULATION_WHEEL, U7::MAX);
+/// let mut count = 0;
+/// filter.handle(0, &modulation, |_| count += 1);
+/// filter.handle(1, &modulation, |_| count += 1);
+/// assert_eq!(count, 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedundancyFilter {
+    window: Option<u64>,
+    controllers: ControlFunctionSet,
+    sent: ChannelMap<Sent>,
+}
+
+/// The values last sent on a channel and when they were sent.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Sent {
+    controllers: [Option<(U7, u64)>; 128],
+    pressure: Option<(U7, u64)>,
+    pitch_bend: Option<(PitchBend, u64)>,
+}
+
+impl Sent {
+    const NONE: Sent = Sent {
+        controllers: [None; 128],
+        pressure: None,
+        pitch_bend: None,
+    };
+}
+
+impl RedundancyFilter {
+    /// Create a filter that drops repeated values sent within `window`, or always if `window`
+    /// is `None`.
+    pub fn new(window: Option<u64>) -> RedundancyFilter {
+        let mut controllers = ControlFunctionSet::ALL;
+        for function in [
+            ControlFunction::DATA_ENTRY_MSB,
+            ControlFunction::DATA_ENTRY_LSB,
+            ControlFunction::DATA_INCREMENT,
+            ControlFunction::DATA_DECREMENT,
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB,
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB,
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB,
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB,
+        ]
+        .iter()
+        .copied()
+        .chain(ControlFunction::all().skip(usize::from(u8::from(ControlFunction::ALL_SOUND_OFF))))
+        {
+            controllers.remove(function);
+        }
+        RedundancyFilter {
+            window,
+            controllers,
+            sent: ChannelMap::new([Sent::NONE; 16]),
+        }
+    }
+
+    /// The time within which repeated values are dropped.
+    pub fn window(&self) -> Option<u64> {
+        self.window
+    }
+
+    /// The controllers that are deduplicated.
+    pub fn controllers(&self) -> ControlFunctionSet {
+        self.controllers
+    }
+
+    /// Set the controllers that are deduplicated.
+    pub fn set_controllers(&mut self, controllers: ControlFunctionSet) {
+        self.controllers = controllers;
+    }
+
+    /// Returns `true` and records the value if `message`, sent at `now`, should be passed on.
+    pub fn allows(&mut self, now: u64, message: &MidiMessage) -> bool {
+        let window = self.window;
+        match message {
+            MidiMessage::ControlChange(c, f, _) if *f == ControlFunction::RESET_ALL_CONTROLLERS => {
+                self.sent[*c] = Sent::NONE;
+                true
+            }
+            MidiMessage::ControlChange(c, f, v) if self.controllers.contains(*f) => {
+                let last = &mut self.sent[*c].controllers[usize::from(u8::from(*f))];
+                record(last, *v, now, window)
+            }
+            MidiMessage::ChannelPressure(c, v) => {
+                record(&mut self.sent[*c].pressure, *v, now, window)
+            }
+            MidiMessage::PitchBendChange(c, v) => {
+                record(&mut self.sent[*c].pitch_bend, *v, now, window)
+            }
+            MidiMessage::Reset => {
+                self.clear();
+                true
+            }
+            _ => true,
+        }
+    }
+
+    /// Call `emit` with `message`, sent at `now`, unless it repeats the last value.
+    pub fn handle<'a, F: FnMut(MidiMessage<'a>)>(
+        &mut self,
+        now: u64,
+        message: &MidiMessage<'a>,
+        mut emit: F,
+    ) {
+        if self.allows(now, message) {
+            emit(message.clone());
+        }
+    }
+
+    /// Forget the values that were sent, so that the next value of each is passed on.
+    pub fn clear(&mut self) {
+        self.sent = ChannelMap::new([Sent::NONE; 16]);
+    }
+}
+
+/// Returns `true` and records `value` as sent at `now` unless it repeats `last` within
+/// `window`.
+fn record<T: Copy + PartialEq>(
+    last: &mut Option<(T, u64)>,
+    value: T,
+    now: u64,
+    window: Option<u64>,
+) -> bool {
+    if let Some((v, time)) = *last {
+        if v == value && window.is_none_or(|w| now.saturating_sub(time) < w) {
+            return false;
+        }
+    }
+    *last = Some((value, now));
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Channel;
+
+    #[test]
+    fn window() {
+        let mut filter = RedundancyFilter::new(Some(10));
+        let bend = MidiMessage::PitchBendChange(Channel::Ch1, PitchBend::MAX);
+        let pressure = MidiMessage::ChannelPressure(Channel::Ch1, U7(3));
+        assert!(filter.allows(0, &bend));
+        assert!(filter.allows(0, &pressure));
+        assert!(!filter.allows(9, &bend));
+        assert!(!filter.allows(9, &pressure));
+        assert!(filter.allows(10, &bend));
+        assert!(filter.allows(10, &MidiMessage::ChannelPressure(Channel::Ch1, U7(4))));
+        assert!(filter.allows(11, &MidiMessage::ChannelPressure(Channel::Ch2, U7(4))));
+    }
+
+    #[test]
+    fn excluded_controllers_and_reset() {
+        let mut filter = RedundancyFilter::new(None);
+        let data = MidiMessage::ControlChange(Channel::Ch1, ControlFunction::DATA_ENTRY_MSB, U7(2));
+        let notes_off =
+            MidiMessage::ControlChange(Channel::Ch1, ControlFunction::ALL_NOTES_OFF, U7(0));
+        let pan = MidiMessage::ControlChange(Channel::Ch1, ControlFunction::PAN, U7(2));
+        let reset =
+            MidiMessage::ControlChange(Channel::Ch1, ControlFunction::RESET_ALL_CONTROLLERS, U7(0));
+        for _ in 0..2 {
+            assert!(filter.allows(0, &data));
+            assert!(filter.allows(0, &notes_off));
+            assert!(filter.allows(0, &reset));
+        }
+        assert!(filter.allows(0, &pan));
+        assert!(!filter.allows(0, &pan));
+        assert!(filter.allows(0, &reset));
+        assert!(filter.allows(0, &pan));
+        filter.set_controllers(ControlFunctionSet::EMPTY);
+        assert!(filter.allows(0, &pan));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{Channel, ChannelMap, ControlFunction, MidiMessage, Note, U7};
+
+/// Rewrites the notes, channels, and controllers of messages using lookup tables, for example to
+/// translate between drum maps or to move a controller to a different number.
+///
+/// Each table maps to `None` to drop matching messages. A new `Remapper` leaves every message
+/// unchanged. Note tables apply to `NoteOn`, `NoteOff`, and `PolyphonicKeyPressure`. Changing the
+/// note table while notes are held may leave notes stuck, since their `NoteOff` will be mapped
+/// differently.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, Note, Remapper, U7};
+/// let mut remapper = Remapper::new();
+/// // Move the kick drum and send drums on channel 10.
+/// remapper.set_note(Note::C2, Some(Note::B1));
+/// remapper.set_channel(Channel::Ch1, Some(Channel::Ch10));
+/// assert_eq!(
+///     remapper.remap(&MidiMessage::NoteOn(Channel::Ch1, Note::C2, U7::MAX)),
+///     Some(MidiMessage::NoteOn(Channel::Ch10, Note::B1, U7::MAX))
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Remapper {
+    notes: [Option<Note>; 128],
+    channels: ChannelMap<Option<Channel>>,
+    controllers: [Option<ControlFunction>; 128],
+}
+
+impl Remapper {
+    /// Create a remapper that leaves every message unchanged.
+    pub fn new() -> Remapper {
+        Remapper {
+            notes: core::array::from_fn(|i| Some(Note::from_u8_lossy(i as u8))),
+            channels: ChannelMap::from_fn(Some),
+            controllers: core::array::from_fn(|i| Some(ControlFunction(U7(i as u8)))),
+        }
+    }
+
+    /// The note that `note` is mapped to.
+    pub fn note(&self, note: Note) -> Option<Note> {
+        self.notes[usize::from(u8::from(note))]
+    }
+
+    /// Map `from` to `to`, or drop messages for `from` if `to` is `None`.
+    pub fn set_note(&mut self, from: Note, to: Option<Note>) {
+        self.notes[usize::from(u8::from(from))] = to;
+    }
+
+    /// The channel that `channel` is mapped to.
+    pub fn channel(&self, channel: Channel) -> Option<Channel> {
+        self.channels[channel]
+    }
+
+    /// Map `from` to `to`, or drop messages on `from` if `to` is `None`.
+    pub fn set_channel(&mut self, from: Channel, to: Option<Channel>) {
+        self.channels[from] = to;
+    }
+
+    /// The controller that `function` is mapped to.
+    pub fn controller(&self, function: ControlFunction) -> Option<ControlFunction> {
+        self.controllers[usize::from(u8::from(function))]
+    }
+
+    /// Map `from` to `to`, or drop `ControlChange` messages for `from` if `to` is `None`.
+    pub fn set_controller(&mut self, from: ControlFunction, to: Option<ControlFunction>) {
+        self.controllers[usize::from(u8::from(from))] = to;
+    }
+
+    /// The rewritten message, or `None` if it is dropped. Messages without a channel are
+    /// returned unchanged.
+    pub fn remap<'a>(&self, message: &MidiMessage<'a>) -> Option<MidiMessage<'a>> {
+        let channel = match message.channel() {
+            Some(c) => self.channel(c)?,
+            None => return Some(message.clone()),
+        };
+        let message = match message {
+            MidiMessage::NoteOff(_, n, v) => MidiMessage::NoteOff(channel, self.note(*n)?, *v),
+            MidiMessage::NoteOn(_, n, v) => MidiMessage::NoteOn(channel, self.note(*n)?, *v),
+            MidiMessage::PolyphonicKeyPressure(_, n, v) => {
+                MidiMessage::PolyphonicKeyPressure(channel, self.note(*n)?, *v)
+            }
+            MidiMessage::ControlChange(_, f, v) => {
+                MidiMessage::ControlChange(channel, self.controller(*f)?, *v)
+            }
+            m => m.clone().with_channel(channel),
+        };
+        Some(message)
+    }
+
+    /// Call `emit` with the rewritten message unless it is dropped.
+    pub fn handle<'a, F: FnMut(MidiMessage<'a>)>(&self, message: &MidiMessage<'a>, mut emit: F) {
+        if let Some(message) = self.remap(message) {
+            emit(message);
+        }
+    }
+}
+
+impl Default for Remapper {
+    fn default() -> Remapper {
+        Remapper::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn identity() {
+        let remapper = Remapper::new();
+        let messages = [
+            MidiMessage::NoteOff(Channel::Ch16, Note::G9, U7(1)),
+            MidiMessage::ControlChange(Channel::Ch1, ControlFunction(U7(127)), U7(2)),
+            MidiMessage::PitchBendChange(Channel::Ch5, crate::PitchBend::MAX),
+            MidiMessage::SysEx(&[U7(1)]),
+        ];
+        for message in messages.iter() {
+            assert_eq!(remapper.remap(message).as_ref(), Some(message));
+        }
+    }
+
+    #[test]
+    fn rewrite_and_drop() {
+        let mut remapper = Remapper::new();
+        remapper.set_controller(
+            ControlFunction::MODULATION_WHEEL,
+            Some(ControlFunction::BREATH_CONTROLLER),
+        );
+        remapper.set_controller(ControlFunction::PAN, None);
+        remapper.set_note(Note::C4, None);
+        remapper.set_channel(Channel::Ch2, None);
+        assert_eq!(
+            remapper.remap(&MidiMessage::ControlChange(
+                Channel::Ch1,
+                ControlFunction::MODULATION_WHEEL,
+                U7(5)
+            )),
+            Some(MidiMessage::ControlChange(
+                Channel::Ch1,
+                ControlFunction::BREATH_CONTROLLER,
+                U7(5)
+            ))
+        );
+        assert_eq!(
+            remapper.remap(&MidiMessage::ControlChange(
+                Channel::Ch1,
+                ControlFunction::PAN,
+                U7(5)
+            )),
+            None
+        );
+        assert_eq!(
+            remapper.remap(&MidiMessage::PolyphonicKeyPressure(
+                Channel::Ch1,
+                Note::C4,
+                U7(5)
+            )),
+            None
+        );
+        let mut count = 0;
+        remapper.handle(&MidiMessage::ProgramChange(Channel::Ch2, U7(0)), |_| {
+            count += 1
+        });
+        assert_eq!(count, 0);
+    }
+}
@@ -0,0 +1,299 @@
+//! Roland exclusive messages, which read and write the parameters of Roland devices by address.
+//!
+//! Addresses and sizes are written as in Roland manuals: each byte of a `u32` holds one 7 bit
+//! byte of the address, most significant first, so address "40 00 7F" is `0x40007F`.
+//!
+//! Documents referred to in this module:
+//! * [Roland]: The "Roland Exclusive Messages" section of the MIDI implementation of Roland
+//!   devices.
+
+use crate::checksum::sum_to_zero_bytes as checksum;
+use crate::{ToSliceError, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// The manufacturer ID of Roland.
+const ROLAND_ID: u8 = 0x41;
+
+/// [Roland] The model ID of a device and the size of its addresses, which are needed to encode
+/// and parse its messages.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Model {
+    id: [u8; 4],
+    id_len: u8,
+    address_size: u8,
+}
+
+impl Model {
+    /// GS sound modules, such as the Sound Canvas series.
+    pub const GS: Model = Model {
+        id: [0x42, 0, 0, 0],
+        id_len: 1,
+        address_size: 3,
+    };
+    /// The MT-32 and compatible LA synthesizers.
+    pub const MT32: Model = Model {
+        id: [0x16, 0, 0, 0],
+        id_len: 1,
+        address_size: 3,
+    };
+    /// The INTEGRA-7 sound module.
+    pub const INTEGRA7: Model = Model {
+        id: [0x00, 0x00, 0x64, 0],
+        id_len: 3,
+        address_size: 4,
+    };
+
+    /// Create a model with a model ID of 1 to 4 bytes and addresses of 3 or 4 bytes. Returns
+    /// `None` if either size is not supported.
+    pub fn new(id: &[U7], address_size: usize) -> Option<Model> {
+        if id.is_empty() || id.len() > 4 || !(3..=4).contains(&address_size) {
+            return None;
+        }
+        let mut model = Model {
+            id: [0; 4],
+            id_len: id.len() as u8,
+            address_size: address_size as u8,
+        };
+        model.id[..id.len()].copy_from_slice(U7::data_to_bytes(id));
+        Some(model)
+    }
+
+    /// The model ID.
+    pub fn id(&self) -> &[u8] {
+        &self.id[..usize::from(self.id_len)]
+    }
+
+    /// The number of bytes in an address.
+    pub fn address_size(&self) -> usize {
+        usize::from(self.address_size)
+    }
+}
+
+/// [Roland] A Data Set 1 (DT1) or Data Request 1 (RQ1) message.
+///
+/// # Example
+/// ```
+/// use wmidi::roland::{Message, Model};
+/// use wmidi::U7;
+/// // Set the GS master volume to 100.
+/// let data = [U7::from_u8_lossy(100)];
+/// let message = Message::DataSet {
+///     address: 0x400004,
+///     data: &data,
+/// };
+/// let mut bytes = [0u8; 11];
+/// message
+///     .copy_sysex_to_slice(U7::from_u8_lossy(0x10), Model::GS, &mut bytes)
+///     .unwrap();
+/// assert_eq!(
+///     bytes,
+///     [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x04, 0x64, 0x58, 0xF7]
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Message<'a> {
+    /// Write `data` starting at `address`.
+    DataSet { address: u32, data: &'a [U7] },
+    /// Ask the device to send `size` bytes starting at `address` as Data Set messages. `size` is
+    /// written in the same notation as addresses.
+    DataRequest { address: u32, size: u32 },
+}
+
+impl<'a> Message<'a> {
+    /// The number of bytes in the message for `model`, including the start and end bytes.
+    pub fn bytes_size(&self, model: Model) -> usize {
+        let body = match self {
+            Message::DataSet { data, .. } => data.len(),
+            Message::DataRequest { .. } => model.address_size(),
+        };
+        5 + model.id().len() + model.address_size() + body + 1
+    }
+
+    /// Write the SysEx message for `model` to `slice`, including the start and end bytes. Roland
+    /// devices default to a `device_id` of 0x10, and 0x7F addresses all devices. Returns the
+    /// number of bytes written.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        model: Model,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size(model);
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let id = model.id();
+        let address_size = model.address_size();
+        slice[..3].copy_from_slice(&[0xF0, ROLAND_ID, u8::from(device_id)]);
+        slice[3..3 + id.len()].copy_from_slice(id);
+        let start = 3 + id.len() + 1;
+        let (command, address) = match self {
+            Message::DataSet { address, .. } => (0x12, *address),
+            Message::DataRequest { address, .. } => (0x11, *address),
+        };
+        slice[start - 1] = command;
+        write_address(address, &mut slice[start..start + address_size]);
+        let body = &mut slice[start + address_size..size - 2];
+        match self {
+            Message::DataSet { data, .. } => body.copy_from_slice(U7::data_to_bytes(data)),
+            Message::DataRequest { size, .. } => write_address(*size, body),
+        }
+        slice[size - 2] = checksum(&slice[start..size - 2]);
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The SysEx message for `model`, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7, model: Model) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size(model)];
+        self.copy_sysex_to_slice(device_id, model, &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a DT1 or RQ1 message
+    /// for `model` or the checksum is incorrect.
+    pub fn from_sysex(payload: &'a [U7], model: Model) -> Option<(U7, Message<'a>)> {
+        let bytes = U7::data_to_bytes(payload);
+        let id = model.id();
+        let address_size = model.address_size();
+        let start = 2 + id.len() + 1;
+        if bytes.len() < start + address_size + 1
+            || bytes[0] != ROLAND_ID
+            || &bytes[2..2 + id.len()] != id
+        {
+            return None;
+        }
+        let (checksum_byte, checked) = bytes[start..].split_last()?;
+        if checksum(checked) != *checksum_byte {
+            return None;
+        }
+        let address = read_address(&checked[..address_size]);
+        let body = &payload[start + address_size..payload.len() - 1];
+        let message = match bytes[start - 1] {
+            0x12 => Message::DataSet {
+                address,
+                data: body,
+            },
+            0x11 if body.len() == address_size => Message::DataRequest {
+                address,
+                size: read_address(U7::data_to_bytes(body)),
+            },
+            _ => return None,
+        };
+        Some((payload[1], message))
+    }
+}
+
+/// Add `offset` bytes to `address`, carrying between the 7 bit bytes of the address. This finds
+/// the address of each message when splitting a block of data into several Data Set messages.
+///
+/// # Example
+/// ```
+/// use wmidi::roland::offset_address;
+/// assert_eq!(offset_address(0x40007F, 1), 0x400100);
+/// ```
+pub fn offset_address(address: u32, offset: u32) -> u32 {
+    let linear = (0..4).fold(0u32, |linear, i| {
+        linear | (address >> (8 * i) & 0x7F) << (7 * i)
+    });
+    let linear = linear.wrapping_add(offset);
+    (0..4).fold(0u32, |address, i| {
+        address | (linear >> (7 * i) & 0x7F) << (8 * i)
+    })
+}
+
+fn write_address(address: u32, slice: &mut [u8]) {
+    let len = slice.len();
+    for (i, byte) in slice.iter_mut().enumerate() {
+        *byte = (address >> (8 * (len - 1 - i))) as u8 & 0x7F;
+    }
+}
+
+fn read_address(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0u32, |address, byte| address << 8 | u32::from(*byte))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let data = [U7(0x01), U7(0x7F), U7(0x00)];
+        let messages = [
+            (
+                Model::GS,
+                Message::DataSet {
+                    address: 0x40007F,
+                    data: &[U7::MIN],
+                },
+            ),
+            (
+                Model::INTEGRA7,
+                Message::DataSet {
+                    address: 0x19000000,
+                    data: &data,
+                },
+            ),
+            (
+                Model::MT32,
+                Message::DataRequest {
+                    address: 0x050000,
+                    size: 0x000100,
+                },
+            ),
+            (
+                Model::new(&[U7(0x00), U7(0x6A)], 4).unwrap(),
+                Message::DataRequest {
+                    address: 0x7F7F7F7F,
+                    size: 0x00000001,
+                },
+            ),
+        ];
+        for (model, message) in messages.iter() {
+            let mut bytes = [0u8; 32];
+            let size = message
+                .copy_sysex_to_slice(U7(0x10), *model, &mut bytes)
+                .unwrap();
+            assert_eq!(size, message.bytes_size(*model));
+            let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(
+                Message::from_sysex(payload, *model),
+                Some((U7(0x10), *message))
+            );
+        }
+    }
+
+    #[test]
+    fn from_sysex_rejects() {
+        let gs_reset = [0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x41];
+        let payload = U7::try_from_bytes(&gs_reset).unwrap();
+        assert!(Message::from_sysex(payload, Model::GS).is_some());
+        assert_eq!(Message::from_sysex(payload, Model::MT32), None);
+        let mut bad_checksum = gs_reset;
+        bad_checksum[8] = 0x40;
+        let payload = U7::try_from_bytes(&bad_checksum).unwrap();
+        assert_eq!(Message::from_sysex(payload, Model::GS), None);
+        assert_eq!(Model::new(&[], 3), None);
+        assert_eq!(Model::new(&[U7(0x42)], 2), None);
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(offset_address(0x00007F, 0x81), 0x000200);
+        assert_eq!(offset_address(0x19000000, 0), 0x19000000);
+        assert_eq!(offset_address(0x7F7F7F, 1), 0x01000000);
+    }
+}
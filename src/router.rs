@@ -0,0 +1,108 @@
+use crate::{Channel, ChannelSet, Filter, MessageKind, MidiMessage};
+use std::boxed::Box;
+use std::vec::Vec;
+
+type Handler<'a> = Box<dyn FnMut(&MidiMessage) + 'a>;
+
+/// Dispatches messages to the handlers registered for them.
+///
+/// Handlers are called in the order they were registered. Every handler whose filter allows a
+/// message is called, so a message may reach more than one handler.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MessageKind, MidiMessage, Note, Router, U7};
+/// let mut notes = 0;
+/// let mut drums = 0;
+/// {
+///     let mut router = Router::new();
+///     router.on(MessageKind::NoteOn, |_| notes += 1);
+///     router.on_channel(MessageKind::NoteOn, Channel::Ch10, |_| drums += 1);
+///     router.route(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX));
+///     router.route(&MidiMessage::NoteOn(Channel::Ch10, Note::C2, U7::MAX));
+///     assert!(!router.route(&MidiMessage::TimingClock));
+/// }
+/// assert_eq!((notes, drums), (2, 1));
+/// ```
+#[derive(Default)]
+pub struct Router<'a> {
+    routes: Vec<(Filter, Handler<'a>)>,
+}
+
+impl<'a> Router<'a> {
+    /// Create a router with no handlers.
+    pub fn new() -> Router<'a> {
+        Router { routes: Vec::new() }
+    }
+
+    /// Call `handler` with every message of `kind`.
+    pub fn on<F: FnMut(&MidiMessage) + 'a>(&mut self, kind: MessageKind, handler: F) {
+        self.on_filter(Filter::from(kind), handler);
+    }
+
+    /// Call `handler` with every message of `kind` on `channel`. Messages without a channel are
+    /// passed to `handler` if they are of `kind`.
+    pub fn on_channel<F: FnMut(&MidiMessage) + 'a>(
+        &mut self,
+        kind: MessageKind,
+        channel: Channel,
+        handler: F,
+    ) {
+        let filter = Filter {
+            channels: ChannelSet::from(channel),
+            ..Filter::from(kind)
+        };
+        self.on_filter(filter, handler);
+    }
+
+    /// Call `handler` with every message that `filter` allows.
+    pub fn on_filter<F: FnMut(&MidiMessage) + 'a>(&mut self, filter: Filter, handler: F) {
+        self.routes.push((filter, Box::new(handler)));
+    }
+
+    /// Pass `message` to each handler registered for it. Returns `true` if any handler was called.
+    pub fn route(&mut self, message: &MidiMessage) -> bool {
+        let mut handled = false;
+        for (filter, handler) in self.routes.iter_mut() {
+            if filter.allows(message) {
+                handler(message);
+                handled = true;
+            }
+        }
+        handled
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ControlFunction, U7};
+
+    #[test]
+    fn routes_in_registration_order() {
+        let mut calls = Vec::new();
+        {
+            let calls = std::cell::RefCell::new(&mut calls);
+            let mut router = Router::new();
+            router.on(MessageKind::ControlChange, |_| calls.borrow_mut().push(1));
+            router.on_filter(Filter::new(), |_| calls.borrow_mut().push(2));
+            router.on_channel(MessageKind::ControlChange, Channel::Ch2, |_| {
+                calls.borrow_mut().push(3)
+            });
+            assert!(router.route(&MidiMessage::ControlChange(
+                Channel::Ch2,
+                ControlFunction::PAN,
+                U7(0)
+            )));
+            assert!(router.route(&MidiMessage::Stop));
+        }
+        assert_eq!(calls, [1, 2, 3, 2]);
+    }
+
+    #[test]
+    fn unhandled() {
+        let mut router = Router::new();
+        router.on_channel(MessageKind::NoteOn, Channel::Ch1, |_| {});
+        assert!(!router.route(&MidiMessage::NoteOn(Channel::Ch2, crate::Note::C4, U7(1))));
+    }
+}
@@ -0,0 +1,387 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+//! * [MTS]: MIDI Tuning Updated Specification (CA-020, CA-021, RP-020)
+//! * [CA-026]: RPN05 Modulation Depth Range
+//! * [MPE]: MIDI Polyphonic Expression, version 1.0
+//! * [UMP]: Universal MIDI Packet (UMP) Format and MIDI 2.0 Protocol, version 1.1.2
+
+use crate::{
+    encode_high_res, Cents, Channel, ControlFunction, LsbMode, MidiMessage, PitchBendSensitivity,
+    U14, U7,
+};
+
+/// A Registered (RPN) or Non-Registered (NRPN) Parameter Number, selected with controllers
+/// 101/100 or 99/98 before sending Data Entry.
+///
+/// [UMP] In MIDI 2.0 the same numbers address the Registered and Assignable Controllers. The
+/// bank is the MSB of the parameter number and the index is the LSB, so the Registered
+/// Controllers of bank 0 are the RPNs of MIDI 1.0.
+///
+/// # Example
+/// ```
+/// use wmidi::{ParameterNumber, U7};
+/// let parameter = ParameterNumber::registered_controller(U7::MIN, U7::from_u8_lossy(1));
+/// assert_eq!(parameter, ParameterNumber::FINE_TUNING);
+/// assert_eq!((parameter.bank(), parameter.index()), (U7::MIN, U7::from_u8_lossy(1)));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ParameterNumber {
+    /// A parameter defined by the MIDI Association.
+    Registered(U14),
+    /// A manufacturer specific parameter.
+    NonRegistered(U14),
+}
+
+impl ParameterNumber {
+    /// [MIDI 1.0] RPN 00/00: The pitch bend range. The data entry MSB is semitones and the LSB is
+    /// cents.
+    pub const PITCH_BEND_SENSITIVITY: ParameterNumber = ParameterNumber::registered(0x0000);
+    /// [MIDI 1.0] RPN 00/01: Fine tuning in 100/8192 cent steps, centered on 0x2000.
+    pub const FINE_TUNING: ParameterNumber = ParameterNumber::registered(0x0001);
+    /// [MIDI 1.0] RPN 00/02: Coarse tuning in semitones, set with the data entry MSB centered on
+    /// 64.
+    pub const COARSE_TUNING: ParameterNumber = ParameterNumber::registered(0x0002);
+    /// [MTS] RPN 00/03: Selects a tuning program.
+    pub const TUNING_PROGRAM_SELECT: ParameterNumber = ParameterNumber::registered(0x0003);
+    /// [MTS] RPN 00/04: Selects a tuning bank.
+    pub const TUNING_BANK_SELECT: ParameterNumber = ParameterNumber::registered(0x0004);
+    /// [CA-026] RPN 00/05: The depth of the modulation wheel.
+    pub const MODULATION_DEPTH_RANGE: ParameterNumber = ParameterNumber::registered(0x0005);
+    /// [MPE] RPN 00/06: The MPE Configuration Message.
+    pub const MPE_CONFIGURATION: ParameterNumber = ParameterNumber::registered(0x0006);
+    /// RPN 7F/7F: Deselects the current parameter so that stray Data Entry messages are ignored.
+    pub const NULL: ParameterNumber = ParameterNumber::registered(0x3FFF);
+
+    const fn registered(number: u16) -> ParameterNumber {
+        ParameterNumber::Registered(U14::from_u16_lossy(number))
+    }
+
+    /// The 14 bit parameter number.
+    pub fn number(self) -> U14 {
+        match self {
+            ParameterNumber::Registered(n) | ParameterNumber::NonRegistered(n) => n,
+        }
+    }
+
+    /// [UMP] The Registered Controller at `index` of `bank`, the same as RPN `bank`/`index`.
+    pub fn registered_controller(bank: U7, index: U7) -> ParameterNumber {
+        ParameterNumber::Registered(from_bank_index(bank, index))
+    }
+
+    /// [UMP] The Assignable Controller at `index` of `bank`, the same as NRPN `bank`/`index`.
+    pub fn assignable_controller(bank: U7, index: U7) -> ParameterNumber {
+        ParameterNumber::NonRegistered(from_bank_index(bank, index))
+    }
+
+    /// Returns `true` for a Registered Parameter Number or Registered Controller.
+    pub fn is_registered(self) -> bool {
+        matches!(self, ParameterNumber::Registered(_))
+    }
+
+    /// The bank of the controller, which is the MSB of the parameter number.
+    pub fn bank(self) -> U7 {
+        U7((u16::from(self.number()) >> 7) as u8)
+    }
+
+    /// The index of the controller within its bank, which is the LSB of the parameter number.
+    pub fn index(self) -> U7 {
+        U7((u16::from(self.number()) & 0x7F) as u8)
+    }
+
+    /// The value the parameter has after a reset, or `None` if it has no standard default.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{ParameterNumber, PitchBendSensitivity};
+    /// assert_eq!(
+    ///     ParameterNumber::PITCH_BEND_SENSITIVITY.default_value(),
+    ///     Some(PitchBendSensitivity::DEFAULT.to_parameter_value())
+    /// );
+    /// assert_eq!(ParameterNumber::MPE_CONFIGURATION.default_value(), None);
+    /// ```
+    pub fn default_value(self) -> Option<U14> {
+        let value = match self {
+            ParameterNumber::PITCH_BEND_SENSITIVITY => {
+                PitchBendSensitivity::DEFAULT.to_parameter_value()
+            }
+            ParameterNumber::FINE_TUNING | ParameterNumber::COARSE_TUNING => {
+                U14::from_u16_lossy(0x2000)
+            }
+            ParameterNumber::TUNING_PROGRAM_SELECT | ParameterNumber::TUNING_BANK_SELECT => {
+                U14::from_u16_lossy(0)
+            }
+            ParameterNumber::MODULATION_DEPTH_RANGE => {
+                ModulationDepthRange::DEFAULT.to_parameter_value()
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Emit the `ControlChange` messages that select this parameter, MSB first.
+    pub fn emit_select<F: FnMut(MidiMessage<'static>)>(self, channel: Channel, mut emit: F) {
+        let (msb_function, lsb_function) = match self {
+            ParameterNumber::Registered(_) => (
+                ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB,
+                ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB,
+            ),
+            ParameterNumber::NonRegistered(_) => (
+                ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB,
+                ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB,
+            ),
+        };
+        emit(MidiMessage::ControlChange(
+            channel,
+            msb_function,
+            self.bank(),
+        ));
+        emit(MidiMessage::ControlChange(
+            channel,
+            lsb_function,
+            self.index(),
+        ));
+    }
+}
+
+fn from_bank_index(bank: U7, index: U7) -> U14 {
+    U14::from_u16_lossy(u16::from(u8::from(bank)) << 7 | u16::from(u8::from(index)))
+}
+
+/// Emit the `ControlChange` messages that set `parameter` to `value`: the parameter select,
+/// Data Entry MSB, Data Entry LSB as determined by `lsb_mode`, and finally a `ParameterNumber::NULL`
+/// select so later Data Entry messages do not change the parameter by accident.
+///
+/// # Example
+/// ```
+/// use wmidi::{encode_parameter, Channel, LsbMode, MidiMessage, ParameterNumber, U14};
+/// # use std::convert::TryFrom;
+/// // Set the pitch bend range to 12 semitones.
+/// let mut messages = Vec::new();
+/// let value = U14::try_from(12 << 7).unwrap();
+/// encode_parameter(
+///     Channel::Ch1,
+///     ParameterNumber::PITCH_BEND_SENSITIVITY,
+///     value,
+///     LsbMode::OmitZero,
+///     |m| messages.push(m),
+/// );
+/// assert_eq!(messages.len(), 5);
+/// ```
+pub fn encode_parameter<F: FnMut(MidiMessage<'static>)>(
+    channel: Channel,
+    parameter: ParameterNumber,
+    value: U14,
+    lsb_mode: LsbMode,
+    mut emit: F,
+) {
+    parameter.emit_select(channel, &mut emit);
+    // Data Entry MSB is always a valid high resolution controller.
+    encode_high_res(
+        channel,
+        ControlFunction::DATA_ENTRY_MSB,
+        value,
+        lsb_mode,
+        &mut emit,
+    )
+    .unwrap();
+    ParameterNumber::NULL.emit_select(channel, emit);
+}
+
+/// [CA-026] The peak depth of the modulation wheel, set with RPN 00/05
+/// (`ParameterNumber::MODULATION_DEPTH_RANGE`). The Data Entry MSB holds semitones and the LSB
+/// holds 128ths of a semitone.
+///
+/// # Example
+/// ```
+/// use wmidi::{Cents, ModulationDepthRange, U7};
+/// let depth = ModulationDepthRange::from_cents(Cents(150.0));
+/// assert_eq!(depth.depth(), Cents(150.0));
+/// assert_eq!(depth.depth_at(U7::MIN), Cents::ZERO);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ModulationDepthRange {
+    /// The whole semitones of the depth, sent as the Data Entry MSB.
+    pub semitones: U7,
+    /// The additional depth in 128ths of a semitone, sent as the Data Entry LSB.
+    pub fraction: U7,
+}
+
+impl ModulationDepthRange {
+    /// [GM2] The default depth of 50 cents.
+    pub const DEFAULT: ModulationDepthRange = ModulationDepthRange {
+        semitones: U7(0),
+        fraction: U7(64),
+    };
+
+    /// Create a depth of `semitones` plus `fraction`/128 of a semitone.
+    pub fn new(semitones: U7, fraction: U7) -> ModulationDepthRange {
+        ModulationDepthRange {
+            semitones,
+            fraction,
+        }
+    }
+
+    /// The closest representable depth to `depth`. Depths are clamped between 0 and just
+    /// under 128 semitones.
+    pub fn from_cents(depth: Cents) -> ModulationDepthRange {
+        let steps = if depth.0.is_nan() {
+            0.0
+        } else {
+            (depth.0 * 128.0 / 100.0).clamp(0.0, 16383.0)
+        };
+        ModulationDepthRange::from_parameter_value(U14::from_u16_lossy((steps + 0.5) as u16))
+    }
+
+    /// The peak depth of modulation, reached when the modulation wheel is at its maximum.
+    pub fn depth(self) -> Cents {
+        let steps = f32::from(u16::from(self.to_parameter_value()));
+        Cents(steps * 100.0 / 128.0)
+    }
+
+    /// The depth of modulation when the modulation wheel MSB is at `wheel`.
+    pub fn depth_at(self, wheel: U7) -> Cents {
+        Cents(self.depth().0 * f32::from(u8::from(wheel)) / 127.0)
+    }
+
+    /// The RPN 00/05 value, with semitones in the MSB and the fraction in the LSB.
+    pub fn to_parameter_value(self) -> U14 {
+        let value = u16::from(u8::from(self.semitones)) << 7 | u16::from(u8::from(self.fraction));
+        U14::from_u16_lossy(value)
+    }
+
+    /// Decode an RPN 00/05 value.
+    pub fn from_parameter_value(value: U14) -> ModulationDepthRange {
+        let value = u16::from(value);
+        ModulationDepthRange {
+            semitones: U7((value >> 7) as u8),
+            fraction: U7((value & 0x7F) as u8),
+        }
+    }
+
+    /// Emit the `ControlChange` messages that set the modulation depth range of `channel`.
+    pub fn encode<F: FnMut(MidiMessage<'static>)>(self, channel: Channel, emit: F) {
+        encode_parameter(
+            channel,
+            ParameterNumber::MODULATION_DEPTH_RANGE,
+            self.to_parameter_value(),
+            LsbMode::OmitZero,
+            emit,
+        );
+    }
+}
+
+impl Default for ModulationDepthRange {
+    fn default() -> ModulationDepthRange {
+        ModulationDepthRange::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::convert::TryFrom;
+
+    fn collect(parameter: ParameterNumber, value: u16, lsb_mode: LsbMode) -> [u8; 18] {
+        let mut bytes = [0u8; 18];
+        let mut len = 0;
+        encode_parameter(
+            Channel::Ch2,
+            parameter,
+            U14::try_from(value).unwrap(),
+            lsb_mode,
+            |m| {
+                len += m.copy_to_slice(&mut bytes[len..]).unwrap();
+            },
+        );
+        assert_eq!(len, bytes.len());
+        bytes
+    }
+
+    #[test]
+    fn registered_parameter() {
+        let bytes = collect(ParameterNumber::FINE_TUNING, 0x2001, LsbMode::Always);
+        assert_eq!(
+            bytes,
+            [
+                0xB1, 101, 0, 0xB1, 100, 1, 0xB1, 6, 0x40, 0xB1, 38, 1, 0xB1, 101, 0x7F, 0xB1, 100,
+                0x7F
+            ]
+        );
+    }
+
+    #[test]
+    fn non_registered_parameter() {
+        let parameter = ParameterNumber::NonRegistered(U14::try_from(0x0102).unwrap());
+        let bytes = collect(parameter, 0x0100, LsbMode::Always);
+        assert_eq!(&bytes[..6], &[0xB1, 99, 2, 0xB1, 98, 2]);
+        assert_eq!(&bytes[6..12], &[0xB1, 6, 2, 0xB1, 38, 0]);
+    }
+
+    #[test]
+    fn bank_and_index() {
+        let parameter = ParameterNumber::assignable_controller(U7(0x7F), U7(0x01));
+        assert_eq!(
+            parameter,
+            ParameterNumber::NonRegistered(U14::try_from(0x3F81).unwrap())
+        );
+        assert!(!parameter.is_registered());
+        assert_eq!(parameter.bank(), U7(0x7F));
+        assert_eq!(parameter.index(), U7(0x01));
+        assert_eq!(parameter.default_value(), None);
+        assert_eq!(
+            ParameterNumber::registered_controller(U7(0x7F), U7(0x7F)),
+            ParameterNumber::NULL
+        );
+        assert_eq!(
+            ParameterNumber::COARSE_TUNING.default_value(),
+            Some(U14::try_from(64 << 7).unwrap())
+        );
+    }
+
+    #[test]
+    fn modulation_depth_range() {
+        assert_eq!(ModulationDepthRange::DEFAULT.depth(), Cents(50.0));
+        assert_eq!(
+            ModulationDepthRange::from_cents(Cents(50.0)),
+            ModulationDepthRange::DEFAULT
+        );
+        let depth = ModulationDepthRange::new(U7(1), U7(32));
+        assert_eq!(u16::from(depth.to_parameter_value()), 0x00A0);
+        assert_eq!(depth.depth(), Cents(125.0));
+        assert_eq!(depth.depth_at(U7::MAX), Cents(125.0));
+        assert_eq!(
+            ModulationDepthRange::from_parameter_value(depth.to_parameter_value()),
+            depth
+        );
+        assert_eq!(
+            ModulationDepthRange::from_cents(Cents(-1.0)),
+            ModulationDepthRange::new(U7(0), U7(0))
+        );
+        assert_eq!(
+            ModulationDepthRange::from_cents(Cents(1E9)),
+            ModulationDepthRange::new(U7::MAX, U7::MAX)
+        );
+        let mut bytes = [0u8; 18];
+        let mut len = 0;
+        depth.encode(Channel::Ch1, |m| {
+            len += m.copy_to_slice(&mut bytes[len..]).unwrap();
+        });
+        assert_eq!(
+            &bytes[..12],
+            &[0xB0, 101, 0, 0xB0, 100, 5, 0xB0, 6, 1, 0xB0, 38, 32]
+        );
+    }
+
+    #[test]
+    fn omit_zero_lsb() {
+        let mut count = 0;
+        encode_parameter(
+            Channel::Ch1,
+            ParameterNumber::COARSE_TUNING,
+            U14::try_from(64 << 7).unwrap(),
+            LsbMode::OmitZero,
+            |_| count += 1,
+        );
+        assert_eq!(count, 5);
+    }
+}
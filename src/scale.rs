@@ -0,0 +1,208 @@
+use crate::{Note, NoteRange};
+
+/// The interval structure of a `Scale`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ScaleKind {
+    /// The major scale, also known as the Ionian mode.
+    Major,
+    /// The natural minor scale, also known as the Aeolian mode.
+    NaturalMinor,
+    /// The natural minor scale with a raised 7th.
+    HarmonicMinor,
+    /// The natural minor scale with a raised 6th and 7th.
+    MelodicMinor,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Locrian,
+    MajorPentatonic,
+    MinorPentatonic,
+    /// All 12 pitch classes.
+    Chromatic,
+}
+
+impl ScaleKind {
+    /// The half steps above the root that make up the scale.
+    pub fn intervals(self) -> &'static [u8] {
+        match self {
+            ScaleKind::Major => &[0, 2, 4, 5, 7, 9, 11],
+            ScaleKind::NaturalMinor => &[0, 2, 3, 5, 7, 8, 10],
+            ScaleKind::HarmonicMinor => &[0, 2, 3, 5, 7, 8, 11],
+            ScaleKind::MelodicMinor => &[0, 2, 3, 5, 7, 9, 11],
+            ScaleKind::Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            ScaleKind::Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            ScaleKind::Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            ScaleKind::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            ScaleKind::Locrian => &[0, 1, 3, 5, 6, 8, 10],
+            ScaleKind::MajorPentatonic => &[0, 2, 4, 7, 9],
+            ScaleKind::MinorPentatonic => &[0, 3, 5, 7, 10],
+            ScaleKind::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        }
+    }
+}
+
+/// A set of pitch classes built on a root.
+///
+/// Scales repeat every octave, so only the pitch class of the root is significant.
+///
+/// # Example
+/// ```
+/// use wmidi::{Note, Scale, ScaleKind};
+/// let c_major = Scale::new(Note::C4, ScaleKind::Major);
+/// assert!(c_major.contains(Note::E2));
+/// assert!(!c_major.contains(Note::Eb2));
+/// assert_eq!(c_major.quantize(Note::Gb4), Note::F4);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Scale {
+    root: u8,
+    // Bit `n` is set if the pitch class `n` half steps above `root` is in the scale.
+    intervals: u16,
+}
+
+impl Scale {
+    /// Create a scale of `kind` with `root` as the first degree.
+    pub fn new(root: Note, kind: ScaleKind) -> Scale {
+        Scale::from_intervals(root, kind.intervals())
+    }
+
+    /// Create a scale from a custom set of intervals, in half steps above `root`. Intervals are
+    /// taken modulo 12 and the root is always part of the scale.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Note, Scale};
+    /// let whole_tone = Scale::from_intervals(Note::C4, &[0, 2, 4, 6, 8, 10]);
+    /// assert!(whole_tone.contains(Note::Gb4));
+    /// ```
+    pub fn from_intervals(root: Note, intervals: &[u8]) -> Scale {
+        let intervals = intervals
+            .iter()
+            .fold(1u16, |mask, interval| mask | 1 << (interval % 12));
+        Scale {
+            root: u8::from(root) % 12,
+            intervals,
+        }
+    }
+
+    /// The pitch class of the root, between 0 and 11 inclusive. `C` is `0`.
+    pub fn root_pitch_class(&self) -> u8 {
+        self.root
+    }
+
+    /// The number of pitch classes in the scale.
+    pub fn degree_count(&self) -> usize {
+        self.intervals.count_ones() as usize
+    }
+
+    /// Returns `true` if the pitch class of `note` is part of the scale.
+    pub fn contains(&self, note: Note) -> bool {
+        let interval = (u8::from(note) + 12 - self.root) % 12;
+        self.intervals & (1 << interval) != 0
+    }
+
+    /// Snap `note` to the nearest note in the scale. When two scale notes are equally close, the
+    /// lower one is chosen. Notes at the edges of the MIDI range snap inwards if needed.
+    pub fn quantize(&self, note: Note) -> Note {
+        for distance in 0..12 {
+            let candidates = [-distance, distance];
+            for half_steps in candidates.iter() {
+                if let Ok(candidate) = note.step(*half_steps) {
+                    if self.contains(candidate) {
+                        return candidate;
+                    }
+                }
+            }
+        }
+        note
+    }
+
+    /// Iterate over all notes of the scale between `lo` and `hi` inclusive, in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Note, Scale, ScaleKind};
+    /// let a_minor_pentatonic = Scale::new(Note::A3, ScaleKind::MinorPentatonic);
+    /// let notes: Vec<Note> = a_minor_pentatonic.notes_in_range(Note::A3, Note::A4).collect();
+    /// assert_eq!(
+    ///     notes,
+    ///     [Note::A3, Note::C4, Note::D4, Note::E4, Note::G4, Note::A4]
+    /// );
+    /// ```
+    pub fn notes_in_range(&self, lo: Note, hi: Note) -> ScaleNotes {
+        ScaleNotes {
+            scale: *self,
+            notes: Note::range(lo, hi),
+        }
+    }
+}
+
+/// An iterator over the notes of a `Scale`. Created with `Scale::notes_in_range`.
+#[derive(Clone, Debug)]
+pub struct ScaleNotes {
+    scale: Scale,
+    notes: NoteRange,
+}
+
+impl Iterator for ScaleNotes {
+    type Item = Note;
+
+    fn next(&mut self) -> Option<Note> {
+        let scale = self.scale;
+        self.notes.find(|note| scale.contains(*note))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn contains() {
+        let d_dorian = Scale::new(Note::D2, ScaleKind::Dorian);
+        for note in [Note::C4, Note::D4, Note::E4, Note::F4, Note::B4].iter() {
+            assert!(d_dorian.contains(*note), "{}", note);
+        }
+        assert!(!d_dorian.contains(Note::Bb4));
+        assert_eq!(d_dorian.degree_count(), 7);
+    }
+
+    #[test]
+    fn quantize_prefers_lower_on_ties() {
+        let c_major_pentatonic = Scale::new(Note::C4, ScaleKind::MajorPentatonic);
+        assert_eq!(c_major_pentatonic.quantize(Note::C4), Note::C4);
+        assert_eq!(c_major_pentatonic.quantize(Note::Db4), Note::C4);
+        assert_eq!(c_major_pentatonic.quantize(Note::F4), Note::E4);
+        assert_eq!(c_major_pentatonic.quantize(Note::Gb4), Note::G4);
+        assert_eq!(c_major_pentatonic.quantize(Note::B4), Note::C5);
+    }
+
+    #[test]
+    fn quantize_stays_in_range() {
+        let scale = Scale::from_intervals(Note::A0, &[0]);
+        assert_eq!(scale.quantize(Note::CMinus1), Note::AMinus1);
+        assert_eq!(scale.quantize(Note::G9), Note::A8);
+    }
+
+    #[test]
+    fn custom_intervals_include_root() {
+        let scale = Scale::from_intervals(Note::E4, &[4, 19]);
+        assert_eq!(scale.degree_count(), 3);
+        assert!(scale.contains(Note::E0));
+        assert!(scale.contains(Note::Ab0));
+        assert!(scale.contains(Note::B0));
+    }
+
+    #[test]
+    fn notes_in_range_handles_edges() {
+        let chromatic = Scale::new(Note::C4, ScaleKind::Chromatic);
+        assert_eq!(
+            chromatic
+                .notes_in_range(Note::LOWEST_NOTE, Note::HIGHEST_NOTE)
+                .count(),
+            128
+        );
+        assert_eq!(chromatic.notes_in_range(Note::D4, Note::C4).count(), 0);
+    }
+}
@@ -0,0 +1,679 @@
+//! The Sample Dump Standard, which transfers samples between samplers and computers.
+//!
+//! A transfer is a `DumpHeader` describing the sample, followed by `DataPacket`s holding 120
+//! bytes of sample data each. The receiver answers each message with a `Handshake`: an ACK to
+//! continue, a NAK to have the packet sent again, a WAIT to pause, or a CANCEL to stop. `Sender`
+//! and `Receiver` track the two sides of a transfer.
+//!
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::{Handshake, HandshakeKind, ToSliceError, U14, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [MIDI 1.0] How the sustain loop of a sample is played.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum LoopType {
+    /// Play the loop forwards.
+    Forward,
+    /// Play the loop forwards, then backwards.
+    Alternating,
+    /// Do not loop.
+    Off,
+}
+
+impl LoopType {
+    fn code(self) -> u8 {
+        match self {
+            LoopType::Forward => 0x00,
+            LoopType::Alternating => 0x01,
+            LoopType::Off => 0x7F,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<LoopType> {
+        match code {
+            0x00 => Some(LoopType::Forward),
+            0x01 => Some(LoopType::Alternating),
+            0x7F => Some(LoopType::Off),
+            _ => None,
+        }
+    }
+}
+
+/// [MIDI 1.0] Describes the sample that follows in the data packets.
+///
+/// Lengths and loop points are in words (samples), and hold up to 21 bits.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DumpHeader {
+    /// The sample number on the sending device.
+    pub sample_number: U14,
+    /// The number of bits per sample, from 8 to 28. Other values are clamped to this range when
+    /// the header is written.
+    pub format: u8,
+    /// The time between samples in nanoseconds.
+    pub period: u32,
+    /// The number of samples.
+    pub length: u32,
+    /// The first sample of the sustain loop.
+    pub loop_start: u32,
+    /// The last sample of the sustain loop.
+    pub loop_end: u32,
+    /// How the sustain loop is played.
+    pub loop_type: LoopType,
+}
+
+impl DumpHeader {
+    /// The number of data bytes holding each sample.
+    pub fn bytes_per_sample(&self) -> usize {
+        usize::from(self.format.clamp(8, 28)).div_ceil(7)
+    }
+
+    /// The number of samples in each data packet.
+    pub fn samples_per_packet(&self) -> usize {
+        DataPacket::DATA_SIZE / self.bytes_per_sample()
+    }
+
+    /// The number of data packets needed to hold `length` samples.
+    pub fn packet_count(&self) -> u32 {
+        self.length.div_ceil(self.samples_per_packet() as u32)
+    }
+
+    /// The sample rate in Hz, or 0.0 if the period is 0.
+    pub fn sample_rate(&self) -> f64 {
+        if self.period == 0 {
+            return 0.0;
+        }
+        1_000_000_000.0 / f64::from(self.period)
+    }
+}
+
+/// [MIDI 1.0] 120 bytes of sample data.
+///
+/// Samples are unsigned, with the smallest value at 0. Each sample is left justified in
+/// `DumpHeader::bytes_per_sample` bytes of 7 bits, most significant bits first.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DataPacket {
+    /// The number of the packet, counting from 0 and wrapping after 127.
+    pub number: U7,
+    /// The sample data.
+    pub data: [U7; DataPacket::DATA_SIZE],
+}
+
+impl DataPacket {
+    /// The number of data bytes in a packet.
+    pub const DATA_SIZE: usize = 120;
+
+    /// Pack as many of `samples` as fit into a packet, using the format of `header`. Only the
+    /// `header.format` least significant bits of each sample are used. Unused bytes are 0.
+    /// Returns the packet and the number of samples used.
+    pub fn from_samples(number: U7, header: &DumpHeader, samples: &[u32]) -> (DataPacket, usize) {
+        let bytes_per_sample = header.bytes_per_sample();
+        let shift = bytes_per_sample * 7 - usize::from(header.format.clamp(8, 28));
+        let mut data = [U7::MIN; DataPacket::DATA_SIZE];
+        let mut count = 0;
+        for (chunk, sample) in data.chunks_exact_mut(bytes_per_sample).zip(samples) {
+            let value = sample << shift;
+            for (i, byte) in chunk.iter_mut().enumerate() {
+                *byte = U7::from_u8_lossy((value >> ((bytes_per_sample - 1 - i) * 7)) as u8);
+            }
+            count += 1;
+        }
+        (DataPacket { number, data }, count)
+    }
+
+    /// The samples in the packet, using the format of `header`. The last packet of a dump may
+    /// hold fewer samples than are returned.
+    pub fn samples<'a>(&'a self, header: &DumpHeader) -> impl 'a + Iterator<Item = u32> {
+        let bytes_per_sample = header.bytes_per_sample();
+        let shift = bytes_per_sample * 7 - usize::from(header.format.clamp(8, 28));
+        self.data.chunks_exact(bytes_per_sample).map(move |chunk| {
+            let value = chunk
+                .iter()
+                .fold(0u32, |value, byte| value << 7 | u32::from(u8::from(*byte)));
+            value >> shift
+        })
+    }
+
+    /// The checksum sent after the data: the exclusive or of the bytes after the start byte.
+    pub fn checksum(&self, device_id: U7) -> U7 {
+        let header = 0x7E ^ u8::from(device_id) ^ 0x02 ^ u8::from(self.number);
+        let checksum = self
+            .data
+            .iter()
+            .fold(header, |checksum, byte| checksum ^ u8::from(*byte));
+        U7::from_u8_lossy(checksum)
+    }
+}
+
+/// [MIDI 1.0] A Sample Dump Standard message.
+///
+/// # Example
+/// ```
+/// use wmidi::sds::Message;
+/// use wmidi::{U14, U7};
+/// let request = Message::Request(U14::from_u16_lossy(0x81));
+/// let mut bytes = [0u8; 7];
+/// request.copy_sysex_to_slice(U7::MIN, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x7E, 0x00, 0x03, 0x01, 0x01, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Message {
+    /// Starts a dump.
+    Header(DumpHeader),
+    /// Sample data.
+    Packet(DataPacket),
+    /// Asks the device to dump the sample with this number.
+    Request(U14),
+    /// Controls the flow of packets.
+    Handshake(Handshake),
+}
+
+impl Message {
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        match self {
+            Message::Header(_) => 21,
+            Message::Packet(_) => 127,
+            Message::Request(_) => 7,
+            Message::Handshake(_) => Handshake::SYSEX_BYTES_SIZE,
+        }
+    }
+
+    /// Write the Universal Non-Real Time SysEx message to `slice`, including the start and end
+    /// bytes. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(
+        &self,
+        device_id: U7,
+        slice: &mut [u8],
+    ) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        slice[..3].copy_from_slice(&[0xF0, 0x7E, u8::from(device_id)]);
+        match self {
+            Message::Header(header) => {
+                let number = u16::from(header.sample_number);
+                slice[3..6].copy_from_slice(&[0x01, (number & 0x7F) as u8, (number >> 7) as u8]);
+                slice[6] = header.format.clamp(8, 28);
+                let words = [
+                    header.period,
+                    header.length,
+                    header.loop_start,
+                    header.loop_end,
+                ];
+                for (chunk, word) in slice[7..19].chunks_exact_mut(3).zip(words.iter()) {
+                    for (i, byte) in chunk.iter_mut().enumerate() {
+                        *byte = (word >> (i * 7)) as u8 & 0x7F;
+                    }
+                }
+                slice[19] = header.loop_type.code();
+            }
+            Message::Packet(packet) => {
+                slice[3..5].copy_from_slice(&[0x02, u8::from(packet.number)]);
+                slice[5..125].copy_from_slice(U7::data_to_bytes(&packet.data));
+                slice[125] = u8::from(packet.checksum(device_id));
+            }
+            Message::Request(number) => {
+                let number = u16::from(*number);
+                slice[3..6].copy_from_slice(&[0x03, (number & 0x7F) as u8, (number >> 7) as u8]);
+            }
+            Message::Handshake(handshake) => {
+                return handshake.copy_sysex_to_slice(device_id, slice);
+            }
+        }
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The Universal Non-Real Time SysEx message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self, device_id: U7) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device_id, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device id and message, or `None` if the payload is not a Sample Dump Standard
+    /// message. Data packets with an incorrect checksum are not returned.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U7, Message)> {
+        if let Some((device_id, handshake)) = Handshake::from_sysex(payload) {
+            return Some((device_id, Message::Handshake(handshake)));
+        }
+        let bytes = U7::data_to_bytes(payload);
+        let (device_id, message) = match bytes {
+            [0x7E, device_id, 0x01, number_lsb, number_msb, format, words @ .., loop_type]
+                if words.len() == 12 =>
+            {
+                let mut values = words.chunks_exact(3).map(|chunk| {
+                    chunk
+                        .iter()
+                        .enumerate()
+                        .fold(0u32, |value, (i, byte)| value | u32::from(*byte) << (i * 7))
+                });
+                let header = DumpHeader {
+                    sample_number: combine(*number_lsb, *number_msb),
+                    format: *format,
+                    period: values.next()?,
+                    length: values.next()?,
+                    loop_start: values.next()?,
+                    loop_end: values.next()?,
+                    loop_type: LoopType::from_code(*loop_type)?,
+                };
+                (device_id, Message::Header(header))
+            }
+            [0x7E, device_id, 0x02, number, data @ .., checksum]
+                if data.len() == DataPacket::DATA_SIZE =>
+            {
+                let mut packet = DataPacket {
+                    number: U7(*number),
+                    data: [U7::MIN; DataPacket::DATA_SIZE],
+                };
+                packet.data.copy_from_slice(&payload[4..124]);
+                if packet.checksum(U7(*device_id)) != U7(*checksum) {
+                    return None;
+                }
+                (device_id, Message::Packet(packet))
+            }
+            [0x7E, device_id, 0x03, number_lsb, number_msb] => (
+                device_id,
+                Message::Request(combine(*number_lsb, *number_msb)),
+            ),
+            _ => return None,
+        };
+        Some((U7(*device_id), message))
+    }
+}
+
+/// The next message a `Sender` should send.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Transmit {
+    /// The dump header.
+    Header,
+    /// The data packet with this index, counting from 0. The packet number is the index modulo
+    /// 128.
+    Packet(u32),
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+enum SenderState {
+    Ready,
+    AwaitingAck,
+    Waiting,
+    Done,
+    Cancelled,
+}
+
+/// Tracks the sending side of a sample dump.
+///
+/// Send the message returned by `next`, call `sent`, then pass the receiver's handshakes to
+/// `handle`. If the receiver does not answer within the time allowed (2 seconds after the header
+/// and 20 milliseconds after a packet), call `timeout` to continue without handshaking, as
+/// receivers are not required to answer.
+///
+/// # Example
+/// ```
+/// use wmidi::sds::{DumpHeader, LoopType, Sender, Transmit};
+/// use wmidi::{Handshake, HandshakeKind, U14, U7};
+/// let header = DumpHeader {
+///     sample_number: U14::MIN,
+///     format: 16,
+///     period: 22675,
+///     length: 60,
+///     loop_start: 0,
+///     loop_end: 59,
+///     loop_type: LoopType::Off,
+/// };
+/// let mut sender = Sender::new(&header);
+/// assert_eq!(sender.next(), Some(Transmit::Header));
+/// sender.sent();
+/// sender.handle(Handshake { kind: HandshakeKind::Ack, packet: U7::MIN });
+/// assert_eq!(sender.next(), Some(Transmit::Packet(0)));
+/// sender.sent();
+/// sender.timeout();
+/// assert_eq!(sender.next(), Some(Transmit::Packet(1)));
+/// sender.sent();
+/// sender.handle(Handshake { kind: HandshakeKind::Ack, packet: U7::from_u8_lossy(1) });
+/// assert!(sender.is_done());
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Sender {
+    packets: u32,
+    next: Option<u32>,
+    state: SenderState,
+}
+
+impl Sender {
+    /// Create a sender for the dump described by `header`.
+    pub fn new(header: &DumpHeader) -> Sender {
+        Sender {
+            packets: header.packet_count(),
+            next: None,
+            state: SenderState::Ready,
+        }
+    }
+
+    /// The message to send now, or `None` if waiting for the receiver or the transfer has
+    /// ended.
+    pub fn next(&self) -> Option<Transmit> {
+        if self.state != SenderState::Ready {
+            return None;
+        }
+        Some(match self.next {
+            None => Transmit::Header,
+            Some(index) => Transmit::Packet(index),
+        })
+    }
+
+    /// Record that the message returned by `next` was sent.
+    pub fn sent(&mut self) {
+        if self.state == SenderState::Ready {
+            self.state = SenderState::AwaitingAck;
+        }
+    }
+
+    /// Update the transfer with a handshake from the receiver. ACK and NAK messages for other
+    /// packets are ignored.
+    pub fn handle(&mut self, handshake: Handshake) {
+        if self.is_done() || self.is_cancelled() {
+            return;
+        }
+        let expected = self
+            .next
+            .map_or(U7::MIN, |index| U7::from_u8_lossy(index as u8));
+        match handshake.kind {
+            HandshakeKind::Ack if handshake.packet == expected || self.next.is_none() => {
+                self.advance()
+            }
+            HandshakeKind::Nak if handshake.packet == expected => self.state = SenderState::Ready,
+            HandshakeKind::Wait => self.state = SenderState::Waiting,
+            HandshakeKind::Cancel => self.state = SenderState::Cancelled,
+            _ => {}
+        }
+    }
+
+    /// Continue as if the receiver sent an ACK, if the sender is waiting for one. A sender
+    /// paused by a WAIT keeps waiting.
+    pub fn timeout(&mut self) {
+        if self.state == SenderState::AwaitingAck {
+            self.advance();
+        }
+    }
+
+    fn advance(&mut self) {
+        let next = self.next.map_or(0, |index| index + 1);
+        self.next = Some(next);
+        self.state = if next >= self.packets {
+            SenderState::Done
+        } else {
+            SenderState::Ready
+        };
+    }
+
+    /// Returns `true` once every packet has been sent and acknowledged.
+    pub fn is_done(&self) -> bool {
+        self.state == SenderState::Done
+    }
+
+    /// Returns `true` if the receiver cancelled the transfer.
+    pub fn is_cancelled(&self) -> bool {
+        self.state == SenderState::Cancelled
+    }
+}
+
+/// Tracks the receiving side of a sample dump, choosing the handshake to answer each message
+/// with.
+///
+/// # Example
+/// ```
+/// use wmidi::sds::{DataPacket, DumpHeader, LoopType, Message, Receiver};
+/// use wmidi::{HandshakeKind, U14, U7};
+/// let header = DumpHeader {
+///     sample_number: U14::MIN,
+///     format: 8,
+///     period: 20833,
+///     length: 60,
+///     loop_start: 0,
+///     loop_end: 59,
+///     loop_type: LoopType::Forward,
+/// };
+/// let mut receiver = Receiver::new();
+/// let reply = receiver.handle(&Message::Header(header)).unwrap();
+/// assert_eq!(reply.kind, HandshakeKind::Ack);
+/// let (packet, _) = DataPacket::from_samples(U7::MIN, &header, &[128; 60]);
+/// receiver.handle(&Message::Packet(packet));
+/// assert!(receiver.is_complete());
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Receiver {
+    header: Option<DumpHeader>,
+    received: u32,
+}
+
+impl Receiver {
+    /// Create a receiver that is not in a transfer.
+    pub fn new() -> Receiver {
+        Receiver::default()
+    }
+
+    /// The header of the current transfer.
+    pub fn header(&self) -> Option<&DumpHeader> {
+        self.header.as_ref()
+    }
+
+    /// The number of data packets received in the current transfer.
+    pub fn received(&self) -> u32 {
+        self.received
+    }
+
+    /// Returns `true` if every packet of the current transfer has been received.
+    pub fn is_complete(&self) -> bool {
+        self.header
+            .is_some_and(|header| self.received >= header.packet_count())
+    }
+
+    /// Update the transfer with `message`, returning the handshake to send back. A header starts
+    /// a new transfer. Packets that are not the next one expected are ignored, and `None` is
+    /// returned for messages that need no answer.
+    pub fn handle(&mut self, message: &Message) -> Option<Handshake> {
+        match message {
+            Message::Header(header) => {
+                self.header = Some(*header);
+                self.received = 0;
+                Some(Handshake {
+                    kind: HandshakeKind::Ack,
+                    packet: U7::MIN,
+                })
+            }
+            Message::Packet(packet) => {
+                if self.header.is_none()
+                    || self.is_complete()
+                    || packet.number != U7::from_u8_lossy(self.received as u8)
+                {
+                    return None;
+                }
+                self.received += 1;
+                Some(Handshake {
+                    kind: HandshakeKind::Ack,
+                    packet: packet.number,
+                })
+            }
+            Message::Handshake(Handshake {
+                kind: HandshakeKind::Cancel,
+                ..
+            }) => {
+                *self = Receiver::new();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse and handle the payload of a `MidiMessage::SysEx`, answering a data packet with an
+    /// incorrect checksum with a NAK.
+    pub fn handle_sysex(&mut self, payload: &[U7]) -> Option<Handshake> {
+        match Message::from_sysex(payload) {
+            Some((_, message)) => self.handle(&message),
+            None => match U7::data_to_bytes(payload) {
+                [0x7E, _, 0x02, number, ..]
+                    if payload.len() == DataPacket::DATA_SIZE + 5 && self.header.is_some() =>
+                {
+                    Some(Handshake {
+                        kind: HandshakeKind::Nak,
+                        packet: U7(*number),
+                    })
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+fn combine(lsb: u8, msb: u8) -> U14 {
+    U14::from_u16_lossy(u16::from(msb) << 7 | u16::from(lsb))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    fn header(format: u8, length: u32) -> DumpHeader {
+        DumpHeader {
+            sample_number: U14::from_u16_lossy(0x1234),
+            format,
+            period: 22675,
+            length,
+            loop_start: 10,
+            loop_end: 0x1F_FFFF,
+            loop_type: LoopType::Alternating,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let header = header(16, 1000);
+        let samples: [u32; 40] = core::array::from_fn(|i| i as u32 * 1000);
+        let (packet, count) = DataPacket::from_samples(U7(5), &header, &samples);
+        assert_eq!(count, 40);
+        let messages = [
+            Message::Header(header),
+            Message::Packet(packet),
+            Message::Request(U14::MAX),
+            Message::Handshake(Handshake {
+                kind: HandshakeKind::Wait,
+                packet: U7(1),
+            }),
+        ];
+        for message in messages.iter() {
+            let mut bytes = [0u8; 127];
+            let size = message.copy_sysex_to_slice(U7(0x02), &mut bytes).unwrap();
+            assert_eq!(size, message.bytes_size());
+            let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(Message::from_sysex(payload), Some((U7(0x02), *message)));
+        }
+
+        let mut bytes = [0u8; 21];
+        Message::Header(DumpHeader {
+            format: 0x90,
+            ..header
+        })
+        .copy_sysex_to_slice(U7(0x02), &mut bytes)
+        .unwrap();
+        assert_eq!(bytes[6], 28);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn samples() {
+        for format in 8..=28 {
+            let header = header(format, 1000);
+            let max = (1u32 << format) - 1;
+            let samples = [0, 1, max / 3, max - 1, max];
+            let (packet, count) = DataPacket::from_samples(U7::MIN, &header, &samples);
+            assert_eq!(count, 5);
+            let decoded: Vec<u32> = packet.samples(&header).take(5).collect();
+            assert_eq!(decoded, samples);
+        }
+        assert_eq!(header(8, 1000).samples_per_packet(), 60);
+        assert_eq!(header(14, 1000).samples_per_packet(), 60);
+        assert_eq!(header(16, 1000).samples_per_packet(), 40);
+        assert_eq!(header(16, 1000).packet_count(), 25);
+        assert_eq!(header(28, 121).packet_count(), 5);
+    }
+
+    #[test]
+    fn sender() {
+        let mut sender = Sender::new(&header(16, 80));
+        sender.sent();
+        sender.handle(Handshake {
+            kind: HandshakeKind::Ack,
+            packet: U7::MIN,
+        });
+        sender.sent();
+        sender.handle(Handshake {
+            kind: HandshakeKind::Nak,
+            packet: U7::MIN,
+        });
+        assert_eq!(sender.next(), Some(Transmit::Packet(0)));
+        sender.sent();
+        sender.handle(Handshake {
+            kind: HandshakeKind::Wait,
+            packet: U7::MIN,
+        });
+        sender.timeout();
+        assert_eq!(sender.next(), None);
+        sender.handle(Handshake {
+            kind: HandshakeKind::Ack,
+            packet: U7::MIN,
+        });
+        assert_eq!(sender.next(), Some(Transmit::Packet(1)));
+        sender.handle(Handshake {
+            kind: HandshakeKind::Cancel,
+            packet: U7::MIN,
+        });
+        assert!(sender.is_cancelled());
+        assert_eq!(sender.next(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn receiver() {
+        let header = header(16, 80);
+        let mut receiver = Receiver::new();
+        let (packet, _) = DataPacket::from_samples(U7::MIN, &header, &[]);
+        assert_eq!(receiver.handle(&Message::Packet(packet)), None);
+        receiver.handle(&Message::Header(header));
+
+        let mut bytes = Message::Packet(packet).to_sysex_vec(U7::MIN);
+        bytes[125] ^= 0x01;
+        let payload = U7::try_from_bytes(&bytes[1..126]).unwrap();
+        assert_eq!(
+            receiver.handle_sysex(payload),
+            Some(Handshake {
+                kind: HandshakeKind::Nak,
+                packet: U7::MIN
+            })
+        );
+        assert_eq!(receiver.received(), 0);
+
+        for number in 0..2 {
+            let packet = DataPacket {
+                number: U7(number),
+                ..packet
+            };
+            let reply = receiver.handle(&Message::Packet(packet)).unwrap();
+            assert_eq!(reply.kind, HandshakeKind::Ack);
+            assert_eq!(reply.packet, U7(number));
+        }
+        assert!(receiver.is_complete());
+    }
+}
@@ -0,0 +1,269 @@
+use crate::SmpteTimeError;
+use core::fmt;
+
+/// The number of frames per second of a SMPTE time code.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum FrameRate {
+    /// 24 frames per second, used for film.
+    Fps24,
+    /// 25 frames per second, used for PAL video.
+    Fps25,
+    /// 29.97 frames per second with drop frame numbering, used for NTSC video. Frames 0 and 1 are
+    /// skipped at the start of every minute except every tenth minute.
+    Fps2997Drop,
+    /// 30 frames per second.
+    Fps30,
+}
+
+impl FrameRate {
+    /// The number of frame numbers in each second.
+    pub fn frames_per_second(self) -> u8 {
+        match self {
+            FrameRate::Fps24 => 24,
+            FrameRate::Fps25 => 25,
+            FrameRate::Fps2997Drop | FrameRate::Fps30 => 30,
+        }
+    }
+
+    /// Decode the 2 bit rate code used by MIDI Time Code. Only the 2 least significant bits are
+    /// used.
+    pub(crate) fn from_code(code: u8) -> FrameRate {
+        match code & 0x03 {
+            0 => FrameRate::Fps24,
+            1 => FrameRate::Fps25,
+            2 => FrameRate::Fps2997Drop,
+            _ => FrameRate::Fps30,
+        }
+    }
+
+    /// The 2 bit rate code used by MIDI Time Code.
+    pub(crate) fn code(self) -> u8 {
+        match self {
+            FrameRate::Fps24 => 0,
+            FrameRate::Fps25 => 1,
+            FrameRate::Fps2997Drop => 2,
+            FrameRate::Fps30 => 3,
+        }
+    }
+
+    /// The exact number of frames per second as a numerator and denominator.
+    pub(crate) fn ratio(self) -> (u64, u64) {
+        match self {
+            FrameRate::Fps2997Drop => (30000, 1001),
+            rate => (u64::from(rate.frames_per_second()), 1),
+        }
+    }
+}
+
+/// A SMPTE time code: a position in hours, minutes, seconds, and frames at a frame rate.
+///
+/// Times wrap around after 24 hours. At `FrameRate::Fps2997Drop` the frame numbers run at 30 per
+/// second but the frames are 1.001 times longer, so the time code stays close to the clock.
+///
+/// # Example
+/// ```
+/// use wmidi::{FrameRate, SmpteTime};
+/// let time = SmpteTime::new(1, 2, 3, 24, FrameRate::Fps25).unwrap();
+/// assert_eq!(time.add_frames(1), SmpteTime::new(1, 2, 4, 0, FrameRate::Fps25).unwrap());
+/// assert_eq!(time.to_seconds(), 3723.96);
+/// assert_eq!(time.to_string(), "01:02:03:24");
+/// assert_eq!(time.with_rate(FrameRate::Fps2997Drop).to_string(), "01:02:03;29");
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SmpteTime {
+    /// The hours, from 0 to 23.
+    pub hours: u8,
+    /// The minutes, from 0 to 59.
+    pub minutes: u8,
+    /// The seconds, from 0 to 59.
+    pub seconds: u8,
+    /// The frame number within the second, from 0 to one less than the frames per second.
+    pub frames: u8,
+    /// The frame rate.
+    pub rate: FrameRate,
+}
+
+impl SmpteTime {
+    /// Create a time, or return `SmpteTimeError` if it is not valid.
+    pub fn new(
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        frames: u8,
+        rate: FrameRate,
+    ) -> Result<SmpteTime, SmpteTimeError> {
+        let time = SmpteTime {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            rate,
+        };
+        if time.is_valid() {
+            Ok(time)
+        } else {
+            Err(SmpteTimeError)
+        }
+    }
+
+    /// Returns `true` if every field is in range, including that drop frame time does not use a
+    /// skipped frame number.
+    pub fn is_valid(&self) -> bool {
+        let skipped = self.rate == FrameRate::Fps2997Drop
+            && self.seconds == 0
+            && self.frames < 2
+            && !self.minutes.is_multiple_of(10);
+        self.hours < 24
+            && self.minutes < 60
+            && self.seconds < 60
+            && self.frames < self.rate.frames_per_second()
+            && !skipped
+    }
+
+    /// The number of frames since 00:00:00:00. The time should be valid.
+    pub fn to_frames(&self) -> u32 {
+        let fps = u32::from(self.rate.frames_per_second());
+        let minutes = u32::from(self.hours) * 60 + u32::from(self.minutes);
+        let count = (minutes * 60 + u32::from(self.seconds)) * fps + u32::from(self.frames);
+        if self.rate == FrameRate::Fps2997Drop {
+            count - 2 * (minutes - minutes / 10)
+        } else {
+            count
+        }
+    }
+
+    /// The time `count` frames after 00:00:00:00, wrapping after 24 hours.
+    pub fn from_frames(count: u32, rate: FrameRate) -> SmpteTime {
+        let fps = u32::from(rate.frames_per_second());
+        let mut count = count % frames_per_day(rate);
+        if rate == FrameRate::Fps2997Drop {
+            // Add back the skipped frame numbers: 18 for every full 10 minutes and 2 for every
+            // minute after the first within the current 10 minutes.
+            let tens = count / 17982;
+            let rest = count % 17982;
+            count += 18 * tens;
+            if rest >= 2 {
+                count += 2 * ((rest - 2) / 1798);
+            }
+        }
+        SmpteTime {
+            hours: (count / (fps * 3600)) as u8,
+            minutes: (count / (fps * 60) % 60) as u8,
+            seconds: (count / fps % 60) as u8,
+            frames: (count % fps) as u8,
+            rate,
+        }
+    }
+
+    /// The time `frames` frames later, or earlier if negative, wrapping around 24 hours.
+    pub fn add_frames(&self, frames: i32) -> SmpteTime {
+        let day = i64::from(frames_per_day(self.rate));
+        let count = (i64::from(self.to_frames()) + i64::from(frames)).rem_euclid(day);
+        SmpteTime::from_frames(count as u32, self.rate)
+    }
+
+    /// The number of frames from `earlier` to `self`, between 0 and 24 hours. `earlier` is
+    /// converted to the rate of `self` first.
+    pub fn frames_since(&self, earlier: &SmpteTime) -> u32 {
+        let earlier = earlier.with_rate(self.rate);
+        let day = frames_per_day(self.rate);
+        (self.to_frames() + day - earlier.to_frames()) % day
+    }
+
+    /// The number of seconds since 00:00:00:00.
+    pub fn to_seconds(&self) -> f64 {
+        let (frames, seconds) = self.rate.ratio();
+        f64::from(self.to_frames()) * seconds as f64 / frames as f64
+    }
+
+    /// The frame that is showing `seconds` after 00:00:00:00, wrapping after 24 hours. Negative
+    /// and NaN values are treated as 0.
+    pub fn from_seconds(seconds: f64, rate: FrameRate) -> SmpteTime {
+        let (frames_per, seconds_per) = rate.ratio();
+        let frames = seconds * frames_per as f64 / seconds_per as f64;
+        // Allow for rounding errors so that a frame's own start time maps back to it.
+        let frames = if frames > 0.0 { frames + 1e-6 } else { 0.0 };
+        let count = (frames as u64 % u64::from(frames_per_day(rate))) as u32;
+        SmpteTime::from_frames(count, rate)
+    }
+
+    /// The frame showing at the same moment at `rate`.
+    pub fn with_rate(&self, rate: FrameRate) -> SmpteTime {
+        if rate == self.rate {
+            return *self;
+        }
+        SmpteTime::from_seconds(self.to_seconds(), rate)
+    }
+}
+
+impl fmt::Display for SmpteTime {
+    /// Format as `HH:MM:SS:FF`, using `;` before the frames for drop frame time.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.rate == FrameRate::Fps2997Drop {
+            ';'
+        } else {
+            ':'
+        };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, separator, self.frames
+        )
+    }
+}
+
+/// The number of frames in 24 hours.
+fn frames_per_day(rate: FrameRate) -> u32 {
+    match rate {
+        FrameRate::Fps2997Drop => 24 * 6 * 17982,
+        rate => u32::from(rate.frames_per_second()) * 86400,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn drop_frame_count() {
+        let rate = FrameRate::Fps2997Drop;
+        for count in (0..frames_per_day(rate)).step_by(7) {
+            let time = SmpteTime::from_frames(count, rate);
+            assert!(time.is_valid(), "{:?}", time);
+            assert_eq!(time.to_frames(), count);
+        }
+        let before = SmpteTime::new(0, 0, 59, 29, rate).unwrap();
+        assert_eq!(
+            before.add_frames(1),
+            SmpteTime::new(0, 1, 0, 2, rate).unwrap()
+        );
+        assert_eq!(SmpteTime::new(0, 1, 0, 0, rate), Err(SmpteTimeError));
+        let midnight = SmpteTime::from_frames(0, rate);
+        assert_eq!(midnight.add_frames(-1).hours, 23);
+        assert_eq!(midnight.frames_since(&before), frames_per_day(rate) - 1799);
+    }
+
+    #[test]
+    fn seconds() {
+        for rate in [
+            FrameRate::Fps24,
+            FrameRate::Fps25,
+            FrameRate::Fps2997Drop,
+            FrameRate::Fps30,
+        ]
+        .iter()
+        {
+            for count in (0..frames_per_day(*rate)).step_by(997) {
+                let time = SmpteTime::from_frames(count, *rate);
+                assert_eq!(SmpteTime::from_seconds(time.to_seconds(), *rate), time);
+            }
+        }
+        // An hour of drop frame time is 3.6 ms shorter than an hour.
+        let hour = SmpteTime::new(1, 0, 0, 0, FrameRate::Fps2997Drop).unwrap();
+        assert!((hour.to_seconds() - 3599.9964).abs() < 1e-6);
+        assert_eq!(
+            hour.with_rate(FrameRate::Fps25),
+            SmpteTime::new(0, 59, 59, 24, FrameRate::Fps25).unwrap()
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{Error, Note};
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The letter name of a note, without any accidental.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Letter {
+    C,
+    D,
+    E,
+    F,
+    G,
+    A,
+    B,
+}
+
+impl Letter {
+    /// The pitch class of the natural note with this letter. `C` is `0` and `B` is `11`.
+    pub fn pitch_class(self) -> u8 {
+        match self {
+            Letter::C => 0,
+            Letter::D => 2,
+            Letter::E => 4,
+            Letter::F => 5,
+            Letter::G => 7,
+            Letter::A => 9,
+            Letter::B => 11,
+        }
+    }
+
+    /// The position of the natural note on the line of fifths, relative to `C`. `F` is `-1` and
+    /// `B` is `5`.
+    pub(crate) fn fifths(self) -> i8 {
+        match self {
+            Letter::F => -1,
+            Letter::C => 0,
+            Letter::G => 1,
+            Letter::D => 2,
+            Letter::A => 3,
+            Letter::E => 4,
+            Letter::B => 5,
+        }
+    }
+
+    /// Get the letter for a line of fifths position. Positions outside of `-1..=5` wrap around.
+    pub(crate) fn from_fifths(fifths: i8) -> Letter {
+        match (fifths + 1).rem_euclid(7) {
+            0 => Letter::F,
+            1 => Letter::C,
+            2 => Letter::G,
+            3 => Letter::D,
+            4 => Letter::A,
+            5 => Letter::E,
+            _ => Letter::B,
+        }
+    }
+
+    /// Get a `str` representation of the letter. For example: `"C"`.
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Letter::C => "C",
+            Letter::D => "D",
+            Letter::E => "E",
+            Letter::F => "F",
+            Letter::G => "G",
+            Letter::A => "A",
+            Letter::B => "B",
+        }
+    }
+}
+
+impl fmt::Display for Letter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
+/// An alteration applied to a `Letter`.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Accidental {
+    DoubleFlat,
+    Flat,
+    Natural,
+    Sharp,
+    DoubleSharp,
+}
+
+impl Accidental {
+    /// The number of half steps the accidental raises the note by. Flats are negative.
+    pub fn semitones(self) -> i8 {
+        match self {
+            Accidental::DoubleFlat => -2,
+            Accidental::Flat => -1,
+            Accidental::Natural => 0,
+            Accidental::Sharp => 1,
+            Accidental::DoubleSharp => 2,
+        }
+    }
+
+    /// Get the accidental that raises a note by `semitones`, or `None` if no such accidental
+    /// exists.
+    pub fn from_semitones(semitones: i8) -> Option<Accidental> {
+        match semitones {
+            -2 => Some(Accidental::DoubleFlat),
+            -1 => Some(Accidental::Flat),
+            0 => Some(Accidental::Natural),
+            1 => Some(Accidental::Sharp),
+            2 => Some(Accidental::DoubleSharp),
+            _ => None,
+        }
+    }
+
+    /// Get a `str` representation of the accidental. The natural accidental is the empty string.
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Accidental::DoubleFlat => "bb",
+            Accidental::Flat => "b",
+            Accidental::Natural => "",
+            Accidental::Sharp => "#",
+            Accidental::DoubleSharp => "##",
+        }
+    }
+}
+
+impl fmt::Display for Accidental {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
+/// A note with an explicit enharmonic spelling.
+///
+/// Unlike `Note`, which only identifies a key on the keyboard, a `SpelledNote` distinguishes
+/// between `A#3` and `Bb3`. The octave follows the letter, so `B#3` sounds the same as `C4`.
+///
+/// # Example
+/// ```
+/// use wmidi::{Accidental, Key, Letter, Note};
+/// let f_major = Key::major(Letter::F, Accidental::Natural);
+/// let spelled = Note::Bb3.spelled_in(&f_major);
+/// assert_eq!(spelled.letter, Letter::B);
+/// assert_eq!(spelled.accidental, Accidental::Flat);
+/// assert_eq!(spelled.octave, 3);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SpelledNote {
+    /// The letter name.
+    pub letter: Letter,
+    /// The alteration applied to `letter`.
+    pub accidental: Accidental,
+    /// The octave of `letter`, where `4` is the octave of middle C.
+    pub octave: i8,
+}
+
+impl SpelledNote {
+    /// Create a new spelled note.
+    ///
+    /// # Example
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use wmidi::{Accidental, Letter, Note, SpelledNote};
+    /// let f_double_sharp = SpelledNote::new(Letter::F, Accidental::DoubleSharp, 4);
+    /// assert_eq!(Note::try_from(f_double_sharp), Ok(Note::G4));
+    /// ```
+    pub const fn new(letter: Letter, accidental: Accidental, octave: i8) -> SpelledNote {
+        SpelledNote {
+            letter,
+            accidental,
+            octave,
+        }
+    }
+}
+
+/// Convert a `SpelledNote` into the `Note` it sounds as. An error is returned if the note is not
+/// between `C-1` and `G9` inclusive.
+impl TryFrom<SpelledNote> for Note {
+    type Error = Error;
+
+    fn try_from(note: SpelledNote) -> Result<Note, Error> {
+        let raw_note = (i16::from(note.octave) + 1) * 12
+            + i16::from(note.letter.pitch_class())
+            + i16::from(note.accidental.semitones());
+        if Note::LOWEST_NOTE as i16 <= raw_note && raw_note <= Note::HIGHEST_NOTE as i16 {
+            Ok(unsafe { Note::from_u8_unchecked(raw_note as u8) })
+        } else {
+            Err(Error::NoteOutOfRange)
+        }
+    }
+}
+
+impl fmt::Display for SpelledNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.letter, self.accidental, self.octave)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fifths_round_trip() {
+        for letter in [
+            Letter::C,
+            Letter::D,
+            Letter::E,
+            Letter::F,
+            Letter::G,
+            Letter::A,
+            Letter::B,
+        ]
+        .iter()
+        {
+            assert_eq!(Letter::from_fifths(letter.fifths()), *letter);
+            assert_eq!(Letter::from_fifths(letter.fifths() + 7), *letter);
+            assert_eq!(Letter::from_fifths(letter.fifths() - 14), *letter);
+        }
+    }
+
+    #[test]
+    fn accidental_semitones_round_trip() {
+        for semitones in -2..=2 {
+            let accidental = Accidental::from_semitones(semitones).unwrap();
+            assert_eq!(accidental.semitones(), semitones);
+        }
+        assert_eq!(Accidental::from_semitones(3), None);
+    }
+
+    #[test]
+    fn try_into_note() {
+        let note = |letter, accidental, octave| {
+            Note::try_from(SpelledNote::new(letter, accidental, octave))
+        };
+        assert_eq!(note(Letter::C, Accidental::Natural, 4), Ok(Note::C4));
+        assert_eq!(note(Letter::B, Accidental::Sharp, 3), Ok(Note::C4));
+        assert_eq!(note(Letter::D, Accidental::DoubleFlat, 4), Ok(Note::C4));
+        assert_eq!(note(Letter::C, Accidental::Natural, -1), Ok(Note::CMinus1));
+        assert_eq!(note(Letter::G, Accidental::Natural, 9), Ok(Note::G9));
+        assert_eq!(
+            note(Letter::C, Accidental::Flat, -1),
+            Err(Error::NoteOutOfRange)
+        );
+        assert_eq!(
+            note(Letter::G, Accidental::Sharp, 9),
+            Err(Error::NoteOutOfRange)
+        );
+        assert_eq!(
+            note(Letter::C, Accidental::Natural, i8::MIN),
+            Err(Error::NoteOutOfRange)
+        );
+    }
+
+    #[test]
+    fn spelling_round_trips() {
+        let key = crate::Key::major(Letter::D, Accidental::Flat);
+        for note in Note::all() {
+            assert_eq!(Note::try_from(note.spelled_in(&key)), Ok(note));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        let note = SpelledNote {
+            letter: Letter::A,
+            accidental: Accidental::Sharp,
+            octave: -1,
+        };
+        assert_eq!(format!("{}", note), "A#-1");
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{Channel, ChannelMap, MidiMessage};
+
+/// Where `Splitter` sends messages that do not have a channel.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum SystemRouting {
+    /// Send a copy to every output that a channel is routed to.
+    All,
+    /// Send to a single output.
+    Output(usize),
+    /// Do not send system messages.
+    Drop,
+}
+
+/// Routes messages to numbered outputs by channel, for example to drive a different instrument
+/// from each channel of a multitimbral stream. This is the counterpart of `Merger`.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, Note, Splitter, SystemRouting, U7};
+/// let mut splitter = Splitter::new();
+/// splitter.outputs[Channel::Ch10] = Some(0);
+/// splitter.system = SystemRouting::Output(0);
+/// let mut out = Vec::new();
+/// splitter.split(&MidiMessage::NoteOn(Channel::Ch10, Note::C2, U7::MAX), |o, m| out.push((o, m)));
+/// splitter.split(&MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7::MAX), |o, m| out.push((o, m)));
+/// assert_eq!(out[0].0, 0);
+/// assert_eq!(out[1].0, 1);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Splitter {
+    /// The output of each channel, or `None` to drop the messages of the channel.
+    pub outputs: ChannelMap<Option<usize>>,
+    /// The output of messages without a channel.
+    pub system: SystemRouting,
+}
+
+impl Splitter {
+    /// Create a splitter that sends each channel to the output with the channel's index and
+    /// copies system messages to every output.
+    pub fn new() -> Splitter {
+        Splitter {
+            outputs: ChannelMap::from_fn(|c| Some(usize::from(c.index()))),
+            system: SystemRouting::All,
+        }
+    }
+
+    /// Call `emit` with each output that `message` is routed to and the message.
+    pub fn split<'a, F: FnMut(usize, MidiMessage<'a>)>(
+        &self,
+        message: &MidiMessage<'a>,
+        mut emit: F,
+    ) {
+        if let Some(channel) = message.channel() {
+            if let Some(output) = self.outputs[channel] {
+                emit(output, message.clone());
+            }
+            return;
+        }
+        match self.system {
+            SystemRouting::All => {
+                for (channel, output) in self.outputs.iter() {
+                    if let Some(output) = *output {
+                        if !self.is_earlier_output(channel, output) {
+                            emit(output, message.clone());
+                        }
+                    }
+                }
+            }
+            SystemRouting::Output(output) => emit(output, message.clone()),
+            SystemRouting::Drop => {}
+        }
+    }
+
+    /// Returns `true` if a channel before `channel` is routed to `output`.
+    fn is_earlier_output(&self, channel: Channel, output: usize) -> bool {
+        self.outputs
+            .iter()
+            .take_while(|(c, _)| *c < channel)
+            .any(|(_, o)| *o == Some(output))
+    }
+}
+
+impl Default for Splitter {
+    fn default() -> Splitter {
+        Splitter::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ControlFunction, U7};
+
+    #[test]
+    fn channels() {
+        let mut splitter = Splitter::new();
+        splitter.outputs[Channel::Ch3] = None;
+        let mut outputs = [None; 2];
+        let mut len = 0;
+        for channel in [Channel::Ch3, Channel::Ch16, Channel::Ch1].iter() {
+            splitter.split(
+                &MidiMessage::ControlChange(*channel, ControlFunction::PAN, U7(0)),
+                |o, m| {
+                    assert_eq!(m.channel(), Some(*channel));
+                    outputs[len] = Some(o);
+                    len += 1;
+                },
+            );
+        }
+        assert_eq!(outputs, [Some(15), Some(0)]);
+    }
+
+    #[test]
+    fn system_messages() {
+        let mut splitter = Splitter::new();
+        splitter.outputs = ChannelMap::from_fn(|c| Some(usize::from(c.index() % 2)));
+        let mut outputs = [None; 3];
+        let mut len = 0;
+        splitter.split(&MidiMessage::Start, |o, _| {
+            outputs[len] = Some(o);
+            len += 1;
+        });
+        assert_eq!(outputs, [Some(0), Some(1), None]);
+
+        splitter.system = SystemRouting::Output(7);
+        splitter.split(&MidiMessage::Stop, |o, _| assert_eq!(o, 7));
+        splitter.system = SystemRouting::Drop;
+        splitter.split(&MidiMessage::Stop, |_, _| panic!("should be dropped"));
+    }
+
+    #[test]
+    fn sysex_passes_unchanged() {
+        let splitter = Splitter {
+            system: SystemRouting::Output(2),
+            ..Splitter::new()
+        };
+        let message = MidiMessage::SysEx(&[U7(0x7D), U7(1)]);
+        let mut count = 0;
+        splitter.split(&message, |o, m| {
+            assert_eq!((o, m), (2, message.clone()));
+            count += 1;
+        });
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{Channel, ChannelMap, MidiMessage, Note, NoteTracker, Velocity};
+
+/// Finds notes that have been sounding for longer than a timeout without a `NoteOff`, for
+/// example because the `NoteOff` was lost.
+///
+/// Pass every message to `handle` with the time it was sent or received. The unit of time is up
+/// to the caller. A `NoteOn` for a note that is already sounding restarts its time.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, Note, StuckNoteDetector, U7};
+/// let mut detector = StuckNoteDetector::new(10_000);
+/// detector.handle(0, &MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX));
+/// detector.handle(5_000, &MidiMessage::NoteOn(Channel::Ch1, Note::E4, U7::MAX));
+/// assert!(!detector.has_stuck_notes(9_999));
+/// assert!(detector.stuck_notes(12_000).eq([(Channel::Ch1, Note::C4)].iter().copied()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StuckNoteDetector {
+    timeout: u64,
+    notes: NoteTracker,
+    started: ChannelMap<[u64; 128]>,
+}
+
+impl StuckNoteDetector {
+    /// Create a detector that reports notes sounding for at least `timeout`.
+    pub fn new(timeout: u64) -> StuckNoteDetector {
+        StuckNoteDetector {
+            timeout,
+            notes: NoteTracker::new(),
+            started: ChannelMap::new([[0; 128]; 16]),
+        }
+    }
+
+    /// The time a note may sound before it is reported.
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    /// The notes that are sounding.
+    pub fn notes(&self) -> &NoteTracker {
+        &self.notes
+    }
+
+    /// Update the sounding notes from `message`, which was sent or received at `now`.
+    pub fn handle(&mut self, now: u64, message: &MidiMessage) {
+        if let MidiMessage::NoteOn(channel, note, velocity) = message {
+            if *velocity != Velocity::MIN {
+                self.started[*channel][usize::from(u8::from(*note))] = now;
+            }
+        }
+        self.notes.handle(message);
+    }
+
+    /// The time `note` started sounding on `channel`, or `None` if it is not sounding.
+    pub fn started(&self, channel: Channel, note: Note) -> Option<u64> {
+        if self.notes.is_active(channel, note) {
+            Some(self.started[channel][usize::from(u8::from(note))])
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the notes that have been sounding for at least the timeout at `now`, ordered
+    /// by channel and then note.
+    pub fn stuck_notes(&self, now: u64) -> impl Iterator<Item = (Channel, Note)> + '_ {
+        self.notes.iter().filter(move |(c, n)| {
+            now.saturating_sub(self.started[*c][usize::from(u8::from(*n))]) >= self.timeout
+        })
+    }
+
+    /// Returns `true` if any note has been sounding for at least the timeout at `now`.
+    pub fn has_stuck_notes(&self, now: u64) -> bool {
+        self.stuck_notes(now).next().is_some()
+    }
+
+    /// Emit a `NoteOff` for every stuck note at `now` and forget them.
+    pub fn release_stuck<F: FnMut(MidiMessage<'static>)>(&mut self, now: u64, mut emit: F) {
+        for channel in Channel::all() {
+            for note in self.notes.notes(channel) {
+                let started = self.started[channel][usize::from(u8::from(note))];
+                if now.saturating_sub(started) >= self.timeout {
+                    let off = MidiMessage::NoteOff(channel, note, Velocity::MIN);
+                    self.notes.handle(&off);
+                    emit(off);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U7;
+
+    #[test]
+    fn release_stuck() {
+        let mut detector = StuckNoteDetector::new(100);
+        detector.handle(0, &MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(1)));
+        detector.handle(0, &MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7(1)));
+        detector.handle(50, &MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7(1)));
+        detector.handle(60, &MidiMessage::NoteOn(Channel::Ch3, Note::D4, U7(1)));
+        detector.handle(70, &MidiMessage::NoteOff(Channel::Ch3, Note::D4, U7(1)));
+        assert_eq!(detector.started(Channel::Ch2, Note::C4), Some(50));
+        assert_eq!(detector.started(Channel::Ch3, Note::D4), None);
+        let mut released = None;
+        detector.release_stuck(120, |m| {
+            assert_eq!(released.replace(m), None);
+        });
+        assert_eq!(
+            released,
+            Some(MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0)))
+        );
+        assert!(!detector.has_stuck_notes(120));
+        assert!(detector.has_stuck_notes(150));
+    }
+}
@@ -0,0 +1,198 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::{Channel, ChannelMap, ControlFunction, MidiMessage, Note, Velocity, U7};
+
+/// [MIDI 1.0] Applies the Damper Pedal (CC 64) to a stream of messages.
+///
+/// While the pedal is held, `NoteOff` messages are deferred and the note keeps sounding. When the
+/// pedal is released, a `NoteOff` is emitted for every deferred note. A note that is struck again
+/// while it is sustained is no longer deferred, so releasing the pedal does not cut off the new
+/// note. A pedal value of 64 or more is down.
+///
+/// All other messages, including the Damper Pedal messages themselves, are passed through. All
+/// Sound Off, All Notes Off, the Omni/Mono/Poly mode messages, and `Reset` forget the deferred
+/// notes of their channels without emitting anything.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, ControlFunction, MidiMessage, Note, SustainTracker, U7};
+/// let mut sustain = SustainTracker::new();
+/// let mut out = Vec::new();
+/// let pedal = |v| MidiMessage::ControlChange(Channel::Ch1, ControlFunction::DAMPER_PEDAL, v);
+/// sustain.handle(&pedal(U7::MAX), |m| out.push(m));
+/// sustain.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX), |m| out.push(m));
+/// sustain.handle(&MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7::MIN), |m| out.push(m));
+/// assert!(sustain.is_sustained(Channel::Ch1, Note::C4));
+/// sustain.handle(&pedal(U7::MIN), |m| out.push(m));
+/// assert_eq!(out.last(), Some(&MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7::MIN)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SustainTracker {
+    pedal: ChannelMap<bool>,
+    sustained: ChannelMap<u128>,
+    release_velocities: ChannelMap<[Velocity; 128]>,
+}
+
+impl SustainTracker {
+    /// Create a tracker with the pedal up on every channel.
+    pub fn new() -> SustainTracker {
+        SustainTracker {
+            pedal: ChannelMap::default(),
+            sustained: ChannelMap::default(),
+            release_velocities: ChannelMap::new([[Velocity::MIN; 128]; 16]),
+        }
+    }
+
+    /// Process `message`, calling `emit` with the messages to send on.
+    pub fn handle<'a, F: FnMut(MidiMessage<'a>)>(
+        &mut self,
+        message: &MidiMessage<'a>,
+        mut emit: F,
+    ) {
+        match message {
+            MidiMessage::NoteOn(c, n, v) if *v != Velocity::MIN => {
+                self.sustained[*c] &= !(1 << u8::from(*n));
+            }
+            MidiMessage::NoteOn(c, n, v) | MidiMessage::NoteOff(c, n, v) if self.pedal[*c] => {
+                self.sustained[*c] |= 1 << u8::from(*n);
+                self.release_velocities[*c][usize::from(u8::from(*n))] = *v;
+                return;
+            }
+            MidiMessage::ControlChange(c, ControlFunction::DAMPER_PEDAL, v) => {
+                let down = *v >= U7(64);
+                if self.pedal[*c] && !down {
+                    emit(message.clone());
+                    self.pedal[*c] = false;
+                    self.release(*c, emit);
+                    return;
+                }
+                self.pedal[*c] = down;
+            }
+            MidiMessage::ControlChange(c, f, _)
+                if *f == ControlFunction::ALL_SOUND_OFF
+                    || u8::from(*f) >= u8::from(ControlFunction::ALL_NOTES_OFF) =>
+            {
+                self.sustained[*c] = 0;
+            }
+            MidiMessage::Reset => *self = SustainTracker::new(),
+            _ => {}
+        }
+        emit(message.clone());
+    }
+
+    /// Returns `true` if the pedal is down on `channel`.
+    pub fn is_pedal_down(&self, channel: Channel) -> bool {
+        self.pedal[channel]
+    }
+
+    /// Returns `true` if `note` has been released on `channel` but is held by the pedal.
+    pub fn is_sustained(&self, channel: Channel, note: Note) -> bool {
+        self.sustained[channel] & (1 << u8::from(note)) != 0
+    }
+
+    /// Emit the deferred `NoteOff` messages of `channel` in ascending note order and forget them.
+    fn release<'a, F: FnMut(MidiMessage<'a>)>(&mut self, channel: Channel, mut emit: F) {
+        let mut bits = self.sustained[channel];
+        while bits != 0 {
+            let index = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+            let note = unsafe { Note::from_u8_unchecked(index) };
+            let velocity = self.release_velocities[channel][usize::from(index)];
+            emit(MidiMessage::NoteOff(channel, note, velocity));
+        }
+        self.sustained[channel] = 0;
+    }
+}
+
+impl Default for SustainTracker {
+    fn default() -> SustainTracker {
+        SustainTracker::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pedal(channel: Channel, value: u8) -> MidiMessage<'static> {
+        MidiMessage::ControlChange(channel, ControlFunction::DAMPER_PEDAL, U7(value))
+    }
+
+    #[test]
+    fn passes_through_without_pedal() {
+        let mut sustain = SustainTracker::new();
+        let message = MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(3));
+        let mut count = 0;
+        sustain.handle(&message, |m| {
+            assert_eq!(m, message);
+            count += 1;
+        });
+        assert_eq!(count, 1);
+        assert!(!sustain.is_sustained(Channel::Ch1, Note::C4));
+    }
+
+    #[test]
+    fn defers_note_off_until_pedal_release() {
+        let mut sustain = SustainTracker::new();
+        sustain.handle(&pedal(Channel::Ch2, 64), |_| {});
+        assert!(sustain.is_pedal_down(Channel::Ch2));
+        let mut count = 0;
+        sustain.handle(&MidiMessage::NoteOff(Channel::Ch2, Note::D4, U7(9)), |_| {
+            count += 1
+        });
+        sustain.handle(&MidiMessage::NoteOn(Channel::Ch2, Note::C4, U7(0)), |_| {
+            count += 1
+        });
+        // A NoteOff on another channel is not affected.
+        sustain.handle(&MidiMessage::NoteOff(Channel::Ch3, Note::C4, U7(0)), |_| {
+            count += 1
+        });
+        assert_eq!(count, 1);
+
+        let mut released = [MidiMessage::Reset, MidiMessage::Reset, MidiMessage::Reset];
+        let mut len = 0;
+        sustain.handle(&pedal(Channel::Ch2, 63), |m| {
+            released[len] = m;
+            len += 1;
+        });
+        assert_eq!(
+            released,
+            [
+                pedal(Channel::Ch2, 63),
+                MidiMessage::NoteOff(Channel::Ch2, Note::C4, U7(0)),
+                MidiMessage::NoteOff(Channel::Ch2, Note::D4, U7(9)),
+            ]
+        );
+        assert!(!sustain.is_sustained(Channel::Ch2, Note::D4));
+    }
+
+    #[test]
+    fn repressed_note_is_not_released() {
+        let mut sustain = SustainTracker::new();
+        sustain.handle(&pedal(Channel::Ch1, 127), |_| {});
+        sustain.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(1)), |_| {});
+        sustain.handle(&MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0)), |_| {});
+        assert!(sustain.is_sustained(Channel::Ch1, Note::C4));
+        sustain.handle(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(1)), |_| {});
+        assert!(!sustain.is_sustained(Channel::Ch1, Note::C4));
+        let mut count = 0;
+        sustain.handle(&pedal(Channel::Ch1, 0), |_| count += 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn all_notes_off_forgets_sustained_notes() {
+        let mut sustain = SustainTracker::new();
+        sustain.handle(&pedal(Channel::Ch1, 127), |_| {});
+        sustain.handle(&MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0)), |_| {});
+        sustain.handle(
+            &MidiMessage::ControlChange(Channel::Ch1, ControlFunction::ALL_NOTES_OFF, U7(0)),
+            |_| {},
+        );
+        assert!(!sustain.is_sustained(Channel::Ch1, Note::C4));
+        assert!(sustain.is_pedal_down(Channel::Ch1));
+        sustain.handle(&MidiMessage::Reset, |_| {});
+        assert!(!sustain.is_pedal_down(Channel::Ch1));
+    }
+}
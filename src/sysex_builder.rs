@@ -0,0 +1,173 @@
+use crate::{FromBytesError, ManufacturerId, MidiMessage, U7};
+use std::vec::Vec;
+
+/// Builds an owned SysEx message from a manufacturer ID and fragments of data.
+///
+/// Fragments given as bytes are checked to be 7 bit data bytes. The first invalid byte is
+/// reported by `build`, so fragments can be chained without checking each one. The start and end
+/// bytes are added when the message is encoded. A maximum length can be set with `max_len` to
+/// bound the memory used when building from untrusted input.
+///
+/// # Example
+/// ```
+/// use wmidi::{ManufacturerId, MidiMessage, SysExBuilder, U7};
+/// let message = SysExBuilder::new(ManufacturerId::NON_COMMERCIAL)
+///     .device_id(U7::from_u8_lossy(0x10))
+///     .bytes(&[0x01, 0x02])
+///     .data(&[U7::MAX])
+///     .build()
+///     .unwrap();
+/// assert_eq!(message.to_vec(), [0xF0, 0x7D, 0x10, 0x01, 0x02, 0x7F, 0xF7]);
+///
+/// let invalid = SysExBuilder::new(ManufacturerId::NON_COMMERCIAL).bytes(&[0x80]);
+/// assert_eq!(invalid.build(), Err(wmidi::FromBytesError::DataByteOutOfRange));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SysExBuilder {
+    data: Vec<U7>,
+    max_len: usize,
+    error: Option<FromBytesError>,
+}
+
+impl SysExBuilder {
+    /// Start a message for `manufacturer`.
+    ///
+    /// # Panics
+    /// Panics if `manufacturer` is `ManufacturerId::Standard(U7::MIN)`, which is not a valid ID.
+    pub fn new(manufacturer: ManufacturerId) -> SysExBuilder {
+        let mut bytes = [0u8; 3];
+        let size = manufacturer.copy_to_slice(&mut bytes).unwrap();
+        SysExBuilder {
+            data: U7::try_from_bytes(&bytes[..size]).unwrap().to_vec(),
+            max_len: usize::MAX,
+            error: None,
+        }
+    }
+
+    /// Append a device ID. Most manufacturers expect it directly after the manufacturer ID.
+    pub fn device_id(self, device_id: U7) -> SysExBuilder {
+        self.data(&[device_id])
+    }
+
+    /// Limit the payload, including the manufacturer ID, to `max_len` data bytes. Fragments that
+    /// would make the payload longer are not appended, and `build` returns
+    /// `FromBytesError::SysExTooLong`. Without a limit, the payload grows with every fragment.
+    pub fn max_len(mut self, max_len: usize) -> SysExBuilder {
+        self.max_len = max_len;
+        if self.data.len() > max_len {
+            self.data.truncate(max_len);
+            self.error.get_or_insert(FromBytesError::SysExTooLong);
+        }
+        self
+    }
+
+    /// Append `data`.
+    pub fn data(mut self, data: &[U7]) -> SysExBuilder {
+        if self.max_len - self.data.len() < data.len() {
+            self.error.get_or_insert(FromBytesError::SysExTooLong);
+        } else {
+            self.data.extend_from_slice(data);
+        }
+        self
+    }
+
+    /// Append `bytes`. If any byte is not a 7 bit data byte, nothing is appended and `build`
+    /// returns `FromBytesError::DataByteOutOfRange`.
+    pub fn bytes(mut self, bytes: &[u8]) -> SysExBuilder {
+        match U7::try_from_bytes(bytes) {
+            Ok(data) => self.data(data),
+            Err(err) => {
+                self.error.get_or_insert(err);
+                self
+            }
+        }
+    }
+
+    /// Append a single byte, which must be a 7 bit data byte.
+    pub fn byte(self, byte: u8) -> SysExBuilder {
+        self.bytes(&[byte])
+    }
+
+    /// The data appended so far, including the manufacturer ID.
+    pub fn payload(&self) -> &[U7] {
+        &self.data
+    }
+
+    /// Returns the payload as a `MidiMessage::SysEx` that borrows from the builder, or the first
+    /// error found in the appended bytes.
+    pub fn message(&self) -> Result<MidiMessage<'_>, FromBytesError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(MidiMessage::SysEx(&self.data)),
+        }
+    }
+
+    /// Finish the message as a `MidiMessage::OwnedSysEx`, or return the first error found in the
+    /// appended bytes.
+    pub fn build(self) -> Result<MidiMessage<'static>, FromBytesError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(MidiMessage::OwnedSysEx(self.data)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let id = ManufacturerId::Extended(U7(0x20), U7(0x29));
+        let builder = SysExBuilder::new(id)
+            .byte(0x02)
+            .bytes(&[])
+            .data(&[U7(0x0C)]);
+        assert_eq!(
+            builder.message(),
+            Ok(MidiMessage::SysEx(&[
+                U7(0x00),
+                U7(0x20),
+                U7(0x29),
+                U7(0x02),
+                U7(0x0C)
+            ]))
+        );
+        assert_eq!(
+            builder.build().unwrap().to_vec(),
+            [0xF0, 0x00, 0x20, 0x29, 0x02, 0x0C, 0xF7]
+        );
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let builder = SysExBuilder::new(ManufacturerId::NON_COMMERCIAL)
+            .bytes(&[0x01, 0xF7])
+            .byte(0x02);
+        assert_eq!(builder.payload(), &[U7(0x7D), U7(0x02)]);
+        assert_eq!(builder.message(), Err(FromBytesError::DataByteOutOfRange));
+        assert_eq!(builder.build(), Err(FromBytesError::DataByteOutOfRange));
+    }
+
+    #[test]
+    fn max_len() {
+        let builder = SysExBuilder::new(ManufacturerId::NON_COMMERCIAL)
+            .max_len(3)
+            .bytes(&[0x01, 0x02])
+            .byte(0x03)
+            .bytes(&[0x80]);
+        assert_eq!(builder.payload(), &[U7(0x7D), U7(0x01), U7(0x02)]);
+        assert_eq!(builder.build(), Err(FromBytesError::SysExTooLong));
+
+        let builder = SysExBuilder::new(ManufacturerId::NON_COMMERCIAL)
+            .bytes(&[0x01, 0x02])
+            .max_len(1);
+        assert_eq!(builder.payload(), &[U7(0x7D)]);
+        assert_eq!(builder.message(), Err(FromBytesError::SysExTooLong));
+        assert!(SysExBuilder::new(ManufacturerId::NON_COMMERCIAL)
+            .max_len(2)
+            .byte(0x01)
+            .build()
+            .is_ok());
+    }
+}
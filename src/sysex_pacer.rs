@@ -0,0 +1,173 @@
+/// The rate at which to send SysEx data. Times are in microseconds.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Pacing {
+    /// The most bytes to send at once, for example the packet size of the transport. At least 1
+    /// byte is always sent.
+    pub chunk_size: usize,
+    /// The number of bytes that may be sent every `interval`.
+    pub bytes: u32,
+    /// The time in which `bytes` bytes may be sent.
+    pub interval: u64,
+}
+
+impl Pacing {
+    /// 3 bytes at a time, with 1 ms for every 3 bytes. This is about the speed of a DIN MIDI
+    /// cable, and suits hardware that cannot keep up with faster transports.
+    pub const DIN: Pacing = Pacing {
+        chunk_size: 3,
+        bytes: 3,
+        interval: 1000,
+    };
+
+    /// The time to wait after sending `len` bytes, rounded up. There is no wait if `bytes` is 0.
+    pub fn delay(&self, len: usize) -> u64 {
+        if self.bytes == 0 {
+            return 0;
+        }
+        (len as u64 * self.interval).div_ceil(u64::from(self.bytes))
+    }
+}
+
+impl Default for Pacing {
+    fn default() -> Pacing {
+        Pacing::DIN
+    }
+}
+
+/// Splits an encoded SysEx message into chunks and tells the caller when to send each one, so
+/// that large dumps do not overrun the receiver.
+///
+/// Either iterate to get each chunk with the time to wait after sending it, or call `poll` with
+/// the current time to get the next chunk once it is due.
+///
+/// # Example
+/// ```
+/// use wmidi::{Pacing, SysExPacer};
+/// let dump = [0xF0, 0x7D, 0x01, 0x02, 0x03, 0xF7];
+/// let chunks: Vec<_> = SysExPacer::new(&dump, Pacing::DIN).collect();
+/// assert_eq!(
+///     chunks,
+///     [(&dump[..3], 1000), (&dump[3..], 1000)]
+/// );
+///
+/// let mut pacer = SysExPacer::new(&dump, Pacing::DIN);
+/// assert_eq!(pacer.poll(0), Some(&dump[..3]));
+/// assert_eq!(pacer.poll(500), None);
+/// assert_eq!(pacer.poll(1000), Some(&dump[3..]));
+/// assert!(pacer.is_done());
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SysExPacer<'a> {
+    bytes: &'a [u8],
+    pacing: Pacing,
+    next_due: Option<u64>,
+}
+
+impl<'a> SysExPacer<'a> {
+    /// Create a pacer that sends `bytes` at the rate of `pacing`.
+    pub fn new(bytes: &'a [u8], pacing: Pacing) -> SysExPacer<'a> {
+        SysExPacer {
+            bytes,
+            pacing,
+            next_due: None,
+        }
+    }
+
+    /// The bytes that have not been sent.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns `true` once every byte has been sent.
+    pub fn is_done(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The time at which the next chunk is due, or `None` if no chunk has been sent by `poll` or
+    /// every byte has been sent.
+    pub fn next_due(&self) -> Option<u64> {
+        if self.is_done() {
+            return None;
+        }
+        self.next_due
+    }
+
+    /// Returns the next chunk if it is due at `now`, and records it as sent. The first chunk is
+    /// due immediately.
+    pub fn poll(&mut self, now: u64) -> Option<&'a [u8]> {
+        if self.next_due.is_some_and(|due| now < due) {
+            return None;
+        }
+        let (chunk, delay) = self.next()?;
+        self.next_due = Some(now.saturating_add(delay));
+        Some(chunk)
+    }
+}
+
+impl<'a> Iterator for SysExPacer<'a> {
+    /// The next chunk, and the time to wait after sending it.
+    type Item = (&'a [u8], u64);
+
+    fn next(&mut self) -> Option<(&'a [u8], u64)> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let len = self.pacing.chunk_size.clamp(1, self.bytes.len());
+        let (chunk, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Some((chunk, self.pacing.delay(len)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len().div_ceil(self.pacing.chunk_size.max(1));
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for SysExPacer<'a> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn chunks() {
+        let bytes = [0u8; 300];
+        let pacing = Pacing {
+            chunk_size: 128,
+            bytes: 3125,
+            interval: 1_000_000,
+        };
+        let pacer = SysExPacer::new(&bytes, pacing);
+        assert_eq!(pacer.len(), 3);
+        let mut chunks = pacer.map(|(chunk, delay)| (chunk.len(), delay));
+        assert_eq!(chunks.next(), Some((128, 40960)));
+        assert_eq!(chunks.next(), Some((128, 40960)));
+        assert_eq!(chunks.next(), Some((44, 14080)));
+        assert_eq!(chunks.next(), None);
+
+        let pacing = Pacing {
+            chunk_size: 0,
+            bytes: 0,
+            interval: 1000,
+        };
+        assert_eq!(SysExPacer::new(&bytes[..2], pacing).count(), 2);
+        assert_eq!(pacing.delay(100), 0);
+        assert_eq!(Pacing::DIN.delay(1), 334);
+    }
+
+    #[test]
+    fn poll() {
+        let bytes = [0u8; 7];
+        let mut pacer = SysExPacer::new(&bytes, Pacing::DIN);
+        assert_eq!(pacer.next_due(), None);
+        assert_eq!(pacer.poll(100).map(<[u8]>::len), Some(3));
+        assert_eq!(pacer.next_due(), Some(1100));
+        assert_eq!(pacer.poll(1099), None);
+        assert_eq!(pacer.poll(1200).map(<[u8]>::len), Some(3));
+        assert_eq!(pacer.poll(2200).map(<[u8]>::len), Some(1));
+        assert!(pacer.is_done());
+        assert_eq!(pacer.next_due(), None);
+        assert_eq!(pacer.poll(5000), None);
+    }
+}
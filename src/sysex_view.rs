@@ -0,0 +1,105 @@
+use crate::{ManufacturerId, MidiMessage, UniversalKind, UniversalSysEx, U7};
+
+/// A SysEx payload split into its manufacturer ID and body, borrowing from the payload.
+///
+/// The body can be split further without copying, so parsers for a manufacturer's messages can be
+/// layered on top of the view. Universal messages can be viewed as a `UniversalSysEx`, which
+/// splits out the device ID and sub-IDs.
+///
+/// # Example
+/// ```
+/// use wmidi::{ManufacturerId, MidiMessage, SysExView, U7};
+/// let bytes = [0xF0, 0x43, 0x10, 0x4C, 0x00, 0x00, 0x7E, 0x00, 0xF7];
+/// let message = MidiMessage::from_bytes(&bytes).unwrap();
+/// let view = SysExView::from_message(&message).unwrap();
+/// assert_eq!(view.manufacturer.name(), Some("Yamaha"));
+/// assert_eq!(view.device_id(), Some(U7::from_u8_lossy(0x10)));
+/// let (model, rest) = view.split_data(1).unwrap();
+/// assert_eq!(U7::data_to_bytes(model), &[0x4C]);
+/// assert_eq!(U7::data_to_bytes(rest), &[0x00, 0x00, 0x7E, 0x00]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SysExView<'a> {
+    /// The manufacturer ID at the start of the payload.
+    pub manufacturer: ManufacturerId,
+    /// The bytes after the manufacturer ID, excluding the end byte.
+    pub body: &'a [U7],
+}
+
+impl<'a> SysExView<'a> {
+    /// View the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns `None` if the payload is too short to hold a manufacturer ID.
+    pub fn from_sysex(payload: &'a [U7]) -> Option<SysExView<'a>> {
+        let (manufacturer, body) = ManufacturerId::from_sysex(payload)?;
+        Some(SysExView { manufacturer, body })
+    }
+
+    /// View a `MidiMessage::SysEx` or `MidiMessage::OwnedSysEx`. Returns `None` for other
+    /// messages.
+    pub fn from_message(message: &'a MidiMessage) -> Option<SysExView<'a>> {
+        match message {
+            MidiMessage::SysEx(payload) => SysExView::from_sysex(payload),
+            #[cfg(feature = "std")]
+            MidiMessage::OwnedSysEx(payload) => SysExView::from_sysex(payload),
+            _ => None,
+        }
+    }
+
+    /// The first byte of the body, which by convention is the device ID. Returns `None` if the
+    /// body is empty.
+    pub fn device_id(&self) -> Option<U7> {
+        self.body.first().copied()
+    }
+
+    /// The bytes after the device ID.
+    pub fn data(&self) -> &'a [U7] {
+        self.body.get(1..).unwrap_or(&[])
+    }
+
+    /// Split the bytes after the device ID into the first `len` bytes and the rest. Returns
+    /// `None` if there are fewer than `len` bytes.
+    pub fn split_data(&self, len: usize) -> Option<(&'a [U7], &'a [U7])> {
+        let data = self.data();
+        if data.len() < len {
+            return None;
+        }
+        Some(data.split_at(len))
+    }
+
+    /// View the message as a Universal message. Returns `None` if it is not one.
+    pub fn universal(&self) -> Option<UniversalSysEx<'a>> {
+        UniversalSysEx::from_body(UniversalKind::from_id(self.manufacturer)?, self.body)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn views() {
+        let payload = U7::try_from_bytes(&[0x00, 0x20, 0x29, 0x02, 0x0C, 0x0E, 0x01]).unwrap();
+        let view = SysExView::from_sysex(payload).unwrap();
+        assert_eq!(
+            view.manufacturer,
+            ManufacturerId::Extended(U7(0x20), U7(0x29))
+        );
+        assert_eq!(view.device_id(), Some(U7(0x02)));
+        assert_eq!(view.data(), &[U7(0x0C), U7(0x0E), U7(0x01)]);
+        assert_eq!(view.split_data(4), None);
+        assert_eq!(view.universal(), None);
+
+        let view = SysExView::from_sysex(&[U7(0x41)]).unwrap();
+        assert_eq!(view.device_id(), None);
+        assert_eq!(view.data(), &[]);
+        assert_eq!(SysExView::from_sysex(&[]), None);
+        assert_eq!(SysExView::from_sysex(&[U7(0x00), U7(0x01)]), None);
+    }
+
+    #[test]
+    fn universal() {
+        let payload = U7::try_from_bytes(&[0x7E, 0x7F, 0x06, 0x01]).unwrap();
+        let universal = SysExView::from_sysex(payload).unwrap().universal().unwrap();
+        assert_eq!(universal, UniversalSysEx::from_sysex(payload).unwrap());
+    }
+}
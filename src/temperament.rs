@@ -0,0 +1,122 @@
+use crate::{Cents, Note};
+
+/// A tuning system, expressed as the detune of each note relative to 12 tone equal temperament.
+pub trait Temperament {
+    /// The detune of `note` relative to its equal tempered pitch.
+    fn offset(&self, note: Note) -> Cents;
+}
+
+/// Standard 12 tone equal temperament. Every offset is zero.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct EqualTemperament;
+
+impl Temperament for EqualTemperament {
+    #[inline(always)]
+    fn offset(&self, _: Note) -> Cents {
+        Cents::ZERO
+    }
+}
+
+/// A temperament defined by a detune for each pitch class, relative to a root. The table repeats
+/// every octave and the root keeps its equal tempered pitch.
+///
+/// # Example
+/// ```
+/// use wmidi::{Note, Temperament, TuningTable};
+/// let just = TuningTable::just_intonation(Note::C4);
+/// // Just major thirds are about 14 cents flat of equal tempered major thirds.
+/// assert!((just.offset(Note::E2).0 + 13.686).abs() < 1E-3);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TuningTable {
+    root: u8,
+    offsets: [Cents; 12],
+}
+
+impl TuningTable {
+    /// 5-limit just intonation offsets, starting from the root.
+    const JUST_INTONATION: [Cents; 12] = [
+        Cents(0.0),
+        Cents(11.731),
+        Cents(3.910),
+        Cents(15.641),
+        Cents(-13.686),
+        Cents(-1.955),
+        Cents(-9.776),
+        Cents(1.955),
+        Cents(13.686),
+        Cents(-15.641),
+        Cents(17.596),
+        Cents(-11.731),
+    ];
+
+    /// Pythagorean (3-limit) offsets, starting from the root. The tritone is the augmented 4th.
+    const PYTHAGOREAN: [Cents; 12] = [
+        Cents(0.0),
+        Cents(-9.775),
+        Cents(3.910),
+        Cents(-5.865),
+        Cents(7.820),
+        Cents(-1.955),
+        Cents(11.730),
+        Cents(1.955),
+        Cents(-7.820),
+        Cents(5.865),
+        Cents(-3.910),
+        Cents(9.775),
+    ];
+
+    /// Create a table from the offsets of each interval above `root`. `offsets[0]` applies to
+    /// the root's pitch class, `offsets[1]` to the pitch class a half step above, and so on.
+    pub fn new(root: Note, offsets: [Cents; 12]) -> TuningTable {
+        TuningTable {
+            root: u8::from(root) % 12,
+            offsets,
+        }
+    }
+
+    /// 5-limit just intonation built on `root`. Intervals use the ratios 16/15, 9/8, 6/5, 5/4,
+    /// 4/3, 45/32, 3/2, 8/5, 5/3, 9/5, and 15/8.
+    pub fn just_intonation(root: Note) -> TuningTable {
+        TuningTable::new(root, TuningTable::JUST_INTONATION)
+    }
+
+    /// Pythagorean tuning built on `root`, with every interval derived from stacked 3/2 fifths.
+    pub fn pythagorean(root: Note) -> TuningTable {
+        TuningTable::new(root, TuningTable::PYTHAGOREAN)
+    }
+
+    /// The offsets of each interval above the root.
+    pub fn offsets(&self) -> &[Cents; 12] {
+        &self.offsets
+    }
+}
+
+impl Temperament for TuningTable {
+    #[inline(always)]
+    fn offset(&self, note: Note) -> Cents {
+        let interval = (u8::from(note) + 12 - self.root) % 12;
+        self.offsets[usize::from(interval)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn root_is_not_detuned() {
+        for root in Note::range(Note::C4, Note::B4) {
+            assert_eq!(TuningTable::pythagorean(root).offset(root), Cents::ZERO);
+            assert_eq!(TuningTable::just_intonation(root).offset(root), Cents::ZERO);
+        }
+    }
+
+    #[test]
+    fn offsets_are_relative_to_root() {
+        let d_pythagorean = TuningTable::pythagorean(Note::D1);
+        assert_eq!(d_pythagorean.offset(Note::A5), Cents(1.955));
+        assert_eq!(d_pythagorean.offset(Note::C5), Cents(-3.910));
+        assert_eq!(EqualTemperament.offset(Note::A5), Cents::ZERO);
+    }
+}
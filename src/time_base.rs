@@ -0,0 +1,153 @@
+/// Converts between audio frames, seconds, MIDI clocks, and Standard MIDI File ticks at a fixed
+/// sample rate, tempo, and division.
+///
+/// Conversions between frames, clocks, and ticks use integer arithmetic so that the same tick
+/// always lands on the same frame. A tick or clock lands on the first frame at or after its
+/// time, and converting that frame back gives the same tick or clock.
+///
+/// # Example
+/// ```
+/// use wmidi::TimeBase;
+/// let time_base = TimeBase {
+///     sample_rate: 48000,
+///     ..TimeBase::new()
+/// };
+/// // At 120 BPM a quarter note is half a second.
+/// assert_eq!(time_base.ticks_to_frames(480), 24000);
+/// assert_eq!(time_base.frames_to_ticks(24000), 480);
+/// assert_eq!(time_base.clocks_to_frames(1), 1000);
+/// assert_eq!(time_base.ticks_to_seconds(960), 1.0);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TimeBase {
+    /// The number of audio frames per second.
+    pub sample_rate: u32,
+    /// The tempo in microseconds per quarter note, as in the Standard MIDI File set tempo meta
+    /// event.
+    pub tempo: u32,
+    /// The number of ticks per quarter note.
+    pub division: u16,
+}
+
+impl TimeBase {
+    /// Create a time base at 44100 Hz, 120 BPM, and 480 ticks per quarter note.
+    pub fn new() -> TimeBase {
+        TimeBase {
+            sample_rate: 44100,
+            tempo: 500_000,
+            division: 480,
+        }
+    }
+
+    /// The tempo in quarter notes per minute.
+    pub fn bpm(&self) -> f64 {
+        60_000_000.0 / f64::from(self.tempo.max(1))
+    }
+
+    /// Set the tempo in quarter notes per minute. A NaN `bpm` leaves the tempo unchanged.
+    pub fn set_bpm(&mut self, bpm: f64) {
+        let tempo = 60_000_000.0 / bpm;
+        if !tempo.is_nan() {
+            self.tempo = tempo.clamp(1.0, f64::from(u32::MAX)) as u32;
+        }
+    }
+
+    /// The number of seconds in `frames` frames.
+    pub fn frames_to_seconds(&self, frames: u64) -> f64 {
+        frames as f64 / f64::from(self.sample_rate.max(1))
+    }
+
+    /// The frame that is playing `seconds` from the start, rounded to the nearest frame.
+    pub fn seconds_to_frames(&self, seconds: f64) -> u64 {
+        ((seconds * f64::from(self.sample_rate)).max(0.0) + 0.5) as u64
+    }
+
+    /// The number of seconds in `ticks` ticks.
+    pub fn ticks_to_seconds(&self, ticks: u64) -> f64 {
+        ticks as f64 * f64::from(self.tempo) / (f64::from(self.division.max(1)) * 1_000_000.0)
+    }
+
+    /// The number of ticks in `seconds`, rounded down.
+    pub fn seconds_to_ticks(&self, seconds: f64) -> u64 {
+        let ticks = seconds * f64::from(self.division) * 1_000_000.0 / f64::from(self.tempo.max(1));
+        ticks.max(0.0) as u64
+    }
+
+    /// The first frame at or after tick `ticks`.
+    pub fn ticks_to_frames(&self, ticks: u64) -> u64 {
+        let numerator = u128::from(ticks) * u128::from(self.tempo) * u128::from(self.sample_rate);
+        let denominator = u128::from(self.division.max(1)) * 1_000_000;
+        numerator.div_ceil(denominator) as u64
+    }
+
+    /// The number of ticks that have started by frame `frames`.
+    pub fn frames_to_ticks(&self, frames: u64) -> u64 {
+        let numerator = u128::from(frames) * u128::from(self.division) * 1_000_000;
+        let denominator = u128::from(self.tempo.max(1)) * u128::from(self.sample_rate.max(1));
+        (numerator / denominator) as u64
+    }
+
+    /// The first frame at or after MIDI clock `clocks`, at 24 clocks per quarter note.
+    pub fn clocks_to_frames(&self, clocks: u64) -> u64 {
+        let numerator = u128::from(clocks) * u128::from(self.tempo) * u128::from(self.sample_rate);
+        numerator.div_ceil(24 * 1_000_000) as u64
+    }
+
+    /// The number of MIDI clocks that have started by frame `frames`.
+    pub fn frames_to_clocks(&self, frames: u64) -> u64 {
+        let numerator = u128::from(frames) * 24 * 1_000_000;
+        let denominator = u128::from(self.tempo.max(1)) * u128::from(self.sample_rate.max(1));
+        (numerator / denominator) as u64
+    }
+
+    /// The number of ticks in `clocks` MIDI clocks, rounded down.
+    pub fn clocks_to_ticks(&self, clocks: u64) -> u64 {
+        clocks * u64::from(self.division) / 24
+    }
+
+    /// The number of MIDI clocks that have started by tick `ticks`.
+    pub fn ticks_to_clocks(&self, ticks: u64) -> u64 {
+        ticks * 24 / u64::from(self.division.max(1))
+    }
+}
+
+impl Default for TimeBase {
+    fn default() -> TimeBase {
+        TimeBase::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ticks_land_on_frames() {
+        let time_base = TimeBase {
+            sample_rate: 44100,
+            tempo: 461_538,
+            division: 96,
+        };
+        for ticks in 0..10_000 {
+            let frame = time_base.ticks_to_frames(ticks);
+            // The tick has started on its own frame, and not on the frame before.
+            assert_eq!(time_base.frames_to_ticks(frame), ticks);
+            assert!(frame == 0 || time_base.frames_to_ticks(frame - 1) < ticks);
+        }
+        for clocks in 0..1_000 {
+            let frame = time_base.clocks_to_frames(clocks);
+            assert_eq!(time_base.frames_to_clocks(frame), clocks);
+        }
+    }
+
+    #[test]
+    fn bpm() {
+        let mut time_base = TimeBase::new();
+        assert_eq!(time_base.bpm(), 120.0);
+        time_base.set_bpm(60.0);
+        assert_eq!(time_base.tempo, 1_000_000);
+        assert_eq!(time_base.clocks_to_ticks(24), 480);
+        assert_eq!(time_base.ticks_to_clocks(479), 23);
+        assert_eq!(time_base.frames_to_clocks(44100), 24);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::MidiMessage;
+
+#[cfg(feature = "std")]
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// A message with the time it was received or should be sent. The unit of `time` is up to the
+/// caller, for example samples, ticks, or microseconds.
+///
+/// # Example
+/// ```
+/// use wmidi::{MidiMessage, Timed};
+/// let event = Timed::new(480u64, MidiMessage::Start);
+/// assert_eq!(event.time, 480);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Timed<T, M = MidiMessage<'static>> {
+    /// The timestamp.
+    pub time: T,
+    /// The message.
+    pub message: M,
+}
+
+impl<T, M> Timed<T, M> {
+    /// Create a timed message.
+    pub fn new(time: T, message: M) -> Timed<T, M> {
+        Timed { time, message }
+    }
+
+    /// Replace the message with the result of `f`, keeping the time.
+    pub fn map<N, F: FnOnce(M) -> N>(self, f: F) -> Timed<T, N> {
+        Timed {
+            time: self.time,
+            message: f(self.message),
+        }
+    }
+}
+
+/// A queue of timed messages that are taken out in order of time. Messages with equal times are
+/// taken out in the order they were pushed.
+///
+/// # Example
+/// ```
+/// use wmidi::{MidiMessage, Timed, TimedQueue};
+/// let mut queue = TimedQueue::new();
+/// queue.push(Timed::new(20, MidiMessage::Stop));
+/// queue.push(Timed::new(10, MidiMessage::Start));
+/// queue.push(Timed::new(10, MidiMessage::TimingClock));
+/// assert_eq!(queue.pop_due(15).map(|t| t.message), Some(MidiMessage::Start));
+/// assert_eq!(queue.pop_due(15).map(|t| t.message), Some(MidiMessage::TimingClock));
+/// assert_eq!(queue.pop_due(15), None);
+/// assert_eq!(queue.len(), 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct TimedQueue<T: Ord, M = MidiMessage<'static>> {
+    heap: BinaryHeap<Entry<T, M>>,
+    sequence: u64,
+}
+
+#[cfg(feature = "std")]
+impl<T: Ord, M> TimedQueue<T, M> {
+    /// Create an empty queue.
+    pub fn new() -> TimedQueue<T, M> {
+        TimedQueue {
+            heap: BinaryHeap::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Add `timed` to the queue.
+    pub fn push(&mut self, timed: Timed<T, M>) {
+        self.heap.push(Entry {
+            sequence: self.sequence,
+            timed,
+        });
+        self.sequence = self.sequence.wrapping_add(1);
+    }
+
+    /// The earliest message, without removing it.
+    pub fn peek(&self) -> Option<&Timed<T, M>> {
+        self.heap.peek().map(|e| &e.timed)
+    }
+
+    /// Remove and return the earliest message.
+    pub fn pop(&mut self) -> Option<Timed<T, M>> {
+        self.heap.pop().map(|e| e.timed)
+    }
+
+    /// Remove and return the earliest message if its time is not after `now`.
+    pub fn pop_due(&mut self, now: T) -> Option<Timed<T, M>> {
+        if self.peek()?.time <= now {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    /// The number of messages in the queue.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if the queue has no messages.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Remove every message.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Ord, M> Default for TimedQueue<T, M> {
+    fn default() -> TimedQueue<T, M> {
+        TimedQueue::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Ord, M> Extend<Timed<T, M>> for TimedQueue<T, M> {
+    fn extend<I: IntoIterator<Item = Timed<T, M>>>(&mut self, iter: I) {
+        for timed in iter {
+            self.push(timed);
+        }
+    }
+}
+
+/// A heap entry that orders earlier times, then earlier pushes, as greater so that
+/// `BinaryHeap` pops them first.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+struct Entry<T, M> {
+    sequence: u64,
+    timed: Timed<T, M>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Ord, M> Ord for Entry<T, M> {
+    fn cmp(&self, other: &Entry<T, M>) -> Ordering {
+        other
+            .timed
+            .time
+            .cmp(&self.timed.time)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Ord, M> PartialOrd for Entry<T, M> {
+    fn partial_cmp(&self, other: &Entry<T, M>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Ord, M> PartialEq for Entry<T, M> {
+    fn eq(&self, other: &Entry<T, M>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Ord, M> Eq for Entry<T, M> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn map() {
+        let timed = Timed::new(3, MidiMessage::Start).map(|m| m.bytes_size());
+        assert_eq!(timed, Timed::new(3, 1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn stable_order() {
+        let mut queue: TimedQueue<u32, u32> = TimedQueue::new();
+        queue.extend((0..100).map(|i| Timed::new(i % 3, i)));
+        let mut previous = None;
+        while let Some(timed) = queue.pop() {
+            if let Some((time, message)) = previous {
+                assert!((time, message) < (timed.time, timed.message));
+            }
+            previous = Some((timed.time, timed.message));
+        }
+        assert!(queue.is_empty());
+    }
+}
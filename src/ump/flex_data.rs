@@ -0,0 +1,483 @@
+use super::{Group, MessageType, SysExStatus, UmpPacket};
+use crate::Channel;
+
+#[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [UMP] Who a flex data message applies to.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum FlexAddress {
+    /// A single channel of the group.
+    Channel(Channel),
+    /// The whole group.
+    Group,
+}
+
+/// [UMP] The kind of a flex data text message, given by its status bank and status.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TextKind {
+    /// The status bank, 0x01 for metadata text and 0x02 for performance text.
+    pub bank: u8,
+    /// The status within the bank.
+    pub status: u8,
+}
+
+impl TextKind {
+    pub const UNKNOWN_METADATA: TextKind = TextKind::metadata(0x00);
+    pub const PROJECT_NAME: TextKind = TextKind::metadata(0x01);
+    pub const SONG_NAME: TextKind = TextKind::metadata(0x02);
+    pub const CLIP_NAME: TextKind = TextKind::metadata(0x03);
+    pub const COPYRIGHT_NOTICE: TextKind = TextKind::metadata(0x04);
+    pub const COMPOSER_NAME: TextKind = TextKind::metadata(0x05);
+    pub const LYRICIST_NAME: TextKind = TextKind::metadata(0x06);
+    pub const ARRANGER_NAME: TextKind = TextKind::metadata(0x07);
+    pub const PUBLISHER_NAME: TextKind = TextKind::metadata(0x08);
+    pub const PRIMARY_PERFORMER_NAME: TextKind = TextKind::metadata(0x09);
+    pub const ACCOMPANYING_PERFORMER_NAME: TextKind = TextKind::metadata(0x0A);
+    pub const RECORDING_DATE: TextKind = TextKind::metadata(0x0B);
+    pub const RECORDING_LOCATION: TextKind = TextKind::metadata(0x0C);
+
+    pub const UNKNOWN_PERFORMANCE_TEXT: TextKind = TextKind::performance(0x00);
+    pub const LYRICS: TextKind = TextKind::performance(0x01);
+    pub const LYRICS_LANGUAGE: TextKind = TextKind::performance(0x02);
+    pub const RUBY: TextKind = TextKind::performance(0x03);
+    pub const RUBY_LANGUAGE: TextKind = TextKind::performance(0x04);
+
+    const fn metadata(status: u8) -> TextKind {
+        TextKind { bank: 0x01, status }
+    }
+
+    const fn performance(status: u8) -> TextKind {
+        TextKind { bank: 0x02, status }
+    }
+}
+
+/// [UMP] A flex data packet holding up to 12 bytes of UTF-8 text. Longer text is split across
+/// several packets, and a character may be split between packets.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TextPacket {
+    /// The kind of text.
+    pub kind: TextKind,
+    /// Where the packet falls in its text. Flex data uses the same values as SysEx.
+    pub form: SysExStatus,
+    data: [u8; 12],
+    len: u8,
+}
+
+impl TextPacket {
+    /// The most bytes of text in a packet.
+    pub const MAX_DATA_LEN: usize = 12;
+
+    /// Create a packet holding `data`, or return `None` if it has more than 12 bytes. Since
+    /// unused bytes are sent as 0, `data` should not contain 0.
+    pub fn new(kind: TextKind, form: SysExStatus, data: &[u8]) -> Option<TextPacket> {
+        if data.len() > TextPacket::MAX_DATA_LEN {
+            return None;
+        }
+        let mut packet = TextPacket {
+            kind,
+            form,
+            data: [0; 12],
+            len: data.len() as u8,
+        };
+        packet.data[..data.len()].copy_from_slice(data);
+        Some(packet)
+    }
+
+    /// The bytes of text in the packet.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..usize::from(self.len)]
+    }
+}
+
+/// [UMP] A flex data message of message type 0xD.
+///
+/// Flex data carries information that a Standard MIDI File holds in meta events, such as tempo
+/// and lyrics, so it can be sent in a live stream.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{FlexAddress, FlexDataMessage};
+/// use wmidi::U4;
+/// // 120 beats per minute is 500ms, or 50,000,000 units of 10ns, per quarter note.
+/// let message = FlexDataMessage::SetTempo(50_000_000);
+/// let packet = message.to_packet(U4::MIN, FlexAddress::Group);
+/// assert_eq!(packet.words(), &[0xD010_0000, 0x02FA_F080, 0, 0]);
+/// assert_eq!(
+///     FlexDataMessage::from_packet(&packet),
+///     Some((U4::MIN, FlexAddress::Group, message))
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum FlexDataMessage {
+    /// The length of a quarter note in units of 10 nanoseconds.
+    SetTempo(u32),
+
+    /// The time signature.
+    SetTimeSignature {
+        /// The number of beats in a bar.
+        numerator: u8,
+        /// The length of a beat as a power of 2, so 2 is a quarter note and 3 an eighth note.
+        denominator_exponent: u8,
+        /// The number of notated 32nd notes in a quarter note, usually 8.
+        notated_32nds_per_quarter: u8,
+    },
+
+    /// The key signature.
+    SetKeySignature {
+        /// The number of sharps, or flats if negative, from -8 to 7.
+        sharps: i8,
+        /// The tonic note, 1 to 7 for A to G, or 0 if unknown.
+        tonic: u8,
+    },
+
+    /// Part of a text message.
+    Text(TextPacket),
+}
+
+impl FlexDataMessage {
+    /// The packet holding the message, addressed to `address` on `group`.
+    pub fn to_packet(&self, group: Group, address: FlexAddress) -> UmpPacket {
+        let (form, bank, status, data) = match *self {
+            FlexDataMessage::SetTempo(tempo) => (SysExStatus::Complete, 0x00, 0x00, [tempo, 0, 0]),
+            FlexDataMessage::SetTimeSignature {
+                numerator,
+                denominator_exponent,
+                notated_32nds_per_quarter,
+            } => {
+                let word = u32::from(numerator) << 24
+                    | u32::from(denominator_exponent) << 16
+                    | u32::from(notated_32nds_per_quarter) << 8;
+                (SysExStatus::Complete, 0x00, 0x01, [word, 0, 0])
+            }
+            FlexDataMessage::SetKeySignature { sharps, tonic } => {
+                let word = u32::from(sharps as u8 & 0x0F) << 28 | u32::from(tonic & 0x0F) << 24;
+                (SysExStatus::Complete, 0x00, 0x05, [word, 0, 0])
+            }
+            FlexDataMessage::Text(ref packet) => {
+                let mut words = [0u32; 3];
+                for (word, chunk) in words.iter_mut().zip(packet.data.chunks_exact(4)) {
+                    *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+                }
+                (packet.form, packet.kind.bank, packet.kind.status, words)
+            }
+        };
+        let (address, channel) = match address {
+            FlexAddress::Channel(channel) => (0x0, channel.index()),
+            FlexAddress::Group => (0x1, 0),
+        };
+        let word = 0xD000_0000
+            | u32::from(u8::from(group)) << 24
+            | form.value() << 22
+            | address << 20
+            | u32::from(channel) << 16
+            | u32::from(bank) << 8
+            | u32::from(status);
+        UmpPacket::new([word, data[0], data[1], data[2]])
+    }
+
+    /// Parse a packet. Returns the group, address and message, or `None` if the packet is not a
+    /// flex data message of a kind held by `FlexDataMessage`.
+    pub fn from_packet(packet: &UmpPacket) -> Option<(Group, FlexAddress, FlexDataMessage)> {
+        if packet.message_type() != MessageType::FlexData {
+            return None;
+        }
+        let words = packet.words();
+        let form = SysExStatus::from_value(words[0] >> 22 & 0x3).unwrap();
+        let address = match words[0] >> 20 & 0x3 {
+            0x0 => FlexAddress::Channel(Channel::from_index((words[0] >> 16) as u8 & 0x0F).ok()?),
+            0x1 => FlexAddress::Group,
+            _ => return None,
+        };
+        let [_, _, bank, status] = words[0].to_be_bytes();
+        let message = match (bank, status) {
+            (0x00, 0x00) => FlexDataMessage::SetTempo(words[1]),
+            (0x00, 0x01) => {
+                let [numerator, denominator_exponent, notated_32nds_per_quarter, _] =
+                    words[1].to_be_bytes();
+                FlexDataMessage::SetTimeSignature {
+                    numerator,
+                    denominator_exponent,
+                    notated_32nds_per_quarter,
+                }
+            }
+            (0x00, 0x05) => FlexDataMessage::SetKeySignature {
+                // Shifting the signed byte keeps the sign of the upper 4 bits.
+                sharps: (words[1] >> 24) as i8 >> 4,
+                tonic: (words[1] >> 24) as u8 & 0x0F,
+            },
+            (0x01, _) | (0x02, _) => {
+                let mut data = [0u8; 12];
+                for (chunk, word) in data.chunks_exact_mut(4).zip(&words[1..]) {
+                    chunk.copy_from_slice(&word.to_be_bytes());
+                }
+                let len = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+                let kind = TextKind { bank, status };
+                FlexDataMessage::Text(TextPacket::new(kind, form, &data[..len]).unwrap())
+            }
+            _ => return None,
+        };
+        Some((packet.group()?, address, message))
+    }
+}
+
+/// [UMP] Splits text into flex data packets.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{FlexAddress, FlexDataMessage, TextKind, TextPackets};
+/// use wmidi::{Channel, U4};
+/// let address = FlexAddress::Channel(Channel::Ch1);
+/// let text = "Happy birthday to you";
+/// let packets: Vec<_> = TextPackets::new(U4::MIN, address, TextKind::LYRICS, text).collect();
+/// assert_eq!(packets.len(), 2);
+/// assert_eq!(packets[0].words(), &[0xD040_0201, 0x4861_7070, 0x7920_6269, 0x7274_6864]);
+/// match FlexDataMessage::from_packet(&packets[1]) {
+///     Some((_, _, FlexDataMessage::Text(packet))) => assert_eq!(packet.data(), b"ay to you"),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TextPackets<'a> {
+    group: Group,
+    address: FlexAddress,
+    kind: TextKind,
+    text: &'a [u8],
+    index: usize,
+    count: usize,
+}
+
+impl<'a> TextPackets<'a> {
+    /// Split `text` into packets addressed to `address` on `group`. Empty text is sent as a
+    /// single empty packet.
+    pub fn new(
+        group: Group,
+        address: FlexAddress,
+        kind: TextKind,
+        text: &'a str,
+    ) -> TextPackets<'a> {
+        TextPackets {
+            group,
+            address,
+            kind,
+            text: text.as_bytes(),
+            index: 0,
+            count: text.len().div_ceil(TextPacket::MAX_DATA_LEN).max(1),
+        }
+    }
+}
+
+impl<'a> Iterator for TextPackets<'a> {
+    type Item = UmpPacket;
+
+    fn next(&mut self) -> Option<UmpPacket> {
+        if self.index == self.count {
+            return None;
+        }
+        let len = self.text.len().min(TextPacket::MAX_DATA_LEN);
+        let (data, rest) = self.text.split_at(len);
+        self.text = rest;
+        let form = SysExStatus::for_index(self.index, self.count);
+        self.index += 1;
+        let packet = TextPacket::new(self.kind, form, data).unwrap();
+        Some(FlexDataMessage::Text(packet).to_packet(self.group, self.address))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.count - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for TextPackets<'a> {}
+
+/// [UMP] Text joined from flex data packets.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Text {
+    /// The group the text was received on.
+    pub group: Group,
+    /// Who the text applies to.
+    pub address: FlexAddress,
+    /// The kind of text.
+    pub kind: TextKind,
+    /// The text. Invalid UTF-8 is replaced with U+FFFD.
+    pub text: String,
+}
+
+/// [UMP] Joins flex data packets into text. Each kind of text may have one message in progress
+/// for each address of each group.
+///
+/// Packets without a start are ignored, and a `Start` or `Complete` packet drops any text in
+/// progress. Text longer than `max_len` bytes is dropped, so a broken or malicious sender cannot
+/// use unbounded memory.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{FlexAddress, TextAssembler, TextKind, TextPackets};
+/// use wmidi::U4;
+/// let mut assembler = TextAssembler::new(256);
+/// let mut text = None;
+/// for packet in TextPackets::new(U4::MIN, FlexAddress::Group, TextKind::SONG_NAME, "Jóga") {
+///     text = assembler.push(&packet);
+/// }
+/// assert_eq!(text.unwrap().text, "Jóga");
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct TextAssembler {
+    max_len: usize,
+    pending: Vec<(Group, FlexAddress, TextKind, Vec<u8>)>,
+}
+
+#[cfg(feature = "std")]
+impl TextAssembler {
+    /// Create an assembler for text of at most `max_len` bytes.
+    pub fn new(max_len: usize) -> TextAssembler {
+        TextAssembler {
+            max_len,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The number of texts in progress.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no text is in progress.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Add a packet. Returns the text once its last packet is added. Packets that are not flex
+    /// data text are ignored.
+    pub fn push(&mut self, packet: &UmpPacket) -> Option<Text> {
+        let (group, address, packet) = match FlexDataMessage::from_packet(packet)? {
+            (group, address, FlexDataMessage::Text(packet)) => (group, address, packet),
+            _ => return None,
+        };
+        let index = self
+            .pending
+            .iter()
+            .position(|(g, a, kind, _)| *g == group && *a == address && *kind == packet.kind);
+        let pending = index.map(|index| self.pending.swap_remove(index).3);
+        let mut text = match packet.form {
+            SysExStatus::Complete | SysExStatus::Start => Vec::new(),
+            SysExStatus::Continue | SysExStatus::End => pending?,
+        };
+        if text.len() + packet.data().len() > self.max_len {
+            return None;
+        }
+        text.extend_from_slice(packet.data());
+        match packet.form {
+            SysExStatus::Complete | SysExStatus::End => Some(Text {
+                group,
+                address,
+                kind: packet.kind,
+                text: String::from_utf8_lossy(&text).into_owned(),
+            }),
+            SysExStatus::Start | SysExStatus::Continue => {
+                self.pending.push((group, address, packet.kind, text));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U4;
+
+    #[test]
+    fn round_trip() {
+        let group = U4(0x7);
+        for message in [
+            FlexDataMessage::SetTempo(u32::MAX),
+            FlexDataMessage::SetTimeSignature {
+                numerator: 6,
+                denominator_exponent: 3,
+                notated_32nds_per_quarter: 8,
+            },
+            FlexDataMessage::SetKeySignature {
+                sharps: -8,
+                tonic: 7,
+            },
+            FlexDataMessage::SetKeySignature {
+                sharps: 7,
+                tonic: 0,
+            },
+            FlexDataMessage::Text(
+                TextPacket::new(TextKind::RUBY, SysExStatus::Continue, b"0123456789AB").unwrap(),
+            ),
+            FlexDataMessage::Text(
+                TextPacket::new(TextKind::RECORDING_DATE, SysExStatus::End, b"").unwrap(),
+            ),
+        ]
+        .iter()
+        {
+            for address in [FlexAddress::Group, FlexAddress::Channel(Channel::Ch16)].iter() {
+                let packet = message.to_packet(group, *address);
+                assert_eq!(
+                    FlexDataMessage::from_packet(&packet),
+                    Some((group, *address, *message))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let message = FlexDataMessage::SetKeySignature {
+            sharps: -3,
+            tonic: 3,
+        };
+        assert_eq!(
+            message.to_packet(U4(0), FlexAddress::Group).words(),
+            &[0xD010_0005, 0xD300_0000, 0, 0]
+        );
+        let message = FlexDataMessage::SetTimeSignature {
+            numerator: 4,
+            denominator_exponent: 2,
+            notated_32nds_per_quarter: 8,
+        };
+        assert_eq!(
+            message
+                .to_packet(U4(1), FlexAddress::Channel(Channel::Ch3))
+                .words(),
+            &[0xD102_0001, 0x0402_0800, 0, 0]
+        );
+        for word in [0xD020_0000, 0xD010_0002, 0xD010_0300].iter() {
+            let packet = UmpPacket::from_words(&[*word, 0, 0, 0]).unwrap();
+            assert_eq!(FlexDataMessage::from_packet(&packet), None);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn assemble() {
+        let text = "a longer lyric that spans several packets";
+        let mut assembler = TextAssembler::new(text.len());
+        let mut lyrics = TextPackets::new(U4(0), FlexAddress::Group, TextKind::LYRICS, text);
+        let mut name = TextPackets::new(U4(0), FlexAddress::Group, TextKind::SONG_NAME, "name");
+        assert_eq!(lyrics.len(), 4);
+        assert_eq!(assembler.push(&lyrics.next().unwrap()), None);
+        let song_name = assembler.push(&name.next().unwrap()).unwrap();
+        assert_eq!(song_name.text, "name");
+        assert_eq!(song_name.kind, TextKind::SONG_NAME);
+        assert_eq!(assembler.push(&lyrics.next().unwrap()), None);
+        assert_eq!(assembler.push(&lyrics.next().unwrap()), None);
+        let lyric = assembler.push(&lyrics.next().unwrap()).unwrap();
+        assert_eq!(lyric.text, text);
+        assert!(assembler.is_empty());
+
+        let mut assembler = TextAssembler::new(text.len() - 1);
+        for packet in TextPackets::new(U4(0), FlexAddress::Group, TextKind::LYRICS, text) {
+            assert_eq!(assembler.push(&packet), None);
+        }
+        assert!(assembler.is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+use super::UmpPacket;
+use crate::U4;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, Not};
+
+/// [UMP] One of the 16 groups of a UMP stream. Each group carries 16 channels, like a MIDI 1.0
+/// port.
+pub type Group = U4;
+
+/// [UMP] A set of groups, for example to choose the packets an input responds to.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{GroupSet, UmpPacket};
+/// use wmidi::U4;
+/// let groups = GroupSet::from(U4::MIN);
+/// // A MIDI 1.0 Note On in group 1, and a JR Timestamp, which has no group.
+/// let note_on = UmpPacket::from_words(&[0x2190_3C7F]).unwrap();
+/// let timestamp = UmpPacket::from_words(&[0x0020_1234]).unwrap();
+/// assert!(!groups.allows(&note_on));
+/// assert!(groups.allows(&note_on.with_group(U4::MIN)));
+/// assert!(groups.allows(&timestamp));
+/// ```
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct GroupSet(u16);
+
+impl GroupSet {
+    /// The set with no groups.
+    pub const EMPTY: GroupSet = GroupSet(0);
+    /// The set with all 16 groups.
+    pub const ALL: GroupSet = GroupSet(0xFFFF);
+
+    /// Create a set from a bit mask where bit `i` holds group `i`.
+    #[inline(always)]
+    pub const fn from_bits(bits: u16) -> GroupSet {
+        GroupSet(bits)
+    }
+
+    /// The bit mask where bit `i` holds group `i`.
+    #[inline(always)]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if `group` is in the set.
+    #[inline(always)]
+    pub fn contains(self, group: Group) -> bool {
+        self.0 & (1 << u8::from(group)) != 0
+    }
+
+    /// Add `group` to the set.
+    #[inline(always)]
+    pub fn insert(&mut self, group: Group) {
+        self.0 |= 1 << u8::from(group);
+    }
+
+    /// Remove `group` from the set.
+    #[inline(always)]
+    pub fn remove(&mut self, group: Group) {
+        self.0 &= !(1 << u8::from(group));
+    }
+
+    /// The number of groups in the set.
+    #[inline(always)]
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns `true` if the set has no groups.
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the groups in the set in ascending order.
+    #[inline(always)]
+    pub fn iter(self) -> GroupSetIter {
+        GroupSetIter { bits: self.0 }
+    }
+
+    /// Returns `true` if `packet` is addressed to a group in the set. Packets of message types
+    /// without a group, such as utility and stream messages, are always allowed.
+    pub fn allows(self, packet: &UmpPacket) -> bool {
+        packet.group().is_none_or(|group| self.contains(group))
+    }
+
+    /// Iterate over the packets of `packets` allowed by the set.
+    pub fn filter_iter<I: IntoIterator<Item = UmpPacket>>(
+        self,
+        packets: I,
+    ) -> impl Iterator<Item = UmpPacket> {
+        packets.into_iter().filter(move |p| self.allows(p))
+    }
+}
+
+impl From<Group> for GroupSet {
+    #[inline(always)]
+    fn from(group: Group) -> GroupSet {
+        GroupSet(1 << u8::from(group))
+    }
+}
+
+impl FromIterator<Group> for GroupSet {
+    fn from_iter<I: IntoIterator<Item = Group>>(iter: I) -> GroupSet {
+        let mut set = GroupSet::EMPTY;
+        for group in iter {
+            set.insert(group);
+        }
+        set
+    }
+}
+
+impl IntoIterator for GroupSet {
+    type Item = Group;
+    type IntoIter = GroupSetIter;
+
+    #[inline(always)]
+    fn into_iter(self) -> GroupSetIter {
+        self.iter()
+    }
+}
+
+impl BitOr for GroupSet {
+    type Output = GroupSet;
+
+    #[inline(always)]
+    fn bitor(self, rhs: GroupSet) -> GroupSet {
+        GroupSet(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for GroupSet {
+    type Output = GroupSet;
+
+    #[inline(always)]
+    fn bitand(self, rhs: GroupSet) -> GroupSet {
+        GroupSet(self.0 & rhs.0)
+    }
+}
+
+impl Not for GroupSet {
+    type Output = GroupSet;
+
+    #[inline(always)]
+    fn not(self) -> GroupSet {
+        GroupSet(!self.0)
+    }
+}
+
+/// An iterator over the groups in a `GroupSet`. Created with `GroupSet::iter`.
+#[derive(Clone, Debug)]
+pub struct GroupSetIter {
+    bits: u16,
+}
+
+impl Iterator for GroupSetIter {
+    type Item = Group;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Group> {
+        if self.bits == 0 {
+            return None;
+        }
+        let index = self.bits.trailing_zeros() as u8;
+        self.bits &= self.bits - 1;
+        Some(U4(index))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for GroupSetIter {}
+
+/// [UMP] Moves packets between groups using a lookup table, the counterpart of
+/// `Remapper::set_channel` for UMP streams.
+///
+/// Each group maps to `None` to drop its packets. A new `GroupRemapper` leaves every packet
+/// unchanged, and packets of message types without a group are never changed.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{GroupRemapper, UmpPacket};
+/// use wmidi::U4;
+/// let mut remapper = GroupRemapper::new();
+/// remapper.set_group(U4::MIN, Some(U4::MAX));
+/// let note_on = UmpPacket::from_words(&[0x2090_3C7F]).unwrap();
+/// assert_eq!(remapper.remap(&note_on).unwrap().words(), &[0x2F90_3C7F]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GroupRemapper {
+    groups: [Option<Group>; 16],
+}
+
+impl GroupRemapper {
+    /// Create a remapper that leaves every packet unchanged.
+    pub fn new() -> GroupRemapper {
+        GroupRemapper {
+            groups: core::array::from_fn(|i| Some(U4(i as u8))),
+        }
+    }
+
+    /// The group that `group` is mapped to.
+    pub fn group(&self, group: Group) -> Option<Group> {
+        self.groups[usize::from(u8::from(group))]
+    }
+
+    /// Map `from` to `to`, or drop packets in `from` if `to` is `None`.
+    pub fn set_group(&mut self, from: Group, to: Option<Group>) {
+        self.groups[usize::from(u8::from(from))] = to;
+    }
+
+    /// The readdressed packet, or `None` if it is dropped.
+    pub fn remap(&self, packet: &UmpPacket) -> Option<UmpPacket> {
+        match packet.group() {
+            Some(group) => Some(packet.with_group(self.group(group)?)),
+            None => Some(*packet),
+        }
+    }
+}
+
+impl Default for GroupRemapper {
+    fn default() -> GroupRemapper {
+        GroupRemapper::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn set() {
+        let mut set = GroupSet::EMPTY;
+        assert!(set.is_empty());
+        set.insert(U4(0xF));
+        set.insert(U4(0x0));
+        set.insert(U4(0x0));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.bits(), 0x8001);
+        assert!(set.iter().eq([U4(0x0), U4(0xF)].iter().copied()));
+        set.remove(U4(0x0));
+        assert_eq!(set, GroupSet::from(U4(0xF)));
+        assert_eq!(set | !set, GroupSet::ALL);
+        assert_eq!(set & !set, GroupSet::EMPTY);
+        assert_eq!(GroupSet::ALL.iter().len(), 16);
+    }
+
+    #[test]
+    fn filter_and_remap() {
+        let packets = [
+            UmpPacket::from_words(&[0x2090_3C7F]).unwrap(),
+            UmpPacket::from_words(&[0x2390_3C7F]).unwrap(),
+            UmpPacket::from_words(&[0xF000_0101, 0x1F, 0, 0]).unwrap(),
+        ];
+        let groups: GroupSet = [U4(0x3)].iter().copied().collect();
+        assert_eq!(groups.filter_iter(packets.iter().copied()).count(), 2);
+
+        let mut remapper = GroupRemapper::default();
+        remapper.set_group(U4(0x0), None);
+        remapper.set_group(U4(0x3), Some(U4(0x4)));
+        assert_eq!(remapper.remap(&packets[0]), None);
+        assert_eq!(remapper.remap(&packets[1]).unwrap().words(), &[0x2490_3C7F]);
+        assert_eq!(remapper.remap(&packets[2]), Some(packets[2]));
+        assert_eq!(remapper.group(U4(0x5)), Some(U4(0x5)));
+    }
+}
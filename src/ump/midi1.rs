@@ -0,0 +1,128 @@
+use super::{Group, MessageType, UmpPacket};
+use crate::MidiMessage;
+use core::convert::TryFrom;
+
+impl<'a> MidiMessage<'a> {
+    /// [UMP] The packet carrying the message unchanged over UMP, addressed to `group`. Channel
+    /// voice messages use message type 0x2, and System Common and System Real Time messages use
+    /// message type 0x1. Returns `None` for SysEx messages, which are split into several packets
+    /// of message type 0x3.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::{Channel, MidiMessage, Note, U4, U7};
+    /// let message = MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX);
+    /// let packet = message.to_ump_packet(U4::MIN).unwrap();
+    /// assert_eq!(packet.words(), &[0x2090_3C7F]);
+    /// assert_eq!(MidiMessage::from_ump_packet(&packet), Some((U4::MIN, message)));
+    /// ```
+    pub fn to_ump_packet(&self, group: Group) -> Option<UmpPacket> {
+        if self.is_sysex() {
+            return None;
+        }
+        let mut bytes = [0u8; 3];
+        self.copy_to_slice(&mut bytes).ok()?;
+        let message_type = if bytes[0] >= 0xF0 { 0x1 } else { 0x2 };
+        let word = message_type << 28
+            | u32::from(u8::from(group)) << 24
+            | u32::from(bytes[0]) << 16
+            | u32::from(bytes[1]) << 8
+            | u32::from(bytes[2]);
+        Some(UmpPacket::new([word, 0, 0, 0]))
+    }
+
+    /// [UMP] Parse a packet of message type 0x1 or 0x2. Returns the group and message, or `None`
+    /// if the packet does not hold a valid MIDI 1.0 message. As with bytes, a Note On with a
+    /// velocity of 0 is read as a Note Off.
+    pub fn from_ump_packet(packet: &UmpPacket) -> Option<(Group, MidiMessage<'static>)> {
+        let word = packet.words()[0];
+        let bytes = [(word >> 16) as u8, (word >> 8) as u8, word as u8];
+        let is_system = bytes[0] >= 0xF0;
+        match packet.message_type() {
+            MessageType::System if is_system && bytes[0] != 0xF0 => {}
+            MessageType::Midi1ChannelVoice if !is_system => {}
+            _ => return None,
+        }
+        let message = MidiMessage::try_from(&bytes[..]).ok()?;
+        Some((packet.group()?, message.drop_unowned_sysex()?))
+    }
+
+    fn is_sysex(&self) -> bool {
+        match self {
+            MidiMessage::SysEx(_) => true,
+            #[cfg(feature = "std")]
+            MidiMessage::OwnedSysEx(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ump::UmpPacket;
+    use crate::{
+        Channel, ControlFunction, MidiMessage, Note, PitchBend, QuarterFrame, U14, U4, U7,
+    };
+
+    #[test]
+    fn round_trip() {
+        let group = U4::from_u8_lossy(0x9);
+        for message in [
+            MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0x40)),
+            MidiMessage::NoteOn(Channel::Ch16, Note::A4, U7(0x7F)),
+            MidiMessage::PolyphonicKeyPressure(Channel::Ch2, Note::B7, U7(1)),
+            MidiMessage::ControlChange(Channel::Ch3, ControlFunction::MODULATION_WHEEL, U7(2)),
+            MidiMessage::ProgramChange(Channel::Ch4, U7(3)),
+            MidiMessage::ChannelPressure(Channel::Ch5, U7(4)),
+            MidiMessage::PitchBendChange(Channel::Ch6, PitchBend::from(U14::MAX)),
+            MidiMessage::MidiTimeCode(QuarterFrame::from(U7(0x15))),
+            MidiMessage::SongPositionPointer(U14::from_u16_lossy(0x1234)),
+            MidiMessage::SongSelect(U7(5)),
+            MidiMessage::TuneRequest,
+            MidiMessage::TimingClock,
+            MidiMessage::Start,
+            MidiMessage::Continue,
+            MidiMessage::Stop,
+            MidiMessage::ActiveSensing,
+            MidiMessage::Reset,
+        ]
+        .iter()
+        {
+            let packet = message.to_ump_packet(group).unwrap();
+            assert_eq!(packet.group(), Some(group));
+            assert_eq!(
+                MidiMessage::from_ump_packet(&packet),
+                Some((group, message.clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let packet = MidiMessage::TimingClock.to_ump_packet(U4::MAX).unwrap();
+        assert_eq!(packet.words(), &[0x1FF8_0000]);
+        let packet = MidiMessage::PitchBendChange(Channel::Ch2, PitchBend::from(U14::MAX))
+            .to_ump_packet(U4::MIN)
+            .unwrap();
+        assert_eq!(packet.words(), &[0x20E1_7F7F]);
+        assert_eq!(MidiMessage::SysEx(&[]).to_ump_packet(U4::MIN), None);
+
+        let note_on = UmpPacket::from_words(&[0x2090_3C00]).unwrap();
+        assert_eq!(
+            MidiMessage::from_ump_packet(&note_on),
+            Some((U4::MIN, MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(0))))
+        );
+        for word in [
+            0x1090_3C00,
+            0x20F8_0000,
+            0x10F0_0000,
+            0x2090_803C,
+            0x4090_3C00,
+        ]
+        .iter()
+        {
+            let packet = UmpPacket::from_words(&[*word, 0]).unwrap();
+            assert_eq!(MidiMessage::from_ump_packet(&packet), None);
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use super::{Group, MessageType, PerNoteController, PerNoteManagement, UmpPacket};
+use crate::{Channel, ControlFunction, Note, ParameterNumber, ProgramNumber, U7};
+
+/// [UMP] Extra data sent with a MIDI 2.0 Note On or Note Off.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum NoteAttribute {
+    /// No attribute, type 0x00.
+    None,
+    /// Data defined by the manufacturer of the receiver, type 0x01.
+    ManufacturerSpecific(u16),
+    /// Data defined by the enabled profile, type 0x02.
+    ProfileSpecific(u16),
+    /// The pitch of the note as a 7.9 fixed point note number, type 0x03. The upper 7 bits are
+    /// the note number and the lower 9 bits the fraction of a semitone above it.
+    Pitch(u16),
+    /// An attribute type reserved for future use, with its data.
+    Reserved(u8, u16),
+}
+
+impl NoteAttribute {
+    fn to_type_and_data(self) -> (u8, u16) {
+        match self {
+            NoteAttribute::None => (0x00, 0),
+            NoteAttribute::ManufacturerSpecific(data) => (0x01, data),
+            NoteAttribute::ProfileSpecific(data) => (0x02, data),
+            NoteAttribute::Pitch(data) => (0x03, data),
+            NoteAttribute::Reserved(attribute_type, data) => (attribute_type, data),
+        }
+    }
+
+    fn from_type_and_data(attribute_type: u8, data: u16) -> NoteAttribute {
+        match attribute_type {
+            0x00 => NoteAttribute::None,
+            0x01 => NoteAttribute::ManufacturerSpecific(data),
+            0x02 => NoteAttribute::ProfileSpecific(data),
+            0x03 => NoteAttribute::Pitch(data),
+            _ => NoteAttribute::Reserved(attribute_type, data),
+        }
+    }
+}
+
+/// [UMP] A MIDI 2.0 channel voice message, sent in a 64 bit packet of message type 0x4.
+///
+/// The messages mirror those of `MidiMessage` with higher resolution values: velocities are 16
+/// bits and other values 32 bits, where the maximum is full scale. Unlike MIDI 1.0, a Note On
+/// with a velocity of 0 is a Note On.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{Midi2Message, NoteAttribute};
+/// use wmidi::{Channel, Note, U4};
+/// let message = Midi2Message::NoteOn(Channel::Ch1, Note::C4, 0xC000, NoteAttribute::None);
+/// let packet = message.to_packet(U4::MIN);
+/// assert_eq!(packet.words(), &[0x4090_3C00, 0xC000_0000]);
+/// assert_eq!(Midi2Message::from_packet(&packet), Some((U4::MIN, message)));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Midi2Message {
+    /// A note is released, with a 16 bit velocity.
+    NoteOff(Channel, Note, u16, NoteAttribute),
+
+    /// A note is pressed, with a 16 bit velocity.
+    NoteOn(Channel, Note, u16, NoteAttribute),
+
+    /// The 32 bit pressure of a single note.
+    PolyphonicKeyPressure(Channel, Note, u32),
+
+    /// A controller changes to a 32 bit value.
+    ControlChange(Channel, ControlFunction, u32),
+
+    /// A Registered or Assignable Controller changes to a 32 bit value. These replace the RPNs
+    /// and NRPNs of MIDI 1.0, and are set in a single message. The bank and index of the
+    /// controller are the MSB and LSB of the `ParameterNumber`.
+    Controller(Channel, ParameterNumber, u32),
+
+    /// A Registered or Assignable Controller changes by a signed 32 bit amount.
+    RelativeController(Channel, ParameterNumber, i32),
+
+    /// Select a program, and optionally the bank it is in as `(bank MSB, bank LSB)`. Unlike
+    /// MIDI 1.0, the bank is set in the same message instead of with earlier Bank Select
+    /// controllers.
+    ProgramChange(Channel, ProgramNumber, Option<(U7, U7)>),
+
+    /// The 32 bit pressure of the whole channel.
+    ChannelPressure(Channel, u32),
+
+    /// The 32 bit pitch bend of the channel, centered on 0x8000_0000.
+    PitchBendChange(Channel, u32),
+
+    /// The 32 bit pitch bend of a single note, centered on 0x8000_0000. It is added to the pitch
+    /// bend of the channel.
+    PerNotePitchBend(Channel, Note, u32),
+
+    /// A controller of a single note changes to a 32 bit value.
+    PerNoteControlChange(Channel, Note, PerNoteController, u32),
+
+    /// Detach or reset the per-note controllers of a note number.
+    PerNoteManagement(Channel, Note, PerNoteManagement),
+}
+
+impl Midi2Message {
+    /// The center of 32 bit pitch bend values, which leaves the pitch unchanged.
+    pub const PITCH_BEND_CENTER: u32 = 0x8000_0000;
+
+    /// The channel of the message.
+    pub fn channel(&self) -> Channel {
+        match *self {
+            Midi2Message::NoteOff(channel, ..)
+            | Midi2Message::NoteOn(channel, ..)
+            | Midi2Message::PolyphonicKeyPressure(channel, ..)
+            | Midi2Message::ControlChange(channel, ..)
+            | Midi2Message::Controller(channel, ..)
+            | Midi2Message::RelativeController(channel, ..)
+            | Midi2Message::ProgramChange(channel, ..)
+            | Midi2Message::ChannelPressure(channel, ..)
+            | Midi2Message::PitchBendChange(channel, ..)
+            | Midi2Message::PerNotePitchBend(channel, ..)
+            | Midi2Message::PerNoteControlChange(channel, ..)
+            | Midi2Message::PerNoteManagement(channel, ..) => channel,
+        }
+    }
+
+    /// The packet holding the message, addressed to `group`.
+    pub fn to_packet(&self, group: Group) -> UmpPacket {
+        let (status, byte3, byte4, data) = match *self {
+            Midi2Message::NoteOff(_, note, velocity, attribute)
+            | Midi2Message::NoteOn(_, note, velocity, attribute) => {
+                let (attribute_type, attribute_data) = attribute.to_type_and_data();
+                let status = match self {
+                    Midi2Message::NoteOff(..) => 0x8,
+                    _ => 0x9,
+                };
+                let data = u32::from(velocity) << 16 | u32::from(attribute_data);
+                (status, u8::from(note), attribute_type, data)
+            }
+            Midi2Message::PolyphonicKeyPressure(_, note, data) => (0xA, u8::from(note), 0, data),
+            Midi2Message::ControlChange(_, function, data) => (0xB, u8::from(function), 0, data),
+            Midi2Message::Controller(_, parameter, data) => {
+                let (bank, index) = (u8::from(parameter.bank()), u8::from(parameter.index()));
+                let status = if parameter.is_registered() { 0x2 } else { 0x3 };
+                (status, bank, index, data)
+            }
+            Midi2Message::RelativeController(_, parameter, data) => {
+                let (bank, index) = (u8::from(parameter.bank()), u8::from(parameter.index()));
+                let status = if parameter.is_registered() { 0x4 } else { 0x5 };
+                (status, bank, index, data as u32)
+            }
+            Midi2Message::ProgramChange(_, program, bank) => {
+                let (bank_valid, (msb, lsb)) = match bank {
+                    Some(bank) => (0x1, bank),
+                    None => (0x0, (U7::MIN, U7::MIN)),
+                };
+                let data = u32::from(u8::from(program)) << 24
+                    | u32::from(u8::from(msb)) << 8
+                    | u32::from(u8::from(lsb));
+                (0xC, 0, bank_valid, data)
+            }
+            Midi2Message::ChannelPressure(_, data) => (0xD, 0, 0, data),
+            Midi2Message::PitchBendChange(_, data) => (0xE, 0, 0, data),
+            Midi2Message::PerNotePitchBend(_, note, data) => (0x6, u8::from(note), 0, data),
+            Midi2Message::PerNoteControlChange(_, note, controller, data) => {
+                let status = match controller {
+                    PerNoteController::Registered(_) => 0x0,
+                    PerNoteController::Assignable(_) => 0x1,
+                };
+                (status, u8::from(note), controller.index(), data)
+            }
+            Midi2Message::PerNoteManagement(_, note, options) => {
+                let flags = u8::from(options.detach) << 1 | u8::from(options.reset);
+                (0xF, u8::from(note), flags, 0)
+            }
+        };
+        let word = 0x4000_0000
+            | u32::from(u8::from(group)) << 24
+            | status << 20
+            | u32::from(self.channel().index()) << 16
+            | u32::from(byte3) << 8
+            | u32::from(byte4);
+        UmpPacket::new([word, data, 0, 0])
+    }
+
+    /// Parse a packet. Returns the group and message, or `None` if the packet is not a MIDI 2.0
+    /// channel voice message of a kind held by `Midi2Message`.
+    pub fn from_packet(packet: &UmpPacket) -> Option<(Group, Midi2Message)> {
+        if packet.message_type() != MessageType::Midi2ChannelVoice {
+            return None;
+        }
+        let (word, data) = (packet.words()[0], packet.words()[1]);
+        let channel = Channel::from_index((word >> 16) as u8 & 0x0F).unwrap();
+        let byte3 = (word >> 8) as u8;
+        let byte4 = word as u8;
+        let note = || Note::from(U7::from_u8_lossy(byte3));
+        let (bank, index) = (U7::from_u8_lossy(byte3), U7::from_u8_lossy(byte4));
+        let message = match word >> 20 & 0xF {
+            0x0 => Midi2Message::PerNoteControlChange(
+                channel,
+                note(),
+                PerNoteController::Registered(byte4),
+                data,
+            ),
+            0x1 => Midi2Message::PerNoteControlChange(
+                channel,
+                note(),
+                PerNoteController::Assignable(byte4),
+                data,
+            ),
+            0x2 => Midi2Message::Controller(
+                channel,
+                ParameterNumber::registered_controller(bank, index),
+                data,
+            ),
+            0x3 => Midi2Message::Controller(
+                channel,
+                ParameterNumber::assignable_controller(bank, index),
+                data,
+            ),
+            0x4 => Midi2Message::RelativeController(
+                channel,
+                ParameterNumber::registered_controller(bank, index),
+                data as i32,
+            ),
+            0x5 => Midi2Message::RelativeController(
+                channel,
+                ParameterNumber::assignable_controller(bank, index),
+                data as i32,
+            ),
+            0x6 => Midi2Message::PerNotePitchBend(channel, note(), data),
+            0x8 | 0x9 => {
+                let velocity = (data >> 16) as u16;
+                let attribute = NoteAttribute::from_type_and_data(byte4, data as u16);
+                match word >> 20 & 0xF {
+                    0x8 => Midi2Message::NoteOff(channel, note(), velocity, attribute),
+                    _ => Midi2Message::NoteOn(channel, note(), velocity, attribute),
+                }
+            }
+            0xA => Midi2Message::PolyphonicKeyPressure(channel, note(), data),
+            0xB => Midi2Message::ControlChange(
+                channel,
+                ControlFunction(U7::from_u8_lossy(byte3)),
+                data,
+            ),
+            0xC => {
+                let byte = |shift: u32| U7::from_u8_lossy((data >> shift) as u8);
+                let bank = if byte4 & 0x1 != 0 {
+                    Some((byte(8), byte(0)))
+                } else {
+                    None
+                };
+                Midi2Message::ProgramChange(channel, byte(24), bank)
+            }
+            0xD => Midi2Message::ChannelPressure(channel, data),
+            0xE => Midi2Message::PitchBendChange(channel, data),
+            0xF => Midi2Message::PerNoteManagement(
+                channel,
+                note(),
+                PerNoteManagement {
+                    detach: byte4 & 0x2 != 0,
+                    reset: byte4 & 0x1 != 0,
+                },
+            ),
+            _ => return None,
+        };
+        Some((packet.group()?, message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{U14, U4};
+
+    #[test]
+    fn round_trip() {
+        let group = U4::from_u8_lossy(0x3);
+        for message in [
+            Midi2Message::NoteOff(Channel::Ch2, Note::A4, 0x1234, NoteAttribute::None),
+            Midi2Message::NoteOn(Channel::Ch16, Note::C4, 0, NoteAttribute::Pitch(0x7800)),
+            Midi2Message::NoteOn(
+                Channel::Ch1,
+                Note::C4,
+                0xFFFF,
+                NoteAttribute::Reserved(0x7F, 1),
+            ),
+            Midi2Message::PolyphonicKeyPressure(Channel::Ch3, Note::B7, u32::MAX),
+            Midi2Message::ControlChange(Channel::Ch4, ControlFunction::MODULATION_WHEEL, 5),
+            Midi2Message::Controller(Channel::Ch5, ParameterNumber::FINE_TUNING, 6),
+            Midi2Message::Controller(
+                Channel::Ch5,
+                ParameterNumber::NonRegistered(U14::from_u16_lossy(0x3FFF)),
+                7,
+            ),
+            Midi2Message::RelativeController(Channel::Ch6, ParameterNumber::COARSE_TUNING, -1),
+            Midi2Message::RelativeController(
+                Channel::Ch6,
+                ParameterNumber::NonRegistered(U14::from_u16_lossy(0x81)),
+                i32::MIN,
+            ),
+            Midi2Message::ProgramChange(Channel::Ch7, U7::MAX, None),
+            Midi2Message::ProgramChange(Channel::Ch7, U7::MIN, Some((U7::MAX, U7::MIN))),
+            Midi2Message::ChannelPressure(Channel::Ch7, 8),
+            Midi2Message::PitchBendChange(Channel::Ch8, Midi2Message::PITCH_BEND_CENTER),
+            Midi2Message::PerNotePitchBend(Channel::Ch9, Note::G3, 9),
+            Midi2Message::PerNoteControlChange(
+                Channel::Ch10,
+                Note::A0,
+                PerNoteController::PITCH,
+                0x3C00_0000,
+            ),
+            Midi2Message::PerNoteControlChange(
+                Channel::Ch11,
+                Note::G9,
+                PerNoteController::Assignable(0xFF),
+                10,
+            ),
+            Midi2Message::PerNoteManagement(
+                Channel::Ch12,
+                Note::C4,
+                PerNoteManagement {
+                    detach: true,
+                    reset: true,
+                },
+            ),
+            Midi2Message::PerNoteManagement(Channel::Ch12, Note::C4, PerNoteManagement::default()),
+        ]
+        .iter()
+        {
+            let packet = message.to_packet(group);
+            assert_eq!(packet.group(), Some(group));
+            assert_eq!(Midi2Message::from_packet(&packet), Some((group, *message)));
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let bank = Some((U7::from_u8_lossy(0x79), U7::from_u8_lossy(0x01)));
+        let message = Midi2Message::ProgramChange(Channel::Ch3, U7::from_u8_lossy(24), bank);
+        assert_eq!(
+            message.to_packet(U4::MIN).words(),
+            &[0x40C2_0001, 0x1800_7901]
+        );
+        // The bank is ignored unless the bank valid flag is set.
+        let packet = UmpPacket::from_words(&[0x40C2_0000, 0x1800_7901]).unwrap();
+        assert_eq!(
+            Midi2Message::from_packet(&packet),
+            Some((
+                U4::MIN,
+                Midi2Message::ProgramChange(Channel::Ch3, U7::from_u8_lossy(24), None)
+            ))
+        );
+        let message = Midi2Message::Controller(
+            Channel::Ch2,
+            ParameterNumber::NonRegistered(U14::from_u16_lossy(0x0102)),
+            0xDEAD_BEEF,
+        );
+        assert_eq!(
+            message.to_packet(U4::MAX).words(),
+            &[0x4F31_0202, 0xDEAD_BEEF]
+        );
+        let message = Midi2Message::NoteOff(
+            Channel::Ch1,
+            Note::C4,
+            0x8000,
+            NoteAttribute::ManufacturerSpecific(0x1234),
+        );
+        assert_eq!(
+            message.to_packet(U4::MIN).words(),
+            &[0x4080_3C01, 0x8000_1234]
+        );
+        let message = Midi2Message::PerNoteManagement(
+            Channel::Ch1,
+            Note::C4,
+            PerNoteManagement {
+                detach: true,
+                reset: false,
+            },
+        );
+        assert_eq!(message.to_packet(U4::MIN).words(), &[0x40F0_3C02, 0]);
+        let packet = UmpPacket::from_words(&[0x4070_3C00, 0]).unwrap();
+        assert_eq!(Midi2Message::from_packet(&packet), None);
+        let packet = UmpPacket::from_words(&[0x2090_3C7F]).unwrap();
+        assert_eq!(Midi2Message::from_packet(&packet), None);
+    }
+}
@@ -0,0 +1,368 @@
+use super::{Group, MessageType, UmpPacket};
+use crate::U4;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [UMP] The header of one chunk of a Mixed Data Set.
+///
+/// A Mixed Data Set is a large block of data, split into chunks of at most 65535 bytes. Each
+/// chunk is a header packet followed by payload packets of 14 bytes.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct MixedDataSetHeader {
+    /// The ID of the set, so up to 16 sets may be sent at once on a group.
+    pub mds_id: U4,
+    /// The number of data bytes in the payload packets of this chunk.
+    pub byte_count: u16,
+    /// The number of chunks in the set, or 0 if unknown.
+    pub chunk_count: u16,
+    /// The number of this chunk, starting from 1.
+    pub chunk_number: u16,
+    /// The manufacturer ID of the format of the data.
+    pub manufacturer_id: u16,
+    /// The device ID of the format of the data.
+    pub device_id: u16,
+    /// The first sub ID of the format of the data.
+    pub sub_id1: u16,
+    /// The second sub ID of the format of the data.
+    pub sub_id2: u16,
+}
+
+/// [UMP] A 128 bit data packet of message type 0x5 that is part of a Mixed Data Set.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MixedDataSetPacket {
+    /// The header of a chunk, with status 0x8.
+    Header(MixedDataSetHeader),
+    /// 14 bytes of data, with status 0x9. Bytes after the end of the chunk are 0.
+    Payload {
+        /// The ID of the set.
+        mds_id: U4,
+        /// The data bytes.
+        data: [u8; 14],
+    },
+}
+
+impl MixedDataSetPacket {
+    /// The number of data bytes in a payload packet.
+    pub const PAYLOAD_LEN: usize = 14;
+
+    /// The ID of the set the packet belongs to.
+    pub fn mds_id(&self) -> U4 {
+        match self {
+            MixedDataSetPacket::Header(header) => header.mds_id,
+            MixedDataSetPacket::Payload { mds_id, .. } => *mds_id,
+        }
+    }
+
+    /// The packet addressed to `group`.
+    pub fn to_packet(&self, group: Group) -> UmpPacket {
+        let status = match self {
+            MixedDataSetPacket::Header(_) => 0x8,
+            MixedDataSetPacket::Payload { .. } => 0x9,
+        };
+        let prefix = 0x5000_0000
+            | u32::from(u8::from(group)) << 24
+            | status << 20
+            | u32::from(u8::from(self.mds_id())) << 16;
+        let words = match self {
+            MixedDataSetPacket::Header(header) => [
+                prefix | u32::from(header.byte_count),
+                u32::from(header.chunk_count) << 16 | u32::from(header.chunk_number),
+                u32::from(header.manufacturer_id) << 16 | u32::from(header.device_id),
+                u32::from(header.sub_id1) << 16 | u32::from(header.sub_id2),
+            ],
+            MixedDataSetPacket::Payload { data, .. } => {
+                let mut words = [
+                    prefix | u32::from(data[0]) << 8 | u32::from(data[1]),
+                    0,
+                    0,
+                    0,
+                ];
+                for (word, chunk) in words[1..].iter_mut().zip(data[2..].chunks_exact(4)) {
+                    *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+                }
+                words
+            }
+        };
+        UmpPacket::new(words)
+    }
+
+    /// Parse a packet. Returns the group and Mixed Data Set packet, or `None` if the packet is
+    /// not part of a Mixed Data Set.
+    pub fn from_packet(packet: &UmpPacket) -> Option<(Group, MixedDataSetPacket)> {
+        if packet.message_type() != MessageType::Data128 {
+            return None;
+        }
+        let words = packet.words();
+        let mds_id = U4((words[0] >> 16) as u8 & 0x0F);
+        let high = |word: u32| (word >> 16) as u16;
+        let message = match words[0] >> 20 & 0xF {
+            0x8 => MixedDataSetPacket::Header(MixedDataSetHeader {
+                mds_id,
+                byte_count: words[0] as u16,
+                chunk_count: high(words[1]),
+                chunk_number: words[1] as u16,
+                manufacturer_id: high(words[2]),
+                device_id: words[2] as u16,
+                sub_id1: high(words[3]),
+                sub_id2: words[3] as u16,
+            }),
+            0x9 => {
+                let mut data = [0u8; 14];
+                data[..2].copy_from_slice(&(words[0] as u16).to_be_bytes());
+                for (chunk, word) in data[2..].chunks_exact_mut(4).zip(&words[1..]) {
+                    chunk.copy_from_slice(&word.to_be_bytes());
+                }
+                MixedDataSetPacket::Payload { mds_id, data }
+            }
+            _ => return None,
+        };
+        Some((packet.group()?, message))
+    }
+}
+
+/// [UMP] Splits one chunk of a Mixed Data Set into a header packet and payload packets.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{MixedDataSetHeader, MixedDataSetPacket, MixedDataSetPackets};
+/// use wmidi::U4;
+/// let header = MixedDataSetHeader {
+///     chunk_count: 1,
+///     chunk_number: 1,
+///     manufacturer_id: 0x0041,
+///     ..MixedDataSetHeader::default()
+/// };
+/// let data = [0xAB; 20];
+/// let packets: Vec<_> = MixedDataSetPackets::new(U4::MIN, header, &data).unwrap().collect();
+/// assert_eq!(packets.len(), 3);
+/// assert_eq!(packets[0].words(), &[0x5080_0014, 0x0001_0001, 0x0041_0000, 0x0000_0000]);
+/// assert_eq!(packets[2].words(), &[0x5090_ABAB, 0xABAB_ABAB, 0x0000_0000, 0x0000_0000]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MixedDataSetPackets<'a> {
+    group: Group,
+    header: Option<MixedDataSetHeader>,
+    mds_id: U4,
+    data: &'a [u8],
+}
+
+impl<'a> MixedDataSetPackets<'a> {
+    /// Split a chunk holding `data` into packets addressed to `group`. The byte count of `header`
+    /// is set from `data`. Returns `None` if `data` is longer than 65535 bytes.
+    pub fn new(
+        group: Group,
+        header: MixedDataSetHeader,
+        data: &'a [u8],
+    ) -> Option<MixedDataSetPackets<'a>> {
+        if data.len() > usize::from(u16::MAX) {
+            return None;
+        }
+        Some(MixedDataSetPackets {
+            group,
+            header: Some(MixedDataSetHeader {
+                byte_count: data.len() as u16,
+                ..header
+            }),
+            mds_id: header.mds_id,
+            data,
+        })
+    }
+}
+
+impl<'a> Iterator for MixedDataSetPackets<'a> {
+    type Item = UmpPacket;
+
+    fn next(&mut self) -> Option<UmpPacket> {
+        if let Some(header) = self.header.take() {
+            return Some(MixedDataSetPacket::Header(header).to_packet(self.group));
+        }
+        if self.data.is_empty() {
+            return None;
+        }
+        let len = self.data.len().min(MixedDataSetPacket::PAYLOAD_LEN);
+        let (chunk, rest) = self.data.split_at(len);
+        self.data = rest;
+        let mut data = [0u8; 14];
+        data[..len].copy_from_slice(chunk);
+        let payload = MixedDataSetPacket::Payload {
+            mds_id: self.mds_id,
+            data,
+        };
+        Some(payload.to_packet(self.group))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.data.len().div_ceil(MixedDataSetPacket::PAYLOAD_LEN)
+            + usize::from(self.header.is_some());
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for MixedDataSetPackets<'a> {}
+
+/// [UMP] A chunk of a Mixed Data Set joined from its packets.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MixedDataSetChunk {
+    /// The group the chunk was received on.
+    pub group: Group,
+    /// The header of the chunk.
+    pub header: MixedDataSetHeader,
+    /// The data of the chunk, with `header.byte_count` bytes.
+    pub data: Vec<u8>,
+}
+
+/// [UMP] Joins Mixed Data Set packets into chunks. Each ID of each group may have one chunk in
+/// progress.
+///
+/// Payload packets without a header are ignored, and a header drops any chunk in progress with
+/// the same ID. Since the byte count of a chunk is 16 bits, a chunk holds at most 65535 bytes.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{MixedDataSetAssembler, MixedDataSetHeader, MixedDataSetPackets};
+/// use wmidi::U4;
+/// let data: Vec<u8> = (0..100).collect();
+/// let mut assembler = MixedDataSetAssembler::new();
+/// let mut chunk = None;
+/// for packet in MixedDataSetPackets::new(U4::MIN, MixedDataSetHeader::default(), &data).unwrap() {
+///     chunk = assembler.push(&packet);
+/// }
+/// assert_eq!(chunk.unwrap().data, data);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct MixedDataSetAssembler {
+    pending: Vec<MixedDataSetChunk>,
+}
+
+#[cfg(feature = "std")]
+impl MixedDataSetAssembler {
+    /// Create an assembler with no chunks in progress.
+    pub fn new() -> MixedDataSetAssembler {
+        MixedDataSetAssembler::default()
+    }
+
+    /// The number of chunks in progress.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no chunk is in progress.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Add a packet. Returns the chunk once all of its bytes are added. Packets that are not
+    /// part of a Mixed Data Set are ignored.
+    pub fn push(&mut self, packet: &UmpPacket) -> Option<MixedDataSetChunk> {
+        let (group, packet) = MixedDataSetPacket::from_packet(packet)?;
+        let index = self
+            .pending
+            .iter()
+            .position(|chunk| chunk.group == group && chunk.header.mds_id == packet.mds_id());
+        let chunk = match (packet, index) {
+            (MixedDataSetPacket::Header(header), index) => {
+                if let Some(index) = index {
+                    self.pending.swap_remove(index);
+                }
+                MixedDataSetChunk {
+                    group,
+                    header,
+                    data: Vec::new(),
+                }
+            }
+            (MixedDataSetPacket::Payload { data, .. }, Some(index)) => {
+                let mut chunk = self.pending.swap_remove(index);
+                let remaining = usize::from(chunk.header.byte_count) - chunk.data.len();
+                chunk
+                    .data
+                    .extend_from_slice(&data[..remaining.min(data.len())]);
+                chunk
+            }
+            (MixedDataSetPacket::Payload { .. }, None) => return None,
+        };
+        if chunk.data.len() == usize::from(chunk.header.byte_count) {
+            return Some(chunk);
+        }
+        self.pending.push(chunk);
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let header = MixedDataSetHeader {
+            mds_id: U4(0xA),
+            byte_count: 0xFFFF,
+            chunk_count: 2,
+            chunk_number: 1,
+            manufacturer_id: 0x1234,
+            device_id: 0x5678,
+            sub_id1: 0x9ABC,
+            sub_id2: 0xDEF0,
+        };
+        let mut data = [0u8; 14];
+        data[13] = 0xFF;
+        for message in [
+            MixedDataSetPacket::Header(header),
+            MixedDataSetPacket::Payload {
+                mds_id: U4(0xF),
+                data,
+            },
+        ]
+        .iter()
+        {
+            let packet = message.to_packet(U4(0x3));
+            assert_eq!(
+                MixedDataSetPacket::from_packet(&packet),
+                Some((U4(0x3), *message))
+            );
+        }
+        let packet = UmpPacket::from_words(&[0x5001_0100, 0, 0, 0]).unwrap();
+        assert_eq!(MixedDataSetPacket::from_packet(&packet), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn assemble() {
+        let data: Vec<u8> = (0..30).collect();
+        let header = MixedDataSetHeader::default();
+        let other = MixedDataSetHeader {
+            mds_id: U4(1),
+            ..header
+        };
+        let mut first = MixedDataSetPackets::new(U4(0), header, &data).unwrap();
+        let mut second = MixedDataSetPackets::new(U4(0), other, &data[..5]).unwrap();
+        assert_eq!(first.len(), 4);
+        let mut assembler = MixedDataSetAssembler::new();
+        assert_eq!(assembler.push(&first.next().unwrap()), None);
+        assert_eq!(assembler.push(&second.next().unwrap()), None);
+        assert_eq!(assembler.push(&first.next().unwrap()), None);
+        assert_eq!(assembler.len(), 2);
+        let chunk = assembler.push(&second.next().unwrap()).unwrap();
+        assert_eq!(
+            (chunk.header.mds_id, chunk.data),
+            (U4(1), data[..5].to_vec())
+        );
+        assert_eq!(assembler.push(&first.next().unwrap()), None);
+        let chunk = assembler.push(&first.next().unwrap()).unwrap();
+        assert_eq!(chunk.data, data);
+        assert!(assembler.is_empty());
+
+        // An empty chunk is complete with its header, and stray payloads are ignored.
+        let mut empty = MixedDataSetPackets::new(U4(2), header, &[]).unwrap();
+        let chunk = assembler.push(&empty.next().unwrap()).unwrap();
+        assert_eq!((chunk.group, chunk.data.len()), (U4(2), 0));
+        assert_eq!(empty.next(), None);
+        let mut stray = MixedDataSetPackets::new(U4(0), header, &data).unwrap();
+        stray.next();
+        assert_eq!(assembler.push(&stray.next().unwrap()), None);
+        assert!(assembler.is_empty());
+    }
+}
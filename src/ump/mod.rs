@@ -0,0 +1,48 @@
+//! Universal MIDI Packets (UMP), the message format of MIDI 2.0.
+//!
+//! A UMP is 1 to 4 32 bit words. The upper 4 bits of the first word give the message type, which
+//! sets the size of the packet, and most message types address one of 16 groups with the next 4
+//! bits. Each group carries 16 channels, so a UMP stream holds up to 256 channels.
+//!
+//! Documents referred to in this module:
+//! * [UMP]: Universal MIDI Packet (UMP) Format and MIDI 2.0 Protocol, version 1.1.2
+
+mod flex_data;
+mod group;
+mod midi1;
+mod midi2;
+mod mixed_data;
+mod negotiation;
+mod packet;
+mod per_note;
+mod stream;
+mod sysex7;
+mod sysex8;
+mod translate;
+mod utility;
+
+pub use self::flex_data::{FlexAddress, FlexDataMessage, TextKind, TextPacket, TextPackets};
+#[cfg(feature = "std")]
+pub use self::flex_data::{Text, TextAssembler};
+pub use self::group::{Group, GroupRemapper, GroupSet, GroupSetIter};
+pub use self::midi2::{Midi2Message, NoteAttribute};
+#[cfg(feature = "std")]
+pub use self::mixed_data::{MixedDataSetAssembler, MixedDataSetChunk};
+pub use self::mixed_data::{MixedDataSetHeader, MixedDataSetPacket, MixedDataSetPackets};
+pub use self::negotiation::{ProtocolNegotiator, StreamResponder};
+pub use self::packet::{MessageType, UmpPacket, UmpPackets};
+#[cfg(feature = "std")]
+pub use self::per_note::PerNoteTracker;
+pub use self::per_note::{PerNoteController, PerNoteManagement};
+pub use self::stream::{
+    EndpointFilter, EndpointInfo, FunctionBlockDirection, FunctionBlockFilter, FunctionBlockInfo,
+    Midi1Mode, Protocol, StreamConfiguration, StreamMessage, UmpVersion,
+};
+#[cfg(feature = "std")]
+pub use self::sysex7::SysEx7Assembler;
+pub use self::sysex7::{SysEx7Packet, SysEx7Packets, SysExStatus};
+#[cfg(feature = "std")]
+pub use self::sysex8::SysEx8Assembler;
+pub use self::sysex8::{SysEx8Packet, SysEx8Packets};
+pub use self::translate::{scale_down, scale_up, Midi1Translator, TranslateError};
+pub use self::utility::UtilityMessage;
@@ -0,0 +1,322 @@
+use super::{
+    EndpointFilter, EndpointInfo, Protocol, StreamConfiguration, StreamMessage, UmpVersion,
+};
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+enum NegotiatorState {
+    Discover,
+    AwaitingInfo,
+    Request(StreamConfiguration),
+    AwaitingNotification,
+    Done,
+}
+
+/// [UMP] Negotiates the protocol and JR Timestamps of a UMP stream with the endpoint at the other
+/// end, as the initiator.
+///
+/// Send the message returned by `next`, call `sent`, then pass the stream messages of the
+/// endpoint to `handle`. The negotiator first asks for the capabilities of the endpoint, then
+/// requests the configuration closest to `preferred` that the endpoint supports, and uses the
+/// configuration the endpoint notifies in reply. If the endpoint does not answer in time, call
+/// `timeout` to stop negotiating and keep the current configuration. Until the endpoint notifies
+/// a configuration, the stream is assumed to use the MIDI 1.0 protocol without JR Timestamps.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{
+///     EndpointInfo, Protocol, ProtocolNegotiator, StreamConfiguration, StreamResponder,
+///     UmpVersion,
+/// };
+/// let preferred = StreamConfiguration {
+///     protocol: Protocol::Midi2,
+///     receive_jr: true,
+///     transmit_jr: true,
+/// };
+/// let endpoint = EndpointInfo {
+///     version: UmpVersion::V1_1,
+///     static_function_blocks: true,
+///     function_block_count: 1,
+///     midi2_protocol: true,
+///     midi1_protocol: true,
+///     receive_jr: false,
+///     transmit_jr: false,
+/// };
+/// let mut negotiator = ProtocolNegotiator::new(preferred);
+/// let mut responder = StreamResponder::new(endpoint);
+/// while let Some(message) = negotiator.next() {
+///     negotiator.sent();
+///     let mut replies = Vec::new();
+///     responder.handle(&message, |m| replies.push(m));
+///     for reply in replies.iter() {
+///         negotiator.handle(reply);
+///     }
+/// }
+/// assert!(negotiator.is_done());
+/// assert_eq!(negotiator.configuration().protocol, Protocol::Midi2);
+/// assert!(!negotiator.configuration().receive_jr);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ProtocolNegotiator {
+    preferred: StreamConfiguration,
+    endpoint: Option<EndpointInfo>,
+    configuration: StreamConfiguration,
+    state: NegotiatorState,
+}
+
+impl ProtocolNegotiator {
+    /// Create a negotiator that asks for `preferred`.
+    pub fn new(preferred: StreamConfiguration) -> ProtocolNegotiator {
+        ProtocolNegotiator {
+            preferred,
+            endpoint: None,
+            configuration: StreamConfiguration {
+                protocol: Protocol::Midi1,
+                receive_jr: false,
+                transmit_jr: false,
+            },
+            state: NegotiatorState::Discover,
+        }
+    }
+
+    /// The message to send now, or `None` if waiting for the endpoint or the negotiation has
+    /// ended.
+    pub fn next(&self) -> Option<StreamMessage> {
+        match self.state {
+            NegotiatorState::Discover => Some(StreamMessage::EndpointDiscovery {
+                version: UmpVersion::V1_1,
+                filter: EndpointFilter {
+                    endpoint_info: true,
+                    stream_configuration: true,
+                    ..EndpointFilter::default()
+                },
+            }),
+            NegotiatorState::Request(request) => {
+                Some(StreamMessage::StreamConfigurationRequest(request))
+            }
+            _ => None,
+        }
+    }
+
+    /// Record that the message returned by `next` was sent.
+    pub fn sent(&mut self) {
+        self.state = match self.state {
+            NegotiatorState::Discover => NegotiatorState::AwaitingInfo,
+            NegotiatorState::Request(_) => NegotiatorState::AwaitingNotification,
+            state => state,
+        };
+    }
+
+    /// Update the negotiation with a stream message from the endpoint. A Stream Configuration
+    /// Notification always changes the configuration, since the endpoint may switch on its own.
+    pub fn handle(&mut self, message: &StreamMessage) {
+        match *message {
+            StreamMessage::EndpointInfo(info) => {
+                self.endpoint = Some(info);
+                if self.state == NegotiatorState::AwaitingInfo {
+                    self.state = NegotiatorState::Request(request(&info, self.preferred));
+                }
+            }
+            StreamMessage::StreamConfigurationNotification(configuration) => {
+                self.configuration = configuration;
+                if self.state == NegotiatorState::AwaitingNotification {
+                    self.state = NegotiatorState::Done;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Stop waiting for the endpoint and keep the current configuration. Older endpoints do not
+    /// answer stream messages at all.
+    pub fn timeout(&mut self) {
+        if let NegotiatorState::AwaitingInfo | NegotiatorState::AwaitingNotification = self.state {
+            self.state = NegotiatorState::Done;
+        }
+    }
+
+    /// The configuration of the stream, as last notified by the endpoint.
+    pub fn configuration(&self) -> StreamConfiguration {
+        self.configuration
+    }
+
+    /// The capabilities of the endpoint, or `None` if it has not sent them.
+    pub fn endpoint(&self) -> Option<&EndpointInfo> {
+        self.endpoint.as_ref()
+    }
+
+    /// Returns `true` once the negotiation has ended.
+    pub fn is_done(&self) -> bool {
+        self.state == NegotiatorState::Done
+    }
+}
+
+/// [UMP] Answers the discovery and stream configuration messages of an initiator, as an endpoint.
+///
+/// Requests to change the configuration are applied with `EndpointInfo::accept`, and answered
+/// with a Stream Configuration Notification of the resulting configuration. The endpoint starts
+/// with the MIDI 1.0 protocol if it supports it and MIDI 2.0 otherwise, without JR Timestamps.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StreamResponder {
+    info: EndpointInfo,
+    configuration: StreamConfiguration,
+}
+
+impl StreamResponder {
+    /// Create a responder for an endpoint with the capabilities in `info`.
+    pub fn new(info: EndpointInfo) -> StreamResponder {
+        let protocol = if info.supports(Protocol::Midi1) {
+            Protocol::Midi1
+        } else {
+            Protocol::Midi2
+        };
+        StreamResponder {
+            info,
+            configuration: StreamConfiguration {
+                protocol,
+                receive_jr: false,
+                transmit_jr: false,
+            },
+        }
+    }
+
+    /// The capabilities of the endpoint.
+    pub fn info(&self) -> &EndpointInfo {
+        &self.info
+    }
+
+    /// The current configuration of the endpoint.
+    pub fn configuration(&self) -> StreamConfiguration {
+        self.configuration
+    }
+
+    /// Handle a stream message from the initiator, calling `emit` with each reply. Only the
+    /// Endpoint Info and Stream Configuration notifications of an Endpoint Discovery are
+    /// answered.
+    pub fn handle<F: FnMut(StreamMessage)>(&mut self, message: &StreamMessage, mut emit: F) {
+        match *message {
+            StreamMessage::EndpointDiscovery { filter, .. } => {
+                if filter.endpoint_info {
+                    emit(StreamMessage::EndpointInfo(self.info));
+                }
+                if filter.stream_configuration {
+                    emit(StreamMessage::StreamConfigurationNotification(
+                        self.configuration,
+                    ));
+                }
+            }
+            StreamMessage::StreamConfigurationRequest(request) => {
+                self.configuration = self.info.accept(self.configuration, request);
+                emit(StreamMessage::StreamConfigurationNotification(
+                    self.configuration,
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The configuration closest to `preferred` that the endpoint described by `info` supports.
+fn request(info: &EndpointInfo, preferred: StreamConfiguration) -> StreamConfiguration {
+    let other = match preferred.protocol {
+        Protocol::Midi1 => Protocol::Midi2,
+        Protocol::Midi2 => Protocol::Midi1,
+    };
+    let protocol = if !info.supports(preferred.protocol) && info.supports(other) {
+        other
+    } else {
+        preferred.protocol
+    };
+    StreamConfiguration {
+        protocol,
+        receive_jr: preferred.receive_jr && info.receive_jr,
+        transmit_jr: preferred.transmit_jr && info.transmit_jr,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INFO: EndpointInfo = EndpointInfo {
+        version: UmpVersion::V1_1,
+        static_function_blocks: false,
+        function_block_count: 0,
+        midi2_protocol: false,
+        midi1_protocol: true,
+        receive_jr: true,
+        transmit_jr: false,
+    };
+
+    const MIDI2_JR: StreamConfiguration = StreamConfiguration {
+        protocol: Protocol::Midi2,
+        receive_jr: true,
+        transmit_jr: true,
+    };
+
+    #[test]
+    fn falls_back_to_supported_protocol() {
+        let mut negotiator = ProtocolNegotiator::new(MIDI2_JR);
+        let mut responder = StreamResponder::new(INFO);
+        let mut sent = 0;
+        while let Some(message) = negotiator.next() {
+            negotiator.sent();
+            sent += 1;
+            responder.handle(&message, |reply| negotiator.handle(&reply));
+        }
+        assert_eq!(sent, 2);
+        assert!(negotiator.is_done());
+        assert_eq!(negotiator.endpoint(), Some(&INFO));
+        let expected = StreamConfiguration {
+            protocol: Protocol::Midi1,
+            receive_jr: true,
+            transmit_jr: false,
+        };
+        assert_eq!(negotiator.configuration(), expected);
+        assert_eq!(responder.configuration(), expected);
+
+        // A later notification from the endpoint still changes the configuration.
+        let mut info = INFO;
+        info.midi2_protocol = true;
+        let mut responder = StreamResponder::new(info);
+        responder.handle(
+            &StreamMessage::StreamConfigurationRequest(MIDI2_JR),
+            |reply| negotiator.handle(&reply),
+        );
+        assert_eq!(negotiator.configuration().protocol, Protocol::Midi2);
+    }
+
+    #[test]
+    fn timeout() {
+        let mut negotiator = ProtocolNegotiator::new(MIDI2_JR);
+        negotiator.timeout();
+        assert!(!negotiator.is_done());
+        negotiator.sent();
+        negotiator.timeout();
+        assert!(negotiator.is_done());
+        assert_eq!(negotiator.next(), None);
+        assert_eq!(negotiator.endpoint(), None);
+        assert_eq!(negotiator.configuration().protocol, Protocol::Midi1);
+
+        // An endpoint that answers discovery but not the request keeps its configuration.
+        let mut negotiator = ProtocolNegotiator::new(MIDI2_JR);
+        negotiator.sent();
+        negotiator.handle(&StreamMessage::EndpointInfo(INFO));
+        negotiator.sent();
+        negotiator.timeout();
+        assert!(negotiator.is_done());
+        assert!(!negotiator.configuration().receive_jr);
+    }
+
+    #[test]
+    fn accept() {
+        let current = StreamConfiguration {
+            protocol: Protocol::Midi1,
+            receive_jr: false,
+            transmit_jr: false,
+        };
+        let accepted = INFO.accept(current, MIDI2_JR);
+        assert_eq!(accepted.protocol, Protocol::Midi1);
+        assert!(accepted.receive_jr);
+        assert!(!accepted.transmit_jr);
+    }
+}
@@ -0,0 +1,304 @@
+use super::Group;
+use crate::{Error, ToSliceError, U4};
+
+/// [UMP] The kind of a packet, given by the upper 4 bits of its first word.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MessageType {
+    /// 0x0, 32 bit utility messages such as timestamps. They have no group.
+    Utility,
+    /// 0x1, 32 bit System Common and System Real Time messages.
+    System,
+    /// 0x2, 32 bit MIDI 1.0 channel voice messages.
+    Midi1ChannelVoice,
+    /// 0x3, 64 bit data messages, which carry 7 bit SysEx.
+    Data64,
+    /// 0x4, 64 bit MIDI 2.0 channel voice messages.
+    Midi2ChannelVoice,
+    /// 0x5, 128 bit data messages, which carry 8 bit SysEx and Mixed Data Sets.
+    Data128,
+    /// 0xD, 128 bit flex data messages, such as tempo and lyrics.
+    FlexData,
+    /// 0xF, 128 bit UMP stream messages, which configure the endpoint. They have no group.
+    Stream,
+    /// A message type reserved for future use. Its size is known, so it can be skipped.
+    Reserved(U4),
+}
+
+impl MessageType {
+    /// The message type of the packet starting with `word`.
+    pub fn from_word(word: u32) -> MessageType {
+        match word >> 28 {
+            0x0 => MessageType::Utility,
+            0x1 => MessageType::System,
+            0x2 => MessageType::Midi1ChannelVoice,
+            0x3 => MessageType::Data64,
+            0x4 => MessageType::Midi2ChannelVoice,
+            0x5 => MessageType::Data128,
+            0xD => MessageType::FlexData,
+            0xF => MessageType::Stream,
+            mt => MessageType::Reserved(U4(mt as u8)),
+        }
+    }
+
+    /// The 4 bit value of the message type.
+    pub fn value(self) -> U4 {
+        match self {
+            MessageType::Utility => U4(0x0),
+            MessageType::System => U4(0x1),
+            MessageType::Midi1ChannelVoice => U4(0x2),
+            MessageType::Data64 => U4(0x3),
+            MessageType::Midi2ChannelVoice => U4(0x4),
+            MessageType::Data128 => U4(0x5),
+            MessageType::FlexData => U4(0xD),
+            MessageType::Stream => U4(0xF),
+            MessageType::Reserved(mt) => mt,
+        }
+    }
+
+    /// The number of 32 bit words in packets of this type.
+    pub fn word_count(self) -> usize {
+        match u8::from(self.value()) {
+            0x0..=0x2 | 0x6 | 0x7 => 1,
+            0x3 | 0x4 | 0x8..=0xA => 2,
+            0xB | 0xC => 3,
+            _ => 4,
+        }
+    }
+
+    /// Returns `true` if packets of this type are addressed to a group.
+    pub fn has_group(self) -> bool {
+        !matches!(self, MessageType::Utility | MessageType::Stream)
+    }
+}
+
+/// [UMP] A single Universal MIDI Packet of 1 to 4 words.
+///
+/// The packet is stored as 4 words, of which only the first `len()` are used. Unused words are
+/// always 0, so packets can be compared directly.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{MessageType, UmpPacket};
+/// use wmidi::U4;
+/// // A MIDI 1.0 Note On in group 2.
+/// let packet = UmpPacket::from_words(&[0x2290_3C7F]).unwrap();
+/// assert_eq!(packet.message_type(), MessageType::Midi1ChannelVoice);
+/// assert_eq!(packet.group(), Some(U4::from_u8_lossy(2)));
+/// assert_eq!(packet.words(), &[0x2290_3C7F]);
+///
+/// let mut bytes = [0u8; 4];
+/// packet.copy_to_be_slice(&mut bytes).unwrap();
+/// assert_eq!(bytes, [0x22, 0x90, 0x3C, 0x7F]);
+/// assert_eq!(UmpPacket::from_be_bytes(&bytes), Ok(packet));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct UmpPacket {
+    words: [u32; 4],
+}
+
+impl UmpPacket {
+    /// Create a packet from `words`, clearing the words after the packet.
+    pub(crate) fn new(mut words: [u32; 4]) -> UmpPacket {
+        let len = MessageType::from_word(words[0]).word_count();
+        for word in words[len..].iter_mut() {
+            *word = 0;
+        }
+        UmpPacket { words }
+    }
+
+    /// Read the packet at the start of `words`. Words after the packet are ignored. Returns
+    /// `Error::NoBytes` if `words` is empty, or `Error::NotEnoughBytes` if the packet is
+    /// incomplete.
+    pub fn from_words(words: &[u32]) -> Result<UmpPacket, Error> {
+        let first = *words.first().ok_or(Error::NoBytes)?;
+        let len = MessageType::from_word(first).word_count();
+        let mut packet = UmpPacket { words: [0; 4] };
+        packet.words[..len].copy_from_slice(words.get(..len).ok_or(Error::NotEnoughBytes)?);
+        Ok(packet)
+    }
+
+    /// Read the packet at the start of `bytes`, where each word is stored most significant byte
+    /// first. Bytes after the packet are ignored.
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<UmpPacket, Error> {
+        let mut words = [0u32; 4];
+        let count = (bytes.len() / 4).min(4);
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        UmpPacket::from_words(&words[..count])
+    }
+
+    /// The message type of the packet.
+    pub fn message_type(&self) -> MessageType {
+        MessageType::from_word(self.words[0])
+    }
+
+    /// The group the packet is addressed to, or `None` for message types without a group.
+    pub fn group(&self) -> Option<Group> {
+        if self.message_type().has_group() {
+            Some(U4((self.words[0] >> 24) as u8 & 0x0F))
+        } else {
+            None
+        }
+    }
+
+    /// The same packet addressed to `group`. Packets of message types without a group are
+    /// returned unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ump::UmpPacket;
+    /// use wmidi::U4;
+    /// let packet = UmpPacket::from_words(&[0x2290_3C7F]).unwrap();
+    /// assert_eq!(packet.with_group(U4::MAX).group(), Some(U4::MAX));
+    /// ```
+    pub fn with_group(self, group: Group) -> UmpPacket {
+        if !self.message_type().has_group() {
+            return self;
+        }
+        let mut words = self.words;
+        words[0] = words[0] & !0x0F00_0000 | u32::from(u8::from(group)) << 24;
+        UmpPacket { words }
+    }
+
+    /// The number of words in the packet.
+    pub fn len(&self) -> usize {
+        self.message_type().word_count()
+    }
+
+    /// Returns `false`, since every packet has at least 1 word.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The words of the packet.
+    pub fn words(&self) -> &[u32] {
+        &self.words[..self.len()]
+    }
+
+    /// Copy the words of the packet to `slice`. Returns the number of words written.
+    pub fn copy_to_slice(&self, slice: &mut [u32]) -> Result<usize, ToSliceError> {
+        let words = self.words();
+        slice
+            .get_mut(..words.len())
+            .ok_or(ToSliceError::BufferTooSmall)?
+            .copy_from_slice(words);
+        Ok(words.len())
+    }
+
+    /// Copy the packet to `slice`, storing each word most significant byte first. Returns the
+    /// number of bytes written.
+    pub fn copy_to_be_slice(&self, slice: &mut [u8]) -> Result<usize, ToSliceError> {
+        let words = self.words();
+        let slice = slice
+            .get_mut(..4 * words.len())
+            .ok_or(ToSliceError::BufferTooSmall)?;
+        for (chunk, word) in slice.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        Ok(4 * words.len())
+    }
+}
+
+/// [UMP] An iterator over the packets in a stream of words.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{UmpPacket, UmpPackets};
+/// let words = [0x2090_3C7F, 0x4090_3C00, 0xFFFF_0000, 0x2080_3C00, 0x4090_3C00];
+/// let mut packets = UmpPackets::new(&words);
+/// assert_eq!(packets.next(), UmpPacket::from_words(&words[..1]).ok());
+/// assert_eq!(packets.next(), UmpPacket::from_words(&words[1..3]).ok());
+/// assert_eq!(packets.next(), UmpPacket::from_words(&words[3..4]).ok());
+/// assert_eq!(packets.next(), None);
+/// // The last packet is incomplete.
+/// assert_eq!(packets.remaining(), &[0x4090_3C00]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct UmpPackets<'a> {
+    words: &'a [u32],
+}
+
+impl<'a> UmpPackets<'a> {
+    /// Iterate over the packets in `words`.
+    pub fn new(words: &'a [u32]) -> UmpPackets<'a> {
+        UmpPackets { words }
+    }
+
+    /// The words that have not been read, which start with an incomplete packet once the
+    /// iterator has finished.
+    pub fn remaining(&self) -> &'a [u32] {
+        self.words
+    }
+}
+
+impl<'a> Iterator for UmpPackets<'a> {
+    type Item = UmpPacket;
+
+    fn next(&mut self) -> Option<UmpPacket> {
+        let packet = UmpPacket::from_words(self.words).ok()?;
+        self.words = &self.words[packet.len()..];
+        Some(packet)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        let sizes = [1, 1, 1, 2, 2, 4, 1, 1, 2, 2, 2, 3, 3, 4, 4, 4];
+        for (mt, size) in sizes.iter().enumerate() {
+            let words = [(mt as u32) << 28 | 0x0A00_0000, 1, 2, 3];
+            let packet = UmpPacket::from_words(&words).unwrap();
+            assert_eq!(packet.len(), *size);
+            assert_eq!(packet.words(), &words[..*size]);
+            assert_eq!(packet.message_type().value(), U4(mt as u8));
+            assert_eq!(packet.message_type().word_count(), *size);
+            assert_eq!(
+                UmpPacket::from_words(&words[..*size - 1]),
+                Err(if *size == 1 {
+                    Error::NoBytes
+                } else {
+                    Error::NotEnoughBytes
+                })
+            );
+        }
+        assert_eq!(
+            MessageType::from_word(0xB000_0000),
+            MessageType::Reserved(U4(0xB))
+        );
+    }
+
+    #[test]
+    fn groups() {
+        let packet = UmpPacket::from_words(&[0x4A90_3C00, 0xFFFF_0000]).unwrap();
+        assert_eq!(packet.group(), Some(U4(0xA)));
+        let packet = UmpPacket::from_words(&[0x0A20_1234]).unwrap();
+        assert_eq!(packet.group(), None);
+        let packet = UmpPacket::from_words(&[0xF000_0000, 0, 0, 0]).unwrap();
+        assert_eq!(packet.group(), None);
+    }
+
+    #[test]
+    fn copy_to_slice() {
+        let words = [0x5001_0203, 0x0405_0607, 0x0809_0A0B, 0x0C0D_0E0F];
+        let packet = UmpPacket::from_words(&words).unwrap();
+        let mut slice = [0u32; 4];
+        assert_eq!(packet.copy_to_slice(&mut slice), Ok(4));
+        assert_eq!(slice, words);
+        assert_eq!(
+            packet.copy_to_slice(&mut slice[..3]),
+            Err(ToSliceError::BufferTooSmall)
+        );
+        let mut bytes = [0u8; 16];
+        assert_eq!(packet.copy_to_be_slice(&mut bytes), Ok(16));
+        assert_eq!(bytes[..5], [0x50, 0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(UmpPacket::from_be_bytes(&bytes), Ok(packet));
+        assert_eq!(
+            UmpPacket::from_be_bytes(&bytes[..15]),
+            Err(Error::NotEnoughBytes)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+#[cfg(feature = "std")]
+use super::Midi2Message;
+#[cfg(feature = "std")]
+use crate::{Channel, ControlFunction, Note};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [UMP] A controller of a single note. Registered Per-Note Controllers are defined by the MIDI
+/// Association, and Assignable Per-Note Controllers by the receiver.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum PerNoteController {
+    /// A Registered Per-Note Controller, sent with status 0x0.
+    Registered(u8),
+    /// An Assignable Per-Note Controller, sent with status 0x1.
+    Assignable(u8),
+}
+
+impl PerNoteController {
+    pub const MODULATION: PerNoteController = PerNoteController::Registered(1);
+    pub const BREATH: PerNoteController = PerNoteController::Registered(2);
+    /// The pitch of the note as a 7.25 fixed point note number, replacing its note number.
+    pub const PITCH: PerNoteController = PerNoteController::Registered(3);
+    pub const VOLUME: PerNoteController = PerNoteController::Registered(7);
+    pub const BALANCE: PerNoteController = PerNoteController::Registered(8);
+    pub const PAN: PerNoteController = PerNoteController::Registered(10);
+    pub const EXPRESSION: PerNoteController = PerNoteController::Registered(11);
+
+    /// The index of the controller.
+    pub fn index(self) -> u8 {
+        match self {
+            PerNoteController::Registered(index) | PerNoteController::Assignable(index) => index,
+        }
+    }
+}
+
+/// [UMP] The options of a Per-Note Management message.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct PerNoteManagement {
+    /// Detach the sounding notes of the note number from later per-note messages, so a new note
+    /// with the same number can be controlled on its own.
+    pub detach: bool,
+    /// Reset the per-note controllers of the note number to their defaults.
+    pub reset: bool,
+}
+
+/// [UMP] Tracks the per-note pitch bend and per-note controllers of each note number of each
+/// channel.
+///
+/// Values persist after a note is released, until they are reset with a Per-Note Management
+/// message or a Reset All Controllers control change on the channel. Detaching notes does not
+/// change the values.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{Midi2Message, PerNoteController, PerNoteManagement, PerNoteTracker};
+/// use wmidi::{Channel, Note};
+/// let mut tracker = PerNoteTracker::new();
+/// let (ch, note) = (Channel::Ch1, Note::C4);
+/// tracker.handle(&Midi2Message::PerNotePitchBend(ch, note, 0x9000_0000));
+/// tracker.handle(&Midi2Message::PerNoteControlChange(ch, note, PerNoteController::PAN, 0));
+/// assert_eq!(tracker.pitch_bend(ch, note), 0x9000_0000);
+/// assert_eq!(tracker.controller(ch, note, PerNoteController::PAN), Some(0));
+///
+/// let reset = PerNoteManagement { detach: false, reset: true };
+/// tracker.handle(&Midi2Message::PerNoteManagement(ch, note, reset));
+/// assert_eq!(tracker.pitch_bend(ch, note), Midi2Message::PITCH_BEND_CENTER);
+/// assert_eq!(tracker.controller(ch, note, PerNoteController::PAN), None);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PerNoteTracker {
+    notes: Vec<NoteState>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct NoteState {
+    channel: Channel,
+    note: Note,
+    pitch_bend: u32,
+    controllers: Vec<(PerNoteController, u32)>,
+}
+
+#[cfg(feature = "std")]
+impl PerNoteTracker {
+    /// Create a tracker with every note at its default.
+    pub fn new() -> PerNoteTracker {
+        PerNoteTracker::default()
+    }
+
+    /// Update the per-note state from `message`.
+    pub fn handle(&mut self, message: &Midi2Message) {
+        match *message {
+            Midi2Message::PerNotePitchBend(channel, note, bend) => {
+                self.state(channel, note).pitch_bend = bend;
+            }
+            Midi2Message::PerNoteControlChange(channel, note, controller, value) => {
+                let controllers = &mut self.state(channel, note).controllers;
+                match controllers.iter_mut().find(|(c, _)| *c == controller) {
+                    Some((_, v)) => *v = value,
+                    None => controllers.push((controller, value)),
+                }
+            }
+            Midi2Message::PerNoteManagement(channel, note, options) if options.reset => {
+                self.notes
+                    .retain(|state| state.channel != channel || state.note != note);
+            }
+            Midi2Message::ControlChange(channel, ControlFunction::RESET_ALL_CONTROLLERS, _) => {
+                self.notes.retain(|state| state.channel != channel);
+            }
+            _ => {}
+        }
+    }
+
+    /// The per-note pitch bend of `note` on `channel`, `Midi2Message::PITCH_BEND_CENTER` by
+    /// default.
+    pub fn pitch_bend(&self, channel: Channel, note: Note) -> u32 {
+        self.find(channel, note)
+            .map_or(Midi2Message::PITCH_BEND_CENTER, |state| state.pitch_bend)
+    }
+
+    /// The value of `controller` for `note` on `channel`, or `None` if it has not been set.
+    pub fn controller(
+        &self,
+        channel: Channel,
+        note: Note,
+        controller: PerNoteController,
+    ) -> Option<u32> {
+        self.find(channel, note)?
+            .controllers
+            .iter()
+            .find(|(c, _)| *c == controller)
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns `true` if every note is at its default.
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Reset every note to its default.
+    pub fn clear(&mut self) {
+        self.notes.clear();
+    }
+
+    fn find(&self, channel: Channel, note: Note) -> Option<&NoteState> {
+        self.notes
+            .iter()
+            .find(|state| state.channel == channel && state.note == note)
+    }
+
+    fn state(&mut self, channel: Channel, note: Note) -> &mut NoteState {
+        let index = match self
+            .notes
+            .iter()
+            .position(|state| state.channel == channel && state.note == note)
+        {
+            Some(index) => index,
+            None => {
+                self.notes.push(NoteState {
+                    channel,
+                    note,
+                    pitch_bend: Midi2Message::PITCH_BEND_CENTER,
+                    controllers: Vec::new(),
+                });
+                self.notes.len() - 1
+            }
+        };
+        &mut self.notes[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn controller_index() {
+        assert_eq!(PerNoteController::VOLUME.index(), 7);
+        assert_eq!(PerNoteController::Assignable(7).index(), 7);
+        assert_ne!(PerNoteController::VOLUME, PerNoteController::Assignable(7));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn tracker() {
+        let mut tracker = PerNoteTracker::new();
+        let volume = PerNoteController::VOLUME;
+        let assignable = PerNoteController::Assignable(7);
+        tracker.handle(&Midi2Message::PerNoteControlChange(
+            Channel::Ch1,
+            Note::C4,
+            volume,
+            1,
+        ));
+        tracker.handle(&Midi2Message::PerNoteControlChange(
+            Channel::Ch1,
+            Note::C4,
+            assignable,
+            2,
+        ));
+        tracker.handle(&Midi2Message::PerNoteControlChange(
+            Channel::Ch1,
+            Note::C4,
+            volume,
+            3,
+        ));
+        tracker.handle(&Midi2Message::PerNotePitchBend(Channel::Ch2, Note::C4, 4));
+        assert_eq!(tracker.controller(Channel::Ch1, Note::C4, volume), Some(3));
+        assert_eq!(
+            tracker.controller(Channel::Ch1, Note::C4, assignable),
+            Some(2)
+        );
+        assert_eq!(tracker.controller(Channel::Ch1, Note::D4, volume), None);
+        assert_eq!(tracker.pitch_bend(Channel::Ch2, Note::C4), 4);
+
+        let detach = PerNoteManagement {
+            detach: true,
+            reset: false,
+        };
+        tracker.handle(&Midi2Message::PerNoteManagement(
+            Channel::Ch1,
+            Note::C4,
+            detach,
+        ));
+        assert_eq!(tracker.controller(Channel::Ch1, Note::C4, volume), Some(3));
+        tracker.handle(&Midi2Message::ControlChange(
+            Channel::Ch1,
+            ControlFunction::RESET_ALL_CONTROLLERS,
+            0,
+        ));
+        assert_eq!(tracker.controller(Channel::Ch1, Note::C4, volume), None);
+        assert_eq!(tracker.pitch_bend(Channel::Ch2, Note::C4), 4);
+        tracker.clear();
+        assert!(tracker.is_empty());
+    }
+}
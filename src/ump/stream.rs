@@ -0,0 +1,601 @@
+use super::{Group, GroupSet, MessageType, UmpPacket};
+use crate::{Identity, ManufacturerId, U14, U4, U7};
+
+/// [UMP] The protocol of the channel voice messages in a UMP stream.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Protocol {
+    /// MIDI 1.0 channel voice messages, of message type 0x2.
+    Midi1,
+    /// MIDI 2.0 channel voice messages, of message type 0x4.
+    Midi2,
+}
+
+impl Protocol {
+    fn value(self) -> u32 {
+        match self {
+            Protocol::Midi1 => 0x01,
+            Protocol::Midi2 => 0x02,
+        }
+    }
+
+    fn from_value(value: u32) -> Option<Protocol> {
+        match value {
+            0x01 => Some(Protocol::Midi1),
+            0x02 => Some(Protocol::Midi2),
+            _ => None,
+        }
+    }
+}
+
+/// [UMP] The version of the UMP format supported by an endpoint.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UmpVersion {
+    /// The major version.
+    pub major: u8,
+    /// The minor version.
+    pub minor: u8,
+}
+
+impl UmpVersion {
+    /// Version 1.1, the version implemented by this module.
+    pub const V1_1: UmpVersion = UmpVersion { major: 1, minor: 1 };
+}
+
+/// [UMP] The notifications requested by an Endpoint Discovery message.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct EndpointFilter {
+    /// Request an Endpoint Info Notification.
+    pub endpoint_info: bool,
+    /// Request a Device Identity Notification.
+    pub device_identity: bool,
+    /// Request an Endpoint Name Notification.
+    pub endpoint_name: bool,
+    /// Request a Product Instance Id Notification.
+    pub product_instance_id: bool,
+    /// Request a Stream Configuration Notification.
+    pub stream_configuration: bool,
+}
+
+impl EndpointFilter {
+    /// Request every notification.
+    pub const ALL: EndpointFilter = EndpointFilter {
+        endpoint_info: true,
+        device_identity: true,
+        endpoint_name: true,
+        product_instance_id: true,
+        stream_configuration: true,
+    };
+
+    fn bits(self) -> u32 {
+        u32::from(self.endpoint_info)
+            | u32::from(self.device_identity) << 1
+            | u32::from(self.endpoint_name) << 2
+            | u32::from(self.product_instance_id) << 3
+            | u32::from(self.stream_configuration) << 4
+    }
+
+    fn from_bits(bits: u32) -> EndpointFilter {
+        EndpointFilter {
+            endpoint_info: bits & 0x01 != 0,
+            device_identity: bits & 0x02 != 0,
+            endpoint_name: bits & 0x04 != 0,
+            product_instance_id: bits & 0x08 != 0,
+            stream_configuration: bits & 0x10 != 0,
+        }
+    }
+}
+
+/// [UMP] The capabilities of an endpoint, sent in an Endpoint Info Notification.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct EndpointInfo {
+    /// The UMP version supported by the endpoint.
+    pub version: UmpVersion,
+    /// `true` if the function blocks of the endpoint never change.
+    pub static_function_blocks: bool,
+    /// The number of function blocks, at most 32. Only the lower 7 bits are sent.
+    pub function_block_count: u8,
+    /// `true` if the endpoint supports the MIDI 2.0 protocol.
+    pub midi2_protocol: bool,
+    /// `true` if the endpoint supports the MIDI 1.0 protocol.
+    pub midi1_protocol: bool,
+    /// `true` if the endpoint can receive JR Timestamps.
+    pub receive_jr: bool,
+    /// `true` if the endpoint can send JR Timestamps.
+    pub transmit_jr: bool,
+}
+
+impl EndpointInfo {
+    /// Returns `true` if the endpoint supports `protocol`.
+    pub fn supports(&self, protocol: Protocol) -> bool {
+        match protocol {
+            Protocol::Midi1 => self.midi1_protocol,
+            Protocol::Midi2 => self.midi2_protocol,
+        }
+    }
+
+    /// The configuration the endpoint switches to when it receives `request` while using
+    /// `current`. An unsupported protocol leaves the protocol unchanged, and JR Timestamps are
+    /// only enabled in the directions the endpoint supports.
+    pub fn accept(
+        &self,
+        current: StreamConfiguration,
+        request: StreamConfiguration,
+    ) -> StreamConfiguration {
+        let protocol = if self.supports(request.protocol) {
+            request.protocol
+        } else {
+            current.protocol
+        };
+        StreamConfiguration {
+            protocol,
+            receive_jr: request.receive_jr && self.receive_jr,
+            transmit_jr: request.transmit_jr && self.transmit_jr,
+        }
+    }
+}
+
+/// [UMP] The protocol and timestamps used by a UMP stream.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StreamConfiguration {
+    /// The protocol of channel voice messages.
+    pub protocol: Protocol,
+    /// `true` if the endpoint receives JR Timestamps.
+    pub receive_jr: bool,
+    /// `true` if the endpoint sends JR Timestamps.
+    pub transmit_jr: bool,
+}
+
+/// [UMP] The notifications requested by a Function Block Discovery message.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct FunctionBlockFilter {
+    /// Request Function Block Info Notifications.
+    pub info: bool,
+    /// Request Function Block Name Notifications.
+    pub name: bool,
+}
+
+impl FunctionBlockFilter {
+    /// Request every notification.
+    pub const ALL: FunctionBlockFilter = FunctionBlockFilter {
+        info: true,
+        name: true,
+    };
+}
+
+/// [UMP] The direction of the messages a function block handles.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum FunctionBlockDirection {
+    /// The block receives messages.
+    Input,
+    /// The block sends messages.
+    Output,
+    /// The block receives and sends messages.
+    Bidirectional,
+}
+
+impl FunctionBlockDirection {
+    fn value(self) -> u32 {
+        match self {
+            FunctionBlockDirection::Input => 0x1,
+            FunctionBlockDirection::Output => 0x2,
+            FunctionBlockDirection::Bidirectional => 0x3,
+        }
+    }
+
+    fn from_value(value: u32) -> Option<FunctionBlockDirection> {
+        match value {
+            0x1 => Some(FunctionBlockDirection::Input),
+            0x2 => Some(FunctionBlockDirection::Output),
+            0x3 => Some(FunctionBlockDirection::Bidirectional),
+            _ => None,
+        }
+    }
+}
+
+/// [UMP] Whether a function block represents a MIDI 1.0 port.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Midi1Mode {
+    /// The block is not a MIDI 1.0 port.
+    NotMidi1,
+    /// The block is a MIDI 1.0 port.
+    Midi1,
+    /// The block is a MIDI 1.0 port limited to the 31.25 kbit/s of a 5 pin DIN cable.
+    Midi1Restricted,
+}
+
+/// [UMP] A function block of an endpoint, sent in a Function Block Info Notification.
+///
+/// A function block is a part of a device, such as a synthesizer or a MIDI port, that uses a
+/// range of groups.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionBlockInfo {
+    /// `true` if the block is in use.
+    pub active: bool,
+    /// The number of the block, from 0 to 31. Only the lower 7 bits are sent.
+    pub number: u8,
+    /// How the block is mainly used, or `None` if unknown.
+    pub ui_hint: Option<FunctionBlockDirection>,
+    /// Whether the block is a MIDI 1.0 port.
+    pub midi1: Midi1Mode,
+    /// The direction of the messages the block handles.
+    pub direction: FunctionBlockDirection,
+    /// The first group used by the block.
+    pub first_group: Group,
+    /// The number of groups used by the block, from 1 to 16.
+    pub group_count: u8,
+    /// The MIDI-CI message version of the block, or 0 if it does not support MIDI-CI.
+    pub ci_version: u8,
+    /// The most SysEx8 streams the block can receive at once.
+    pub max_sysex8_streams: u8,
+}
+
+impl FunctionBlockInfo {
+    /// The groups used by the block. Groups past the last group are left out.
+    ///
+    /// # Example
+    /// ```
+    /// # use wmidi::ump::{FunctionBlockDirection, FunctionBlockInfo, Midi1Mode};
+    /// use wmidi::U4;
+    /// let info = FunctionBlockInfo {
+    ///     first_group: U4::from_u8_lossy(14),
+    ///     group_count: 4,
+    ///     // ...
+    /// #   active: true,
+    /// #   number: 0,
+    /// #   ui_hint: None,
+    /// #   midi1: Midi1Mode::NotMidi1,
+    /// #   direction: FunctionBlockDirection::Bidirectional,
+    /// #   ci_version: 0,
+    /// #   max_sysex8_streams: 0,
+    /// };
+    /// assert_eq!(info.groups().bits(), 0xC000);
+    /// ```
+    pub fn groups(&self) -> GroupSet {
+        let first = u32::from(u8::from(self.first_group));
+        let count = u32::from(self.group_count).min(16 - first);
+        GroupSet::from_bits((((1u32 << count) - 1) << first) as u16)
+    }
+}
+
+/// [UMP] A UMP stream message of message type 0xF, used to discover and configure the endpoints
+/// at each end of a UMP stream. Stream messages have no group.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{EndpointFilter, StreamMessage, UmpVersion};
+/// let message = StreamMessage::EndpointDiscovery {
+///     version: UmpVersion::V1_1,
+///     filter: EndpointFilter::ALL,
+/// };
+/// let packet = message.to_packet();
+/// assert_eq!(packet.words(), &[0xF000_0101, 0x0000_001F, 0, 0]);
+/// assert_eq!(packet.group(), None);
+/// assert_eq!(StreamMessage::from_packet(&packet), Some(message));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum StreamMessage {
+    /// Ask an endpoint for notifications describing it, status 0x000.
+    EndpointDiscovery {
+        /// The UMP version supported by the sender.
+        version: UmpVersion,
+        /// The notifications to send.
+        filter: EndpointFilter,
+    },
+
+    /// The capabilities of an endpoint, status 0x001.
+    EndpointInfo(EndpointInfo),
+
+    /// The identity of the device, as sent in a MIDI 1.0 Identity Reply, status 0x002.
+    DeviceIdentity(Identity),
+
+    /// Ask an endpoint to change its stream configuration, status 0x005.
+    StreamConfigurationRequest(StreamConfiguration),
+
+    /// The current stream configuration of an endpoint, status 0x006.
+    StreamConfigurationNotification(StreamConfiguration),
+
+    /// Ask an endpoint for notifications describing a function block, status 0x010.
+    FunctionBlockDiscovery {
+        /// The number of the block, or 0xFF for all blocks.
+        number: u8,
+        /// The notifications to send.
+        filter: FunctionBlockFilter,
+    },
+
+    /// A function block of an endpoint, status 0x011.
+    FunctionBlockInfo(FunctionBlockInfo),
+}
+
+impl StreamMessage {
+    /// The packet holding the message.
+    pub fn to_packet(&self) -> UmpPacket {
+        let (status, data, words) = match *self {
+            StreamMessage::EndpointDiscovery { version, filter } => {
+                (0x000, version_bits(version), [filter.bits(), 0, 0])
+            }
+            StreamMessage::EndpointInfo(info) => {
+                let word = u32::from(info.static_function_blocks) << 31
+                    | u32::from(info.function_block_count & 0x7F) << 24
+                    | u32::from(info.midi2_protocol) << 9
+                    | u32::from(info.midi1_protocol) << 8
+                    | u32::from(info.receive_jr) << 1
+                    | u32::from(info.transmit_jr);
+                (0x001, version_bits(info.version), [word, 0, 0])
+            }
+            StreamMessage::DeviceIdentity(identity) => {
+                let manufacturer = match identity.manufacturer {
+                    ManufacturerId::Standard(id) => [U7(0), id, U7(0), U7(0)],
+                    ManufacturerId::Extended(a, b) => [U7(0), U7(0), a, b],
+                };
+                let [family_lsb, family_msb] = split(identity.family);
+                let [member_lsb, member_msb] = split(identity.member);
+                let words = [
+                    to_word(manufacturer),
+                    to_word([family_lsb, family_msb, member_lsb, member_msb]),
+                    to_word(identity.version),
+                ];
+                (0x002, 0, words)
+            }
+            StreamMessage::StreamConfigurationRequest(configuration) => {
+                (0x005, configuration_bits(configuration), [0, 0, 0])
+            }
+            StreamMessage::StreamConfigurationNotification(configuration) => {
+                (0x006, configuration_bits(configuration), [0, 0, 0])
+            }
+            StreamMessage::FunctionBlockDiscovery { number, filter } => {
+                let bits = u32::from(filter.info) | u32::from(filter.name) << 1;
+                (0x010, u32::from(number) << 8 | bits, [0, 0, 0])
+            }
+            StreamMessage::FunctionBlockInfo(info) => {
+                let midi1 = match info.midi1 {
+                    Midi1Mode::NotMidi1 => 0x0,
+                    Midi1Mode::Midi1 => 0x1,
+                    Midi1Mode::Midi1Restricted => 0x2,
+                };
+                let data = u32::from(info.active) << 15
+                    | u32::from(info.number & 0x7F) << 8
+                    | info.ui_hint.map_or(0, FunctionBlockDirection::value) << 4
+                    | midi1 << 2
+                    | info.direction.value();
+                let word = u32::from(u8::from(info.first_group)) << 24
+                    | u32::from(info.group_count) << 16
+                    | u32::from(info.ci_version) << 8
+                    | u32::from(info.max_sysex8_streams);
+                (0x011, data, [word, 0, 0])
+            }
+        };
+        UmpPacket::new([
+            0xF000_0000 | status << 16 | data,
+            words[0],
+            words[1],
+            words[2],
+        ])
+    }
+
+    /// Parse a packet. Returns `None` if the packet is not a stream message of a kind held by
+    /// `StreamMessage`.
+    pub fn from_packet(packet: &UmpPacket) -> Option<StreamMessage> {
+        if packet.message_type() != MessageType::Stream || packet.words()[0] >> 26 & 0x3 != 0 {
+            return None;
+        }
+        let words = packet.words();
+        let data = words[0] & 0xFFFF;
+        let version = UmpVersion {
+            major: (data >> 8) as u8,
+            minor: data as u8,
+        };
+        let configuration = || {
+            Some(StreamConfiguration {
+                protocol: Protocol::from_value(data >> 8)?,
+                receive_jr: data & 0x2 != 0,
+                transmit_jr: data & 0x1 != 0,
+            })
+        };
+        let message = match words[0] >> 16 & 0x3FF {
+            0x000 => StreamMessage::EndpointDiscovery {
+                version,
+                filter: EndpointFilter::from_bits(words[1]),
+            },
+            0x001 => StreamMessage::EndpointInfo(EndpointInfo {
+                version,
+                static_function_blocks: words[1] >> 31 != 0,
+                function_block_count: (words[1] >> 24) as u8 & 0x7F,
+                midi2_protocol: words[1] & 0x200 != 0,
+                midi1_protocol: words[1] & 0x100 != 0,
+                receive_jr: words[1] & 0x2 != 0,
+                transmit_jr: words[1] & 0x1 != 0,
+            }),
+            0x002 => {
+                // The first byte of the manufacturer word is reserved.
+                let [_, a, b, c] = from_word(words[1] & 0x00FF_FFFF)?;
+                let [family_lsb, family_msb, member_lsb, member_msb] = from_word(words[2])?;
+                StreamMessage::DeviceIdentity(Identity {
+                    manufacturer: match a {
+                        U7(0) => ManufacturerId::Extended(b, c),
+                        id => ManufacturerId::Standard(id),
+                    },
+                    family: combine(family_lsb, family_msb),
+                    member: combine(member_lsb, member_msb),
+                    version: from_word(words[3])?,
+                })
+            }
+            0x005 => StreamMessage::StreamConfigurationRequest(configuration()?),
+            0x006 => StreamMessage::StreamConfigurationNotification(configuration()?),
+            0x010 => StreamMessage::FunctionBlockDiscovery {
+                number: (data >> 8) as u8,
+                filter: FunctionBlockFilter {
+                    info: data & 0x1 != 0,
+                    name: data & 0x2 != 0,
+                },
+            },
+            0x011 => StreamMessage::FunctionBlockInfo(FunctionBlockInfo {
+                active: data >> 15 != 0,
+                number: (data >> 8) as u8 & 0x7F,
+                ui_hint: FunctionBlockDirection::from_value(data >> 4 & 0x3),
+                midi1: match data >> 2 & 0x3 {
+                    0x0 => Midi1Mode::NotMidi1,
+                    0x1 => Midi1Mode::Midi1,
+                    0x2 => Midi1Mode::Midi1Restricted,
+                    _ => return None,
+                },
+                direction: FunctionBlockDirection::from_value(data & 0x3)?,
+                first_group: U4::from_u8_lossy((words[1] >> 24) as u8),
+                group_count: (words[1] >> 16) as u8,
+                ci_version: (words[1] >> 8) as u8,
+                max_sysex8_streams: words[1] as u8,
+            }),
+            _ => return None,
+        };
+        Some(message)
+    }
+}
+
+fn version_bits(version: UmpVersion) -> u32 {
+    u32::from(version.major) << 8 | u32::from(version.minor)
+}
+
+fn configuration_bits(configuration: StreamConfiguration) -> u32 {
+    configuration.protocol.value() << 8
+        | u32::from(configuration.receive_jr) << 1
+        | u32::from(configuration.transmit_jr)
+}
+
+fn to_word(bytes: [U7; 4]) -> u32 {
+    let bytes = U7::data_to_bytes(&bytes);
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn from_word(word: u32) -> Option<[U7; 4]> {
+    let mut bytes = [U7::MIN; 4];
+    bytes.copy_from_slice(U7::try_from_bytes(&word.to_be_bytes()).ok()?);
+    Some(bytes)
+}
+
+/// The LSB and MSB of `value`.
+fn split(value: U14) -> [U7; 2] {
+    let value = u16::from(value);
+    [U7(value as u8 & 0x7F), U7((value >> 7) as u8)]
+}
+
+fn combine(lsb: U7, msb: U7) -> U14 {
+    U14::from_u16_lossy(u16::from(u8::from(msb)) << 7 | u16::from(u8::from(lsb)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let configuration = StreamConfiguration {
+            protocol: Protocol::Midi2,
+            receive_jr: true,
+            transmit_jr: false,
+        };
+        for message in [
+            StreamMessage::EndpointDiscovery {
+                version: UmpVersion { major: 2, minor: 0 },
+                filter: EndpointFilter {
+                    device_identity: true,
+                    ..EndpointFilter::default()
+                },
+            },
+            StreamMessage::EndpointInfo(EndpointInfo {
+                version: UmpVersion::V1_1,
+                static_function_blocks: true,
+                function_block_count: 32,
+                midi2_protocol: true,
+                midi1_protocol: false,
+                receive_jr: false,
+                transmit_jr: true,
+            }),
+            StreamMessage::DeviceIdentity(Identity {
+                manufacturer: ManufacturerId::Extended(U7(0x20), U7(0x29)),
+                family: U14::from_u16_lossy(0x3FFF),
+                member: U14::from_u16_lossy(0x0081),
+                version: [U7(1), U7(2), U7(3), U7(4)],
+            }),
+            StreamMessage::DeviceIdentity(Identity {
+                manufacturer: ManufacturerId::Standard(U7(0x41)),
+                family: U14::from_u16_lossy(0),
+                member: U14::from_u16_lossy(1),
+                version: [U7(0); 4],
+            }),
+            StreamMessage::StreamConfigurationRequest(configuration),
+            StreamMessage::StreamConfigurationNotification(StreamConfiguration {
+                protocol: Protocol::Midi1,
+                ..configuration
+            }),
+            StreamMessage::FunctionBlockDiscovery {
+                number: 0xFF,
+                filter: FunctionBlockFilter::ALL,
+            },
+            StreamMessage::FunctionBlockInfo(FunctionBlockInfo {
+                active: true,
+                number: 31,
+                ui_hint: None,
+                midi1: Midi1Mode::Midi1Restricted,
+                direction: FunctionBlockDirection::Bidirectional,
+                first_group: U4(0xF),
+                group_count: 1,
+                ci_version: 0x02,
+                max_sysex8_streams: 0xFF,
+            }),
+        ]
+        .iter()
+        {
+            let packet = message.to_packet();
+            assert_eq!(packet.len(), 4);
+            assert_eq!(StreamMessage::from_packet(&packet), Some(*message));
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let message = StreamMessage::DeviceIdentity(Identity {
+            manufacturer: ManufacturerId::Standard(U7(0x43)),
+            family: U14::from_u16_lossy(0x0102),
+            member: U14::from_u16_lossy(0x0304),
+            version: [U7(5), U7(6), U7(7), U7(8)],
+        });
+        assert_eq!(
+            message.to_packet().words(),
+            &[0xF002_0000, 0x0043_0000, 0x0202_0406, 0x0506_0708]
+        );
+        let message = StreamMessage::FunctionBlockInfo(FunctionBlockInfo {
+            active: true,
+            number: 1,
+            ui_hint: Some(FunctionBlockDirection::Input),
+            midi1: Midi1Mode::NotMidi1,
+            direction: FunctionBlockDirection::Output,
+            first_group: U4(2),
+            group_count: 3,
+            ci_version: 0,
+            max_sysex8_streams: 0,
+        });
+        assert_eq!(
+            message.to_packet().words(),
+            &[0xF011_8112, 0x0203_0000, 0, 0]
+        );
+        if let StreamMessage::FunctionBlockInfo(info) = message {
+            assert_eq!(info.groups().bits(), 0x001C);
+        }
+        for words in [
+            // Not the complete form.
+            [0xF400_0101, 0, 0, 0],
+            // An unknown protocol.
+            [0xF005_0300, 0, 0, 0],
+            // A reserved direction.
+            [0xF011_0000, 0, 0, 0],
+            // A manufacturer byte out of range.
+            [0xF002_0000, 0x0080_0000, 0, 0],
+            // An unsupported status.
+            [0xF003_0000, 0, 0, 0],
+        ]
+        .iter()
+        {
+            let packet = UmpPacket::from_words(words).unwrap();
+            assert_eq!(StreamMessage::from_packet(&packet), None);
+        }
+    }
+}
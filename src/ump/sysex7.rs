@@ -0,0 +1,330 @@
+use super::{Group, MessageType, UmpPacket};
+use crate::U7;
+
+#[cfg(feature = "std")]
+use crate::{FromBytesError, MidiMessage};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [UMP] Where a SysEx packet falls in its message.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum SysExStatus {
+    /// The whole message fits in one packet.
+    Complete,
+    /// The first packet of a message.
+    Start,
+    /// A packet between the first and last.
+    Continue,
+    /// The last packet of a message.
+    End,
+}
+
+impl SysExStatus {
+    pub(crate) fn value(self) -> u32 {
+        match self {
+            SysExStatus::Complete => 0x0,
+            SysExStatus::Start => 0x1,
+            SysExStatus::Continue => 0x2,
+            SysExStatus::End => 0x3,
+        }
+    }
+
+    pub(crate) fn from_value(value: u32) -> Option<SysExStatus> {
+        match value {
+            0x0 => Some(SysExStatus::Complete),
+            0x1 => Some(SysExStatus::Start),
+            0x2 => Some(SysExStatus::Continue),
+            0x3 => Some(SysExStatus::End),
+            _ => None,
+        }
+    }
+
+    /// The status of packet `index` of `count` packets.
+    pub(crate) fn for_index(index: usize, count: usize) -> SysExStatus {
+        match (index, count) {
+            (_, 1) => SysExStatus::Complete,
+            (0, _) => SysExStatus::Start,
+            (i, n) if i + 1 == n => SysExStatus::End,
+            _ => SysExStatus::Continue,
+        }
+    }
+}
+
+/// [UMP] A 64 bit data packet of message type 0x3, which carries up to 6 bytes of a 7 bit SysEx
+/// message. The start and end bytes of the message are not sent.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SysEx7Packet {
+    /// Where the packet falls in its message.
+    pub status: SysExStatus,
+    data: [U7; 6],
+    len: u8,
+}
+
+impl SysEx7Packet {
+    /// The most data bytes in a packet.
+    pub const MAX_DATA_LEN: usize = 6;
+
+    /// Create a packet holding `data`, or return `None` if it has more than 6 bytes.
+    pub fn new(status: SysExStatus, data: &[U7]) -> Option<SysEx7Packet> {
+        if data.len() > SysEx7Packet::MAX_DATA_LEN {
+            return None;
+        }
+        let mut packet = SysEx7Packet {
+            status,
+            data: [U7::MIN; 6],
+            len: data.len() as u8,
+        };
+        packet.data[..data.len()].copy_from_slice(data);
+        Some(packet)
+    }
+
+    /// The data bytes of the packet.
+    pub fn data(&self) -> &[U7] {
+        &self.data[..usize::from(self.len)]
+    }
+
+    /// The packet addressed to `group`.
+    pub fn to_packet(&self, group: Group) -> UmpPacket {
+        let bytes = U7::data_to_bytes(&self.data);
+        let word0 = 0x3000_0000
+            | u32::from(u8::from(group)) << 24
+            | self.status.value() << 20
+            | u32::from(self.len) << 16
+            | u32::from(bytes[0]) << 8
+            | u32::from(bytes[1]);
+        let word1 = u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]);
+        UmpPacket::new([word0, word1, 0, 0])
+    }
+
+    /// Parse a packet. Returns the group and SysEx packet, or `None` if the packet is not a valid
+    /// SysEx7 packet.
+    pub fn from_packet(packet: &UmpPacket) -> Option<(Group, SysEx7Packet)> {
+        if packet.message_type() != MessageType::Data64 {
+            return None;
+        }
+        let (word0, word1) = (packet.words()[0], packet.words()[1]);
+        let status = SysExStatus::from_value(word0 >> 20 & 0xF)?;
+        let len = (word0 >> 16 & 0xF) as usize;
+        let [_, _, byte0, byte1] = word0.to_be_bytes();
+        let [byte2, byte3, byte4, byte5] = word1.to_be_bytes();
+        let bytes = [byte0, byte1, byte2, byte3, byte4, byte5];
+        let data = U7::try_from_bytes(bytes.get(..len)?).ok()?;
+        Some((packet.group()?, SysEx7Packet::new(status, data)?))
+    }
+}
+
+/// [UMP] Splits the payload of a SysEx message into SysEx7 packets.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{SysEx7Packet, SysEx7Packets, SysExStatus};
+/// use wmidi::{MidiMessage, U4};
+/// let bytes = [0xF0, 0x7E, 0x7F, 0x06, 0x01, 0x01, 0x02, 0x03, 0xF7];
+/// let payload = match MidiMessage::from_bytes(&bytes).unwrap() {
+///     MidiMessage::SysEx(payload) => payload,
+///     _ => unreachable!(),
+/// };
+/// let packets: Vec<_> = SysEx7Packets::new(U4::MIN, payload).collect();
+/// assert_eq!(packets.len(), 2);
+/// assert_eq!(packets[0].words(), &[0x3016_7E7F, 0x0601_0102]);
+/// assert_eq!(packets[1].words(), &[0x3031_0300, 0x0000_0000]);
+/// let (_, last) = SysEx7Packet::from_packet(&packets[1]).unwrap();
+/// assert_eq!(last.status, SysExStatus::End);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SysEx7Packets<'a> {
+    group: Group,
+    payload: &'a [U7],
+    index: usize,
+    count: usize,
+}
+
+impl<'a> SysEx7Packets<'a> {
+    /// Split `payload`, which excludes the start and end bytes, into packets addressed to
+    /// `group`. An empty payload is sent as a single empty packet.
+    pub fn new(group: Group, payload: &'a [U7]) -> SysEx7Packets<'a> {
+        SysEx7Packets {
+            group,
+            payload,
+            index: 0,
+            count: payload.len().div_ceil(SysEx7Packet::MAX_DATA_LEN).max(1),
+        }
+    }
+}
+
+impl<'a> Iterator for SysEx7Packets<'a> {
+    type Item = UmpPacket;
+
+    fn next(&mut self) -> Option<UmpPacket> {
+        if self.index == self.count {
+            return None;
+        }
+        let len = self.payload.len().min(SysEx7Packet::MAX_DATA_LEN);
+        let (data, rest) = self.payload.split_at(len);
+        self.payload = rest;
+        let status = SysExStatus::for_index(self.index, self.count);
+        self.index += 1;
+        Some(
+            SysEx7Packet::new(status, data)
+                .unwrap()
+                .to_packet(self.group),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.count - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for SysEx7Packets<'a> {}
+
+/// [UMP] Joins SysEx7 packets into SysEx messages. Each group may have one message in progress.
+///
+/// Messages are limited to `max_len` data bytes, so a broken or malicious sender cannot use
+/// unbounded memory. A `Start` or `Complete` packet drops any message in progress on its group.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{SysEx7Assembler, SysEx7Packet, SysEx7Packets};
+/// use wmidi::{MidiMessage, U4, U7};
+/// let payload = U7::try_from_bytes(&[0x7D, 1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+/// let mut assembler = SysEx7Assembler::new(1024);
+/// let mut message = None;
+/// for packet in SysEx7Packets::new(U4::MIN, payload) {
+///     let (group, packet) = SysEx7Packet::from_packet(&packet).unwrap();
+///     message = assembler.push(group, &packet).unwrap();
+/// }
+/// assert_eq!(message, Some(MidiMessage::OwnedSysEx(payload.to_vec())));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct SysEx7Assembler {
+    max_len: usize,
+    messages: [Option<Vec<U7>>; 16],
+}
+
+#[cfg(feature = "std")]
+impl SysEx7Assembler {
+    /// Create an assembler for messages of at most `max_len` data bytes.
+    pub fn new(max_len: usize) -> SysEx7Assembler {
+        SysEx7Assembler {
+            max_len,
+            messages: Default::default(),
+        }
+    }
+
+    /// Add a packet received on `group`. Returns the message once its last packet is added.
+    ///
+    /// Returns `FromBytesError::UnexpectedEndSysExByte` for a `Continue` or `End` packet without
+    /// a message in progress, and `FromBytesError::SysExTooLong` if the message is longer than
+    /// the limit. The message in progress is dropped on error.
+    pub fn push(
+        &mut self,
+        group: Group,
+        packet: &SysEx7Packet,
+    ) -> Result<Option<MidiMessage<'static>>, FromBytesError> {
+        let slot = &mut self.messages[usize::from(u8::from(group))];
+        let mut message = match packet.status {
+            SysExStatus::Complete | SysExStatus::Start => Vec::new(),
+            SysExStatus::Continue | SysExStatus::End => {
+                slot.take().ok_or(FromBytesError::UnexpectedEndSysExByte)?
+            }
+        };
+        *slot = None;
+        if message.len() + packet.data().len() > self.max_len {
+            return Err(FromBytesError::SysExTooLong);
+        }
+        message.extend_from_slice(packet.data());
+        match packet.status {
+            SysExStatus::Complete | SysExStatus::End => Ok(Some(MidiMessage::OwnedSysEx(message))),
+            SysExStatus::Start | SysExStatus::Continue => {
+                *slot = Some(message);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns `true` if a message is in progress on `group`.
+    pub fn is_receiving(&self, group: Group) -> bool {
+        self.messages[usize::from(u8::from(group))].is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U4;
+
+    #[test]
+    fn packets() {
+        let payload: [U7; 13] = core::array::from_fn(|i| U7(i as u8));
+        let group = U4(0x5);
+        let mut packets = SysEx7Packets::new(group, &payload)
+            .map(|packet| SysEx7Packet::from_packet(&packet).unwrap());
+        for status in [SysExStatus::Start, SysExStatus::Continue].iter() {
+            let (g, packet) = packets.next().unwrap();
+            assert_eq!((g, packet.status), (group, *status));
+        }
+        let (g, packet) = packets.next().unwrap();
+        assert_eq!((g, packet.status), (group, SysExStatus::End));
+        assert_eq!(packet.data(), &[U7(12)]);
+        assert_eq!(packets.next(), None);
+
+        let mut packets = SysEx7Packets::new(group, &[]);
+        let (_, packet) = SysEx7Packet::from_packet(&packets.next().unwrap()).unwrap();
+        assert_eq!(packet.status, SysExStatus::Complete);
+        assert!(packet.data().is_empty());
+        assert_eq!(packets.next(), None);
+
+        assert_eq!(SysEx7Packet::new(SysExStatus::Start, &[U7(0); 7]), None);
+        let invalid = [0x3007_0000, 0, 0x3040_0000, 0, 0x3001_8000, 0];
+        for words in invalid.chunks(2) {
+            let packet = UmpPacket::from_words(words).unwrap();
+            assert_eq!(SysEx7Packet::from_packet(&packet), None);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn assemble() {
+        let payload: Vec<U7> = (0..20).map(U7).collect();
+        let mut assembler = SysEx7Assembler::new(20);
+        let mut first = SysEx7Packets::new(U4(0), &payload);
+        let mut second = SysEx7Packets::new(U4(1), &payload[..8]);
+        let mut push = |packet: UmpPacket| {
+            let (group, packet) = SysEx7Packet::from_packet(&packet).unwrap();
+            assembler.push(group, &packet)
+        };
+        assert_eq!(push(first.next().unwrap()), Ok(None));
+        assert_eq!(push(second.next().unwrap()), Ok(None));
+        assert_eq!(push(first.next().unwrap()), Ok(None));
+        assert_eq!(
+            push(second.next().unwrap()),
+            Ok(Some(MidiMessage::OwnedSysEx(payload[..8].to_vec())))
+        );
+        assert_eq!(push(first.next().unwrap()), Ok(None));
+        assert_eq!(
+            push(first.next().unwrap()),
+            Ok(Some(MidiMessage::OwnedSysEx(payload.clone())))
+        );
+
+        let end = SysEx7Packet::new(SysExStatus::End, &[]).unwrap();
+        assert_eq!(
+            assembler.push(U4(0), &end),
+            Err(FromBytesError::UnexpectedEndSysExByte)
+        );
+        let mut assembler = SysEx7Assembler::new(10);
+        let start = SysEx7Packet::new(SysExStatus::Start, &payload[..6]).unwrap();
+        assert_eq!(assembler.push(U4(0), &start), Ok(None));
+        assert!(assembler.is_receiving(U4(0)));
+        // A new start drops the message in progress, leaving 6 bytes, so 6 more are too long.
+        assert_eq!(assembler.push(U4(0), &start), Ok(None));
+        let end = SysEx7Packet::new(SysExStatus::End, &payload[..6]).unwrap();
+        assert_eq!(
+            assembler.push(U4(0), &end),
+            Err(FromBytesError::SysExTooLong)
+        );
+        assert!(!assembler.is_receiving(U4(0)));
+    }
+}
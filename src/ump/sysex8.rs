@@ -0,0 +1,297 @@
+use super::{Group, MessageType, SysExStatus, UmpPacket};
+
+#[cfg(feature = "std")]
+use crate::FromBytesError;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// [UMP] A 128 bit data packet of message type 0x5, which carries up to 13 bytes of an 8 bit
+/// SysEx message.
+///
+/// Each message is sent on a stream, given by an 8 bit stream ID, so several messages may be
+/// sent at once on the same group. Unlike 7 bit SysEx, the data bytes may use all 8 bits.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SysEx8Packet {
+    /// Where the packet falls in its message.
+    pub status: SysExStatus,
+    /// The stream the message is sent on.
+    pub stream_id: u8,
+    data: [u8; 13],
+    len: u8,
+}
+
+impl SysEx8Packet {
+    /// The most data bytes in a packet.
+    pub const MAX_DATA_LEN: usize = 13;
+
+    /// Create a packet holding `data`, or return `None` if it has more than 13 bytes.
+    pub fn new(status: SysExStatus, stream_id: u8, data: &[u8]) -> Option<SysEx8Packet> {
+        if data.len() > SysEx8Packet::MAX_DATA_LEN {
+            return None;
+        }
+        let mut packet = SysEx8Packet {
+            status,
+            stream_id,
+            data: [0; 13],
+            len: data.len() as u8,
+        };
+        packet.data[..data.len()].copy_from_slice(data);
+        Some(packet)
+    }
+
+    /// The data bytes of the packet.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..usize::from(self.len)]
+    }
+
+    /// The packet addressed to `group`.
+    pub fn to_packet(&self, group: Group) -> UmpPacket {
+        // The byte count includes the stream ID.
+        let word0 = 0x5000_0000
+            | u32::from(u8::from(group)) << 24
+            | self.status.value() << 20
+            | u32::from(self.len + 1) << 16
+            | u32::from(self.stream_id) << 8
+            | u32::from(self.data[0]);
+        let mut words = [word0, 0, 0, 0];
+        for (word, chunk) in words[1..].iter_mut().zip(self.data[1..].chunks_exact(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        UmpPacket::new(words)
+    }
+
+    /// Parse a packet. Returns the group and SysEx packet, or `None` if the packet is not a valid
+    /// SysEx8 packet.
+    pub fn from_packet(packet: &UmpPacket) -> Option<(Group, SysEx8Packet)> {
+        if packet.message_type() != MessageType::Data128 {
+            return None;
+        }
+        let words = packet.words();
+        let status = SysExStatus::from_value(words[0] >> 20 & 0xF)?;
+        let len = ((words[0] >> 16 & 0xF) as usize).checked_sub(1)?;
+        let [_, _, stream_id, first] = words[0].to_be_bytes();
+        let mut data = [first; 13];
+        for (chunk, word) in data[1..].chunks_exact_mut(4).zip(&words[1..]) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        let packet_data = data.get(..len)?;
+        Some((
+            packet.group()?,
+            SysEx8Packet::new(status, stream_id, packet_data)?,
+        ))
+    }
+}
+
+/// [UMP] Splits an 8 bit SysEx message into SysEx8 packets.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{SysEx8Packet, SysEx8Packets, SysExStatus};
+/// use wmidi::U4;
+/// let data: Vec<u8> = (0..20).map(|i| i * 10).collect();
+/// let packets: Vec<_> = SysEx8Packets::new(U4::MIN, 0x42, &data).collect();
+/// assert_eq!(packets.len(), 2);
+/// assert_eq!(packets[1].words(), &[0x5038_4282, 0x8C96_A0AA, 0xB4BE_0000, 0x0000_0000]);
+/// let (_, first) = SysEx8Packet::from_packet(&packets[0]).unwrap();
+/// assert_eq!(first.status, SysExStatus::Start);
+/// assert_eq!(first.stream_id, 0x42);
+/// assert_eq!(first.data(), &data[..13]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SysEx8Packets<'a> {
+    group: Group,
+    stream_id: u8,
+    data: &'a [u8],
+    index: usize,
+    count: usize,
+}
+
+impl<'a> SysEx8Packets<'a> {
+    /// Split `data` into packets addressed to `group` on the stream `stream_id`. An empty message
+    /// is sent as a single empty packet.
+    pub fn new(group: Group, stream_id: u8, data: &'a [u8]) -> SysEx8Packets<'a> {
+        SysEx8Packets {
+            group,
+            stream_id,
+            data,
+            index: 0,
+            count: data.len().div_ceil(SysEx8Packet::MAX_DATA_LEN).max(1),
+        }
+    }
+}
+
+impl<'a> Iterator for SysEx8Packets<'a> {
+    type Item = UmpPacket;
+
+    fn next(&mut self) -> Option<UmpPacket> {
+        if self.index == self.count {
+            return None;
+        }
+        let len = self.data.len().min(SysEx8Packet::MAX_DATA_LEN);
+        let (data, rest) = self.data.split_at(len);
+        self.data = rest;
+        let status = SysExStatus::for_index(self.index, self.count);
+        self.index += 1;
+        let packet = SysEx8Packet::new(status, self.stream_id, data).unwrap();
+        Some(packet.to_packet(self.group))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.count - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for SysEx8Packets<'a> {}
+
+/// [UMP] Joins SysEx8 packets into messages. Each stream of each group may have one message in
+/// progress.
+///
+/// Messages are limited to `max_len` bytes, so a broken or malicious sender cannot use unbounded
+/// memory. A `Start` or `Complete` packet drops any message in progress on its stream.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{SysEx8Assembler, SysEx8Packet, SysEx8Packets};
+/// use wmidi::U4;
+/// let data = [0xFF; 40];
+/// let mut assembler = SysEx8Assembler::new(1024);
+/// let mut message = None;
+/// for packet in SysEx8Packets::new(U4::MIN, 7, &data) {
+///     let (group, packet) = SysEx8Packet::from_packet(&packet).unwrap();
+///     message = assembler.push(group, &packet).unwrap();
+/// }
+/// assert_eq!(message, Some(data.to_vec()));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct SysEx8Assembler {
+    max_len: usize,
+    pending: Vec<(Group, u8, Vec<u8>)>,
+}
+
+#[cfg(feature = "std")]
+impl SysEx8Assembler {
+    /// Create an assembler for messages of at most `max_len` bytes.
+    pub fn new(max_len: usize) -> SysEx8Assembler {
+        SysEx8Assembler {
+            max_len,
+            pending: Vec::new(),
+        }
+    }
+
+    /// The number of messages in progress.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no message is in progress.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Add a packet received on `group`. Returns the message once its last packet is added.
+    ///
+    /// Returns `FromBytesError::UnexpectedEndSysExByte` for a `Continue` or `End` packet without
+    /// a message in progress on its stream, and `FromBytesError::SysExTooLong` if the message is
+    /// longer than the limit. The message in progress is dropped on error.
+    pub fn push(
+        &mut self,
+        group: Group,
+        packet: &SysEx8Packet,
+    ) -> Result<Option<Vec<u8>>, FromBytesError> {
+        let index = self
+            .pending
+            .iter()
+            .position(|(g, stream_id, _)| *g == group && *stream_id == packet.stream_id);
+        let pending = index.map(|index| self.pending.swap_remove(index).2);
+        let mut message = match packet.status {
+            SysExStatus::Complete | SysExStatus::Start => Vec::new(),
+            SysExStatus::Continue | SysExStatus::End => {
+                pending.ok_or(FromBytesError::UnexpectedEndSysExByte)?
+            }
+        };
+        if message.len() + packet.data().len() > self.max_len {
+            return Err(FromBytesError::SysExTooLong);
+        }
+        message.extend_from_slice(packet.data());
+        match packet.status {
+            SysExStatus::Complete | SysExStatus::End => Ok(Some(message)),
+            SysExStatus::Start | SysExStatus::Continue => {
+                self.pending.push((group, packet.stream_id, message));
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::U4;
+
+    #[test]
+    fn packets() {
+        let data: [u8; 256] = core::array::from_fn(|i| i as u8);
+        let group = U4(0xC);
+        let packets = || {
+            SysEx8Packets::new(group, 0xFF, &data)
+                .map(|packet| SysEx8Packet::from_packet(&packet).unwrap())
+        };
+        assert_eq!(packets().count(), 20);
+        let mut joined = data.iter();
+        for (g, packet) in packets() {
+            assert_eq!((g, packet.stream_id), (group, 0xFF));
+            let len = packet.data().len();
+            assert!(packet.data().iter().eq(joined.by_ref().take(len)));
+        }
+        assert_eq!(joined.next(), None);
+        let (_, last) = packets().last().unwrap();
+        assert_eq!(last.status, SysExStatus::End);
+        assert_eq!(last.data(), &data[247..]);
+
+        let packet = SysEx8Packet::new(SysExStatus::Complete, 1, &[]).unwrap();
+        assert_eq!(packet.to_packet(U4(0)).words(), &[0x5001_0100, 0, 0, 0]);
+        assert_eq!(SysEx8Packet::new(SysExStatus::Start, 0, &[0; 14]), None);
+        for word in [0x5000_0000, 0x500F_0000, 0x5081_0000].iter() {
+            let packet = UmpPacket::from_words(&[*word, 0, 0, 0]).unwrap();
+            assert_eq!(SysEx8Packet::from_packet(&packet), None);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn assemble() {
+        let data = [0x80u8; 30];
+        let mut assembler = SysEx8Assembler::new(30);
+        let mut first = SysEx8Packets::new(U4(0), 1, &data);
+        let mut second = SysEx8Packets::new(U4(0), 2, &data[..20]);
+        let mut push = |packet: UmpPacket| {
+            let (group, packet) = SysEx8Packet::from_packet(&packet).unwrap();
+            assembler.push(group, &packet)
+        };
+        assert_eq!(push(first.next().unwrap()), Ok(None));
+        assert_eq!(push(second.next().unwrap()), Ok(None));
+        assert_eq!(push(first.next().unwrap()), Ok(None));
+        assert_eq!(push(second.next().unwrap()), Ok(Some(data[..20].to_vec())));
+        assert_eq!(push(first.next().unwrap()), Ok(Some(data.to_vec())));
+        assert!(assembler.is_empty());
+
+        let end = SysEx8Packet::new(SysExStatus::End, 1, &[]).unwrap();
+        assert_eq!(
+            assembler.push(U4(0), &end),
+            Err(FromBytesError::UnexpectedEndSysExByte)
+        );
+        let start = SysEx8Packet::new(SysExStatus::Start, 1, &data[..13]).unwrap();
+        let next = SysEx8Packet::new(SysExStatus::Continue, 1, &data[..13]).unwrap();
+        assert_eq!(assembler.push(U4(0), &start), Ok(None));
+        assert_eq!(assembler.push(U4(1), &start), Ok(None));
+        assert_eq!(assembler.len(), 2);
+        assert_eq!(assembler.push(U4(0), &next), Ok(None));
+        assert_eq!(
+            assembler.push(U4(0), &next),
+            Err(FromBytesError::SysExTooLong)
+        );
+        assert_eq!(assembler.len(), 1);
+    }
+}
@@ -0,0 +1,590 @@
+use super::{Midi2Message, NoteAttribute};
+use crate::{
+    encode_parameter, Channel, ControlFunction, LsbMode, MidiMessage, ParameterNumber, PitchBend,
+    U14, U7,
+};
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+/// [UMP] Scale `value` from `src_bits` to `dst_bits` bits, where `src_bits` is at most
+/// `dst_bits`.
+///
+/// This is the Min-Center-Max method of the UMP specification: the minimum, center and maximum
+/// values map to the minimum, center and maximum of the larger range, and values in between are
+/// spread evenly.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{scale_down, scale_up};
+/// assert_eq!(scale_up(0x00, 7, 32), 0x0000_0000);
+/// assert_eq!(scale_up(0x40, 7, 32), 0x8000_0000);
+/// assert_eq!(scale_up(0x7F, 7, 32), 0xFFFF_FFFF);
+/// assert_eq!(scale_down(scale_up(0x51, 7, 16), 16, 7), 0x51);
+/// ```
+pub fn scale_up(value: u32, src_bits: u32, dst_bits: u32) -> u32 {
+    let scale_bits = dst_bits - src_bits;
+    let shifted = value << scale_bits;
+    if value <= 1 << (src_bits - 1) {
+        return shifted;
+    }
+    // Above the center, repeat the bits below the top bit to fill the new lower bits.
+    let repeat_bits = src_bits - 1;
+    let repeat = value & ((1 << repeat_bits) - 1);
+    let mut repeat = if scale_bits > repeat_bits {
+        repeat << (scale_bits - repeat_bits)
+    } else {
+        repeat >> (repeat_bits - scale_bits)
+    };
+    let mut result = shifted;
+    while repeat != 0 {
+        result |= repeat;
+        repeat >>= repeat_bits;
+    }
+    result
+}
+
+/// [UMP] Scale `value` from `src_bits` to `dst_bits` bits, where `src_bits` is at least
+/// `dst_bits`, by dropping the lower bits.
+pub fn scale_down(value: u32, src_bits: u32, dst_bits: u32) -> u32 {
+    value >> (src_bits - dst_bits)
+}
+
+/// [UMP] The error when a message has no equivalent single message in the other protocol.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TranslateError;
+
+#[cfg(feature = "std")]
+impl error::Error for TranslateError {}
+
+impl fmt::Display for TranslateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// [UMP] Translate a MIDI 1.0 channel voice message that maps to a single MIDI 2.0 message.
+///
+/// Values are scaled up with `scale_up`, and a Note On with a velocity of 0 becomes a Note Off
+/// with a velocity of 0x8000. A Program Change is sent without a bank. System messages and the
+/// controllers for bank select, RPNs and NRPNs return an error, since they have no equivalent
+/// or depend on earlier messages. Use `Midi1Translator` to translate bank selects, RPNs and
+/// NRPNs.
+///
+/// # Example
+/// ```
+/// use std::convert::TryFrom;
+/// use wmidi::ump::{Midi2Message, NoteAttribute};
+/// use wmidi::{Channel, MidiMessage, Note, U7};
+/// let message = MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX);
+/// assert_eq!(
+///     Midi2Message::try_from(&message),
+///     Ok(Midi2Message::NoteOn(Channel::Ch1, Note::C4, 0xFFFF, NoteAttribute::None))
+/// );
+/// ```
+impl<'a, 'b> TryFrom<&'b MidiMessage<'a>> for Midi2Message {
+    type Error = TranslateError;
+
+    fn try_from(message: &'b MidiMessage<'a>) -> Result<Midi2Message, TranslateError> {
+        let up = |value: U7, bits: u32| scale_up(u32::from(u8::from(value)), 7, bits);
+        let message = match *message {
+            MidiMessage::NoteOff(channel, note, velocity) => {
+                Midi2Message::NoteOff(channel, note, up(velocity, 16) as u16, NoteAttribute::None)
+            }
+            MidiMessage::NoteOn(channel, note, U7(0)) => {
+                Midi2Message::NoteOff(channel, note, 0x8000, NoteAttribute::None)
+            }
+            MidiMessage::NoteOn(channel, note, velocity) => {
+                Midi2Message::NoteOn(channel, note, up(velocity, 16) as u16, NoteAttribute::None)
+            }
+            MidiMessage::PolyphonicKeyPressure(channel, note, pressure) => {
+                Midi2Message::PolyphonicKeyPressure(channel, note, up(pressure, 32))
+            }
+            MidiMessage::ControlChange(channel, function, value) => {
+                if is_parameter_control(function) {
+                    return Err(TranslateError);
+                }
+                Midi2Message::ControlChange(channel, function, up(value, 32))
+            }
+            MidiMessage::ProgramChange(channel, program) => {
+                Midi2Message::ProgramChange(channel, program, None)
+            }
+            MidiMessage::ChannelPressure(channel, pressure) => {
+                Midi2Message::ChannelPressure(channel, up(pressure, 32))
+            }
+            MidiMessage::PitchBendChange(channel, bend) => {
+                let bend = scale_up(u32::from(u16::from(bend)), 14, 32);
+                Midi2Message::PitchBendChange(channel, bend)
+            }
+            _ => return Err(TranslateError),
+        };
+        Ok(message)
+    }
+}
+
+/// [UMP] Translate a MIDI 2.0 channel voice message that maps to a single MIDI 1.0 message.
+///
+/// Values are scaled down with `scale_down`, note attributes are dropped, and a Note On whose
+/// velocity scales to 0 is sent with a velocity of 1 so it is not read as a Note Off. Registered
+/// and Assignable Controllers, Program Changes with a bank, and per-note messages return an
+/// error. Use `Midi2Message::to_midi1` to send controllers as RPNs and NRPNs and banks as Bank
+/// Select controllers.
+impl TryFrom<Midi2Message> for MidiMessage<'static> {
+    type Error = TranslateError;
+
+    fn try_from(message: Midi2Message) -> Result<MidiMessage<'static>, TranslateError> {
+        let down = |value: u32, bits: u32| U7(scale_down(value, bits, 7) as u8);
+        let message = match message {
+            Midi2Message::NoteOff(channel, note, velocity, _) => {
+                MidiMessage::NoteOff(channel, note, down(u32::from(velocity), 16))
+            }
+            Midi2Message::NoteOn(channel, note, velocity, _) => {
+                let velocity = down(u32::from(velocity), 16).max(U7(1));
+                MidiMessage::NoteOn(channel, note, velocity)
+            }
+            Midi2Message::PolyphonicKeyPressure(channel, note, pressure) => {
+                MidiMessage::PolyphonicKeyPressure(channel, note, down(pressure, 32))
+            }
+            Midi2Message::ControlChange(channel, function, value) => {
+                MidiMessage::ControlChange(channel, function, down(value, 32))
+            }
+            Midi2Message::ProgramChange(channel, program, None) => {
+                MidiMessage::ProgramChange(channel, program)
+            }
+            Midi2Message::ChannelPressure(channel, pressure) => {
+                MidiMessage::ChannelPressure(channel, down(pressure, 32))
+            }
+            Midi2Message::PitchBendChange(channel, bend) => {
+                let bend = U14::from_u16_lossy(scale_down(bend, 32, 14) as u16);
+                MidiMessage::PitchBendChange(channel, PitchBend::from(bend))
+            }
+            Midi2Message::Controller(..)
+            | Midi2Message::RelativeController(..)
+            | Midi2Message::ProgramChange(..)
+            | Midi2Message::PerNotePitchBend(..)
+            | Midi2Message::PerNoteControlChange(..)
+            | Midi2Message::PerNoteManagement(..) => return Err(TranslateError),
+        };
+        Ok(message)
+    }
+}
+
+impl Midi2Message {
+    /// [UMP] Emit the MIDI 1.0 messages equivalent to this message.
+    ///
+    /// Registered and Assignable Controllers are sent as an RPN or NRPN with a 14 bit Data Entry
+    /// value, followed by a null RPN select. A Program Change with a bank is sent after a Bank
+    /// Select MSB and LSB. Messages without an equivalent, such as relative controllers, emit
+    /// nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ump::Midi2Message;
+    /// use wmidi::{Channel, ControlFunction, MidiMessage, ParameterNumber, U7};
+    /// let message = Midi2Message::Controller(
+    ///     Channel::Ch1,
+    ///     ParameterNumber::PITCH_BEND_SENSITIVITY,
+    ///     0x1800_0000,
+    /// );
+    /// let mut messages = Vec::new();
+    /// message.to_midi1(|m| messages.push(m));
+    /// assert_eq!(messages.len(), 5);
+    /// // 12 semitones in the Data Entry MSB.
+    /// let data_entry = U7::from_u8_lossy(12);
+    /// assert_eq!(
+    ///     messages[2],
+    ///     MidiMessage::ControlChange(Channel::Ch1, ControlFunction::DATA_ENTRY_MSB, data_entry)
+    /// );
+    /// ```
+    pub fn to_midi1<F: FnMut(MidiMessage<'static>)>(&self, mut emit: F) {
+        match *self {
+            Midi2Message::Controller(channel, parameter, value) => {
+                let value = U14::from_u16_lossy(scale_down(value, 32, 14) as u16);
+                encode_parameter(channel, parameter, value, LsbMode::OmitZero, emit);
+            }
+            Midi2Message::ProgramChange(channel, program, Some((msb, lsb))) => {
+                emit(MidiMessage::ControlChange(
+                    channel,
+                    ControlFunction::BANK_SELECT,
+                    msb,
+                ));
+                emit(MidiMessage::ControlChange(
+                    channel,
+                    ControlFunction::BANK_SELECT_LSB,
+                    lsb,
+                ));
+                emit(MidiMessage::ProgramChange(channel, program));
+            }
+            message => {
+                if let Ok(message) = MidiMessage::try_from(message) {
+                    emit(message);
+                }
+            }
+        }
+    }
+}
+
+/// [UMP] Translates a stream of MIDI 1.0 channel voice messages to MIDI 2.0.
+///
+/// Messages are translated with `Midi2Message::try_from`, except for RPNs and NRPNs. The
+/// parameter selected on each channel is tracked, and each Data Entry MSB or LSB is sent as a
+/// Registered or Assignable Controller holding the 14 bit value received so far. As with MIDI
+/// 1.0 receivers, a Data Entry MSB resets the LSB to 0.
+///
+/// Bank Select MSB and LSB are held until the next Program Change on the channel, which is sent
+/// with the bank. Like a MIDI 1.0 receiver, the translator remembers the last MSB and LSB, so a
+/// bank select of only one of them keeps the other.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::{Midi1Translator, Midi2Message};
+/// use wmidi::{encode_parameter, Channel, LsbMode, ParameterNumber, U14};
+/// let mut translator = Midi1Translator::new();
+/// let mut messages = Vec::new();
+/// encode_parameter(
+///     Channel::Ch2,
+///     ParameterNumber::FINE_TUNING,
+///     U14::from_u16_lossy(0x2000),
+///     LsbMode::OmitZero,
+///     |m| messages.extend(translator.handle(&m)),
+/// );
+/// assert_eq!(
+///     messages,
+///     [Midi2Message::Controller(Channel::Ch2, ParameterNumber::FINE_TUNING, 0x8000_0000)]
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Midi1Translator {
+    channels: [Selection; 16],
+    banks: [BankSelect; 16],
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct BankSelect {
+    msb: U7,
+    lsb: U7,
+    // Set by a bank select and cleared by the Program Change that uses it.
+    pending: bool,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Selection {
+    // `Some(true)` for an RPN and `Some(false)` for an NRPN.
+    registered: Option<bool>,
+    msb: Option<U7>,
+    lsb: Option<U7>,
+    data_msb: U7,
+}
+
+impl Selection {
+    fn select(&mut self, registered: bool, msb: Option<U7>, lsb: Option<U7>) {
+        if self.registered != Some(registered) {
+            *self = Selection {
+                registered: Some(registered),
+                ..Selection::default()
+            };
+        }
+        self.msb = msb.or(self.msb);
+        self.lsb = lsb.or(self.lsb);
+    }
+
+    fn parameter(&self) -> Option<ParameterNumber> {
+        let (bank, index) = (self.msb?, self.lsb?);
+        let parameter = match self.registered? {
+            true => ParameterNumber::registered_controller(bank, index),
+            false => ParameterNumber::assignable_controller(bank, index),
+        };
+        if parameter.number() == ParameterNumber::NULL.number() {
+            return None;
+        }
+        Some(parameter)
+    }
+}
+
+impl Midi1Translator {
+    /// Create a translator with no parameters or banks selected.
+    pub fn new() -> Midi1Translator {
+        Midi1Translator::default()
+    }
+
+    /// Process `message`, returning its translation if it has one.
+    pub fn handle(&mut self, message: &MidiMessage) -> Option<Midi2Message> {
+        let (channel, function, value) = match *message {
+            MidiMessage::ControlChange(channel, function, value) => (channel, function, value),
+            MidiMessage::ProgramChange(channel, program) => {
+                let bank = &mut self.banks[usize::from(channel.index())];
+                let selected = if bank.pending {
+                    Some((bank.msb, bank.lsb))
+                } else {
+                    None
+                };
+                bank.pending = false;
+                return Some(Midi2Message::ProgramChange(channel, program, selected));
+            }
+            _ => return Midi2Message::try_from(message).ok(),
+        };
+        let bank = &mut self.banks[usize::from(channel.index())];
+        let selection = &mut self.channels[usize::from(channel.index())];
+        let lsb = match function {
+            ControlFunction::BANK_SELECT => {
+                bank.msb = value;
+                bank.pending = true;
+                return None;
+            }
+            ControlFunction::BANK_SELECT_LSB => {
+                bank.lsb = value;
+                bank.pending = true;
+                return None;
+            }
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB => {
+                selection.select(true, Some(value), None);
+                return None;
+            }
+            ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB => {
+                selection.select(true, None, Some(value));
+                return None;
+            }
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB => {
+                selection.select(false, Some(value), None);
+                return None;
+            }
+            ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB => {
+                selection.select(false, None, Some(value));
+                return None;
+            }
+            ControlFunction::DATA_ENTRY_MSB => {
+                selection.data_msb = value;
+                U7(0)
+            }
+            ControlFunction::DATA_ENTRY_LSB => value,
+            _ => return Midi2Message::try_from(message).ok(),
+        };
+        let data = u16::from(u8::from(selection.data_msb)) << 7 | u16::from(u8::from(lsb));
+        let value = scale_up(u32::from(data), 14, 32);
+        Some(Midi2Message::Controller(
+            channel,
+            selection.parameter()?,
+            value,
+        ))
+    }
+
+    /// Forget the parameter and bank selected on `channel`.
+    pub fn reset(&mut self, channel: Channel) {
+        self.channels[usize::from(channel.index())] = Selection::default();
+        self.banks[usize::from(channel.index())] = BankSelect::default();
+    }
+}
+
+fn is_parameter_control(function: ControlFunction) -> bool {
+    matches!(
+        function,
+        ControlFunction::BANK_SELECT
+            | ControlFunction::BANK_SELECT_LSB
+            | ControlFunction::DATA_ENTRY_MSB
+            | ControlFunction::DATA_ENTRY_LSB
+            | ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_LSB
+            | ControlFunction::NON_REGISTERED_PARAMETER_NUMBER_MSB
+            | ControlFunction::REGISTERED_PARAMETER_NUMBER_LSB
+            | ControlFunction::REGISTERED_PARAMETER_NUMBER_MSB
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Note;
+
+    #[test]
+    fn scaling() {
+        for bits in [7, 14].iter() {
+            let max = (1 << bits) - 1;
+            for value in 0..=max {
+                let up = scale_up(value, *bits, 32);
+                assert_eq!(scale_down(up, 32, *bits), value);
+                if value > 0 {
+                    assert!(up > scale_up(value - 1, *bits, 32));
+                }
+            }
+            assert_eq!(scale_up(max, *bits, 32), u32::MAX);
+            assert_eq!(scale_up(max, *bits, 16), 0xFFFF);
+        }
+        assert_eq!(scale_up(0x2000, 14, 32), 0x8000_0000);
+        assert_eq!(scale_up(0x41, 7, 16), 0x8208);
+    }
+
+    #[test]
+    fn single_messages() {
+        let ch = Channel::Ch3;
+        for (midi1, midi2) in [
+            (
+                MidiMessage::NoteOff(ch, Note::C4, U7(0x40)),
+                Midi2Message::NoteOff(ch, Note::C4, 0x8000, NoteAttribute::None),
+            ),
+            (
+                MidiMessage::PolyphonicKeyPressure(ch, Note::A4, U7(0x7F)),
+                Midi2Message::PolyphonicKeyPressure(ch, Note::A4, u32::MAX),
+            ),
+            (
+                MidiMessage::ControlChange(ch, ControlFunction::MODULATION_WHEEL, U7(0)),
+                Midi2Message::ControlChange(ch, ControlFunction::MODULATION_WHEEL, 0),
+            ),
+            (
+                MidiMessage::ProgramChange(ch, U7(0x7F)),
+                Midi2Message::ProgramChange(ch, U7(0x7F), None),
+            ),
+            (
+                MidiMessage::ChannelPressure(ch, U7(0x40)),
+                Midi2Message::ChannelPressure(ch, 0x8000_0000),
+            ),
+            (
+                MidiMessage::PitchBendChange(ch, PitchBend::CENTER),
+                Midi2Message::PitchBendChange(ch, Midi2Message::PITCH_BEND_CENTER),
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(Midi2Message::try_from(midi1), Ok(*midi2));
+            assert_eq!(MidiMessage::try_from(*midi2).as_ref(), Ok(midi1));
+        }
+
+        assert_eq!(
+            Midi2Message::try_from(&MidiMessage::NoteOn(ch, Note::C4, U7(0))),
+            Ok(Midi2Message::NoteOff(
+                ch,
+                Note::C4,
+                0x8000,
+                NoteAttribute::None
+            ))
+        );
+        assert_eq!(
+            MidiMessage::try_from(Midi2Message::NoteOn(
+                ch,
+                Note::C4,
+                0x01FF,
+                NoteAttribute::Pitch(0)
+            )),
+            Ok(MidiMessage::NoteOn(ch, Note::C4, U7(1)))
+        );
+        for message in [
+            MidiMessage::ControlChange(ch, ControlFunction::DATA_ENTRY_MSB, U7(0)),
+            MidiMessage::ControlChange(ch, ControlFunction::BANK_SELECT, U7(0)),
+            MidiMessage::TimingClock,
+        ]
+        .iter()
+        {
+            assert_eq!(Midi2Message::try_from(message), Err(TranslateError));
+        }
+        for message in [
+            Midi2Message::PerNotePitchBend(ch, Note::C4, 0),
+            Midi2Message::ProgramChange(ch, U7(0), Some((U7(0), U7(0)))),
+        ]
+        .iter()
+        {
+            assert_eq!(MidiMessage::try_from(*message), Err(TranslateError));
+        }
+    }
+
+    #[test]
+    fn program_change() {
+        let ch = Channel::Ch6;
+        let message = Midi2Message::ProgramChange(ch, U7(24), Some((U7(0x79), U7(1))));
+        let mut translator = Midi1Translator::new();
+        let mut midi1 = [None, None, None];
+        let mut midi1_count = 0;
+        let mut midi2 = None;
+        message.to_midi1(|m| {
+            midi1[midi1_count] = Some(m.clone());
+            midi1_count += 1;
+            if let Some(message) = translator.handle(&m) {
+                assert_eq!(midi2.replace(message), None);
+            }
+        });
+        assert_eq!(
+            midi1,
+            [
+                Some(MidiMessage::ControlChange(
+                    ch,
+                    ControlFunction::BANK_SELECT,
+                    U7(0x79)
+                )),
+                Some(MidiMessage::ControlChange(
+                    ch,
+                    ControlFunction::BANK_SELECT_LSB,
+                    U7(1)
+                )),
+                Some(MidiMessage::ProgramChange(ch, U7(24))),
+            ]
+        );
+        assert_eq!(midi2, Some(message));
+
+        // The bank is only sent with the first Program Change after a bank select, and a bank
+        // select of the MSB alone keeps the last LSB.
+        let program_change = MidiMessage::ProgramChange(ch, U7(25));
+        assert_eq!(
+            translator.handle(&program_change),
+            Some(Midi2Message::ProgramChange(ch, U7(25), None))
+        );
+        let bank_select = MidiMessage::ControlChange(ch, ControlFunction::BANK_SELECT, U7(0x78));
+        assert_eq!(translator.handle(&bank_select), None);
+        assert_eq!(
+            translator.handle(&program_change),
+            Some(Midi2Message::ProgramChange(
+                ch,
+                U7(25),
+                Some((U7(0x78), U7(1)))
+            ))
+        );
+        translator.handle(&bank_select);
+        translator.reset(ch);
+        assert_eq!(
+            translator.handle(&program_change),
+            Some(Midi2Message::ProgramChange(ch, U7(25), None))
+        );
+
+        let mut count = 0;
+        Midi2Message::ProgramChange(ch, U7(0), None).to_midi1(|_| count += 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn parameters() {
+        let parameter = ParameterNumber::NonRegistered(U14::from_u16_lossy(0x0123));
+        let message = Midi2Message::Controller(Channel::Ch4, parameter, u32::MAX);
+        let mut translator = Midi1Translator::new();
+        let mut midi1_count = 0;
+        let mut midi2 = [None; 3];
+        let mut midi2_count = 0;
+        message.to_midi1(|m| {
+            midi1_count += 1;
+            if let Some(message) = translator.handle(&m) {
+                midi2[midi2_count] = Some(message);
+                midi2_count += 1;
+            }
+        });
+        // Select, Data Entry MSB and LSB, and the null RPN select.
+        assert_eq!(midi1_count, 6);
+        let msb_only = scale_up(0x3F80, 14, 32);
+        assert_eq!(
+            midi2,
+            [
+                Some(Midi2Message::Controller(Channel::Ch4, parameter, msb_only)),
+                Some(message),
+                None
+            ]
+        );
+
+        // Data Entry after the null RPN, or without a selection, is dropped.
+        let data_entry =
+            MidiMessage::ControlChange(Channel::Ch4, ControlFunction::DATA_ENTRY_MSB, U7(1));
+        assert_eq!(translator.handle(&data_entry), None);
+        let data_entry =
+            MidiMessage::ControlChange(Channel::Ch5, ControlFunction::DATA_ENTRY_MSB, U7(1));
+        assert_eq!(translator.handle(&data_entry), None);
+
+        let mut count = 0;
+        Midi2Message::RelativeController(Channel::Ch1, parameter, 1).to_midi1(|_| count += 1);
+        assert_eq!(count, 0);
+    }
+}
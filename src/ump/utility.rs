@@ -0,0 +1,124 @@
+use super::{MessageType, UmpPacket};
+
+/// [UMP] A 32 bit utility message of message type 0x0. Utility messages have no group.
+///
+/// Jitter reduction (JR) times count ticks of 1/31250 of a second, wrapping every 2.09 seconds.
+/// A JR Timestamp is sent just before the message it times.
+///
+/// # Example
+/// ```
+/// use wmidi::ump::UtilityMessage;
+/// let message = UtilityMessage::JrTimestamp(0x1234);
+/// let packet = message.to_packet();
+/// assert_eq!(packet.words(), &[0x0020_1234]);
+/// assert_eq!(packet.group(), None);
+/// assert_eq!(UtilityMessage::from_packet(&packet), Some(message));
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum UtilityMessage {
+    /// No operation, status 0x0.
+    Noop,
+    /// The time of the sender's clock in JR ticks, status 0x1.
+    JrClock(u16),
+    /// The time the next message was sent at in JR ticks, status 0x2.
+    JrTimestamp(u16),
+    /// The number of Delta Clockstamp ticks in a quarter note, status 0x3.
+    DeltaClockstampTicksPerQuarterNote(u16),
+    /// The number of ticks since the last event, status 0x4. Only the lower 20 bits are sent.
+    DeltaClockstamp(u32),
+}
+
+impl UtilityMessage {
+    /// The number of JR ticks in a second.
+    pub const JR_TICKS_PER_SECOND: u32 = 31250;
+
+    /// The largest number of ticks a Delta Clockstamp can hold.
+    pub const MAX_DELTA_CLOCKSTAMP: u32 = 0x000F_FFFF;
+
+    /// The packet holding the message.
+    pub fn to_packet(&self) -> UmpPacket {
+        let (status, data) = match *self {
+            UtilityMessage::Noop => (0x0, 0),
+            UtilityMessage::JrClock(time) => (0x1, u32::from(time)),
+            UtilityMessage::JrTimestamp(time) => (0x2, u32::from(time)),
+            UtilityMessage::DeltaClockstampTicksPerQuarterNote(ticks) => (0x3, u32::from(ticks)),
+            UtilityMessage::DeltaClockstamp(ticks) => {
+                (0x4, ticks & UtilityMessage::MAX_DELTA_CLOCKSTAMP)
+            }
+        };
+        UmpPacket::new([status << 20 | data, 0, 0, 0])
+    }
+
+    /// Parse a packet. Returns `None` if the packet is not a utility message of a known kind.
+    pub fn from_packet(packet: &UmpPacket) -> Option<UtilityMessage> {
+        if packet.message_type() != MessageType::Utility {
+            return None;
+        }
+        let word = packet.words()[0];
+        let message = match word >> 20 & 0xF {
+            0x0 => UtilityMessage::Noop,
+            0x1 => UtilityMessage::JrClock(word as u16),
+            0x2 => UtilityMessage::JrTimestamp(word as u16),
+            0x3 => UtilityMessage::DeltaClockstampTicksPerQuarterNote(word as u16),
+            0x4 => UtilityMessage::DeltaClockstamp(word & UtilityMessage::MAX_DELTA_CLOCKSTAMP),
+            _ => return None,
+        };
+        Some(message)
+    }
+
+    /// The JR time of `micros` microseconds, wrapped to 16 bits.
+    ///
+    /// # Example
+    /// ```
+    /// use wmidi::ump::UtilityMessage;
+    /// assert_eq!(UtilityMessage::jr_time_from_micros(1_000_000), 31250);
+    /// assert_eq!(UtilityMessage::jr_time_from_micros(32 * 0x10001), 1);
+    /// ```
+    pub fn jr_time_from_micros(micros: u64) -> u16 {
+        // A tick is exactly 32 microseconds.
+        (micros / 32) as u16
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for message in [
+            UtilityMessage::Noop,
+            UtilityMessage::JrClock(0xFFFF),
+            UtilityMessage::JrTimestamp(1),
+            UtilityMessage::DeltaClockstampTicksPerQuarterNote(480),
+            UtilityMessage::DeltaClockstamp(UtilityMessage::MAX_DELTA_CLOCKSTAMP),
+        ]
+        .iter()
+        {
+            let packet = message.to_packet();
+            assert_eq!(packet.len(), 1);
+            assert_eq!(UtilityMessage::from_packet(&packet), Some(*message));
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(UtilityMessage::Noop.to_packet().words(), &[0x0000_0000]);
+        assert_eq!(
+            UtilityMessage::DeltaClockstamp(0x0123_4567)
+                .to_packet()
+                .words(),
+            &[0x0043_4567]
+        );
+        // The group and reserved bits are ignored.
+        let packet = UmpPacket::from_words(&[0x0F1F_0010]).unwrap();
+        assert_eq!(
+            UtilityMessage::from_packet(&packet),
+            Some(UtilityMessage::JrClock(0x0010))
+        );
+        for word in [0x0050_0000, 0x1010_0000].iter() {
+            let packet = UmpPacket::from_words(&[*word, 0]).unwrap();
+            assert_eq!(UtilityMessage::from_packet(&packet), None);
+        }
+    }
+}
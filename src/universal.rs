@@ -0,0 +1,327 @@
+//! Documents referred to in this module:
+//! * [MIDI 1.0]: The Complete MIDI 1.0 Detailed Specification, Third Edition (1996)
+
+use crate::{ManufacturerId, MidiMessage, ToSliceError, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Whether a Universal SysEx message is Non-Real Time (ID 0x7E) or Real Time (ID 0x7F).
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UniversalKind {
+    /// Messages that may be processed when the receiver is ready, such as sample dumps and
+    /// tuning dumps.
+    NonRealTime,
+    /// Messages that must be processed as they arrive, such as time code and machine control.
+    RealTime,
+}
+
+impl UniversalKind {
+    /// The manufacturer ID that starts messages of this kind.
+    pub fn id(self) -> ManufacturerId {
+        match self {
+            UniversalKind::NonRealTime => ManufacturerId::UNIVERSAL_NON_REAL_TIME,
+            UniversalKind::RealTime => ManufacturerId::UNIVERSAL_REAL_TIME,
+        }
+    }
+
+    /// The kind of messages started by `id`, or `None` if it is not a Universal ID.
+    pub fn from_id(id: ManufacturerId) -> Option<UniversalKind> {
+        match id {
+            ManufacturerId::UNIVERSAL_NON_REAL_TIME => Some(UniversalKind::NonRealTime),
+            ManufacturerId::UNIVERSAL_REAL_TIME => Some(UniversalKind::RealTime),
+            _ => None,
+        }
+    }
+}
+
+/// [MIDI 1.0] The group of a Universal SysEx message, given by its sub-ID #1.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UniversalCategory {
+    /// Non-Real Time 0x01.
+    SampleDumpHeader,
+    /// Non-Real Time 0x02.
+    SampleDataPacket,
+    /// Non-Real Time 0x03.
+    SampleDumpRequest,
+    /// Non-Real Time 0x04, time code cueing.
+    TimeCodeCueing,
+    /// Non-Real Time 0x05.
+    SampleDumpExtensions,
+    /// Non-Real Time 0x06, including Device Inquiry.
+    GeneralInformation,
+    /// Non-Real Time 0x07.
+    FileDump,
+    /// Non-Real Time 0x08, bulk tuning dumps and scale/octave tuning.
+    TuningStandard,
+    /// Non-Real Time 0x09, General MIDI System On and Off.
+    GeneralMidi,
+    /// Non-Real Time 0x0A.
+    DownloadableSounds,
+    /// Non-Real Time 0x0B.
+    FileReference,
+    /// Non-Real Time 0x0C.
+    MidiVisualControl,
+    /// Non-Real Time 0x0D.
+    CapabilityInquiry,
+    /// Non-Real Time 0x7B.
+    EndOfFile,
+    /// Non-Real Time 0x7C.
+    Wait,
+    /// Non-Real Time 0x7D.
+    Cancel,
+    /// Non-Real Time 0x7E.
+    Nak,
+    /// Non-Real Time 0x7F.
+    Ack,
+    /// Real Time 0x01, full time code messages and user bits.
+    TimeCode,
+    /// Real Time 0x02.
+    ShowControl,
+    /// Real Time 0x03, bar numbers and time signatures.
+    NotationInformation,
+    /// Real Time 0x04, master volume, balance, and tuning.
+    DeviceControl,
+    /// Real Time 0x05, time code cueing.
+    RealTimeCueing,
+    /// Real Time 0x06.
+    MachineControlCommand,
+    /// Real Time 0x07.
+    MachineControlResponse,
+    /// Real Time 0x08, single note tuning changes.
+    RealTimeTuningStandard,
+    /// Real Time 0x09.
+    ControllerDestination,
+    /// Real Time 0x0A.
+    KeyBasedInstrumentControl,
+    /// Real Time 0x0B.
+    ScalablePolyphony,
+    /// Real Time 0x0C.
+    MobilePhoneControl,
+}
+
+impl UniversalCategory {
+    /// The category of messages of `kind` with `sub_id1`, or `None` if it is not defined.
+    pub fn new(kind: UniversalKind, sub_id1: U7) -> Option<UniversalCategory> {
+        let category = match (kind, u8::from(sub_id1)) {
+            (UniversalKind::NonRealTime, 0x01) => UniversalCategory::SampleDumpHeader,
+            (UniversalKind::NonRealTime, 0x02) => UniversalCategory::SampleDataPacket,
+            (UniversalKind::NonRealTime, 0x03) => UniversalCategory::SampleDumpRequest,
+            (UniversalKind::NonRealTime, 0x04) => UniversalCategory::TimeCodeCueing,
+            (UniversalKind::NonRealTime, 0x05) => UniversalCategory::SampleDumpExtensions,
+            (UniversalKind::NonRealTime, 0x06) => UniversalCategory::GeneralInformation,
+            (UniversalKind::NonRealTime, 0x07) => UniversalCategory::FileDump,
+            (UniversalKind::NonRealTime, 0x08) => UniversalCategory::TuningStandard,
+            (UniversalKind::NonRealTime, 0x09) => UniversalCategory::GeneralMidi,
+            (UniversalKind::NonRealTime, 0x0A) => UniversalCategory::DownloadableSounds,
+            (UniversalKind::NonRealTime, 0x0B) => UniversalCategory::FileReference,
+            (UniversalKind::NonRealTime, 0x0C) => UniversalCategory::MidiVisualControl,
+            (UniversalKind::NonRealTime, 0x0D) => UniversalCategory::CapabilityInquiry,
+            (UniversalKind::NonRealTime, 0x7B) => UniversalCategory::EndOfFile,
+            (UniversalKind::NonRealTime, 0x7C) => UniversalCategory::Wait,
+            (UniversalKind::NonRealTime, 0x7D) => UniversalCategory::Cancel,
+            (UniversalKind::NonRealTime, 0x7E) => UniversalCategory::Nak,
+            (UniversalKind::NonRealTime, 0x7F) => UniversalCategory::Ack,
+            (UniversalKind::RealTime, 0x01) => UniversalCategory::TimeCode,
+            (UniversalKind::RealTime, 0x02) => UniversalCategory::ShowControl,
+            (UniversalKind::RealTime, 0x03) => UniversalCategory::NotationInformation,
+            (UniversalKind::RealTime, 0x04) => UniversalCategory::DeviceControl,
+            (UniversalKind::RealTime, 0x05) => UniversalCategory::RealTimeCueing,
+            (UniversalKind::RealTime, 0x06) => UniversalCategory::MachineControlCommand,
+            (UniversalKind::RealTime, 0x07) => UniversalCategory::MachineControlResponse,
+            (UniversalKind::RealTime, 0x08) => UniversalCategory::RealTimeTuningStandard,
+            (UniversalKind::RealTime, 0x09) => UniversalCategory::ControllerDestination,
+            (UniversalKind::RealTime, 0x0A) => UniversalCategory::KeyBasedInstrumentControl,
+            (UniversalKind::RealTime, 0x0B) => UniversalCategory::ScalablePolyphony,
+            (UniversalKind::RealTime, 0x0C) => UniversalCategory::MobilePhoneControl,
+            _ => return None,
+        };
+        Some(category)
+    }
+
+    /// Returns `true` if messages in the category have a sub-ID #2. The sample dump messages and
+    /// the handshaking messages are followed directly by their data.
+    pub fn has_sub_id2(self) -> bool {
+        !matches!(
+            self,
+            UniversalCategory::SampleDumpHeader
+                | UniversalCategory::SampleDataPacket
+                | UniversalCategory::SampleDumpRequest
+                | UniversalCategory::EndOfFile
+                | UniversalCategory::Wait
+                | UniversalCategory::Cancel
+                | UniversalCategory::Nak
+                | UniversalCategory::Ack
+        )
+    }
+}
+
+/// [MIDI 1.0] A Universal System Exclusive message, split into its header and data.
+///
+/// Universal messages start with 0x7E or 0x7F, followed by the device ID, sub-ID #1, and for most
+/// categories sub-ID #2. A device ID of 0x7F addresses all devices. Messages with a sub-ID #1
+/// that is not defined are assumed to have a sub-ID #2.
+///
+/// # Example
+/// ```
+/// use wmidi::{MidiMessage, UniversalCategory, UniversalKind, UniversalSysEx, U7};
+/// let bytes = [0xF0, 0x7F, 0x7F, 0x04, 0x01, 0x00, 0x40, 0xF7];
+/// let message = MidiMessage::from_bytes(&bytes).unwrap();
+/// let message = UniversalSysEx::from_message(&message).unwrap();
+/// assert_eq!(message.kind, UniversalKind::RealTime);
+/// assert_eq!(message.device_id, UniversalSysEx::ALL_DEVICES);
+/// assert_eq!(message.category(), Some(UniversalCategory::DeviceControl));
+/// assert_eq!(message.sub_id2, Some(U7::from_u8_lossy(0x01)));
+/// assert_eq!(U7::data_to_bytes(message.data), &[0x00, 0x40]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct UniversalSysEx<'a> {
+    /// Non-Real Time or Real Time.
+    pub kind: UniversalKind,
+    /// The device the message is for, or `UniversalSysEx::ALL_DEVICES`.
+    pub device_id: U7,
+    /// The category of the message.
+    pub sub_id1: U7,
+    /// The message within the category, if the category has one.
+    pub sub_id2: Option<U7>,
+    /// The bytes after the sub-IDs, excluding the end byte.
+    pub data: &'a [U7],
+}
+
+impl<'a> UniversalSysEx<'a> {
+    /// The device ID that addresses all devices.
+    pub const ALL_DEVICES: U7 = U7(0x7F);
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns `None` if the payload is not a Universal message or is too short.
+    pub fn from_sysex(payload: &'a [U7]) -> Option<UniversalSysEx<'a>> {
+        let (id, rest) = ManufacturerId::from_sysex(payload)?;
+        UniversalSysEx::from_body(UniversalKind::from_id(id)?, rest)
+    }
+
+    /// Parse the bytes after the ID of a message of `kind`.
+    pub(crate) fn from_body(kind: UniversalKind, body: &'a [U7]) -> Option<UniversalSysEx<'a>> {
+        let (device_id, sub_id1, rest) = match body {
+            [device_id, sub_id1, rest @ ..] => (*device_id, *sub_id1, rest),
+            _ => return None,
+        };
+        let has_sub_id2 =
+            UniversalCategory::new(kind, sub_id1).is_none_or(UniversalCategory::has_sub_id2);
+        let (sub_id2, data) = match rest {
+            [sub_id2, data @ ..] if has_sub_id2 => (Some(*sub_id2), data),
+            _ if has_sub_id2 => return None,
+            data => (None, data),
+        };
+        Some(UniversalSysEx {
+            kind,
+            device_id,
+            sub_id1,
+            sub_id2,
+            data,
+        })
+    }
+
+    /// Parse a `MidiMessage::SysEx` or `MidiMessage::OwnedSysEx`. Returns `None` for other
+    /// messages.
+    pub fn from_message(message: &'a MidiMessage) -> Option<UniversalSysEx<'a>> {
+        match message {
+            MidiMessage::SysEx(payload) => UniversalSysEx::from_sysex(payload),
+            #[cfg(feature = "std")]
+            MidiMessage::OwnedSysEx(payload) => UniversalSysEx::from_sysex(payload),
+            _ => None,
+        }
+    }
+
+    /// The category given by `sub_id1`, or `None` if it is not defined.
+    pub fn category(&self) -> Option<UniversalCategory> {
+        UniversalCategory::new(self.kind, self.sub_id1)
+    }
+
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(&self) -> usize {
+        5 + usize::from(self.sub_id2.is_some()) + self.data.len()
+    }
+
+    /// Write the message to `slice`, including the start and end bytes. Returns the number of
+    /// bytes written.
+    pub fn copy_sysex_to_slice(&self, slice: &mut [u8]) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let id = match self.kind {
+            UniversalKind::NonRealTime => 0x7E,
+            UniversalKind::RealTime => 0x7F,
+        };
+        slice[..4].copy_from_slice(&[0xF0, id, u8::from(self.device_id), u8::from(self.sub_id1)]);
+        let mut i = 4;
+        if let Some(sub_id2) = self.sub_id2 {
+            slice[i] = u8::from(sub_id2);
+            i += 1;
+        }
+        slice[i..i + self.data.len()].copy_from_slice(U7::data_to_bytes(self.data));
+        slice[size - 1] = 0xF7;
+        Ok(size)
+    }
+
+    /// The message, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(&mut bytes).unwrap();
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        let messages = [
+            [0xF0, 0x7E, 0x10, 0x06, 0x01, 0xF7].as_ref(),
+            &[0xF0, 0x7E, 0x10, 0x7F, 0x05, 0xF7],
+            &[0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7],
+            &[0xF0, 0x7F, 0x7F, 0x70, 0x01, 0x02, 0x03, 0xF7],
+        ];
+        for bytes in messages.iter() {
+            let message = MidiMessage::try_from(*bytes).unwrap();
+            let universal = UniversalSysEx::from_message(&message).unwrap();
+            let mut out = [0u8; 8];
+            assert_eq!(universal.copy_sysex_to_slice(&mut out), Ok(bytes.len()));
+            assert_eq!(&out[..bytes.len()], *bytes);
+        }
+    }
+
+    #[test]
+    fn from_sysex() {
+        let payload = U7::try_from_bytes(&[0x7E, 0x00, 0x7F, 0x05]).unwrap();
+        let ack = UniversalSysEx::from_sysex(payload).unwrap();
+        assert_eq!(ack.category(), Some(UniversalCategory::Ack));
+        assert_eq!(ack.sub_id2, None);
+        assert_eq!(ack.data, &[U7(0x05)]);
+        let payload = U7::try_from_bytes(&[0x7F, 0x7F, 0x06, 0x02]).unwrap();
+        let stop = UniversalSysEx::from_sysex(payload).unwrap();
+        assert_eq!(
+            stop.category(),
+            Some(UniversalCategory::MachineControlCommand)
+        );
+        assert_eq!(stop.sub_id2, Some(U7(0x02)));
+        assert!(stop.data.is_empty());
+
+        assert_eq!(
+            UniversalSysEx::from_sysex(&[U7(0x7F), U7(0x7F), U7(0x06)]),
+            None
+        );
+        assert_eq!(
+            UniversalSysEx::from_sysex(&[U7(0x41), U7(0x10), U7(0x42)]),
+            None
+        );
+        assert_eq!(
+            UniversalSysEx::from_message(&MidiMessage::TimingClock),
+            None
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{MidiMessage, Velocity, U7};
+
+/// The shape applied to a velocity before `VelocityScaler` scales it.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum VelocityCurve {
+    /// Leave the velocity unchanged.
+    Linear,
+    /// Raise soft velocities so that notes are easier to play loudly.
+    Soft,
+    /// Lower soft velocities so that notes are harder to play loudly.
+    Hard,
+    /// Look up each velocity in a table.
+    Table([U7; 128]),
+}
+
+impl VelocityCurve {
+    /// The shaped velocity, between 0.0 and 127.0.
+    fn apply(&self, velocity: Velocity) -> f32 {
+        let x = f32::from(u8::from(velocity)) / 127.0;
+        let y = match self {
+            VelocityCurve::Linear => x,
+            VelocityCurve::Soft => x * (2.0 - x),
+            VelocityCurve::Hard => x * x,
+            VelocityCurve::Table(table) => {
+                return f32::from(u8::from(table[usize::from(u8::from(velocity))]))
+            }
+        };
+        y * 127.0
+    }
+}
+
+/// Reshapes the velocity of `NoteOn` messages, and optionally key and channel pressure.
+///
+/// Each velocity is shaped by `curve`, multiplied by `gain`, shifted by `offset`, rounded, and
+/// then clamped between `min` and `max`. A `NoteOn` never has its velocity scaled to 0 so that it
+/// is not turned into a `NoteOff`, and a `NoteOn` with velocity 0 is left unchanged.
+///
+/// # Example
+/// ```
+/// use wmidi::{Channel, MidiMessage, Note, VelocityScaler, U7};
+/// let scaler = VelocityScaler {
+///     gain: 0.5,
+///     offset: 20,
+///     ..VelocityScaler::new()
+/// };
+/// assert_eq!(
+///     scaler.apply(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::MAX)),
+///     MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7::new(84).unwrap())
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VelocityScaler {
+    /// The shape applied before scaling.
+    pub curve: VelocityCurve,
+    /// The amount to multiply the shaped velocity by.
+    pub gain: f32,
+    /// The amount to add after multiplying by `gain`.
+    pub offset: i8,
+    /// The lowest result.
+    pub min: U7,
+    /// The highest result.
+    pub max: U7,
+    /// If `true`, `PolyphonicKeyPressure` and `ChannelPressure` are scaled as well.
+    pub scale_pressure: bool,
+}
+
+impl VelocityScaler {
+    /// Create a scaler that leaves every velocity unchanged.
+    pub fn new() -> VelocityScaler {
+        VelocityScaler {
+            curve: VelocityCurve::Linear,
+            gain: 1.0,
+            offset: 0,
+            min: U7::MIN,
+            max: U7::MAX,
+            scale_pressure: false,
+        }
+    }
+
+    /// The scaled value of `velocity`.
+    pub fn scale(&self, velocity: Velocity) -> Velocity {
+        let value = self.curve.apply(velocity) * self.gain + f32::from(self.offset);
+        let value = if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 127.0)
+        };
+        let value = U7((value + 0.5) as u8);
+        value.max(self.min).min(self.max)
+    }
+
+    /// The message with its velocity or pressure scaled. Other messages are returned unchanged.
+    pub fn apply<'a>(&self, message: &MidiMessage<'a>) -> MidiMessage<'a> {
+        match message {
+            MidiMessage::NoteOn(c, n, v) if *v != Velocity::MIN => {
+                MidiMessage::NoteOn(*c, *n, self.scale(*v).max(U7(1)))
+            }
+            MidiMessage::PolyphonicKeyPressure(c, n, v) if self.scale_pressure => {
+                MidiMessage::PolyphonicKeyPressure(*c, *n, self.scale(*v))
+            }
+            MidiMessage::ChannelPressure(c, v) if self.scale_pressure => {
+                MidiMessage::ChannelPressure(*c, self.scale(*v))
+            }
+            m => m.clone(),
+        }
+    }
+
+    /// Call `emit` with the scaled message.
+    pub fn handle<'a, F: FnMut(MidiMessage<'a>)>(&self, message: &MidiMessage<'a>, mut emit: F) {
+        emit(self.apply(message));
+    }
+}
+
+impl Default for VelocityScaler {
+    fn default() -> VelocityScaler {
+        VelocityScaler::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Channel, Note};
+
+    #[test]
+    fn identity() {
+        let scaler = VelocityScaler::new();
+        for v in 0..128 {
+            assert_eq!(scaler.scale(U7(v)), U7(v));
+        }
+    }
+
+    #[test]
+    fn curves() {
+        let soft = VelocityScaler {
+            curve: VelocityCurve::Soft,
+            ..VelocityScaler::new()
+        };
+        let hard = VelocityScaler {
+            curve: VelocityCurve::Hard,
+            ..VelocityScaler::new()
+        };
+        assert!(soft.scale(U7(64)) > U7(64));
+        assert!(hard.scale(U7(64)) < U7(64));
+        for v in [0, 127].iter() {
+            assert_eq!(soft.scale(U7(*v)), U7(*v));
+            assert_eq!(hard.scale(U7(*v)), U7(*v));
+        }
+        let mut table = [U7(0); 128];
+        table[100] = U7(3);
+        let lookup = VelocityScaler {
+            curve: VelocityCurve::Table(table),
+            ..VelocityScaler::new()
+        };
+        assert_eq!(lookup.scale(U7(100)), U7(3));
+    }
+
+    #[test]
+    fn clamping() {
+        let scaler = VelocityScaler {
+            gain: 0.0,
+            min: U7(10),
+            max: U7(20),
+            ..VelocityScaler::new()
+        };
+        assert_eq!(scaler.scale(U7(127)), U7(10));
+        let loud = VelocityScaler {
+            offset: 100,
+            max: U7(20),
+            ..VelocityScaler::new()
+        };
+        assert_eq!(loud.scale(U7(127)), U7(20));
+    }
+
+    #[test]
+    fn messages() {
+        let silent = VelocityScaler {
+            gain: 0.0,
+            scale_pressure: true,
+            ..VelocityScaler::new()
+        };
+        assert_eq!(
+            silent.apply(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(100))),
+            MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(1))
+        );
+        assert_eq!(
+            silent.apply(&MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(0))),
+            MidiMessage::NoteOn(Channel::Ch1, Note::C4, U7(0))
+        );
+        assert_eq!(
+            silent.apply(&MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(100))),
+            MidiMessage::NoteOff(Channel::Ch1, Note::C4, U7(100))
+        );
+        assert_eq!(
+            silent.apply(&MidiMessage::ChannelPressure(Channel::Ch1, U7(100))),
+            MidiMessage::ChannelPressure(Channel::Ch1, U7(0))
+        );
+        assert_eq!(
+            VelocityScaler::new().apply(&MidiMessage::ChannelPressure(Channel::Ch1, U7(100))),
+            MidiMessage::ChannelPressure(Channel::Ch1, U7(100))
+        );
+    }
+}
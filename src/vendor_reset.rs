@@ -0,0 +1,106 @@
+use crate::checksum::sum_to_zero_bytes as roland_checksum;
+use crate::{ToSliceError, U4, U7};
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// The vendor SysEx messages that switch a sound module into the Roland GS or Yamaha XG mode and
+/// reset it.
+///
+/// Both are sent to a device number between 0 and 15, which is sent as 0x10 plus the number. 0 is
+/// the factory default of most modules. The GS Reset ends with a Roland checksum.
+///
+/// # Example
+/// ```
+/// use wmidi::{VendorReset, U4};
+/// let mut bytes = [0u8; 11];
+/// VendorReset::GsReset.copy_sysex_to_slice(U4::MIN, &mut bytes).unwrap();
+/// assert_eq!(bytes, [0xF0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x41, 0xF7]);
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum VendorReset {
+    /// Roland GS Reset, a Data Set 1 message writing 0x00 to address 0x40007F of a GS module.
+    GsReset,
+    /// Yamaha XG System On, a Parameter Change message writing 0x00 to address 0x00007E of an XG
+    /// module.
+    XgSystemOn,
+}
+
+impl VendorReset {
+    /// The number of bytes in the message, including the start and end bytes.
+    pub fn bytes_size(self) -> usize {
+        match self {
+            VendorReset::GsReset => 11,
+            VendorReset::XgSystemOn => 9,
+        }
+    }
+
+    /// Write the SysEx message for device number `device` to `slice`, including the start and
+    /// end bytes. Returns the number of bytes written.
+    pub fn copy_sysex_to_slice(self, device: U4, slice: &mut [u8]) -> Result<usize, ToSliceError> {
+        let size = self.bytes_size();
+        if slice.len() < size {
+            return Err(ToSliceError::BufferTooSmall);
+        }
+        let device_id = 0x10 | u8::from(device);
+        match self {
+            VendorReset::GsReset => {
+                let address_and_data = [0x40, 0x00, 0x7F, 0x00];
+                slice[..5].copy_from_slice(&[0xF0, 0x41, device_id, 0x42, 0x12]);
+                slice[5..9].copy_from_slice(&address_and_data);
+                slice[9..size].copy_from_slice(&[roland_checksum(&address_and_data), 0xF7]);
+            }
+            VendorReset::XgSystemOn => slice[..size]
+                .copy_from_slice(&[0xF0, 0x43, device_id, 0x4C, 0x00, 0x00, 0x7E, 0x00, 0xF7]),
+        }
+        Ok(size)
+    }
+
+    /// The SysEx message for device number `device`, including the start and end bytes.
+    #[cfg(feature = "std")]
+    pub fn to_sysex_vec(self, device: U4) -> Vec<u8> {
+        let mut bytes = vec![0; self.bytes_size()];
+        self.copy_sysex_to_slice(device, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Parse the payload of a `MidiMessage::SysEx`, which excludes the start and end bytes.
+    /// Returns the device number and message, or `None` if the payload is not a GS Reset or XG
+    /// System On.
+    pub fn from_sysex(payload: &[U7]) -> Option<(U4, VendorReset)> {
+        let (device_id, message) = match U7::data_to_bytes(payload) {
+            [0x41, device_id, 0x42, 0x12, 0x40, 0x00, 0x7F, 0x00, 0x41] => {
+                (device_id, VendorReset::GsReset)
+            }
+            [0x43, device_id, 0x4C, 0x00, 0x00, 0x7E, 0x00] => (device_id, VendorReset::XgSystemOn),
+            _ => return None,
+        };
+        if device_id & 0xF0 != 0x10 {
+            return None;
+        }
+        Some((U4::from_u8_lossy(*device_id), message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MidiMessage;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn round_trip() {
+        for reset in [VendorReset::GsReset, VendorReset::XgSystemOn].iter() {
+            let mut bytes = [0u8; 11];
+            let size = reset.copy_sysex_to_slice(U4::MAX, &mut bytes).unwrap();
+            assert_eq!(bytes[2], 0x1F);
+            let payload = match MidiMessage::try_from(&bytes[..size]).unwrap() {
+                MidiMessage::SysEx(payload) => payload,
+                _ => panic!("expected SysEx"),
+            };
+            assert_eq!(VendorReset::from_sysex(payload), Some((U4::MAX, *reset)));
+        }
+        let payload = U7::try_from_bytes(&[0x43, 0x20, 0x4C, 0x00, 0x00, 0x7E, 0x00]).unwrap();
+        assert_eq!(VendorReset::from_sysex(payload), None);
+    }
+}